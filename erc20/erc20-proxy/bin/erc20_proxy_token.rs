@@ -4,7 +4,7 @@
 #[macro_use]
 extern crate alloc;
 
-use alloc::{collections::BTreeSet, format};
+use alloc::{collections::BTreeSet, format, string::String};
 use casper_contract::{
     contract_api::{runtime, storage},
     unwrap_or_revert::UnwrapOrRevert,
@@ -121,6 +121,56 @@ fn decrease_allowance() {
     mappings::set_key(&mappings::decrease_allowance_key(), ret);
 }
 
+#[no_mangle]
+fn balance_of_at() {
+    let erc20_address: ContractHash = mappings::get_key(&mappings::erc20_key());
+    let owner: Key = runtime::get_named_arg("owner");
+    let snapshot_id: u64 = runtime::get_named_arg("snapshot_id");
+    let args: RuntimeArgs = runtime_args! {
+        "owner" => owner,
+        "snapshot_id" => snapshot_id,
+    };
+
+    let ret: U256 = runtime::call_contract(erc20_address, "balance_of_at", args);
+    mappings::set_key(&mappings::balance_of_at_key(), ret);
+}
+
+#[no_mangle]
+fn total_supply_at() {
+    let erc20_address: ContractHash = mappings::get_key(&mappings::erc20_key());
+    let snapshot_id: u64 = runtime::get_named_arg("snapshot_id");
+    let args: RuntimeArgs = runtime_args! {
+        "snapshot_id" => snapshot_id,
+    };
+
+    let ret: U256 = runtime::call_contract(erc20_address, "total_supply_at", args);
+    mappings::set_key(&mappings::total_supply_at_key(), ret);
+}
+
+#[no_mangle]
+fn get_past_votes() {
+    let erc20_address: ContractHash = mappings::get_key(&mappings::erc20_key());
+    let account: Key = runtime::get_named_arg("account");
+    let timestamp: u64 = runtime::get_named_arg("timestamp");
+    let args: RuntimeArgs = runtime_args! {
+        "account" => account,
+        "timestamp" => timestamp,
+    };
+
+    let ret: U256 = runtime::call_contract(erc20_address, "get_past_votes", args);
+    mappings::set_key(&mappings::get_past_votes_key(), ret);
+}
+
+#[no_mangle]
+fn on_token_received() {
+    let from: Key = runtime::get_named_arg("from");
+    let amount: U256 = runtime::get_named_arg("amount");
+    let data: String = runtime::get_named_arg("data");
+    mappings::set_key(&mappings::received_from_key(), from);
+    mappings::set_key(&mappings::received_amount_key(), amount);
+    mappings::set_key(&mappings::received_data_key(), data);
+}
+
 fn get_entry_points() -> EntryPoints {
     let mut entry_points = EntryPoints::new();
     entry_points.add_entry_point(EntryPoint::new(
@@ -195,6 +245,45 @@ fn get_entry_points() -> EntryPoints {
         EntryPointAccess::Public,
         EntryPointType::Contract,
     ));
+    entry_points.add_entry_point(EntryPoint::new(
+        "balance_of_at",
+        vec![
+            Parameter::new("owner", Key::cl_type()),
+            Parameter::new("snapshot_id", u64::cl_type()),
+        ],
+        <()>::cl_type(),
+        EntryPointAccess::Public,
+        EntryPointType::Contract,
+    ));
+    entry_points.add_entry_point(EntryPoint::new(
+        "total_supply_at",
+        vec![Parameter::new("snapshot_id", u64::cl_type())],
+        <()>::cl_type(),
+        EntryPointAccess::Public,
+        EntryPointType::Contract,
+    ));
+    entry_points.add_entry_point(EntryPoint::new(
+        "get_past_votes",
+        vec![
+            Parameter::new("account", Key::cl_type()),
+            Parameter::new("timestamp", u64::cl_type()),
+        ],
+        <()>::cl_type(),
+        EntryPointAccess::Public,
+        EntryPointType::Contract,
+    ));
+    entry_points.add_entry_point(EntryPoint::new(
+        "on_token_received",
+        vec![
+            Parameter::new("operator", Key::cl_type()),
+            Parameter::new("from", Key::cl_type()),
+            Parameter::new("amount", U256::cl_type()),
+            Parameter::new("data", String::cl_type()),
+        ],
+        <()>::cl_type(),
+        EntryPointAccess::Public,
+        EntryPointType::Contract,
+    ));
     entry_points
 }
 
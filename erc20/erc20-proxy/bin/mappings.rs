@@ -66,3 +66,27 @@ pub fn increase_allowance_key() -> String {
 pub fn decrease_allowance_key() -> String {
     format!("decrease_allowance_result")
 }
+
+pub fn balance_of_at_key() -> String {
+    format!("balance_of_at")
+}
+
+pub fn total_supply_at_key() -> String {
+    format!("total_supply_at")
+}
+
+pub fn get_past_votes_key() -> String {
+    format!("get_past_votes")
+}
+
+pub fn received_from_key() -> String {
+    format!("received_from")
+}
+
+pub fn received_amount_key() -> String {
+    format!("received_amount")
+}
+
+pub fn received_data_key() -> String {
+    format!("received_data")
+}
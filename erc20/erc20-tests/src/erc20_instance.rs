@@ -6,11 +6,13 @@ use blake2::{
 };
 use casper_types::{
     account::AccountHash, bytesrepr::ToBytes, runtime_args, CLTyped, ContractPackageHash, Key,
-    RuntimeArgs, U256,
+    PublicKey, RuntimeArgs, Signature, U256,
 };
-use test_env::{TestContract, TestEnv};
+use test_env::{balance_key, TestContract, TestEnv};
 
 pub type TokenId = U256;
+/// Chain name the test tokens bind their permit domain to.
+pub const CHAIN_NAME: &str = "casper-test";
 pub type Meta = BTreeMap<String, String>;
 
 pub struct ERC20Instance(TestContract);
@@ -61,7 +63,36 @@ impl ERC20Instance {
                 "initial_supply" => supply,
                 "name" => name,
                 "symbol" => symbol,
-                "decimals" => decimals
+                "decimals" => decimals,
+                "chain_name" => CHAIN_NAME
+            },
+        )
+    }
+
+    /// Installs the token built with every extension feature, capped at `cap`.
+    #[allow(clippy::too_many_arguments)]
+    pub fn new_extended(
+        env: &TestEnv,
+        contract_name: &str,
+        sender: AccountHash,
+        name: &str,
+        symbol: &str,
+        decimals: u8,
+        supply: U256,
+        cap: U256,
+    ) -> TestContract {
+        TestContract::new(
+            env,
+            "erc20-token-extended.wasm",
+            contract_name,
+            sender,
+            runtime_args! {
+                "initial_supply" => supply,
+                "name" => name,
+                "symbol" => symbol,
+                "decimals" => decimals,
+                "chain_name" => CHAIN_NAME,
+                "cap" => cap
             },
         )
     }
@@ -108,6 +139,10 @@ impl ERC20Instance {
     //     ))
     // }
 
+    pub fn named_keys(&self) -> Vec<String> {
+        self.0.named_keys()
+    }
+
     pub fn transfer<T: Into<Key>>(&self, sender: AccountHash, recipient: T, amount: U256) {
         self.0.call_contract(
             sender,
@@ -119,6 +154,53 @@ impl ERC20Instance {
         );
     }
 
+    pub fn transfer_and_call<T: Into<Key>>(
+        &self,
+        sender: AccountHash,
+        recipient: T,
+        amount: U256,
+        data: &str,
+    ) {
+        self.0.call_contract(
+            sender,
+            "transfer_and_call",
+            runtime_args! {
+                "recipient" => recipient.into(),
+                "amount" => amount,
+                "data" => data.to_string()
+            },
+        );
+    }
+
+    pub fn batch_transfer(&self, sender: AccountHash, recipients: Vec<Key>, amounts: Vec<U256>) {
+        self.0.call_contract(
+            sender,
+            "batch_transfer",
+            runtime_args! {
+                "recipients" => recipients,
+                "amounts" => amounts
+            },
+        );
+    }
+
+    pub fn batch_transfer_from(
+        &self,
+        sender: AccountHash,
+        owner: Key,
+        recipients: Vec<Key>,
+        amounts: Vec<U256>,
+    ) {
+        self.0.call_contract(
+            sender,
+            "batch_transfer_from",
+            runtime_args! {
+                "owner" => owner,
+                "recipients" => recipients,
+                "amounts" => amounts
+            },
+        );
+    }
+
     pub fn transfer_from(&self, sender: AccountHash, owner: Key, recipient: Key, amount: U256) {
         self.0.call_contract(
             sender,
@@ -142,6 +224,192 @@ impl ERC20Instance {
         );
     }
 
+    #[allow(clippy::too_many_arguments)]
+    pub fn permit(
+        &self,
+        sender: AccountHash,
+        public_key: PublicKey,
+        signature: Signature,
+        owner: Key,
+        spender: Key,
+        value: U256,
+        deadline: u64,
+    ) {
+        self.0.call_contract(
+            sender,
+            "permit",
+            runtime_args! {
+                "public" => public_key,
+                "signature" => signature,
+                "owner" => owner,
+                "spender" => spender,
+                "value" => value,
+                "deadline" => deadline
+            },
+        );
+    }
+
+    #[allow(clippy::too_many_arguments)]
+    pub fn transfer_with_authorization(
+        &self,
+        sender: AccountHash,
+        public_key: PublicKey,
+        signature: Signature,
+        from: Key,
+        to: Key,
+        value: U256,
+        valid_after: u64,
+        valid_before: u64,
+        nonce: [u8; 32],
+        block_time: u64,
+    ) {
+        self.0.call_contract_with_block_time(
+            sender,
+            "transfer_with_authorization",
+            runtime_args! {
+                "public" => public_key,
+                "signature" => signature,
+                "from" => from,
+                "to" => to,
+                "value" => value,
+                "valid_after" => valid_after,
+                "valid_before" => valid_before,
+                "nonce" => nonce
+            },
+            block_time,
+        );
+    }
+
+    #[allow(clippy::too_many_arguments)]
+    pub fn receive_with_authorization(
+        &self,
+        sender: AccountHash,
+        public_key: PublicKey,
+        signature: Signature,
+        from: Key,
+        to: Key,
+        value: U256,
+        valid_after: u64,
+        valid_before: u64,
+        nonce: [u8; 32],
+        block_time: u64,
+    ) {
+        self.0.call_contract_with_block_time(
+            sender,
+            "receive_with_authorization",
+            runtime_args! {
+                "public" => public_key,
+                "signature" => signature,
+                "from" => from,
+                "to" => to,
+                "value" => value,
+                "valid_after" => valid_after,
+                "valid_before" => valid_before,
+                "nonce" => nonce
+            },
+            block_time,
+        );
+    }
+
+    pub fn cancel_authorization(
+        &self,
+        sender: AccountHash,
+        public_key: PublicKey,
+        signature: Signature,
+        authorizer: Key,
+        nonce: [u8; 32],
+    ) {
+        self.0.call_contract(
+            sender,
+            "cancel_authorization",
+            runtime_args! {
+                "public" => public_key,
+                "signature" => signature,
+                "authorizer" => authorizer,
+                "nonce" => nonce
+            },
+        );
+    }
+
+    pub fn transfer_at<T: Into<Key>>(
+        &self,
+        sender: AccountHash,
+        recipient: T,
+        amount: U256,
+        block_time: u64,
+    ) {
+        self.0.call_contract_with_block_time(
+            sender,
+            "transfer",
+            runtime_args! {
+                "recipient" => recipient.into(),
+                "amount" => amount
+            },
+            block_time,
+        );
+    }
+
+    pub fn migrate_balances(&self, sender: AccountHash, owners: Vec<Key>) {
+        self.0.call_contract(
+            sender,
+            "migrate_balances",
+            runtime_args! {
+                "owners" => owners
+            },
+        );
+    }
+
+    pub fn delegate<T: Into<Key>>(&self, sender: AccountHash, delegatee: T, block_time: u64) {
+        self.0.call_contract_with_block_time(
+            sender,
+            "delegate",
+            runtime_args! {
+                "delegatee" => delegatee.into()
+            },
+            block_time,
+        );
+    }
+
+    pub fn delegate_by_sig(
+        &self,
+        sender: AccountHash,
+        public_key: PublicKey,
+        signature: Signature,
+        delegatee: Key,
+        expiry: u64,
+        block_time: u64,
+    ) {
+        self.0.call_contract_with_block_time(
+            sender,
+            "delegate_by_sig",
+            runtime_args! {
+                "public" => public_key,
+                "signature" => signature,
+                "delegatee" => delegatee,
+                "expiry" => expiry
+            },
+            block_time,
+        );
+    }
+
+    pub fn get_past_votes_fn<T: Into<Key>>(
+        &self,
+        sender: AccountHash,
+        account: T,
+        timestamp: u64,
+        block_time: u64,
+    ) {
+        self.0.call_contract_with_block_time(
+            sender,
+            "get_past_votes",
+            runtime_args! {
+                "account" => account.into(),
+                "timestamp" => timestamp
+            },
+            block_time,
+        );
+    }
+
     pub fn increase_allowance<T: Into<Key>>(&self, sender: AccountHash, spender: T, amount: U256) {
         self.0.call_contract(
             sender,
@@ -196,18 +464,128 @@ impl ERC20Instance {
         );
     }
 
+    pub fn pause(&self, sender: AccountHash) {
+        self.0.call_contract(sender, "pause", runtime_args! {});
+    }
+
+    pub fn unpause(&self, sender: AccountHash) {
+        self.0.call_contract(sender, "unpause", runtime_args! {});
+    }
+
+    pub fn snapshot(&self, sender: AccountHash) {
+        self.0.call_contract(sender, "snapshot", runtime_args! {});
+    }
+
+    pub fn balance_of_at_fn<T: Into<Key>>(&self, sender: AccountHash, owner: T, snapshot_id: u64) {
+        self.0.call_contract(
+            sender,
+            "balance_of_at",
+            runtime_args! {
+                "owner" => owner.into(),
+                "snapshot_id" => snapshot_id
+            },
+        );
+    }
+
+    pub fn total_supply_at_fn(&self, sender: AccountHash, snapshot_id: u64) {
+        self.0.call_contract(
+            sender,
+            "total_supply_at",
+            runtime_args! {
+                "snapshot_id" => snapshot_id
+            },
+        );
+    }
+
+    pub fn grant_role<T: Into<Key>>(&self, sender: AccountHash, role: &str, account: T) {
+        self.0.call_contract(
+            sender,
+            "grant_role",
+            runtime_args! {
+                "role" => role.to_string(),
+                "account" => account.into()
+            },
+        );
+    }
+
+    pub fn revoke_role<T: Into<Key>>(&self, sender: AccountHash, role: &str, account: T) {
+        self.0.call_contract(
+            sender,
+            "revoke_role",
+            runtime_args! {
+                "role" => role.to_string(),
+                "account" => account.into()
+            },
+        );
+    }
+
+    pub fn renounce_role<T: Into<Key>>(&self, sender: AccountHash, role: &str, account: T) {
+        self.0.call_contract(
+            sender,
+            "renounce_role",
+            runtime_args! {
+                "role" => role.to_string(),
+                "account" => account.into()
+            },
+        );
+    }
+
+    pub fn has_role<T: Into<Key>>(&self, role: &str, account: T) -> bool {
+        self.0
+            .query_dictionary(
+                "roles",
+                key_and_value_to_str(&account.into(), &role.to_string()),
+            )
+            .unwrap_or_default()
+    }
+
     pub fn balance_of<T: Into<Key>>(&self, account: T) -> U256 {
         self.0
-            .query_dictionary("balances", key_to_str(&account.into()))
+            .query_dictionary("balances", balance_key(&account.into()))
             .unwrap_or_default()
     }
 
     pub fn nonce<T: Into<Key>>(&self, account: T) -> U256 {
         self.0
-            .query_dictionary("nonce", key_to_str(&account.into()))
+            .query_dictionary("nonces", key_to_str(&account.into()))
             .unwrap_or_default()
     }
 
+    pub fn authorization_used<T: Into<Key>>(&self, authorizer: T, nonce: [u8; 32]) -> bool {
+        self.0
+            .query_dictionary(
+                "authorizations",
+                key_and_value_to_str(&authorizer.into(), &nonce),
+            )
+            .unwrap_or_default()
+    }
+
+    pub fn delegates<T: Into<Key>>(&self, account: T) -> Option<Key> {
+        self.0
+            .query_dictionary("delegates", key_to_str(&account.into()))
+    }
+
+    pub fn checkpoints<T: Into<Key>>(&self, account: T) -> Vec<(u64, U256)> {
+        let account: Key = account.into();
+        let len: u32 = self
+            .0
+            .query_dictionary("num_checkpoints", key_to_str(&account))
+            .unwrap_or_default();
+        (0..len)
+            .map(|index| {
+                self.0
+                    .query_dictionary("checkpoints", key_and_value_to_str(&account, &index))
+                    .unwrap()
+            })
+            .collect()
+    }
+
+    pub fn votes<T: Into<Key>>(&self, account: T) -> U256 {
+        self.checkpoints(account)
+            .last()
+            .map_or_else(U256::zero, |(_, votes)| *votes)
+    }
+
     pub fn allowance<T: Into<Key>>(&self, owner: T, spender: T) -> U256 {
         let owner: Key = owner.into();
         let spender: Key = spender.into();
@@ -243,6 +621,26 @@ impl ERC20Instance {
         self.0.query_named_key(String::from("total_supply"))
     }
 
+    pub fn cap(&self) -> U256 {
+        self.0.query_named_key(String::from("cap"))
+    }
+
+    pub fn paused(&self) -> bool {
+        self.0.query_named_key(String::from("paused"))
+    }
+
+    pub fn current_snapshot_id(&self) -> u64 {
+        self.0.query_named_key(String::from("current_snapshot_id"))
+    }
+
+    pub fn domain_separator(&self) -> String {
+        self.0.query_named_key(String::from("domain_separator"))
+    }
+
+    pub fn permit_type_hash(&self) -> String {
+        self.0.query_named_key(String::from("permit_type_hash"))
+    }
+
     pub fn contract_package_hash(&self) -> ContractPackageHash {
         self.0
             .query_named_key(String::from("contract_package_hash"))
@@ -256,6 +654,30 @@ impl ERC20Instance {
         self.0.query_named_key("transfer_result".to_string())
     }
 
+    pub fn balance_of_at_res(&self) -> U256 {
+        self.0.query_named_key("balance_of_at".to_string())
+    }
+
+    pub fn total_supply_at_res(&self) -> U256 {
+        self.0.query_named_key("total_supply_at".to_string())
+    }
+
+    pub fn get_past_votes_res(&self) -> U256 {
+        self.0.query_named_key("get_past_votes".to_string())
+    }
+
+    pub fn received_from(&self) -> Key {
+        self.0.query_named_key("received_from".to_string())
+    }
+
+    pub fn received_amount(&self) -> U256 {
+        self.0.query_named_key("received_amount".to_string())
+    }
+
+    pub fn received_data(&self) -> String {
+        self.0.query_named_key("received_data".to_string())
+    }
+
     pub fn package_hash_result(&self) -> ContractPackageHash {
         self.0.query_named_key("package_hash".to_string())
     }
@@ -1,12 +1,14 @@
-use casper_types::{account::AccountHash, Key, U256};
+use casper_types::{account::AccountHash, crypto, Key, PublicKey, SecretKey, Signature, U256};
+use renvm_sig::{hash_message, keccak256};
 use test_env::{TestContract, TestEnv};
 
-use crate::erc20_instance::ERC20Instance;
+use crate::erc20_instance::{ERC20Instance, CHAIN_NAME};
 
 const NAME: &str = "ERC20";
 const SYMBOL: &str = "ERC";
 const DECIMALS: u8 = 8;
 const INIT_TOTAL_SUPPLY: u64 = 1000;
+const PERMIT_DEADLINE: u64 = 4_000_000_000;
 
 fn deploy() -> (
     TestEnv,
@@ -46,10 +48,7 @@ fn test_deploy() {
     assert_eq!(token.name(), NAME);
     assert_eq!(token.symbol(), SYMBOL);
     // assert_eq!(token.meta(), meta::contract_meta());
-    assert_eq!(
-        token.total_supply(),
-        (INIT_TOTAL_SUPPLY + INIT_TOTAL_SUPPLY).into()
-    );
+    assert_eq!(token.total_supply(), INIT_TOTAL_SUPPLY.into());
     assert_eq!(token.decimals(), DECIMALS);
     assert_eq!(token.balance_of(owner), INIT_TOTAL_SUPPLY.into());
     assert_eq!(token.balance_of(user), 0.into());
@@ -123,6 +122,165 @@ fn test_erc20_transfer() {
     }
 }
 
+#[test]
+fn test_erc20_migrate_balances_keeps_cep18_balances() {
+    let (env, token, owner, _, _) = deploy();
+    let user = env.next_user();
+    token.transfer(owner, user, 100.into());
+
+    // balances written by the shared core already use the CEP-18 item key
+    token.migrate_balances(
+        user,
+        vec![owner.into(), user.into(), env.next_user().into()],
+    );
+    assert_eq!(token.balance_of(owner), (INIT_TOTAL_SUPPLY - 100).into());
+    assert_eq!(token.balance_of(user), 100.into());
+}
+
+#[test]
+fn test_erc20_transfer_and_call() {
+    let (_, token, owner, proxy, _) = deploy();
+    let package_hash = proxy.package_hash_result();
+    let amount: U256 = 100.into();
+
+    // the proxy's on_token_received records what it was called with
+    token.transfer_and_call(owner, package_hash, amount, "deposit");
+
+    assert_eq!(token.balance_of(package_hash), amount);
+    assert_eq!(
+        token.balance_of(owner),
+        U256::from(INIT_TOTAL_SUPPLY) - amount
+    );
+    assert_eq!(proxy.received_from(), Key::from(owner));
+    assert_eq!(proxy.received_amount(), amount);
+    assert_eq!(proxy.received_data(), "deposit");
+}
+
+#[test]
+fn test_erc20_transfer_and_call_to_account() {
+    let (env, token, owner, _, _) = deploy();
+    let user = env.next_user();
+    let amount: U256 = 100.into();
+
+    token.transfer_and_call(owner, user, amount, "");
+
+    assert_eq!(token.balance_of(user), amount);
+}
+
+#[test]
+#[should_panic]
+fn test_erc20_transfer_and_call_to_contract_without_hook() {
+    let (_, token, owner, _, _) = deploy();
+    let package_hash = token.contract_package_hash();
+
+    token.transfer_and_call(owner, package_hash, 100.into(), "");
+}
+
+#[test]
+fn test_erc20_batch_transfer() {
+    let (env, token, owner, _, _) = deploy();
+    let user1 = env.next_user();
+    let user2 = env.next_user();
+
+    token.batch_transfer(
+        owner,
+        vec![user1.into(), user2.into(), user1.into()],
+        vec![100.into(), 200.into(), 50.into()],
+    );
+
+    assert_eq!(token.balance_of(owner), (INIT_TOTAL_SUPPLY - 350).into());
+    assert_eq!(token.balance_of(user1), 150.into());
+    assert_eq!(token.balance_of(user2), 200.into());
+}
+
+#[test]
+#[should_panic]
+fn test_erc20_batch_transfer_length_mismatch() {
+    let (env, token, owner, _, _) = deploy();
+    let user = env.next_user();
+
+    token.batch_transfer(owner, vec![user.into()], vec![100.into(), 200.into()]);
+}
+
+#[test]
+#[should_panic]
+fn test_erc20_batch_transfer_too_much() {
+    let (env, token, owner, _, _) = deploy();
+    let user1 = env.next_user();
+    let user2 = env.next_user();
+
+    // the first transfer alone would succeed, the batch does not
+    token.batch_transfer(
+        owner,
+        vec![user1.into(), user2.into()],
+        vec![INIT_TOTAL_SUPPLY.into(), 1.into()],
+    );
+}
+
+#[test]
+#[should_panic]
+fn test_erc20_batch_transfer_to_sender() {
+    let (env, token, owner, _, _) = deploy();
+    let user = env.next_user();
+
+    token.batch_transfer(
+        owner,
+        vec![user.into(), owner.into()],
+        vec![100.into(), 100.into()],
+    );
+}
+
+#[test]
+#[should_panic]
+fn test_erc20_batch_transfer_zero_amount() {
+    let (env, token, owner, _, _) = deploy();
+    let user1 = env.next_user();
+    let user2 = env.next_user();
+
+    token.batch_transfer(
+        owner,
+        vec![user1.into(), user2.into()],
+        vec![100.into(), 0.into()],
+    );
+}
+
+#[test]
+fn test_erc20_batch_transfer_from() {
+    let (env, token, owner, _, _) = deploy();
+    let spender = env.next_user();
+    let user1 = env.next_user();
+    let user2 = env.next_user();
+
+    token.approve(owner, spender, 500.into());
+    token.batch_transfer_from(
+        spender,
+        owner.into(),
+        vec![user1.into(), user2.into()],
+        vec![100.into(), 300.into()],
+    );
+
+    assert_eq!(token.balance_of(user1), 100.into());
+    assert_eq!(token.balance_of(user2), 300.into());
+    assert_eq!(token.allowance(owner, spender), 100.into());
+}
+
+#[test]
+#[should_panic]
+fn test_erc20_batch_transfer_from_over_allowance() {
+    let (env, token, owner, _, _) = deploy();
+    let spender = env.next_user();
+    let user1 = env.next_user();
+    let user2 = env.next_user();
+
+    token.approve(owner, spender, 300.into());
+    token.batch_transfer_from(
+        spender,
+        owner.into(),
+        vec![user1.into(), user2.into()],
+        vec![200.into(), 200.into()],
+    );
+}
+
 #[test]
 fn test_erc20_transfer_with_same_sender_and_recipient() {
     let (env, token, owner, proxy, _proxy2) = deploy();
@@ -262,3 +420,966 @@ fn test_erc20_decrease_allowance() {
         Err(e) => assert!(false, "Decrease Allowance Failed ERROR:{}", e),
     }
 }
+
+#[test]
+fn test_erc20_deployer_holds_admin_and_minter_roles() {
+    let (env, token, owner, _, _) = deploy();
+    let user = env.next_user();
+    assert!(token.has_role("DEFAULT_ADMIN", owner));
+    assert!(token.has_role("MINTER", owner));
+    assert!(!token.has_role("MINTER", user));
+}
+
+#[test]
+#[should_panic]
+fn test_erc20_mint_without_minter_role() {
+    let (env, token, _, _, _) = deploy();
+    let user = env.next_user();
+    token.mint(user, user, 10.into());
+}
+
+#[test]
+#[should_panic]
+fn test_erc20_burn_other_account_without_minter_role() {
+    let (env, token, owner, _, _) = deploy();
+    let user = env.next_user();
+    token.burn(user, owner, 10.into());
+}
+
+#[test]
+fn test_erc20_grant_and_revoke_minter_role() {
+    let (env, token, owner, _, _) = deploy();
+    let user = env.next_user();
+    let amount: U256 = 10.into();
+    token.grant_role(owner, "MINTER", user);
+    assert!(token.has_role("MINTER", user));
+    token.mint(user, user, amount);
+    assert_eq!(token.balance_of(user), amount);
+    token.revoke_role(owner, "MINTER", user);
+    assert!(!token.has_role("MINTER", user));
+}
+
+#[test]
+#[should_panic]
+fn test_erc20_grant_role_without_admin_role() {
+    let (env, token, _, _, _) = deploy();
+    let user = env.next_user();
+    token.grant_role(user, "MINTER", user);
+}
+
+#[test]
+fn test_erc20_renounce_role() {
+    let (_, token, owner, _, _) = deploy();
+    token.renounce_role(owner, "MINTER", owner);
+    assert!(!token.has_role("MINTER", owner));
+    assert!(token.has_role("DEFAULT_ADMIN", owner));
+}
+
+/// Signs the digest `permit` checks for the given arguments at the owner's current nonce,
+/// returning the public key and the signature a wallet would hand over.
+fn sign_permit(
+    token: &ERC20Instance,
+    secret_key: &SecretKey,
+    spender: Key,
+    value: U256,
+) -> (PublicKey, Signature) {
+    let public_key: PublicKey = secret_key.into();
+    let owner = Key::from(public_key.to_account_hash());
+    let nonce = token.nonce(owner);
+    let data = format!(
+        "{}{}{}{}{}{}",
+        token.permit_type_hash(),
+        owner,
+        spender,
+        value,
+        nonce,
+        PERMIT_DEADLINE
+    );
+    let hash = hex::encode(keccak256(data.as_bytes()));
+    let digest = hash_message(format!("{}{}", token.domain_separator(), hash));
+    let signature = crypto::sign(digest, secret_key, &public_key);
+    (public_key, signature)
+}
+
+#[test]
+fn test_erc20_permit_ed25519() {
+    let (env, token, _, _, _) = deploy();
+    let spender = env.next_user();
+    let secret_key = SecretKey::ed25519_from_bytes([42u8; 32]).unwrap();
+    let (public_key, signature) = sign_permit(&token, &secret_key, spender.into(), 500.into());
+    let owner = Key::from(public_key.to_account_hash());
+    token.permit(
+        spender,
+        public_key,
+        signature,
+        owner,
+        spender.into(),
+        500.into(),
+        PERMIT_DEADLINE,
+    );
+    assert_eq!(token.allowance(owner, spender.into()), 500.into());
+}
+
+#[test]
+fn test_erc20_permit_emits_permit_used() {
+    let (env, token, _, _, _) = deploy();
+    let spender = env.next_user();
+    let secret_key = SecretKey::ed25519_from_bytes([42u8; 32]).unwrap();
+    let (public_key, signature) = sign_permit(&token, &secret_key, spender.into(), 500.into());
+    let owner = Key::from(public_key.to_account_hash());
+    token.permit(
+        spender,
+        public_key,
+        signature,
+        owner,
+        spender.into(),
+        500.into(),
+        PERMIT_DEADLINE,
+    );
+    let events = env.last_events();
+    let permit_used = events
+        .iter()
+        .find(|event| event["event_type"] == "erc20_permit_used")
+        .unwrap();
+    assert_eq!(permit_used["owner"], owner.to_string());
+    assert_eq!(permit_used["spender"], Key::from(spender).to_string());
+    assert_eq!(permit_used["value"], "500");
+    assert_eq!(permit_used["nonce"], "0");
+}
+
+#[test]
+fn test_erc20_permit_writes_no_debug_keys() {
+    let (env, token, _, _, _) = deploy();
+    let spender = env.next_user();
+    let secret_key = SecretKey::ed25519_from_bytes([42u8; 32]).unwrap();
+    let (public_key, signature) = sign_permit(&token, &secret_key, spender.into(), 500.into());
+    let owner = Key::from(public_key.to_account_hash());
+    token.permit(
+        spender,
+        public_key,
+        signature,
+        owner,
+        spender.into(),
+        500.into(),
+        PERMIT_DEADLINE,
+    );
+    // the digest and verification keys are only written with the debug-keys feature
+    assert!(!token
+        .named_keys()
+        .iter()
+        .any(|name| name.starts_with("digest_") || name.starts_with("VERIFY")));
+}
+
+#[test]
+fn test_erc20_permit_secp256k1() {
+    let (env, token, _, _, _) = deploy();
+    let spender = env.next_user();
+    let secret_key = SecretKey::secp256k1_from_bytes([42u8; 32]).unwrap();
+    let (public_key, signature) = sign_permit(&token, &secret_key, spender.into(), 500.into());
+    let owner = Key::from(public_key.to_account_hash());
+    token.permit(
+        spender,
+        public_key,
+        signature,
+        owner,
+        spender.into(),
+        500.into(),
+        PERMIT_DEADLINE,
+    );
+    assert_eq!(token.allowance(owner, spender.into()), 500.into());
+}
+
+#[test]
+#[should_panic]
+fn test_erc20_permit_with_key_of_another_owner() {
+    let (env, token, owner, _, _) = deploy();
+    let spender = env.next_user();
+    let secret_key = SecretKey::ed25519_from_bytes([42u8; 32]).unwrap();
+    let (public_key, signature) = sign_permit(&token, &secret_key, spender.into(), 500.into());
+    // a valid signature, but not by the owner whose tokens it approves
+    token.permit(
+        spender,
+        public_key,
+        signature,
+        owner.into(),
+        spender.into(),
+        500.into(),
+        PERMIT_DEADLINE,
+    );
+}
+
+#[test]
+#[should_panic]
+fn test_erc20_permit_with_tampered_value() {
+    let (env, token, _, _, _) = deploy();
+    let spender = env.next_user();
+    let secret_key = SecretKey::secp256k1_from_bytes([42u8; 32]).unwrap();
+    let (public_key, signature) = sign_permit(&token, &secret_key, spender.into(), 500.into());
+    let owner = Key::from(public_key.to_account_hash());
+    token.permit(
+        spender,
+        public_key,
+        signature,
+        owner,
+        spender.into(),
+        5000.into(),
+        PERMIT_DEADLINE,
+    );
+}
+
+#[test]
+fn test_erc20_domain_separator() {
+    let (_, token, _, _, _) = deploy();
+    let eip_712_domain =
+        "EIP712Domain(string name,string version,string chainName,bytes32 verifyingPackage)";
+    let data = format!(
+        "{}{}{}{}{}",
+        hex::encode(keccak256(eip_712_domain.as_bytes())),
+        hex::encode(keccak256(NAME.as_bytes())),
+        hex::encode(keccak256("1".as_bytes())),
+        hex::encode(keccak256(CHAIN_NAME.as_bytes())),
+        hex::encode(token.contract_package_hash().value())
+    );
+    assert_eq!(
+        token.domain_separator(),
+        hex::encode(keccak256(data.as_bytes()))
+    );
+}
+
+#[test]
+fn test_erc20_permit_uses_owner_nonce() {
+    let (env, token, _, _, _) = deploy();
+    let spender = env.next_user();
+    let secret_key = SecretKey::ed25519_from_bytes([42u8; 32]).unwrap();
+    let (public_key, signature) = sign_permit(&token, &secret_key, spender.into(), 500.into());
+    let owner = Key::from(public_key.to_account_hash());
+    token.permit(
+        spender,
+        public_key.clone(),
+        signature,
+        owner,
+        spender.into(),
+        500.into(),
+        PERMIT_DEADLINE,
+    );
+    assert_eq!(token.nonce(owner), 1.into());
+    assert_eq!(token.nonce(spender), 0.into());
+
+    // the next permit is signed at the owner's new nonce, whoever submits it
+    let relayer = env.next_user();
+    let (_, signature) = sign_permit(&token, &secret_key, spender.into(), 700.into());
+    token.permit(
+        relayer,
+        public_key,
+        signature,
+        owner,
+        spender.into(),
+        700.into(),
+        PERMIT_DEADLINE,
+    );
+    assert_eq!(token.allowance(owner, spender.into()), 700.into());
+    assert_eq!(token.nonce(owner), 2.into());
+}
+
+#[test]
+#[should_panic]
+fn test_erc20_permit_replayed() {
+    let (env, token, _, _, _) = deploy();
+    let spender = env.next_user();
+    let secret_key = SecretKey::ed25519_from_bytes([42u8; 32]).unwrap();
+    let (public_key, signature) = sign_permit(&token, &secret_key, spender.into(), 500.into());
+    let owner = Key::from(public_key.to_account_hash());
+    for _ in 0..2 {
+        token.permit(
+            spender,
+            public_key.clone(),
+            signature.clone(),
+            owner,
+            spender.into(),
+            500.into(),
+            PERMIT_DEADLINE,
+        );
+    }
+}
+
+const TRANSFER_WITH_AUTHORIZATION: &str = "TransferWithAuthorization(address from,address to,uint256 value,uint256 validAfter,uint256 validBefore,bytes32 nonce)";
+const RECEIVE_WITH_AUTHORIZATION: &str = "ReceiveWithAuthorization(address from,address to,uint256 value,uint256 validAfter,uint256 validBefore,bytes32 nonce)";
+// block time, in milliseconds, authorizations are submitted at
+const NOW: u64 = 1_000_000_000;
+
+/// Signs the digest an authorization of `authorization_type` is checked against, returning the
+/// public key and signature along with the account the tokens are moved from.
+#[allow(clippy::too_many_arguments)]
+fn sign_authorization(
+    token: &ERC20Instance,
+    secret_key: &SecretKey,
+    authorization_type: &str,
+    to: Key,
+    value: U256,
+    valid_after: u64,
+    valid_before: u64,
+    nonce: [u8; 32],
+) -> (PublicKey, Signature, Key) {
+    let public_key: PublicKey = secret_key.into();
+    let from = Key::from(public_key.to_account_hash());
+    let data = format!(
+        "{}{}{}{}{}{}{}",
+        hex::encode(keccak256(authorization_type.as_bytes())),
+        from,
+        to,
+        value,
+        valid_after,
+        valid_before,
+        hex::encode(nonce)
+    );
+    let hash = hex::encode(keccak256(data.as_bytes()));
+    let digest = hash_message(format!("{}{}", token.domain_separator(), hash));
+    let signature = crypto::sign(digest, secret_key, &public_key);
+    (public_key, signature, from)
+}
+
+/// Deploys the token and funds the account of an ed25519 key that signs authorizations.
+fn deploy_with_authorizer() -> (TestEnv, ERC20Instance, SecretKey, Key) {
+    let (env, token, owner, _, _) = deploy();
+    let secret_key = SecretKey::ed25519_from_bytes([42u8; 32]).unwrap();
+    let public_key: PublicKey = (&secret_key).into();
+    let authorizer = Key::from(public_key.to_account_hash());
+    token.transfer(owner, authorizer, 500.into());
+    (env, token, secret_key, authorizer)
+}
+
+#[test]
+fn test_erc20_transfer_with_authorization() {
+    let (env, token, secret_key, authorizer) = deploy_with_authorizer();
+    let relayer = env.next_user();
+    let recipient = env.next_user();
+    let nonce = [1u8; 32];
+    let (public_key, signature, from) = sign_authorization(
+        &token,
+        &secret_key,
+        TRANSFER_WITH_AUTHORIZATION,
+        recipient.into(),
+        200.into(),
+        0,
+        PERMIT_DEADLINE,
+        nonce,
+    );
+    assert!(!token.authorization_used(authorizer, nonce));
+    token.transfer_with_authorization(
+        relayer,
+        public_key,
+        signature,
+        from,
+        recipient.into(),
+        200.into(),
+        0,
+        PERMIT_DEADLINE,
+        nonce,
+        NOW,
+    );
+    assert_eq!(token.balance_of(authorizer), 300.into());
+    assert_eq!(token.balance_of(recipient), 200.into());
+    assert!(token.authorization_used(authorizer, nonce));
+    // authorizations leave the permit nonce alone
+    assert_eq!(token.nonce(authorizer), 0.into());
+}
+
+#[test]
+fn test_erc20_transfer_with_authorization_nonces_out_of_order() {
+    let (env, token, secret_key, authorizer) = deploy_with_authorizer();
+    let relayer = env.next_user();
+    let recipient = env.next_user();
+    let first = [1u8; 32];
+    let second = [2u8; 32];
+    let signed_first = sign_authorization(
+        &token,
+        &secret_key,
+        TRANSFER_WITH_AUTHORIZATION,
+        recipient.into(),
+        100.into(),
+        0,
+        PERMIT_DEADLINE,
+        first,
+    );
+    let signed_second = sign_authorization(
+        &token,
+        &secret_key,
+        TRANSFER_WITH_AUTHORIZATION,
+        recipient.into(),
+        100.into(),
+        0,
+        PERMIT_DEADLINE,
+        second,
+    );
+    // nonces are random rather than sequential, so they can be used in any order
+    for (nonce, (public_key, signature, from)) in
+        vec![(second, signed_second), (first, signed_first)]
+    {
+        token.transfer_with_authorization(
+            relayer,
+            public_key,
+            signature,
+            from,
+            recipient.into(),
+            100.into(),
+            0,
+            PERMIT_DEADLINE,
+            nonce,
+            NOW,
+        );
+    }
+    assert_eq!(token.balance_of(authorizer), 300.into());
+    assert_eq!(token.balance_of(recipient), 200.into());
+}
+
+#[test]
+#[should_panic]
+fn test_erc20_transfer_with_authorization_replayed() {
+    let (env, token, secret_key, _) = deploy_with_authorizer();
+    let relayer = env.next_user();
+    let recipient = env.next_user();
+    let nonce = [1u8; 32];
+    let (public_key, signature, from) = sign_authorization(
+        &token,
+        &secret_key,
+        TRANSFER_WITH_AUTHORIZATION,
+        recipient.into(),
+        100.into(),
+        0,
+        PERMIT_DEADLINE,
+        nonce,
+    );
+    for _ in 0..2 {
+        token.transfer_with_authorization(
+            relayer,
+            public_key.clone(),
+            signature.clone(),
+            from,
+            recipient.into(),
+            100.into(),
+            0,
+            PERMIT_DEADLINE,
+            nonce,
+            NOW,
+        );
+    }
+}
+
+#[test]
+#[should_panic]
+fn test_erc20_transfer_with_authorization_expired() {
+    let (env, token, secret_key, _) = deploy_with_authorizer();
+    let relayer = env.next_user();
+    let recipient = env.next_user();
+    let nonce = [1u8; 32];
+    let valid_before = NOW / 1000;
+    let (public_key, signature, from) = sign_authorization(
+        &token,
+        &secret_key,
+        TRANSFER_WITH_AUTHORIZATION,
+        recipient.into(),
+        100.into(),
+        0,
+        valid_before,
+        nonce,
+    );
+    token.transfer_with_authorization(
+        relayer,
+        public_key,
+        signature,
+        from,
+        recipient.into(),
+        100.into(),
+        0,
+        valid_before,
+        nonce,
+        NOW,
+    );
+}
+
+#[test]
+#[should_panic]
+fn test_erc20_transfer_with_authorization_not_yet_valid() {
+    let (env, token, secret_key, _) = deploy_with_authorizer();
+    let relayer = env.next_user();
+    let recipient = env.next_user();
+    let nonce = [1u8; 32];
+    let valid_after = NOW / 1000;
+    let (public_key, signature, from) = sign_authorization(
+        &token,
+        &secret_key,
+        TRANSFER_WITH_AUTHORIZATION,
+        recipient.into(),
+        100.into(),
+        valid_after,
+        PERMIT_DEADLINE,
+        nonce,
+    );
+    token.transfer_with_authorization(
+        relayer,
+        public_key,
+        signature,
+        from,
+        recipient.into(),
+        100.into(),
+        valid_after,
+        PERMIT_DEADLINE,
+        nonce,
+        NOW,
+    );
+}
+
+#[test]
+fn test_erc20_receive_with_authorization() {
+    let (env, token, secret_key, authorizer) = deploy_with_authorizer();
+    let recipient = env.next_user();
+    let nonce = [1u8; 32];
+    let (public_key, signature, from) = sign_authorization(
+        &token,
+        &secret_key,
+        RECEIVE_WITH_AUTHORIZATION,
+        recipient.into(),
+        200.into(),
+        0,
+        PERMIT_DEADLINE,
+        nonce,
+    );
+    token.receive_with_authorization(
+        recipient,
+        public_key,
+        signature,
+        from,
+        recipient.into(),
+        200.into(),
+        0,
+        PERMIT_DEADLINE,
+        nonce,
+        NOW,
+    );
+    assert_eq!(token.balance_of(authorizer), 300.into());
+    assert_eq!(token.balance_of(recipient), 200.into());
+}
+
+#[test]
+#[should_panic]
+fn test_erc20_receive_with_authorization_by_relayer() {
+    let (env, token, secret_key, _) = deploy_with_authorizer();
+    let relayer = env.next_user();
+    let recipient = env.next_user();
+    let nonce = [1u8; 32];
+    let (public_key, signature, from) = sign_authorization(
+        &token,
+        &secret_key,
+        RECEIVE_WITH_AUTHORIZATION,
+        recipient.into(),
+        200.into(),
+        0,
+        PERMIT_DEADLINE,
+        nonce,
+    );
+    token.receive_with_authorization(
+        relayer,
+        public_key,
+        signature,
+        from,
+        recipient.into(),
+        200.into(),
+        0,
+        PERMIT_DEADLINE,
+        nonce,
+        NOW,
+    );
+}
+
+#[test]
+#[should_panic]
+fn test_erc20_transfer_with_canceled_authorization() {
+    let (env, token, secret_key, authorizer) = deploy_with_authorizer();
+    let relayer = env.next_user();
+    let recipient = env.next_user();
+    let nonce = [1u8; 32];
+    let (public_key, signature, from) = sign_authorization(
+        &token,
+        &secret_key,
+        TRANSFER_WITH_AUTHORIZATION,
+        recipient.into(),
+        100.into(),
+        0,
+        PERMIT_DEADLINE,
+        nonce,
+    );
+    let data = format!(
+        "{}{}{}",
+        hex::encode(keccak256(
+            "CancelAuthorization(address authorizer,bytes32 nonce)".as_bytes()
+        )),
+        authorizer,
+        hex::encode(nonce)
+    );
+    let hash = hex::encode(keccak256(data.as_bytes()));
+    let digest = hash_message(format!("{}{}", token.domain_separator(), hash));
+    let cancel_signature = crypto::sign(digest, &secret_key, &public_key);
+    token.cancel_authorization(
+        relayer,
+        public_key.clone(),
+        cancel_signature,
+        authorizer,
+        nonce,
+    );
+    assert!(token.authorization_used(authorizer, nonce));
+    token.transfer_with_authorization(
+        relayer,
+        public_key,
+        signature,
+        from,
+        recipient.into(),
+        100.into(),
+        0,
+        PERMIT_DEADLINE,
+        nonce,
+        NOW,
+    );
+}
+
+const CAP: u64 = 2000;
+
+/// Deploys the token built with every extension feature, and a proxy reading its snapshots.
+fn deploy_extended() -> (TestEnv, ERC20Instance, AccountHash, ERC20Instance) {
+    let env = TestEnv::new();
+    let owner = env.next_user();
+    let token: TestContract = ERC20Instance::new_extended(
+        &env,
+        NAME,
+        owner,
+        NAME,
+        SYMBOL,
+        DECIMALS,
+        INIT_TOTAL_SUPPLY.into(),
+        CAP.into(),
+    );
+    let proxy: TestContract = ERC20Instance::proxy(&env, Key::Hash(token.contract_hash()), owner);
+    (
+        env,
+        ERC20Instance::instance(token),
+        owner,
+        ERC20Instance::instance(proxy),
+    )
+}
+
+#[test]
+fn test_erc20_capped_mint_up_to_cap() {
+    let (env, token, owner, _) = deploy_extended();
+    let user = env.next_user();
+    assert_eq!(token.cap(), CAP.into());
+
+    token.mint(owner, user, (CAP - INIT_TOTAL_SUPPLY).into());
+
+    assert_eq!(token.total_supply(), CAP.into());
+}
+
+#[test]
+#[should_panic]
+fn test_erc20_capped_mint_over_cap() {
+    let (env, token, owner, _) = deploy_extended();
+    let user = env.next_user();
+
+    token.mint(owner, user, (CAP - INIT_TOTAL_SUPPLY + 1).into());
+}
+
+#[test]
+#[should_panic]
+fn test_erc20_capped_initial_supply_over_cap() {
+    let env = TestEnv::new();
+    let owner = env.next_user();
+    ERC20Instance::new_extended(
+        &env,
+        NAME,
+        owner,
+        NAME,
+        SYMBOL,
+        DECIMALS,
+        (CAP + 1).into(),
+        CAP.into(),
+    );
+}
+
+#[test]
+#[should_panic]
+fn test_erc20_paused_transfer() {
+    let (env, token, owner, _) = deploy_extended();
+    let user = env.next_user();
+
+    token.pause(owner);
+    assert!(token.paused());
+    token.transfer(owner, user, 100.into());
+}
+
+#[test]
+fn test_erc20_unpaused_transfer() {
+    let (env, token, owner, _) = deploy_extended();
+    let user = env.next_user();
+
+    token.pause(owner);
+    token.unpause(owner);
+    assert!(!token.paused());
+    token.transfer(owner, user, 100.into());
+
+    assert_eq!(token.balance_of(user), 100.into());
+}
+
+#[test]
+#[should_panic]
+fn test_erc20_pause_without_pauser_role() {
+    let (env, token, _, _) = deploy_extended();
+    let user = env.next_user();
+
+    token.pause(user);
+}
+
+#[test]
+fn test_erc20_burnable_by_holder_and_spender() {
+    let (env, token, owner, _) = deploy_extended();
+    let spender = env.next_user();
+
+    token.burn(owner, owner, 100.into());
+    token.approve(owner, spender, 200.into());
+    token.burn(spender, owner, 150.into());
+
+    assert_eq!(token.balance_of(owner), (INIT_TOTAL_SUPPLY - 250).into());
+    assert_eq!(token.total_supply(), (INIT_TOTAL_SUPPLY - 250).into());
+    assert_eq!(token.allowance(owner, spender), 50.into());
+}
+
+#[test]
+#[should_panic]
+fn test_erc20_burnable_by_minter_without_allowance() {
+    let (env, token, owner, _) = deploy_extended();
+    let user = env.next_user();
+    token.transfer(owner, user, 100.into());
+
+    // MINTER no longer burns other holders' tokens
+    token.burn(owner, user, 100.into());
+}
+
+#[test]
+fn test_erc20_snapshot() {
+    let (env, token, owner, proxy) = deploy_extended();
+    let user = env.next_user();
+
+    token.transfer(owner, user, 100.into());
+    token.snapshot(owner);
+    assert_eq!(token.current_snapshot_id(), 1);
+    token.transfer(owner, user, 200.into());
+    token.mint(owner, user, 300.into());
+    token.snapshot(owner);
+    token.burn(user, user, 50.into());
+
+    proxy.balance_of_at_fn(owner, user, 1);
+    assert_eq!(proxy.balance_of_at_res(), 100.into());
+    proxy.balance_of_at_fn(owner, owner, 1);
+    assert_eq!(proxy.balance_of_at_res(), (INIT_TOTAL_SUPPLY - 100).into());
+    proxy.total_supply_at_fn(owner, 1);
+    assert_eq!(proxy.total_supply_at_res(), INIT_TOTAL_SUPPLY.into());
+
+    proxy.balance_of_at_fn(owner, user, 2);
+    assert_eq!(proxy.balance_of_at_res(), 600.into());
+    proxy.total_supply_at_fn(owner, 2);
+    assert_eq!(
+        proxy.total_supply_at_res(),
+        (INIT_TOTAL_SUPPLY + 300).into()
+    );
+    assert_eq!(token.balance_of(user), 550.into());
+}
+
+#[test]
+#[should_panic]
+fn test_erc20_balance_of_at_future_snapshot() {
+    let (_, token, owner, proxy) = deploy_extended();
+
+    token.snapshot(owner);
+    proxy.balance_of_at_fn(owner, owner, 2);
+}
+
+const DELEGATION: &str = "Delegation(address delegatee,uint256 nonce,uint256 expiry)";
+
+/// Signs a delegation of the votes of the `secret_key` account to `delegatee`, returning the
+/// public key and signature.
+fn sign_delegation(
+    token: &ERC20Instance,
+    secret_key: &SecretKey,
+    delegatee: Key,
+    expiry: u64,
+) -> (PublicKey, Signature) {
+    let public_key: PublicKey = secret_key.into();
+    let delegator = Key::from(public_key.to_account_hash());
+    let data = format!(
+        "{}{}{}{}",
+        hex::encode(keccak256(DELEGATION.as_bytes())),
+        delegatee,
+        token.nonce(delegator),
+        expiry
+    );
+    let hash = hex::encode(keccak256(data.as_bytes()));
+    let digest = hash_message(format!("{}{}", token.domain_separator(), hash));
+    let signature = crypto::sign(digest, secret_key, &public_key);
+    (public_key, signature)
+}
+
+#[test]
+fn test_erc20_delegate() {
+    let (env, token, owner, _, _) = deploy();
+    let user = env.next_user();
+
+    assert_eq!(token.votes(owner), 0.into());
+    token.delegate(owner, owner, NOW);
+    assert_eq!(token.delegates(owner), Some(owner.into()));
+    assert_eq!(token.votes(owner), INIT_TOTAL_SUPPLY.into());
+
+    // balances that were never delegated carry no votes
+    token.transfer_at(owner, user, 300.into(), NOW + 1000);
+    assert_eq!(token.votes(owner), (INIT_TOTAL_SUPPLY - 300).into());
+    assert_eq!(token.votes(user), 0.into());
+
+    token.delegate(user, owner, NOW + 2000);
+    assert_eq!(token.votes(owner), INIT_TOTAL_SUPPLY.into());
+    assert_eq!(token.checkpoints(owner).len(), 3);
+}
+
+#[test]
+fn test_erc20_redelegate() {
+    let (env, token, owner, _, _) = deploy();
+    let user = env.next_user();
+
+    token.delegate(owner, owner, NOW);
+    token.delegate(owner, user, NOW + 1000);
+    assert_eq!(token.votes(owner), 0.into());
+    assert_eq!(token.votes(user), INIT_TOTAL_SUPPLY.into());
+    assert_eq!(token.delegates(owner), Some(user.into()));
+}
+
+#[test]
+fn test_erc20_mint_and_burn_move_votes() {
+    let (_, token, owner, _, _) = deploy();
+
+    token.delegate(owner, owner, 0);
+    token.mint(owner, owner, 100.into());
+    assert_eq!(token.votes(owner), (INIT_TOTAL_SUPPLY + 100).into());
+    token.burn(owner, owner, 300.into());
+    assert_eq!(token.votes(owner), (INIT_TOTAL_SUPPLY - 200).into());
+    // every change happened at the same block time, so it is a single checkpoint
+    assert_eq!(token.checkpoints(owner).len(), 1);
+}
+
+#[test]
+fn test_erc20_get_past_votes() {
+    let (env, token, owner, proxy, _) = deploy();
+    let user = env.next_user();
+
+    token.delegate(owner, owner, NOW);
+    token.transfer_at(owner, user, 300.into(), NOW + 1000);
+    token.delegate(user, user, NOW + 2000);
+
+    proxy.get_past_votes_fn(owner, owner, NOW - 1, NOW + 3000);
+    assert_eq!(proxy.get_past_votes_res(), 0.into());
+    proxy.get_past_votes_fn(owner, owner, NOW, NOW + 3000);
+    assert_eq!(proxy.get_past_votes_res(), INIT_TOTAL_SUPPLY.into());
+    proxy.get_past_votes_fn(owner, owner, NOW + 500, NOW + 3000);
+    assert_eq!(proxy.get_past_votes_res(), INIT_TOTAL_SUPPLY.into());
+    proxy.get_past_votes_fn(owner, owner, NOW + 1000, NOW + 3000);
+    assert_eq!(proxy.get_past_votes_res(), (INIT_TOTAL_SUPPLY - 300).into());
+    proxy.get_past_votes_fn(owner, user, NOW + 1999, NOW + 3000);
+    assert_eq!(proxy.get_past_votes_res(), 0.into());
+    proxy.get_past_votes_fn(owner, user, NOW + 2000, NOW + 3000);
+    assert_eq!(proxy.get_past_votes_res(), 300.into());
+}
+
+#[test]
+fn test_erc20_get_past_votes_searches_checkpoints() {
+    let (env, token, owner, proxy, _) = deploy();
+    let user = env.next_user();
+
+    token.delegate(owner, owner, NOW);
+    // a checkpoint every second, each 100 votes lower than the one before
+    for step in 1..=6u64 {
+        token.transfer_at(owner, user, 100.into(), NOW + step * 1000);
+    }
+    assert_eq!(token.checkpoints(owner).len(), 7);
+
+    for step in 0..=6u64 {
+        let votes: U256 = (INIT_TOTAL_SUPPLY - step * 100).into();
+        proxy.get_past_votes_fn(owner, owner, NOW + step * 1000, NOW + 10000);
+        assert_eq!(proxy.get_past_votes_res(), votes);
+        proxy.get_past_votes_fn(owner, owner, NOW + step * 1000 + 999, NOW + 10000);
+        assert_eq!(proxy.get_past_votes_res(), votes);
+    }
+}
+
+#[test]
+#[should_panic]
+fn test_erc20_get_past_votes_at_current_time() {
+    let (_, token, owner, proxy, _) = deploy();
+
+    token.delegate(owner, owner, NOW);
+    proxy.get_past_votes_fn(owner, owner, NOW, NOW);
+}
+
+#[test]
+fn test_erc20_delegate_by_sig() {
+    let (env, token, secret_key, delegator) = deploy_with_authorizer();
+    let relayer = env.next_user();
+    let delegatee = env.next_user();
+    let (public_key, signature) =
+        sign_delegation(&token, &secret_key, delegatee.into(), PERMIT_DEADLINE);
+
+    token.delegate_by_sig(
+        relayer,
+        public_key,
+        signature,
+        delegatee.into(),
+        PERMIT_DEADLINE,
+        NOW,
+    );
+    assert_eq!(token.delegates(delegator), Some(delegatee.into()));
+    assert_eq!(token.votes(delegatee), 500.into());
+    assert_eq!(token.nonce(delegator), 1.into());
+}
+
+#[test]
+#[should_panic]
+fn test_erc20_delegate_by_sig_replayed() {
+    let (env, token, secret_key, _) = deploy_with_authorizer();
+    let relayer = env.next_user();
+    let delegatee = env.next_user();
+    let (public_key, signature) =
+        sign_delegation(&token, &secret_key, delegatee.into(), PERMIT_DEADLINE);
+
+    for _ in 0..2 {
+        token.delegate_by_sig(
+            relayer,
+            public_key.clone(),
+            signature.clone(),
+            delegatee.into(),
+            PERMIT_DEADLINE,
+            NOW,
+        );
+    }
+}
+
+#[test]
+#[should_panic]
+fn test_erc20_delegate_by_sig_expired() {
+    let (env, token, secret_key, _) = deploy_with_authorizer();
+    let relayer = env.next_user();
+    let delegatee = env.next_user();
+    let expiry = NOW / 1000 - 1;
+    let (public_key, signature) = sign_delegation(&token, &secret_key, delegatee.into(), expiry);
+
+    token.delegate_by_sig(
+        relayer,
+        public_key,
+        signature,
+        delegatee.into(),
+        expiry,
+        NOW,
+    );
+}
@@ -4,16 +4,19 @@
 #[macro_use]
 extern crate alloc;
 
-use alloc::{boxed::Box, collections::BTreeSet, format, string::String};
+use alloc::{boxed::Box, collections::BTreeSet, format, string::String, vec::Vec};
 use casper_contract::{
     contract_api::{runtime, storage},
     unwrap_or_revert::UnwrapOrRevert,
 };
 use casper_types::{
     runtime_args, CLType, CLTyped, CLValue, ContractHash, ContractPackageHash, EntryPoint,
-    EntryPointAccess, EntryPointType, EntryPoints, Group, Key, Parameter, RuntimeArgs, URef, U256,
+    EntryPointAccess, EntryPointType, EntryPoints, Group, Key, Parameter, PublicKey, RuntimeArgs,
+    Signature, URef, U256,
+};
+use contract_utils::{
+    AccessControl, ContractContext, OnChainContractStorage, Votes, CEP18, MINTER_ROLE,
 };
-use contract_utils::{ContractContext, OnChainContractStorage};
 use erc20::ERC20;
 
 #[derive(Default)]
@@ -25,6 +28,17 @@ impl ContractContext<OnChainContractStorage> for Token {
     }
 }
 
+impl AccessControl<OnChainContractStorage> for Token {}
+impl CEP18<OnChainContractStorage> for Token {
+    fn before_transfer(&mut self, from: Key, to: Key, amount: U256) {
+        ERC20::before_token_transfer(self, from, to, amount);
+    }
+
+    fn after_transfer(&mut self, from: Key, to: Key, amount: U256) {
+        Votes::move_voting_power(self, from, to, amount);
+    }
+}
+impl Votes<OnChainContractStorage> for Token {}
 impl ERC20<OnChainContractStorage> for Token {}
 impl Token {
     fn constructor(
@@ -43,13 +57,12 @@ impl Token {
             name,
             symbol,
             decimals,
-            initial_supply,
             domain_separator,
             permit_type_hash,
             Key::from(contract_hash),
             package_hash,
         );
-        let _ret = ERC20::mint(self, self.get_caller(), initial_supply);
+        CEP18::mint(self, self.get_caller(), initial_supply);
     }
 }
 
@@ -63,6 +76,10 @@ fn constructor() {
     let permit_type_hash: String = runtime::get_named_arg("permit_type_hash");
     let contract_hash: ContractHash = runtime::get_named_arg("contract_hash");
     let package_hash: ContractPackageHash = runtime::get_named_arg("package_hash");
+    if cfg!(feature = "capped") {
+        let cap: U256 = runtime::get_named_arg("cap");
+        erc20::data::set_cap(cap);
+    }
 
     Token::default().constructor(
         name,
@@ -93,6 +110,26 @@ fn transfer() {
     runtime::ret(CLValue::from_t(ret).unwrap_or_revert());
 }
 
+/// This function is to transfer tokens to a recipient and, if it is a contract, call its `on_token_received` entry point in the same deploy
+///
+/// # Parameters
+///
+/// * `recipient` - A Key that holds the account address or contract package hash of the recipient
+///
+/// * `amount` - A U256 that holds the amount for transfer
+///
+/// * `data` - A String that holds data passed on to `on_token_received`
+///
+
+#[no_mangle]
+fn transfer_and_call() {
+    let recipient: Key = runtime::get_named_arg("recipient");
+    let amount: U256 = runtime::get_named_arg("amount");
+    let data: String = runtime::get_named_arg("data");
+    let ret = Token::default().transfer_and_call(recipient, amount, data);
+    runtime::ret(CLValue::from_t(ret).unwrap_or_revert());
+}
+
 /// This function is to transfer tokens against the address that has been approved before by owner
 ///
 /// # Parameters
@@ -124,14 +161,51 @@ fn transfer_from() {
     runtime::ret(CLValue::from_t(ret).unwrap_or_revert());
 }
 
+/// This function is to transfer tokens to several recipients at once, reverting the whole batch if any transfer fails
+///
+/// # Parameters
+///
+/// * `recipients` - A Vec of Keys that holds the account addresses of the recipients
+///
+/// * `amounts` - A Vec of U256 that holds the amount for each recipient, in the same order
+///
+
+#[no_mangle]
+fn batch_transfer() {
+    let recipients: Vec<Key> = runtime::get_named_arg("recipients");
+    let amounts: Vec<U256> = runtime::get_named_arg("amounts");
+    let ret = Token::default().batch_transfer(recipients, amounts);
+    runtime::ret(CLValue::from_t(ret).unwrap_or_revert());
+}
+
+/// This function is to transfer tokens of an owner to several recipients at once against the allowance given by the owner
+///
+/// # Parameters
+///
+/// * `owner` - A Key that holds the account address of the owner
+///
+/// * `recipients` - A Vec of Keys that holds the account addresses of the recipients
+///
+/// * `amounts` - A Vec of U256 that holds the amount for each recipient, in the same order
+///
+
+#[no_mangle]
+fn batch_transfer_from() {
+    let owner: Key = runtime::get_named_arg("owner");
+    let recipients: Vec<Key> = runtime::get_named_arg("recipients");
+    let amounts: Vec<U256> = runtime::get_named_arg("amounts");
+    let ret = Token::default().batch_transfer_from(owner, recipients, amounts);
+    runtime::ret(CLValue::from_t(ret).unwrap_or_revert());
+}
+
 /// This function is to get meta transaction signer and verify if it is equal
 /// to the signer public key or not then call approve.
 ///
 /// # Parameters
 ///
-/// * `public_key` - A string slice that holds the public key of the meta transaction signer,  Subscriber have to get it from running cryptoxide project externally.
+/// * `public` - A PublicKey that holds the ed25519 or secp256k1 key of the owner
 ///
-/// * `signature` - A string slice that holds the signature of the meta transaction,  Subscriber have to get it from running cryptoxide project externally.
+/// * `signature` - A Signature that holds the owner's signature of the permit digest
 ///
 /// * `owner` - A Key that holds the account address of the owner
 ///
@@ -144,8 +218,8 @@ fn transfer_from() {
 
 #[no_mangle]
 fn permit() {
-    let public_key: String = runtime::get_named_arg("public");
-    let signature: String = runtime::get_named_arg("signature");
+    let public_key: PublicKey = runtime::get_named_arg("public");
+    let signature: Signature = runtime::get_named_arg("signature");
     let owner: Key = runtime::get_named_arg("owner");
     let spender: Key = runtime::get_named_arg("spender");
     let value: U256 = runtime::get_named_arg("value");
@@ -153,6 +227,211 @@ fn permit() {
     Token::default().permit(public_key, signature, owner, spender, value, deadline);
 }
 
+/// This function is to move tokens from one account to another with the sender's signed authorization, callable by anyone
+///
+/// # Parameters
+///
+/// * `public` - A PublicKey that holds the ed25519 or secp256k1 key of `from`
+///
+/// * `signature` - A Signature that holds the signature of the authorization
+///
+/// * `from` - A Key that holds the account address tokens are moved from
+///
+/// * `to` - A Key that holds the account address tokens are moved to
+///
+/// * `value` - A U256 that holds the amount to move
+///
+/// * `valid_after` - A u64 that holds the time in seconds after which the authorization is valid
+///
+/// * `valid_before` - A u64 that holds the time in seconds before which the authorization is valid
+///
+/// * `nonce` - A 32 byte array that holds the unique nonce of the authorization
+///
+
+#[no_mangle]
+fn transfer_with_authorization() {
+    let public_key: PublicKey = runtime::get_named_arg("public");
+    let signature: Signature = runtime::get_named_arg("signature");
+    let from: Key = runtime::get_named_arg("from");
+    let to: Key = runtime::get_named_arg("to");
+    let value: U256 = runtime::get_named_arg("value");
+    let valid_after: u64 = runtime::get_named_arg("valid_after");
+    let valid_before: u64 = runtime::get_named_arg("valid_before");
+    let nonce: [u8; 32] = runtime::get_named_arg("nonce");
+    Token::default().transfer_with_authorization(
+        public_key,
+        signature,
+        from,
+        to,
+        value,
+        valid_after,
+        valid_before,
+        nonce,
+    );
+}
+
+/// This function is to move tokens with the sender's signed authorization, only callable by the recipient
+///
+/// # Parameters
+///
+/// * `public` - A PublicKey that holds the ed25519 or secp256k1 key of `from`
+///
+/// * `signature` - A Signature that holds the signature of the authorization
+///
+/// * `from` - A Key that holds the account address tokens are moved from
+///
+/// * `to` - A Key that holds the account address tokens are moved to, which must be the caller
+///
+/// * `value` - A U256 that holds the amount to move
+///
+/// * `valid_after` - A u64 that holds the time in seconds after which the authorization is valid
+///
+/// * `valid_before` - A u64 that holds the time in seconds before which the authorization is valid
+///
+/// * `nonce` - A 32 byte array that holds the unique nonce of the authorization
+///
+
+#[no_mangle]
+fn receive_with_authorization() {
+    let public_key: PublicKey = runtime::get_named_arg("public");
+    let signature: Signature = runtime::get_named_arg("signature");
+    let from: Key = runtime::get_named_arg("from");
+    let to: Key = runtime::get_named_arg("to");
+    let value: U256 = runtime::get_named_arg("value");
+    let valid_after: u64 = runtime::get_named_arg("valid_after");
+    let valid_before: u64 = runtime::get_named_arg("valid_before");
+    let nonce: [u8; 32] = runtime::get_named_arg("nonce");
+    Token::default().receive_with_authorization(
+        public_key,
+        signature,
+        from,
+        to,
+        value,
+        valid_after,
+        valid_before,
+        nonce,
+    );
+}
+
+/// This function is to cancel an authorization that has not been used yet
+///
+/// # Parameters
+///
+/// * `public` - A PublicKey that holds the ed25519 or secp256k1 key of `authorizer`
+///
+/// * `signature` - A Signature that holds the signature of the cancellation
+///
+/// * `authorizer` - A Key that holds the account address that signed the authorization
+///
+/// * `nonce` - A 32 byte array that holds the nonce of the authorization to cancel
+///
+
+#[no_mangle]
+fn cancel_authorization() {
+    let public_key: PublicKey = runtime::get_named_arg("public");
+    let signature: Signature = runtime::get_named_arg("signature");
+    let authorizer: Key = runtime::get_named_arg("authorizer");
+    let nonce: [u8; 32] = runtime::get_named_arg("nonce");
+    Token::default().cancel_authorization(public_key, signature, authorizer, nonce);
+}
+
+/// This function is to return whether an authorizer has used or canceled a nonce
+///
+/// # Parameters
+///
+/// * `authorizer` - A Key that holds the account address of the authorizer
+///
+/// * `nonce` - A 32 byte array that holds the nonce
+///
+
+#[no_mangle]
+fn authorization_state() {
+    let authorizer: Key = runtime::get_named_arg("authorizer");
+    let nonce: [u8; 32] = runtime::get_named_arg("nonce");
+    let ret: bool = Token::default().authorization_state(authorizer, nonce);
+    runtime::ret(CLValue::from_t(ret).unwrap_or_revert());
+}
+
+/// This function is to delegate the caller's votes to delegatee
+///
+/// # Parameters
+///
+/// * `delegatee` - A Key that holds the account address that will vote with the caller's balance
+///
+
+#[no_mangle]
+fn delegate() {
+    let delegatee: Key = runtime::get_named_arg("delegatee");
+    Token::default().delegate(delegatee);
+}
+
+/// This function is to delegate the votes of the signer to delegatee with their signed delegation
+///
+/// # Parameters
+///
+/// * `public` - A PublicKey that holds the ed25519 or secp256k1 key of the delegator
+///
+/// * `signature` - A Signature that holds the signature of the delegation
+///
+/// * `delegatee` - A Key that holds the account address that will vote with the delegator's balance
+///
+/// * `expiry` - A u64 that holds the time in seconds after which the signature is no longer valid
+///
+
+#[no_mangle]
+fn delegate_by_sig() {
+    let public_key: PublicKey = runtime::get_named_arg("public");
+    let signature: Signature = runtime::get_named_arg("signature");
+    let delegatee: Key = runtime::get_named_arg("delegatee");
+    let expiry: u64 = runtime::get_named_arg("expiry");
+    Token::default().delegate_by_sig(public_key, signature, delegatee, expiry);
+}
+
+/// This function is to return the account an account has delegated its votes to
+///
+/// # Parameters
+///
+/// * `account` - A Key that holds the account address of the delegator
+///
+
+#[no_mangle]
+fn delegates() {
+    let account: Key = runtime::get_named_arg("account");
+    let ret: Key = Token::default().delegates(account);
+    runtime::ret(CLValue::from_t(ret).unwrap_or_revert());
+}
+
+/// This function is to return the current votes of an account
+///
+/// # Parameters
+///
+/// * `account` - A Key that holds the account address of the delegate
+///
+
+#[no_mangle]
+fn get_votes() {
+    let account: Key = runtime::get_named_arg("account");
+    let ret: U256 = Token::default().get_votes(account);
+    runtime::ret(CLValue::from_t(ret).unwrap_or_revert());
+}
+
+/// This function is to return the votes an account had at a past block time
+///
+/// # Parameters
+///
+/// * `account` - A Key that holds the account address of the delegate
+///
+/// * `timestamp` - A u64 that holds the block time in milliseconds, which must be in the past
+///
+
+#[no_mangle]
+fn get_past_votes() {
+    let account: Key = runtime::get_named_arg("account");
+    let timestamp: u64 = runtime::get_named_arg("timestamp");
+    let ret: U256 = Token::default().get_past_votes(account, timestamp);
+    runtime::ret(CLValue::from_t(ret).unwrap_or_revert());
+}
+
 /// This function is to approve tokens against the address that user provided
 ///
 /// # Parameters
@@ -180,7 +459,7 @@ fn approve() {
     Token::default().approve(spender, amount);
 }
 
-/// This function is to mint token against the address that user provided
+/// This function is to mint token against the address that user provided, only callable by a MINTER
 ///
 /// # Parameters
 ///
@@ -193,10 +472,12 @@ fn approve() {
 fn mint() {
     let to: Key = runtime::get_named_arg("to");
     let amount: U256 = runtime::get_named_arg("amount");
-    Token::default().mint(to, amount);
+    let mut token = Token::default();
+    token.assert_role(MINTER_ROLE);
+    token.mint(to, amount);
 }
 
-/// This function is to burn token against the address that user provided
+/// This function is to burn token against the address that user provided, callable by the holder or a MINTER, or with the `burnable` feature the holder or a spender with allowance
 ///
 /// # Parameters
 ///
@@ -209,7 +490,96 @@ fn mint() {
 fn burn() {
     let from: Key = runtime::get_named_arg("from");
     let amount: U256 = runtime::get_named_arg("amount");
-    Token::default().burn(from, amount);
+    let mut token = Token::default();
+    if cfg!(feature = "burnable") {
+        token.burn_from(from, amount);
+        return;
+    }
+    if token.get_caller() != from {
+        token.assert_role(MINTER_ROLE);
+    }
+    token.burn(from, amount);
+}
+
+/// This function is to return the maximum total supply, with the `capped` feature
+///
+
+#[cfg(feature = "capped")]
+#[no_mangle]
+fn cap() {
+    let ret: U256 = Token::default().cap();
+    runtime::ret(CLValue::from_t(ret).unwrap_or_revert());
+}
+
+/// This function is to stop transfers, mints and burns, callable by a PAUSER with the `pausable` feature
+///
+
+#[cfg(feature = "pausable")]
+#[no_mangle]
+fn pause() {
+    Token::default().pause();
+}
+
+/// This function is to resume transfers, mints and burns, callable by a PAUSER with the `pausable` feature
+///
+
+#[cfg(feature = "pausable")]
+#[no_mangle]
+fn unpause() {
+    Token::default().unpause();
+}
+
+/// This function is to return whether the token is paused, with the `pausable` feature
+///
+
+#[cfg(feature = "pausable")]
+#[no_mangle]
+fn paused() {
+    let ret: bool = Token::default().paused();
+    runtime::ret(CLValue::from_t(ret).unwrap_or_revert());
+}
+
+/// This function is to take a snapshot of balances and total supply, callable by a SNAPSHOT role holder with the `snapshot` feature
+///
+
+#[cfg(feature = "snapshot")]
+#[no_mangle]
+fn snapshot() {
+    let ret: u64 = Token::default().snapshot();
+    runtime::ret(CLValue::from_t(ret).unwrap_or_revert());
+}
+
+/// This function is to return the balance of owner at a snapshot, with the `snapshot` feature
+///
+/// # Parameters
+///
+/// * `owner` - A Key that holds the account address of the user
+///
+/// * `snapshot_id` - A u64 that holds the id returned by `snapshot`
+///
+
+#[cfg(feature = "snapshot")]
+#[no_mangle]
+fn balance_of_at() {
+    let owner: Key = runtime::get_named_arg("owner");
+    let snapshot_id: u64 = runtime::get_named_arg("snapshot_id");
+    let ret: U256 = Token::default().balance_of_at(owner, snapshot_id);
+    runtime::ret(CLValue::from_t(ret).unwrap_or_revert());
+}
+
+/// This function is to return the total supply at a snapshot, with the `snapshot` feature
+///
+/// # Parameters
+///
+/// * `snapshot_id` - A u64 that holds the id returned by `snapshot`
+///
+
+#[cfg(feature = "snapshot")]
+#[no_mangle]
+fn total_supply_at() {
+    let snapshot_id: u64 = runtime::get_named_arg("snapshot_id");
+    let ret: U256 = Token::default().total_supply_at(snapshot_id);
+    runtime::ret(CLValue::from_t(ret).unwrap_or_revert());
 }
 
 /// This function is to return the Balance  of owner against the address that user provided
@@ -226,6 +596,19 @@ fn balance_of() {
     runtime::ret(CLValue::from_t(ret).unwrap_or_revert());
 }
 
+/// This function is to move balances written before the CEP-18 item keys to them
+///
+/// # Parameters
+///
+/// * `owners` - A Vec<Key> that holds the account addresses whose balances are moved
+///
+
+#[no_mangle]
+fn migrate_balances() {
+    let owners: Vec<Key> = runtime::get_named_arg("owners");
+    Token::default().migrate_balances(owners);
+}
+
 /// This function is to return the Nonce of owner against the address that user provided
 ///
 /// # Parameters
@@ -258,6 +641,15 @@ fn symbol() {
     runtime::ret(CLValue::from_t(ret).unwrap_or_revert());
 }
 
+/// This function is to return the Decimals of contract
+///
+
+#[no_mangle]
+fn decimals() {
+    let ret: u8 = Token::default().decimals();
+    runtime::ret(CLValue::from_t(ret).unwrap_or_revert());
+}
+
 /// This function is to return the Allowance of owner and spender that user provided
 ///
 /// # Parameters
@@ -350,6 +742,71 @@ fn decrease_allowance_js_client() {
     let _ret: Result<(), u32> = Token::default().decrease_allowance(spender, amount);
 }
 
+/// This function is to grant a role to an account, only callable by holders of the role's admin role
+///
+/// # Parameters
+///
+/// * `role` - A String that holds the name of the role
+///
+/// * `account` - A Key that holds the account address or package hash receiving the role
+///
+
+#[no_mangle]
+fn grant_role() {
+    let role: String = runtime::get_named_arg("role");
+    let account: Key = runtime::get_named_arg("account");
+    Token::default().grant_role(role, account);
+}
+
+/// This function is to revoke a role from an account, only callable by holders of the role's admin role
+///
+/// # Parameters
+///
+/// * `role` - A String that holds the name of the role
+///
+/// * `account` - A Key that holds the account address or package hash losing the role
+///
+
+#[no_mangle]
+fn revoke_role() {
+    let role: String = runtime::get_named_arg("role");
+    let account: Key = runtime::get_named_arg("account");
+    Token::default().revoke_role(role, account);
+}
+
+/// This function is to give up a role held by the caller
+///
+/// # Parameters
+///
+/// * `role` - A String that holds the name of the role
+///
+/// * `account` - A Key that must be equal to the caller
+///
+
+#[no_mangle]
+fn renounce_role() {
+    let role: String = runtime::get_named_arg("role");
+    let account: Key = runtime::get_named_arg("account");
+    Token::default().renounce_role(role, account);
+}
+
+/// This function is to check whether an account holds a role
+///
+/// # Parameters
+///
+/// * `role` - A String that holds the name of the role
+///
+/// * `account` - A Key that holds the account address or package hash
+///
+
+#[no_mangle]
+fn has_role() {
+    let role: String = runtime::get_named_arg("role");
+    let account: Key = runtime::get_named_arg("account");
+    let ret: bool = Token::default().has_role(&role, account);
+    runtime::ret(CLValue::from_t(ret).unwrap_or_revert());
+}
+
 /// This function is to fetch a Contract Package Hash
 ///
 
@@ -359,6 +816,15 @@ fn package_hash() {
     runtime::ret(CLValue::from_t(ret).unwrap_or_revert());
 }
 
+/// This function is to return the hex encoded domain separator permits are signed over
+///
+
+#[no_mangle]
+fn domain_separator() {
+    let ret: String = Token::default().get_domain_separator();
+    runtime::ret(CLValue::from_t(ret).unwrap_or_revert());
+}
+
 #[no_mangle]
 fn call() {
     // Contract name must be same for all new versions of the contracts
@@ -376,12 +842,13 @@ fn call() {
 
         let decimals: u8 = runtime::get_named_arg("decimals");
         let initial_supply: U256 = runtime::get_named_arg("initial_supply");
+        let chain_name: String = runtime::get_named_arg("chain_name");
 
         let (domain_separator, permit_type_hash) =
-            Token::default().get_permit_type_and_domain_separator(&name, contract_hash);
+            Token::default().get_permit_type_and_domain_separator(&name, &chain_name, package_hash);
 
         // Prepare constructor args
-        let constructor_args = runtime_args! {
+        let mut constructor_args = runtime_args! {
             "name" => name,
             "symbol" => symbol,
              "decimals" => decimals,
@@ -392,6 +859,10 @@ fn call() {
              "package_hash"=> package_hash
 
         };
+        if cfg!(feature = "capped") {
+            let cap: U256 = runtime::get_named_arg("cap");
+            constructor_args.insert("cap", cap).unwrap_or_revert();
+        }
 
         // Add the constructor group to the package hash with a single URef.
         let constructor_access: URef =
@@ -458,18 +929,22 @@ fn call() {
 
 fn get_entry_points() -> EntryPoints {
     let mut entry_points = EntryPoints::new();
+    let mut constructor_params = vec![
+        Parameter::new("name", String::cl_type()),
+        Parameter::new("symbol", String::cl_type()),
+        Parameter::new("decimals", u8::cl_type()),
+        Parameter::new("initial_supply", U256::cl_type()),
+        Parameter::new("domain_separator", String::cl_type()),
+        Parameter::new("permit_type_hash", String::cl_type()),
+        Parameter::new("contract_hash", ContractHash::cl_type()),
+        Parameter::new("package_hash", ContractPackageHash::cl_type()),
+    ];
+    if cfg!(feature = "capped") {
+        constructor_params.push(Parameter::new("cap", U256::cl_type()));
+    }
     entry_points.add_entry_point(EntryPoint::new(
         "constructor",
-        vec![
-            Parameter::new("name", String::cl_type()),
-            Parameter::new("symbol", String::cl_type()),
-            Parameter::new("decimals", u8::cl_type()),
-            Parameter::new("initial_supply", U256::cl_type()),
-            Parameter::new("domain_separator", String::cl_type()),
-            Parameter::new("permit_type_hash", String::cl_type()),
-            Parameter::new("contract_hash", ContractHash::cl_type()),
-            Parameter::new("package_hash", ContractPackageHash::cl_type()),
-        ],
+        constructor_params,
         <()>::cl_type(),
         EntryPointAccess::Groups(vec![Group::new("constructor")]),
         EntryPointType::Contract,
@@ -488,6 +963,20 @@ fn get_entry_points() -> EntryPoints {
         EntryPointAccess::Public,
         EntryPointType::Contract,
     ));
+    entry_points.add_entry_point(EntryPoint::new(
+        "transfer_and_call",
+        vec![
+            Parameter::new("recipient", Key::cl_type()),
+            Parameter::new("amount", U256::cl_type()),
+            Parameter::new("data", String::cl_type()),
+        ],
+        CLType::Result {
+            ok: Box::new(CLType::Unit),
+            err: Box::new(CLType::U32),
+        },
+        EntryPointAccess::Public,
+        EntryPointType::Contract,
+    ));
     entry_points.add_entry_point(EntryPoint::new(
         "transfer_from",
         vec![
@@ -502,11 +991,38 @@ fn get_entry_points() -> EntryPoints {
         EntryPointAccess::Public,
         EntryPointType::Contract,
     ));
+    entry_points.add_entry_point(EntryPoint::new(
+        "batch_transfer",
+        vec![
+            Parameter::new("recipients", Vec::<Key>::cl_type()),
+            Parameter::new("amounts", Vec::<U256>::cl_type()),
+        ],
+        CLType::Result {
+            ok: Box::new(CLType::Unit),
+            err: Box::new(CLType::U32),
+        },
+        EntryPointAccess::Public,
+        EntryPointType::Contract,
+    ));
+    entry_points.add_entry_point(EntryPoint::new(
+        "batch_transfer_from",
+        vec![
+            Parameter::new("owner", Key::cl_type()),
+            Parameter::new("recipients", Vec::<Key>::cl_type()),
+            Parameter::new("amounts", Vec::<U256>::cl_type()),
+        ],
+        CLType::Result {
+            ok: Box::new(CLType::Unit),
+            err: Box::new(CLType::U32),
+        },
+        EntryPointAccess::Public,
+        EntryPointType::Contract,
+    ));
     entry_points.add_entry_point(EntryPoint::new(
         "permit",
         vec![
-            Parameter::new("public", String::cl_type()),
-            Parameter::new("signature", String::cl_type()),
+            Parameter::new("public", PublicKey::cl_type()),
+            Parameter::new("signature", Signature::cl_type()),
             Parameter::new("owner", Key::cl_type()),
             Parameter::new("spender", Key::cl_type()),
             Parameter::new("value", U256::cl_type()),
@@ -516,6 +1032,103 @@ fn get_entry_points() -> EntryPoints {
         EntryPointAccess::Public,
         EntryPointType::Contract,
     ));
+    entry_points.add_entry_point(EntryPoint::new(
+        "transfer_with_authorization",
+        vec![
+            Parameter::new("public", PublicKey::cl_type()),
+            Parameter::new("signature", Signature::cl_type()),
+            Parameter::new("from", Key::cl_type()),
+            Parameter::new("to", Key::cl_type()),
+            Parameter::new("value", U256::cl_type()),
+            Parameter::new("valid_after", u64::cl_type()),
+            Parameter::new("valid_before", u64::cl_type()),
+            Parameter::new("nonce", <[u8; 32]>::cl_type()),
+        ],
+        <()>::cl_type(),
+        EntryPointAccess::Public,
+        EntryPointType::Contract,
+    ));
+    entry_points.add_entry_point(EntryPoint::new(
+        "receive_with_authorization",
+        vec![
+            Parameter::new("public", PublicKey::cl_type()),
+            Parameter::new("signature", Signature::cl_type()),
+            Parameter::new("from", Key::cl_type()),
+            Parameter::new("to", Key::cl_type()),
+            Parameter::new("value", U256::cl_type()),
+            Parameter::new("valid_after", u64::cl_type()),
+            Parameter::new("valid_before", u64::cl_type()),
+            Parameter::new("nonce", <[u8; 32]>::cl_type()),
+        ],
+        <()>::cl_type(),
+        EntryPointAccess::Public,
+        EntryPointType::Contract,
+    ));
+    entry_points.add_entry_point(EntryPoint::new(
+        "cancel_authorization",
+        vec![
+            Parameter::new("public", PublicKey::cl_type()),
+            Parameter::new("signature", Signature::cl_type()),
+            Parameter::new("authorizer", Key::cl_type()),
+            Parameter::new("nonce", <[u8; 32]>::cl_type()),
+        ],
+        <()>::cl_type(),
+        EntryPointAccess::Public,
+        EntryPointType::Contract,
+    ));
+    entry_points.add_entry_point(EntryPoint::new(
+        "authorization_state",
+        vec![
+            Parameter::new("authorizer", Key::cl_type()),
+            Parameter::new("nonce", <[u8; 32]>::cl_type()),
+        ],
+        bool::cl_type(),
+        EntryPointAccess::Public,
+        EntryPointType::Contract,
+    ));
+    entry_points.add_entry_point(EntryPoint::new(
+        "delegate",
+        vec![Parameter::new("delegatee", Key::cl_type())],
+        <()>::cl_type(),
+        EntryPointAccess::Public,
+        EntryPointType::Contract,
+    ));
+    entry_points.add_entry_point(EntryPoint::new(
+        "delegate_by_sig",
+        vec![
+            Parameter::new("public", PublicKey::cl_type()),
+            Parameter::new("signature", Signature::cl_type()),
+            Parameter::new("delegatee", Key::cl_type()),
+            Parameter::new("expiry", u64::cl_type()),
+        ],
+        <()>::cl_type(),
+        EntryPointAccess::Public,
+        EntryPointType::Contract,
+    ));
+    entry_points.add_entry_point(EntryPoint::new(
+        "delegates",
+        vec![Parameter::new("account", Key::cl_type())],
+        Key::cl_type(),
+        EntryPointAccess::Public,
+        EntryPointType::Contract,
+    ));
+    entry_points.add_entry_point(EntryPoint::new(
+        "get_votes",
+        vec![Parameter::new("account", Key::cl_type())],
+        U256::cl_type(),
+        EntryPointAccess::Public,
+        EntryPointType::Contract,
+    ));
+    entry_points.add_entry_point(EntryPoint::new(
+        "get_past_votes",
+        vec![
+            Parameter::new("account", Key::cl_type()),
+            Parameter::new("timestamp", u64::cl_type()),
+        ],
+        U256::cl_type(),
+        EntryPointAccess::Public,
+        EntryPointType::Contract,
+    ));
     entry_points.add_entry_point(EntryPoint::new(
         "approve",
         vec![
@@ -534,6 +1147,13 @@ fn get_entry_points() -> EntryPoints {
         EntryPointAccess::Public,
         EntryPointType::Contract,
     ));
+    entry_points.add_entry_point(EntryPoint::new(
+        "migrate_balances",
+        vec![Parameter::new("owners", Vec::<Key>::cl_type())],
+        <()>::cl_type(),
+        EntryPointAccess::Public,
+        EntryPointType::Contract,
+    ));
     entry_points.add_entry_point(EntryPoint::new(
         "nonce",
         vec![Parameter::new("owner", Key::cl_type())],
@@ -592,6 +1212,13 @@ fn get_entry_points() -> EntryPoints {
         EntryPointAccess::Public,
         EntryPointType::Contract,
     ));
+    entry_points.add_entry_point(EntryPoint::new(
+        "domain_separator",
+        vec![],
+        String::cl_type(),
+        EntryPointAccess::Public,
+        EntryPointType::Contract,
+    ));
     entry_points.add_entry_point(EntryPoint::new(
         "symbol",
         vec![],
@@ -599,6 +1226,13 @@ fn get_entry_points() -> EntryPoints {
         EntryPointAccess::Public,
         EntryPointType::Contract,
     ));
+    entry_points.add_entry_point(EntryPoint::new(
+        "decimals",
+        vec![],
+        u8::cl_type(),
+        EntryPointAccess::Public,
+        EntryPointType::Contract,
+    ));
     entry_points.add_entry_point(EntryPoint::new(
         "increase_allowance",
         vec![
@@ -645,5 +1279,104 @@ fn get_entry_points() -> EntryPoints {
         EntryPointAccess::Public,
         EntryPointType::Contract,
     ));
+    entry_points.add_entry_point(EntryPoint::new(
+        "grant_role",
+        vec![
+            Parameter::new("role", String::cl_type()),
+            Parameter::new("account", Key::cl_type()),
+        ],
+        <()>::cl_type(),
+        EntryPointAccess::Public,
+        EntryPointType::Contract,
+    ));
+    entry_points.add_entry_point(EntryPoint::new(
+        "revoke_role",
+        vec![
+            Parameter::new("role", String::cl_type()),
+            Parameter::new("account", Key::cl_type()),
+        ],
+        <()>::cl_type(),
+        EntryPointAccess::Public,
+        EntryPointType::Contract,
+    ));
+    entry_points.add_entry_point(EntryPoint::new(
+        "renounce_role",
+        vec![
+            Parameter::new("role", String::cl_type()),
+            Parameter::new("account", Key::cl_type()),
+        ],
+        <()>::cl_type(),
+        EntryPointAccess::Public,
+        EntryPointType::Contract,
+    ));
+    entry_points.add_entry_point(EntryPoint::new(
+        "has_role",
+        vec![
+            Parameter::new("role", String::cl_type()),
+            Parameter::new("account", Key::cl_type()),
+        ],
+        bool::cl_type(),
+        EntryPointAccess::Public,
+        EntryPointType::Contract,
+    ));
+    #[cfg(feature = "capped")]
+    entry_points.add_entry_point(EntryPoint::new(
+        "cap",
+        vec![],
+        U256::cl_type(),
+        EntryPointAccess::Public,
+        EntryPointType::Contract,
+    ));
+    #[cfg(feature = "pausable")]
+    entry_points.add_entry_point(EntryPoint::new(
+        "pause",
+        vec![],
+        <()>::cl_type(),
+        EntryPointAccess::Public,
+        EntryPointType::Contract,
+    ));
+    #[cfg(feature = "pausable")]
+    entry_points.add_entry_point(EntryPoint::new(
+        "unpause",
+        vec![],
+        <()>::cl_type(),
+        EntryPointAccess::Public,
+        EntryPointType::Contract,
+    ));
+    #[cfg(feature = "pausable")]
+    entry_points.add_entry_point(EntryPoint::new(
+        "paused",
+        vec![],
+        bool::cl_type(),
+        EntryPointAccess::Public,
+        EntryPointType::Contract,
+    ));
+    #[cfg(feature = "snapshot")]
+    entry_points.add_entry_point(EntryPoint::new(
+        "snapshot",
+        vec![],
+        u64::cl_type(),
+        EntryPointAccess::Public,
+        EntryPointType::Contract,
+    ));
+    #[cfg(feature = "snapshot")]
+    entry_points.add_entry_point(EntryPoint::new(
+        "balance_of_at",
+        vec![
+            Parameter::new("owner", Key::cl_type()),
+            Parameter::new("snapshot_id", u64::cl_type()),
+        ],
+        U256::cl_type(),
+        EntryPointAccess::Public,
+        EntryPointType::Contract,
+    ));
+    #[cfg(feature = "snapshot")]
+    entry_points.add_entry_point(EntryPoint::new(
+        "total_supply_at",
+        vec![Parameter::new("snapshot_id", u64::cl_type())],
+        U256::cl_type(),
+        EntryPointAccess::Public,
+        EntryPointType::Contract,
+    ));
     entry_points
 }
@@ -8,37 +8,44 @@ use casper_contract::{
     unwrap_or_revert::UnwrapOrRevert,
 };
 use casper_types::{system::CallStackElement, ContractPackageHash, Key, URef, U256};
-use contract_utils::{get_key, key_to_str, set_key, Dict};
+use contract_utils::{get_key, key_and_value_to_str, key_to_str, set_key, Dict};
 
 use crate::event::ERC20Event;
 
-const BALANCES_DICT: &str = "balances";
 pub const NONCES_DICT: &str = "nonces";
-pub const ALLOWANCES_DICT: &str = "allowances";
+pub const AUTHORIZATIONS_DICT: &str = "authorizations";
+pub const ACCOUNT_SNAPSHOTS_DICT: &str = "account_snapshots";
 
-pub const NAME: &str = "name";
-// pub const META: &str = "meta";
-pub const SYMBOL: &str = "symbol";
-pub const DECIMALS: &str = "decimals";
-pub const TOTAL_SUPPLY: &str = "total_supply";
 pub const SELF_CONTRACT_HASH: &str = "self_contract_hash";
 pub const DOMAIN_SEPARATOR: &str = "domain_separator";
 pub const PERMIT_TYPE_HASH: &str = "permit_type_hash";
+/// Version in the permit domain. It is bumped by hand when the permit format changes rather
+/// than following the package's contract version, as every upgrade would then void
+/// outstanding signatures, which binding the package hash is meant to avoid.
+pub const PERMIT_VERSION: &str = "1";
 pub const CONTRACT_PACKAGE_HASH: &str = "contract_package_hash";
+pub const CAP: &str = "cap";
+pub const PAUSED: &str = "paused";
+pub const CURRENT_SNAPSHOT_ID: &str = "current_snapshot_id";
+pub const TOTAL_SUPPLY_SNAPSHOTS: &str = "total_supply_snapshots";
 
-pub struct Balances {
+/// Values recorded for snapshots, as `(snapshot id, value)` pairs in increasing id order. A
+/// value is recorded the first time it changes after a snapshot, so it holds for that snapshot.
+pub type Snapshots = Vec<(u64, U256)>;
+
+pub struct Nonces {
     dict: Dict,
 }
 
-impl Balances {
-    pub fn instance() -> Balances {
-        Balances {
-            dict: Dict::instance(BALANCES_DICT),
+impl Nonces {
+    pub fn instance() -> Nonces {
+        Nonces {
+            dict: Dict::instance(NONCES_DICT),
         }
     }
 
     pub fn init() {
-        Dict::init(BALANCES_DICT)
+        Dict::init(NONCES_DICT)
     }
 
     pub fn get(&self, owner: &Key) -> U256 {
@@ -50,84 +57,89 @@ impl Balances {
     }
 }
 
-pub struct Nonces {
+/// Nonces of signed authorizations each authorizer has used or canceled.
+pub struct Authorizations {
     dict: Dict,
 }
 
-impl Nonces {
-    pub fn instance() -> Nonces {
-        Nonces {
-            dict: Dict::instance(NONCES_DICT),
+impl Authorizations {
+    pub fn instance() -> Authorizations {
+        Authorizations {
+            dict: Dict::instance(AUTHORIZATIONS_DICT),
         }
     }
 
     pub fn init() {
-        Dict::init(NONCES_DICT)
+        Dict::init(AUTHORIZATIONS_DICT)
     }
 
-    pub fn get(&self, owner: &Key) -> U256 {
-        self.dict.get(&key_to_str(owner)).unwrap_or_default()
+    pub fn is_used(&self, authorizer: &Key, nonce: &[u8; 32]) -> bool {
+        self.dict
+            .get(&key_and_value_to_str(authorizer, nonce))
+            .unwrap_or_default()
     }
 
-    pub fn set(&self, owner: &Key, value: U256) {
-        self.dict.set(&key_to_str(owner), value);
+    pub fn set_used(&self, authorizer: &Key, nonce: &[u8; 32]) {
+        self.dict
+            .set(&key_and_value_to_str(authorizer, nonce), true);
     }
 }
 
-pub struct Allowances {
+/// Balances of each account at the snapshots taken before they changed.
+pub struct AccountSnapshots {
     dict: Dict,
 }
 
-impl Allowances {
-    pub fn instance() -> Allowances {
-        Allowances {
-            dict: Dict::instance(ALLOWANCES_DICT),
+impl AccountSnapshots {
+    pub fn instance() -> AccountSnapshots {
+        AccountSnapshots {
+            dict: Dict::instance(ACCOUNT_SNAPSHOTS_DICT),
         }
     }
 
     pub fn init() {
-        Dict::init(ALLOWANCES_DICT)
+        Dict::init(ACCOUNT_SNAPSHOTS_DICT)
     }
 
-    pub fn get(&self, owner: &Key, spender: &Key) -> U256 {
-        self.dict.get_by_keys((owner, spender)).unwrap_or_default()
+    pub fn get(&self, owner: &Key) -> Snapshots {
+        self.dict.get(&key_to_str(owner)).unwrap_or_default()
     }
 
-    pub fn set(&self, owner: &Key, spender: &Key, value: U256) {
-        self.dict.set_by_keys((owner, spender), value);
+    pub fn set(&self, owner: &Key, snapshots: Snapshots) {
+        self.dict.set(&key_to_str(owner), snapshots);
     }
 }
 
-pub fn name() -> String {
-    get_key(NAME).unwrap_or_revert()
+pub fn set_cap(cap: U256) {
+    set_key(CAP, cap);
 }
 
-pub fn set_name(name: String) {
-    set_key(NAME, name);
+pub fn get_cap() -> U256 {
+    get_key(CAP).unwrap_or_revert()
 }
 
-pub fn symbol() -> String {
-    get_key(SYMBOL).unwrap_or_revert()
+pub fn set_paused(paused: bool) {
+    set_key(PAUSED, paused);
 }
 
-pub fn set_symbol(symbol: String) {
-    set_key(SYMBOL, symbol);
+pub fn get_paused() -> bool {
+    get_key(PAUSED).unwrap_or_default()
 }
 
-pub fn decimals() -> u8 {
-    get_key(DECIMALS).unwrap_or_revert()
+pub fn set_current_snapshot_id(id: u64) {
+    set_key(CURRENT_SNAPSHOT_ID, id);
 }
 
-pub fn set_decimals(decimals: u8) {
-    set_key(DECIMALS, decimals);
+pub fn get_current_snapshot_id() -> u64 {
+    get_key(CURRENT_SNAPSHOT_ID).unwrap_or_default()
 }
 
-pub fn total_supply() -> U256 {
-    get_key(TOTAL_SUPPLY).unwrap_or_default()
+pub fn set_total_supply_snapshots(snapshots: Snapshots) {
+    set_key(TOTAL_SUPPLY_SNAPSHOTS, snapshots);
 }
 
-pub fn set_total_supply(total_supply: U256) {
-    set_key(TOTAL_SUPPLY, total_supply);
+pub fn get_total_supply_snapshots() -> Snapshots {
+    get_key(TOTAL_SUPPLY_SNAPSHOTS).unwrap_or_default()
 }
 
 pub fn set_domain_separator(domain_separator: String) {
@@ -237,6 +249,58 @@ pub fn emit(event: &ERC20Event) {
             param.insert("token_id", token_id.to_string());
             events.push(param);
         }
+        ERC20Event::PermitUsed {
+            owner,
+            spender,
+            value,
+            nonce,
+        } => {
+            let mut param = BTreeMap::new();
+            param.insert(CONTRACT_PACKAGE_HASH, package.to_string());
+            param.insert("event_type", "erc20_permit_used".to_string());
+            param.insert("owner", owner.to_string());
+            param.insert("spender", spender.to_string());
+            param.insert("value", value.to_string());
+            param.insert("nonce", nonce.to_string());
+            events.push(param);
+        }
+        ERC20Event::AuthorizationUsed { authorizer, nonce } => {
+            let mut param = BTreeMap::new();
+            param.insert(CONTRACT_PACKAGE_HASH, package.to_string());
+            param.insert("event_type", "erc20_authorization_used".to_string());
+            param.insert("authorizer", authorizer.to_string());
+            param.insert("nonce", hex::encode(nonce));
+            events.push(param);
+        }
+        ERC20Event::AuthorizationCanceled { authorizer, nonce } => {
+            let mut param = BTreeMap::new();
+            param.insert(CONTRACT_PACKAGE_HASH, package.to_string());
+            param.insert("event_type", "erc20_authorization_canceled".to_string());
+            param.insert("authorizer", authorizer.to_string());
+            param.insert("nonce", hex::encode(nonce));
+            events.push(param);
+        }
+        ERC20Event::Paused { account } => {
+            let mut param = BTreeMap::new();
+            param.insert(CONTRACT_PACKAGE_HASH, package.to_string());
+            param.insert("event_type", "erc20_paused".to_string());
+            param.insert("account", account.to_string());
+            events.push(param);
+        }
+        ERC20Event::Unpaused { account } => {
+            let mut param = BTreeMap::new();
+            param.insert(CONTRACT_PACKAGE_HASH, package.to_string());
+            param.insert("event_type", "erc20_unpaused".to_string());
+            param.insert("account", account.to_string());
+            events.push(param);
+        }
+        ERC20Event::Snapshot { id } => {
+            let mut param = BTreeMap::new();
+            param.insert(CONTRACT_PACKAGE_HASH, package.to_string());
+            param.insert("event_type", "erc20_snapshot".to_string());
+            param.insert("id", id.to_string());
+            events.push(param);
+        }
     };
     for param in events {
         let _: URef = storage::new_uref(param);
@@ -1,49 +1,20 @@
-use crate::alloc::string::ToString;
-use crate::data::{self, Allowances, Balances, Nonces};
-use alloc::collections::BTreeMap;
-use alloc::{format, string::String, vec::Vec};
-use casper_contract::contract_api::storage;
+use crate::data::{self, AccountSnapshots, Authorizations, Nonces, Snapshots};
+use crate::event::ERC20Event;
+use alloc::{
+    format,
+    string::{String, ToString},
+};
 use casper_contract::{contract_api::runtime, unwrap_or_revert::UnwrapOrRevert};
-use casper_types::{
-    system::mint::Error as MintError, ApiError, BlockTime, ContractHash, ContractPackageHash, Key,
-    URef, U256,
+use casper_types::{ApiError, BlockTime, ContractPackageHash, Key, PublicKey, Signature, U256};
+#[cfg(feature = "debug-keys")]
+use contract_utils::set_key;
+use contract_utils::{
+    verify_signature, zero_address, AccessControl, CEP18Error, ContractStorage, Votes, CEP18,
+    DEFAULT_ADMIN_ROLE, MINTER_ROLE, PAUSER_ROLE, SNAPSHOT_ROLE,
 };
-use contract_utils::{set_key, ContractContext, ContractStorage};
-use cryptoxide::ed25519;
 use hex::encode;
 use renvm_sig::{hash_message, keccak256};
 
-pub enum ERC20Event {
-    Approval {
-        owner: Key,
-        spender: Key,
-        value: U256,
-    },
-    Transfer {
-        from: Key,
-        to: Key,
-        value: U256,
-    },
-}
-
-impl ERC20Event {
-    pub fn type_name(&self) -> String {
-        match self {
-            ERC20Event::Approval {
-                owner: _,
-                spender: _,
-                value: _,
-            } => "approve",
-            ERC20Event::Transfer {
-                from: _,
-                to: _,
-                value: _,
-            } => "erc20_transfer",
-        }
-        .to_string()
-    }
-}
-
 #[repr(u16)]
 pub enum Error {
     /// 65,536 for (UniswapV2 Core ERC20 EXPIRED)
@@ -68,6 +39,20 @@ pub enum Error {
     UniswapV2CoreERC20UnderFlow4 = 9,
     /// 65,546 for (UniswapV2 Core ERC20 UnderFlow5)
     UniswapV2CoreERC20UnderFlow5 = 10,
+    /// 65,681 for (UniswapV2 Core ERC20 Authorization Not Yet Valid)
+    UniswapV2CoreERC20AuthorizationNotYetValid = 145,
+    /// 65,682 for (UniswapV2 Core ERC20 Authorization Expired)
+    UniswapV2CoreERC20AuthorizationExpired = 146,
+    /// 65,683 for (UniswapV2 Core ERC20 Authorization Used)
+    UniswapV2CoreERC20AuthorizationUsed = 147,
+    /// 65,684 for (UniswapV2 Core ERC20 Caller Must Be Payee)
+    UniswapV2CoreERC20CallerMustBePayee = 148,
+    /// 65,690 for (UniswapV2 Core ERC20 Cap Exceeded)
+    UniswapV2CoreERC20CapExceeded = 154,
+    /// 65,691 for (UniswapV2 Core ERC20 Paused)
+    UniswapV2CoreERC20Paused = 155,
+    /// 65,692 for (UniswapV2 Core ERC20 Invalid Snapshot Id)
+    UniswapV2CoreERC20InvalidSnapshotId = 156,
 }
 
 impl From<Error> for ApiError {
@@ -76,122 +61,161 @@ impl From<Error> for ApiError {
     }
 }
 
-pub trait ERC20<Storage: ContractStorage>: ContractContext<Storage> {
+pub trait ERC20<Storage: ContractStorage>:
+    CEP18<Storage> + AccessControl<Storage> + Votes<Storage>
+{
     fn init(
         &mut self,
         name: String,
         symbol: String,
         decimals: u8,
-        initial_supply: U256,
         domain_separator: String,
         permit_type_hash: String,
         contract_hash: Key,
         package_hash: ContractPackageHash,
     ) {
-        data::set_name(name);
-        data::set_symbol(symbol);
+        CEP18::init(self, name, symbol, decimals);
         data::set_domain_separator(domain_separator);
         data::set_permit_type_hash(permit_type_hash);
-        data::set_total_supply(initial_supply);
-        data::set_decimals(decimals);
         data::set_hash(contract_hash);
         data::set_package_hash(package_hash);
         Nonces::init();
+        Authorizations::init();
         let nonces = Nonces::instance();
         nonces.set(&Key::from(self.get_caller()), U256::from(0));
-        Allowances::init();
-        Balances::init();
+        AccessControl::init(self);
+        self._grant_role(DEFAULT_ADMIN_ROLE.to_string(), self.get_caller());
+        self._grant_role(MINTER_ROLE.to_string(), self.get_caller());
+        if cfg!(feature = "pausable") {
+            data::set_paused(false);
+            self._grant_role(PAUSER_ROLE.to_string(), self.get_caller());
+        }
+        if cfg!(feature = "snapshot") {
+            AccountSnapshots::init();
+            self._grant_role(SNAPSHOT_ROLE.to_string(), self.get_caller());
+        }
     }
 
-    fn balance_of(&mut self, owner: Key) -> U256 {
-        Balances::instance().get(&owner)
+    /// Runs the checks and bookkeeping of the extensions the token is built with before any
+    /// balance changes. Tokens call it from `CEP18::before_transfer`, so transfers, mints and
+    /// burns all go through it.
+    fn before_token_transfer(&mut self, from: Key, to: Key, amount: U256) {
+        if cfg!(feature = "pausable") {
+            self.when_not_paused();
+        }
+        if cfg!(feature = "capped") && from == zero_address() {
+            self.assert_within_cap(amount);
+        }
+        if cfg!(feature = "snapshot") {
+            self.update_snapshots(from, to);
+        }
     }
 
-    fn nonce(&mut self, owner: Key) -> U256 {
-        Nonces::instance().get(&owner)
+    fn cap(&self) -> U256 {
+        data::get_cap()
     }
 
-    fn transfer(&mut self, recipient: Key, amount: U256) -> Result<(), u32> {
-        self.make_transfer(self.get_caller(), recipient, amount)
+    fn assert_within_cap(&mut self, amount: U256) {
+        let supply: Option<U256> = self.total_supply().checked_add(amount);
+        if supply.map_or(true, |supply| supply > data::get_cap()) {
+            runtime::revert(Error::UniswapV2CoreERC20CapExceeded);
+        }
     }
 
-    fn approve(&mut self, spender: Key, amount: U256) {
-        self._approve(self.get_caller(), spender, amount);
+    /// Stops transfers, mints and burns. Callable by a PAUSER.
+    fn pause(&mut self) {
+        self.assert_role(PAUSER_ROLE);
+        if !data::get_paused() {
+            data::set_paused(true);
+            data::emit(&ERC20Event::Paused {
+                account: self.get_caller(),
+            });
+        }
     }
 
-    fn _approve(&mut self, owner: Key, spender: Key, amount: U256) {
-        Allowances::instance().set(&owner, &spender, amount);
-        self.emit(&ERC20Event::Approval {
-            owner: owner,
-            spender: spender,
-            value: amount,
-        });
+    fn unpause(&mut self) {
+        self.assert_role(PAUSER_ROLE);
+        if data::get_paused() {
+            data::set_paused(false);
+            data::emit(&ERC20Event::Unpaused {
+                account: self.get_caller(),
+            });
+        }
     }
 
-    fn allowance(&mut self, owner: Key, spender: Key) -> U256 {
-        Allowances::instance().get(&owner, &spender)
+    fn paused(&self) -> bool {
+        data::get_paused()
     }
 
-    fn increase_allowance(&mut self, spender: Key, amount: U256) -> Result<(), u32> {
-        let allowances = Allowances::instance();
-        let owner: Key = self.get_caller();
-
-        let spender_allowance: U256 = allowances.get(&owner, &spender);
-        let new_allowance: U256 = spender_allowance
-            .checked_add(amount)
-            .ok_or(Error::UniswapV2CoreERC20OverFlow1)
-            .unwrap_or_revert();
-
-        if owner != spender {
-            self._approve(owner, spender, new_allowance);
-            return Ok(());
-        } else {
-            return Err(4);
+    fn when_not_paused(&self) {
+        if data::get_paused() {
+            runtime::revert(Error::UniswapV2CoreERC20Paused);
         }
     }
 
-    fn decrease_allowance(&mut self, spender: Key, amount: U256) -> Result<(), u32> {
-        let allowances = Allowances::instance();
-
-        let owner: Key = self.get_caller();
-
-        let spender_allowance: U256 = allowances.get(&owner, &spender);
+    /// Burns `amount` of `from`'s tokens for the caller, who must be `from` or spend `from`'s
+    /// allowance. Builds without the `burnable` feature let a MINTER burn instead.
+    fn burn_from(&mut self, from: Key, amount: U256) {
+        let caller: Key = self.get_caller();
+        if caller != from {
+            let new_allowance: U256 = self
+                .allowance(from, caller)
+                .checked_sub(amount)
+                .ok_or(CEP18Error::UniswapV2CoreCEP18InsufficientAllowance)
+                .unwrap_or_revert();
+            self._approve(from, caller, new_allowance);
+        }
+        self.burn(from, amount);
+    }
 
-        let new_allowance: U256 = spender_allowance
-            .checked_sub(amount)
-            .ok_or(Error::UniswapV2CoreERC20UnderFlow1)
+    /// Takes a snapshot of all balances and the total supply, returning its id. Ids start at 1
+    /// and balances are only copied when they next change. Callable by a SNAPSHOT role holder.
+    fn snapshot(&mut self) -> u64 {
+        self.assert_role(SNAPSHOT_ROLE);
+        let id: u64 = data::get_current_snapshot_id()
+            .checked_add(1)
+            .ok_or(Error::UniswapV2CoreERC20OverFlow1)
             .unwrap_or_revert();
+        data::set_current_snapshot_id(id);
+        data::emit(&ERC20Event::Snapshot { id });
+        id
+    }
 
-        if new_allowance >= 0.into() && new_allowance < spender_allowance && owner != spender {
-            self._approve(owner, spender, new_allowance);
-            return Ok(());
-        } else {
-            return Err(4);
-        }
+    fn balance_of_at(&mut self, owner: Key, snapshot_id: u64) -> U256 {
+        let snapshots: Snapshots = AccountSnapshots::instance().get(&owner);
+        value_at(snapshot_id, &snapshots).unwrap_or_else(|| self.balance_of(owner))
+    }
+
+    fn total_supply_at(&mut self, snapshot_id: u64) -> U256 {
+        let snapshots: Snapshots = data::get_total_supply_snapshots();
+        value_at(snapshot_id, &snapshots).unwrap_or_else(|| self.total_supply())
     }
 
-    fn transfer_from(&mut self, owner: Key, recipient: Key, amount: U256) -> Result<(), u32> {
-        if owner != recipient && amount != 0.into() {
-            let ret: Result<(), u32> = self.make_transfer(owner, recipient, amount);
-            if ret.is_ok() {
-                let allowances = Allowances::instance();
-                let spender_allowance: U256 = allowances.get(&owner, &self.get_caller());
-                let new_allowance: U256 = spender_allowance
-                    .checked_sub(amount)
-                    .ok_or(Error::UniswapV2CoreERC20UnderFlow2)
-                    .unwrap_or_revert();
-                if new_allowance >= 0.into()
-                    && new_allowance < spender_allowance
-                    && owner != self.get_caller()
-                {
-                    self._approve(owner, self.get_caller(), new_allowance);
-                    return Ok(());
-                } else {
-                    return Err(4);
+    /// Records the balances about to change, and the total supply on mints and burns, for the
+    /// current snapshot unless they have been recorded for it already.
+    fn update_snapshots(&mut self, from: Key, to: Key) {
+        let id: u64 = data::get_current_snapshot_id();
+        if id == 0 {
+            return;
+        }
+        let account_snapshots: AccountSnapshots = AccountSnapshots::instance();
+        for account in [from, to].iter() {
+            if *account == zero_address() {
+                let mut snapshots: Snapshots = data::get_total_supply_snapshots();
+                if record(&mut snapshots, id, self.total_supply()) {
+                    data::set_total_supply_snapshots(snapshots);
+                }
+            } else {
+                let mut snapshots: Snapshots = account_snapshots.get(account);
+                if record(&mut snapshots, id, self.balance_of(*account)) {
+                    account_snapshots.set(account, snapshots);
                 }
             }
         }
-        Ok(())
+    }
+
+    fn nonce(&mut self, owner: Key) -> U256 {
+        Nonces::instance().get(&owner)
     }
 
     /// This function is to get signer and verify if it is equal
@@ -199,42 +223,27 @@ pub trait ERC20<Storage: ContractStorage>: ContractContext<Storage> {
     ///
     /// # Parameters
     ///
-    /// * `public_key` - A string slice that holds the public key of the meta transaction signer
+    /// * `public_key` - A PublicKey that holds the ed25519 or secp256k1 key of the meta transaction signer
     ///
-    /// * `signature` - A string slice that holds the signature of the meta transaction
+    /// * `signature` - A Signature that holds the ed25519 or secp256k1 signature of the meta transaction
     ///
     /// * `digest` - A u8 array that holds the digest
     ///
-    /// * `owner` - An Accounthash that holds the account address of the signer
+    /// * `owner` - A Key that holds the account address the public key must belong to
     ///
 
     fn ecrecover(
         &mut self,
-        public_key: String,
-        signature: String,
+        public_key: PublicKey,
+        signature: Signature,
         digest: [u8; 32],
         owner: Key,
     ) -> bool {
-        let public_key_without_spaces: String = public_key.split_whitespace().collect();
-        let public_key_string: Vec<&str> = public_key_without_spaces.split(',').collect();
-        let mut public_key_vec: Vec<u8> = Vec::new();
-        let mut public_counter: usize = 0;
-        while public_counter < 32 {
-            public_key_vec.push(public_key_string[public_counter].parse::<u8>().unwrap());
-            public_counter = public_counter + 1;
-        }
-        let signature_without_spaces: String = signature.split_whitespace().collect();
-        let signature_string: Vec<&str> = signature_without_spaces.split(',').collect();
-        let mut signature_vec: Vec<u8> = Vec::new();
-        let mut signature_counter: usize = 0;
-        while signature_counter < 64 {
-            signature_vec.push(signature_string[signature_counter].parse::<u8>().unwrap());
-            signature_counter = signature_counter + 1;
-        }
-        let result: bool = ed25519::verify(&digest, &public_key_vec, &signature_vec);
-        let verify_key: String = format!("{}{}", "VERIFY", owner);
-        set_key(&verify_key, result);
-        return result;
+        let result: bool = Key::from(public_key.to_account_hash()) == owner
+            && verify_signature(&public_key, &signature, &digest);
+        #[cfg(feature = "debug-keys")]
+        set_key(&format!("{}{}", "VERIFY", owner), result);
+        result
     }
 
     /// This function is to get meta transaction signer and verify if it is equal
@@ -242,9 +251,9 @@ pub trait ERC20<Storage: ContractStorage>: ContractContext<Storage> {
     ///
     /// # Parameters
     ///
-    /// * `public_key` - A string slice that holds the public key of the meta transaction signer,  Subscriber have to get it from running cryptoxide project externally.
+    /// * `public_key` - A PublicKey that holds the ed25519 or secp256k1 key of the owner
     ///
-    /// * `signature` - A string slice that holds the signature of the meta transaction,  Subscriber have to get it from running cryptoxide project externally.
+    /// * `signature` - A Signature that holds the owner's signature of the permit digest
     ///
     /// * `owner` - A Key that holds the account address of the owner
     ///
@@ -257,8 +266,8 @@ pub trait ERC20<Storage: ContractStorage>: ContractContext<Storage> {
 
     fn permit(
         &mut self,
-        public_key: String,
-        signature: String,
+        public_key: PublicKey,
+        signature: Signature,
         owner: Key,
         spender: Key,
         value: U256,
@@ -266,7 +275,7 @@ pub trait ERC20<Storage: ContractStorage>: ContractContext<Storage> {
     ) {
         let domain_separator: String = data::get_domain_separator();
         let permit_type_hash: String = data::get_permit_type_hash();
-        let nonce: U256 = self.nonce(Key::from(self.get_caller()));
+        let nonce: U256 = self.nonce(owner);
         let deadline_into_blocktime: BlockTime = BlockTime::new(deadline * 1000);
         let blocktime: BlockTime = runtime::get_blocktime();
         if deadline_into_blocktime >= blocktime {
@@ -278,18 +287,17 @@ pub trait ERC20<Storage: ContractStorage>: ContractContext<Storage> {
             let hash_string: String = hex::encode(hash);
             let encode_packed: String = format!("{}{}", domain_separator, hash_string);
             let digest: [u8; 32] = hash_message(encode_packed);
-            let digest_string: String = hex::encode(digest);
-            let digest_key: String = format!("{}{}", "digest_", owner);
-            set_key(&digest_key, digest_string);
-            self.set_nonce(Key::from(self.get_caller()));
-            let result: bool =
-                self.ecrecover(public_key, signature, digest, Key::from(self.get_caller()));
+            #[cfg(feature = "debug-keys")]
+            set_key(&format!("{}{}", "digest_", owner), hex::encode(digest));
+            self.set_nonce(owner);
+            let result: bool = self.ecrecover(public_key, signature, digest, owner);
             if result == true {
-                Allowances::instance().set(&owner, &spender, value);
-                self.emit(&ERC20Event::Approval {
-                    owner: owner,
-                    spender: spender,
-                    value: value,
+                self._approve(owner, spender, value);
+                data::emit(&ERC20Event::PermitUsed {
+                    owner,
+                    spender,
+                    value,
+                    nonce,
                 });
             } else {
                 //signature verification failed
@@ -301,131 +309,209 @@ pub trait ERC20<Storage: ContractStorage>: ContractContext<Storage> {
         }
     }
 
-    fn mint(&mut self, recipient: Key, amount: U256) {
-        let balances: Balances = Balances::instance();
-        let balance: U256 = balances.get(&recipient);
-        balances.set(
-            &recipient,
-            balance
-                .checked_add(amount)
-                .ok_or(Error::UniswapV2CoreERC20OverFlow2)
-                .unwrap_or_revert(),
-        );
-        data::set_total_supply(
-            data::total_supply()
-                .checked_add(amount)
-                .ok_or(Error::UniswapV2CoreERC20OverFlow3)
-                .unwrap_or_revert(),
+    fn authorization_state(&self, authorizer: Key, nonce: [u8; 32]) -> bool {
+        Authorizations::instance().is_used(&authorizer, &nonce)
+    }
+
+    /// Moves `value` from `from` to `to` with `from`'s signature of a `TransferWithAuthorization`
+    /// over the permit domain, so anyone can relay it. `nonce` is any 32 bytes `from` has not
+    /// used or canceled before, and the transfer is only valid after `valid_after` and before
+    /// `valid_before`, both in seconds.
+    fn transfer_with_authorization(
+        &mut self,
+        public_key: PublicKey,
+        signature: Signature,
+        from: Key,
+        to: Key,
+        value: U256,
+        valid_after: u64,
+        valid_before: u64,
+        nonce: [u8; 32],
+    ) {
+        self.use_authorization(
+            "TransferWithAuthorization(address from,address to,uint256 value,uint256 validAfter,uint256 validBefore,bytes32 nonce)",
+            public_key,
+            signature,
+            from,
+            to,
+            value,
+            valid_after,
+            valid_before,
+            nonce,
         );
-        let address_0: Key = Key::from_formatted_str(
-            "account-hash-0000000000000000000000000000000000000000000000000000000000000000",
-        )
-        .unwrap();
-        self.emit(&ERC20Event::Transfer {
-            from: address_0,
-            to: recipient,
-            value: amount,
-        });
     }
 
-    fn burn(&mut self, recipient: Key, amount: U256) {
-        let balances: Balances = Balances::instance();
-        let balance: U256 = balances.get(&recipient);
-        if balance >= amount {
-            balances.set(
-                &recipient,
-                balance
-                    .checked_sub(amount)
-                    .ok_or(Error::UniswapV2CoreERC20UnderFlow3)
-                    .unwrap_or_revert(),
-            );
-            data::set_total_supply(
-                data::total_supply()
-                    .checked_sub(amount)
-                    .ok_or(Error::UniswapV2CoreERC20UnderFlow4)
-                    .unwrap_or_revert(),
-            );
-            let address_0: Key = Key::from_formatted_str(
-                "account-hash-0000000000000000000000000000000000000000000000000000000000000000",
-            )
-            .unwrap();
-            self.emit(&ERC20Event::Transfer {
-                from: recipient,
-                to: address_0,
-                value: amount,
-            });
-        } else {
-            // PosError::InsufficientPaymentForAmountSpent
-            runtime::revert(MintError::InsufficientFunds)
+    /// Same as `transfer_with_authorization` for a `ReceiveWithAuthorization`, which only `to`
+    /// may submit. This keeps a relayer from front-running a contract that pulls the tokens
+    /// as part of a larger call.
+    fn receive_with_authorization(
+        &mut self,
+        public_key: PublicKey,
+        signature: Signature,
+        from: Key,
+        to: Key,
+        value: U256,
+        valid_after: u64,
+        valid_before: u64,
+        nonce: [u8; 32],
+    ) {
+        if self.get_caller() != to {
+            runtime::revert(Error::UniswapV2CoreERC20CallerMustBePayee);
         }
+        self.use_authorization(
+            "ReceiveWithAuthorization(address from,address to,uint256 value,uint256 validAfter,uint256 validBefore,bytes32 nonce)",
+            public_key,
+            signature,
+            from,
+            to,
+            value,
+            valid_after,
+            valid_before,
+            nonce,
+        );
     }
 
-    fn set_nonce(&mut self, recipient: Key) {
-        let nonces: Nonces = Nonces::instance();
-        let nonce: U256 = nonces.get(&recipient);
-        nonces.set(&recipient, nonce + U256::from(1));
+    /// Marks `nonce` of `authorizer` as used with their signature of a `CancelAuthorization`,
+    /// so an authorization signed with it can no longer be submitted.
+    fn cancel_authorization(
+        &mut self,
+        public_key: PublicKey,
+        signature: Signature,
+        authorizer: Key,
+        nonce: [u8; 32],
+    ) {
+        let authorizations: Authorizations = Authorizations::instance();
+        if authorizations.is_used(&authorizer, &nonce) {
+            runtime::revert(Error::UniswapV2CoreERC20AuthorizationUsed);
+        }
+        let type_hash: String = encode(keccak256(
+            "CancelAuthorization(address authorizer,bytes32 nonce)".as_bytes(),
+        ));
+        let encoded: String = format!("{}{}{}", type_hash, authorizer, encode(nonce));
+        let digest: [u8; 32] = self.authorization_digest(encoded);
+        if !self.ecrecover(public_key, signature, digest, authorizer) {
+            runtime::revert(Error::UniswapV2CoreERC20SignatureVerificationFailed);
+        }
+        authorizations.set_used(&authorizer, &nonce);
+        data::emit(&ERC20Event::AuthorizationCanceled { authorizer, nonce });
     }
 
-    fn make_transfer(&mut self, sender: Key, recipient: Key, amount: U256) -> Result<(), u32> {
-        if sender != recipient && amount != 0.into() {
-            let balances: Balances = Balances::instance();
-            let sender_balance: U256 = balances.get(&sender);
-            let recipient_balance: U256 = balances.get(&recipient);
-            balances.set(
-                &sender,
-                sender_balance
-                    .checked_sub(amount)
-                    .ok_or(Error::UniswapV2CoreERC20UnderFlow5)
-                    .unwrap_or_revert(),
-            );
-            balances.set(
-                &recipient,
-                recipient_balance
-                    .checked_add(amount)
-                    .ok_or(Error::UniswapV2CoreERC20OverFlow4)
-                    .unwrap_or_revert(),
-            );
-            self.emit(&ERC20Event::Transfer {
-                from: sender,
-                to: recipient,
-                value: amount,
-            });
+    fn use_authorization(
+        &mut self,
+        authorization_type: &str,
+        public_key: PublicKey,
+        signature: Signature,
+        from: Key,
+        to: Key,
+        value: U256,
+        valid_after: u64,
+        valid_before: u64,
+        nonce: [u8; 32],
+    ) {
+        let blocktime: BlockTime = runtime::get_blocktime();
+        if BlockTime::new(valid_after.saturating_mul(1000)) >= blocktime {
+            runtime::revert(Error::UniswapV2CoreERC20AuthorizationNotYetValid);
+        }
+        if BlockTime::new(valid_before.saturating_mul(1000)) <= blocktime {
+            runtime::revert(Error::UniswapV2CoreERC20AuthorizationExpired);
+        }
+        let authorizations: Authorizations = Authorizations::instance();
+        if authorizations.is_used(&from, &nonce) {
+            runtime::revert(Error::UniswapV2CoreERC20AuthorizationUsed);
+        }
+        let type_hash: String = encode(keccak256(authorization_type.as_bytes()));
+        let encoded: String = format!(
+            "{}{}{}{}{}{}{}",
+            type_hash,
+            from,
+            to,
+            value,
+            valid_after,
+            valid_before,
+            encode(nonce)
+        );
+        let digest: [u8; 32] = self.authorization_digest(encoded);
+        if !self.ecrecover(public_key, signature, digest, from) {
+            runtime::revert(Error::UniswapV2CoreERC20SignatureVerificationFailed);
+        }
+        authorizations.set_used(&from, &nonce);
+        data::emit(&ERC20Event::AuthorizationUsed {
+            authorizer: from,
+            nonce,
+        });
+        if let Err(e) = self.make_transfer(from, to, value) {
+            runtime::revert(e);
         }
-
-        Ok(())
     }
 
-    fn total_supply(&mut self) -> U256 {
-        data::total_supply()
+    /// Delegates the votes of the `public_key` account to `delegatee` with its signature of a
+    /// `Delegation`, so anyone can relay it. The signature uses up the account's permit nonce
+    /// and is only valid until `expiry`, in seconds.
+    fn delegate_by_sig(
+        &mut self,
+        public_key: PublicKey,
+        signature: Signature,
+        delegatee: Key,
+        expiry: u64,
+    ) {
+        if BlockTime::new(expiry.saturating_mul(1000)) < runtime::get_blocktime() {
+            runtime::revert(Error::UniswapV2CoreERC20EXPIRED);
+        }
+        let delegator: Key = Key::from(public_key.to_account_hash());
+        let nonce: U256 = self.nonce(delegator);
+        let type_hash: String = encode(keccak256(
+            "Delegation(address delegatee,uint256 nonce,uint256 expiry)".as_bytes(),
+        ));
+        let encoded: String = format!("{}{}{}{}", type_hash, delegatee, nonce, expiry);
+        let digest: [u8; 32] = self.authorization_digest(encoded);
+        if !self.ecrecover(public_key, signature, digest, delegator) {
+            runtime::revert(Error::UniswapV2CoreERC20SignatureVerificationFailed);
+        }
+        self.set_nonce(delegator);
+        self._delegate(delegator, delegatee);
     }
 
-    fn name(&mut self) -> String {
-        data::name()
+    /// Returns the digest signed for an authorization whose fields are concatenated in `encoded`, hashed
+    /// under the permit domain the same way `permit` is.
+    fn authorization_digest(&self, encoded: String) -> [u8; 32] {
+        let hash: String = encode(keccak256(encoded.as_bytes()));
+        hash_message(format!("{}{}", data::get_domain_separator(), hash))
     }
 
-    fn symbol(&mut self) -> String {
-        data::symbol()
+    fn set_nonce(&mut self, recipient: Key) {
+        let nonces: Nonces = Nonces::instance();
+        let nonce: U256 = nonces.get(&recipient);
+        nonces.set(&recipient, nonce + U256::from(1));
     }
 
+    /// Returns the hex encoded domain separator and permit type hash permits are signed over.
+    /// The domain binds the token name, the permit version, the chain name and the package
+    /// hash, so signatures survive upgrades but not a move to another chain or package.
     fn get_permit_type_and_domain_separator(
         &mut self,
         name: &str,
-        contract_hash: ContractHash,
+        chain_name: &str,
+        package_hash: ContractPackageHash,
     ) -> (String, String) {
         let eip_712_domain: &str =
-            "EIP712Domain(string name,string version,uint256 chainId,address verifyingContract)";
+            "EIP712Domain(string name,string version,string chainName,bytes32 verifyingPackage)";
         let permit_type: &str =
             "Permit(address owner,address spender,uint256 value,uint256 nonce,uint256 deadline)";
-        let chain_id: &str = "101";
         let eip_domain_hash: [u8; 32] = keccak256(eip_712_domain.as_bytes()); // to take a byte hash of EIP712Domain
         let name_hash: [u8; 32] = keccak256(name.as_bytes()); // to take a byte hash of name
-        let one_hash: [u8; 32] = keccak256("1".as_bytes()); // to take a byte hash of "1"
+        let version_hash: [u8; 32] = keccak256(data::PERMIT_VERSION.as_bytes()); // to take a byte hash of the permit version
+        let chain_name_hash: [u8; 32] = keccak256(chain_name.as_bytes()); // to take a byte hash of the chain name
         let eip_domain_hash: String = encode(eip_domain_hash); // to encode and convert eip_domain_hash into string
         let name_hash: String = encode(name_hash); // to encode and convert name_hash into string
-        let one_hash: String = encode(one_hash); // to encode and convert one_hash into string
+        let version_hash: String = encode(version_hash); // to encode and convert version_hash into string
+        let chain_name_hash: String = encode(chain_name_hash); // to encode and convert chain_name_hash into string
         let concatenated_data: String = format!(
             "{}{}{}{}{}",
-            eip_domain_hash, name_hash, one_hash, chain_id, contract_hash
+            eip_domain_hash,
+            name_hash,
+            version_hash,
+            chain_name_hash,
+            encode(package_hash.value())
         ); //string contactination
         let domain_separator: [u8; 32] = keccak256(concatenated_data.as_bytes()); //to take a byte hash of concatenated Data
         let permit_type_hash: [u8; 32] = keccak256(permit_type.as_bytes()); // to take a byte hash of Permit Type
@@ -433,39 +519,32 @@ pub trait ERC20<Storage: ContractStorage>: ContractContext<Storage> {
         let permit_type_hash: String = encode(permit_type_hash);
         (domain_separator, permit_type_hash)
     }
-    fn emit(&mut self, erc20_event: &ERC20Event) {
-        let mut events = Vec::new();
-        let package = data::get_package_hash();
-        match erc20_event {
-            ERC20Event::Approval {
-                owner,
-                spender,
-                value,
-            } => {
-                let mut event = BTreeMap::new();
-                event.insert("contract_package_hash", package.to_string());
-                event.insert("event_type", erc20_event.type_name());
-                event.insert("owner", owner.to_string());
-                event.insert("spender", spender.to_string());
-                event.insert("value", value.to_string());
-                events.push(event);
-            }
-            ERC20Event::Transfer { from, to, value } => {
-                let mut event = BTreeMap::new();
-                event.insert("contract_package_hash", package.to_string());
-                event.insert("event_type", erc20_event.type_name());
-                event.insert("from", from.to_string());
-                event.insert("to", to.to_string());
-                event.insert("value", value.to_string());
-                events.push(event);
-            }
-        };
-        for event in events {
-            let _: URef = storage::new_uref(event);
-        }
-    }
-
     fn get_package_hash(&mut self) -> ContractPackageHash {
         data::get_package_hash()
     }
+
+    fn get_domain_separator(&self) -> String {
+        data::get_domain_separator()
+    }
+}
+
+/// Returns the value recorded for `snapshot_id`, or `None` if it has not changed since, in which
+/// case the current value holds.
+fn value_at(snapshot_id: u64, snapshots: &Snapshots) -> Option<U256> {
+    if snapshot_id == 0 || snapshot_id > data::get_current_snapshot_id() {
+        runtime::revert(Error::UniswapV2CoreERC20InvalidSnapshotId);
+    }
+    let index: usize = match snapshots.binary_search_by_key(&snapshot_id, |(id, _)| *id) {
+        Ok(index) | Err(index) => index,
+    };
+    snapshots.get(index).map(|(_, value)| *value)
+}
+
+/// Appends `value` for snapshot `id` unless a value was already recorded for it.
+fn record(snapshots: &mut Snapshots, id: u64, value: U256) -> bool {
+    if snapshots.last().map_or(false, |(last, _)| *last >= id) {
+        return false;
+    }
+    snapshots.push((id, value));
+    true
 }
@@ -1,5 +1,5 @@
 use alloc::vec::Vec;
-use casper_types::Key;
+use casper_types::{Key, U256};
 
 use crate::TokenId;
 
@@ -25,4 +25,27 @@ pub enum ERC20Event {
     MetadataUpdate {
         token_id: TokenId,
     },
+    PermitUsed {
+        owner: Key,
+        spender: Key,
+        value: U256,
+        nonce: U256,
+    },
+    AuthorizationUsed {
+        authorizer: Key,
+        nonce: [u8; 32],
+    },
+    AuthorizationCanceled {
+        authorizer: Key,
+        nonce: [u8; 32],
+    },
+    Paused {
+        account: Key,
+    },
+    Unpaused {
+        account: Key,
+    },
+    Snapshot {
+        id: u64,
+    },
 }
@@ -30,6 +30,29 @@ impl FACTORYInstance {
         ))
     }
 
+    /// Installs the factory as released before storage versions, to be upgraded in tests.
+    pub fn new_legacy<T: Into<Key>>(
+        env: &TestEnv,
+        contract_name: &str,
+        sender: AccountHash,
+        fee_to_setter: T,
+    ) -> FACTORYInstance {
+        FACTORYInstance(TestContract::new(
+            env,
+            "factory-legacy.wasm",
+            contract_name,
+            sender,
+            runtime_args! {
+                "fee_to_setter" => fee_to_setter.into(),
+            },
+        ))
+    }
+
+    /// Adds a new version to the package installed under `contract_name` by `sender`.
+    pub fn upgrade(env: &TestEnv, contract_name: &str, sender: AccountHash) -> TestContract {
+        TestContract::new(env, "factory.wasm", contract_name, sender, runtime_args! {})
+    }
+
     pub fn constructor<T: Into<Key>>(&self, sender: AccountHash, fee_to_setter: T) {
         self.0.call_contract(
             sender,
@@ -40,16 +63,77 @@ impl FACTORYInstance {
         );
     }
 
-    pub fn set_fee_to_setter<T: Into<Key>>(&self, sender: AccountHash, fee_to_setter: T) {
+    pub fn propose_fee_to_setter<T: Into<Key>>(&self, sender: AccountHash, fee_to_setter: T) {
         self.0.call_contract(
             sender,
-            "set_fee_to_setter",
+            "propose_fee_to_setter",
             runtime_args! {
                 "fee_to_setter" => fee_to_setter.into(),
             },
         );
     }
 
+    pub fn accept_fee_to_setter(&self, sender: AccountHash) {
+        self.0
+            .call_contract(sender, "accept_fee_to_setter", runtime_args! {});
+    }
+
+    pub fn cancel_fee_to_setter_proposal(&self, sender: AccountHash) {
+        self.0
+            .call_contract(sender, "cancel_fee_to_setter_proposal", runtime_args! {});
+    }
+
+    pub fn propose_owner<T: Into<Key>>(&self, sender: AccountHash, owner: T) {
+        self.0.call_contract(
+            sender,
+            "propose_owner",
+            runtime_args! {
+                "owner" => owner.into(),
+            },
+        );
+    }
+
+    pub fn accept_ownership(&self, sender: AccountHash) {
+        self.0
+            .call_contract(sender, "accept_ownership", runtime_args! {});
+    }
+
+    pub fn cancel_owner_proposal(&self, sender: AccountHash) {
+        self.0
+            .call_contract(sender, "cancel_owner_proposal", runtime_args! {});
+    }
+
+    pub fn grant_role<T: Into<Key>>(&self, sender: AccountHash, role: &str, account: T) {
+        self.0.call_contract(
+            sender,
+            "grant_role",
+            runtime_args! {
+                "role" => role.to_string(),
+                "account" => account.into()
+            },
+        );
+    }
+
+    pub fn revoke_role<T: Into<Key>>(&self, sender: AccountHash, role: &str, account: T) {
+        self.0.call_contract(
+            sender,
+            "revoke_role",
+            runtime_args! {
+                "role" => role.to_string(),
+                "account" => account.into()
+            },
+        );
+    }
+
+    pub fn has_role<T: Into<Key>>(&self, role: &str, account: T) -> bool {
+        self.0
+            .query_dictionary(
+                "roles",
+                key_and_value_to_str(&account.into(), &role.to_string()),
+            )
+            .unwrap_or_default()
+    }
+
     pub fn set_fee_to<T: Into<Key>>(&self, sender: AccountHash, fee_to: T) {
         self.0.call_contract(
             sender,
@@ -60,12 +144,26 @@ impl FACTORYInstance {
         );
     }
 
+    pub fn sync_fee_on(&self, sender: AccountHash, start: U256, count: U256) {
+        self.0.call_contract(
+            sender,
+            "sync_fee_on",
+            runtime_args! {
+                "start" => start,
+                "count" => count,
+            },
+        );
+    }
+
     pub fn create_pair<T: Into<Key>>(
         &self,
         sender: AccountHash,
         token_a: T,
         token_b: T,
         pair_hash: T,
+        fee: u32,
+        curve_type: u8,
+        amplification: u64,
     ) {
         self.0.call_contract(
             sender,
@@ -74,6 +172,59 @@ impl FACTORYInstance {
             "token_a" => token_a.into(),
             "token_b" => token_b.into(),
             "pair_hash" => pair_hash.into(),
+            "fee" => fee,
+            "curve_type" => curve_type,
+            "amplification" => amplification,
+            },
+        );
+    }
+
+    /// `create_pair` of factories installed before fee tiers and curves.
+    pub fn legacy_create_pair<T: Into<Key>>(
+        &self,
+        sender: AccountHash,
+        token_a: T,
+        token_b: T,
+        pair_hash: T,
+    ) {
+        self.0.call_contract(
+            sender,
+            "create_pair",
+            runtime_args! {
+                "token_a" => token_a.into(),
+                "token_b" => token_b.into(),
+                "pair_hash" => pair_hash.into(),
+            },
+        );
+    }
+
+    pub fn migrate_all_pairs(&self, sender: AccountHash, start: U256, count: U256) {
+        self.0.call_contract(
+            sender,
+            "migrate_all_pairs",
+            runtime_args! {
+                "start" => start,
+                "count" => count,
+            },
+        );
+    }
+
+    pub fn enable_fee_tier(&self, sender: AccountHash, fee: u32) {
+        self.0.call_contract(
+            sender,
+            "enable_fee_tier",
+            runtime_args! {
+                "fee" => fee,
+            },
+        );
+    }
+
+    pub fn disable_fee_tier(&self, sender: AccountHash, fee: u32) {
+        self.0.call_contract(
+            sender,
+            "disable_fee_tier",
+            runtime_args! {
+                "fee" => fee,
             },
         );
     }
@@ -88,12 +239,130 @@ impl FACTORYInstance {
         );
     }
 
-    pub fn get_white_lists<T: Into<Key>>(&self, account: T) -> Key {
+    pub fn remove_from_white_list<T: Into<Key>>(&self, sender: AccountHash, white_list: T) {
+        self.0.call_contract(
+            sender,
+            "remove_from_white_list",
+            runtime_args! {
+                "white_list" => white_list.into(),
+            },
+        );
+    }
+
+    pub fn set_permissionless(&self, sender: AccountHash, permissionless: bool) {
+        self.0.call_contract(
+            sender,
+            "set_permissionless",
+            runtime_args! {
+                "permissionless" => permissionless,
+            },
+        );
+    }
+
+    pub fn pause(&self, sender: AccountHash) {
+        self.0.call_contract(sender, "pause", runtime_args! {});
+    }
+
+    pub fn unpause(&self, sender: AccountHash) {
+        self.0.call_contract(sender, "unpause", runtime_args! {});
+    }
+
+    pub fn pause_pair<T: Into<Key>>(&self, sender: AccountHash, pair: T) {
+        self.0.call_contract(
+            sender,
+            "pause_pair",
+            runtime_args! {
+                "pair" => pair.into(),
+            },
+        );
+    }
+
+    pub fn unpause_pair<T: Into<Key>>(&self, sender: AccountHash, pair: T) {
+        self.0.call_contract(
+            sender,
+            "unpause_pair",
+            runtime_args! {
+                "pair" => pair.into(),
+            },
+        );
+    }
+
+    pub fn deny_token<T: Into<Key>>(&self, sender: AccountHash, token: T) {
+        self.0.call_contract(
+            sender,
+            "deny_token",
+            runtime_args! {
+                "token" => token.into(),
+            },
+        );
+    }
+
+    pub fn allow_token<T: Into<Key>>(&self, sender: AccountHash, token: T) {
+        self.0.call_contract(
+            sender,
+            "allow_token",
+            runtime_args! {
+                "token" => token.into(),
+            },
+        );
+    }
+
+    pub fn delist_pair<T: Into<Key>>(&self, sender: AccountHash, token_a: T, token_b: T, fee: u32) {
+        self.0.call_contract(
+            sender,
+            "delist_pair",
+            runtime_args! {
+                "token_a" => token_a.into(),
+                "token_b" => token_b.into(),
+                "fee" => fee,
+            },
+        );
+    }
+
+    pub fn is_token_denied<T: Into<Key>>(&self, token: T) -> bool {
         self.0
-            .query_dictionary("white_lists", key_to_str(&account.into()))
+            .query_dictionary("denied_tokens", key_to_str(&token.into()))
+            .unwrap_or_default()
+    }
+
+    pub fn is_pair_delisted<T: Into<Key>>(&self, pair: T) -> bool {
+        self.0
+            .query_dictionary("delisted_pairs", key_to_str(&pair.into()))
+            .unwrap_or_default()
+    }
+
+    pub fn is_white_listed<T: Into<Key>>(&self, account: T) -> bool {
+        let white_list: Option<Key> = self
+            .0
+            .query_dictionary("white_lists", key_to_str(&account.into()));
+        match white_list {
+            Some(white_list) => white_list != Key::from(AccountHash::new([0u8; 32])),
+            None => false,
+        }
+    }
+
+    pub fn white_list_length(&self) -> U256 {
+        self.0.query_named_key(String::from("white_list_length"))
+    }
+
+    pub fn white_list_at(&self, index: U256) -> Key {
+        self.0
+            .query_dictionary("white_list_accounts", index.to_string())
             .unwrap()
     }
 
+    pub fn is_permissionless(&self) -> bool {
+        self.0.query_named_key(String::from("permissionless"))
+    }
+
+    pub fn paused(&self) -> bool {
+        self.0.query_named_key(String::from("paused"))
+    }
+
+    pub fn storage_version(&self) -> u32 {
+        self.0.query_named_key(String::from("storage_version"))
+    }
+
     pub fn self_contract_hash(&self) -> Key {
         self.0.query_named_key(String::from("self_contract_hash"))
     }
@@ -111,17 +380,60 @@ impl FACTORYInstance {
         self.0.query_named_key(String::from("fee_to_setter"))
     }
 
-    pub fn all_pairs(&self) -> Vec<Key> {
-        self.0.query_named_key(String::from("all_pairs"))
+    pub fn pending_fee_to_setter(&self) -> Key {
+        self.0
+            .query_named_key(String::from("pending_fee_to_setter"))
+    }
+
+    pub fn owner(&self) -> Key {
+        self.0.query_named_key(String::from("owner"))
+    }
+
+    pub fn pending_owner(&self) -> Key {
+        self.0.query_named_key(String::from("pending_owner"))
+    }
+
+    pub fn all_pairs_length(&self) -> U256 {
+        self.0.query_named_key(String::from("all_pairs_length"))
     }
 
-    pub fn get_pair<T: Into<Key>>(&self, token0: T, token1: T) -> Key {
+    pub fn pair_at(&self, index: U256) -> Key {
+        self.0
+            .query_dictionary("pair_list", index.to_string())
+            .unwrap()
+    }
+
+    pub fn pairs_for_token_length<T: Into<Key>>(&self, token: T) -> U256 {
+        self.0
+            .query_dictionary("token_pairs_length", key_to_str(&token.into()))
+            .unwrap_or_default()
+    }
+
+    pub fn pair_for_token_at<T: Into<Key>>(&self, token: T, index: U256) -> Key {
+        self.0
+            .query_dictionary("token_pairs", key_and_value_to_str(&token.into(), &index))
+            .unwrap()
+    }
+
+    pub fn get_pair<T: Into<Key>>(&self, token0: T, token1: T, fee: u32) -> Key {
         let token0: Key = token0.into();
         let token1: Key = token1.into();
         self.0
-            .query_dictionary("pairs", keys_to_str(&token0, &token1))
+            .query_dictionary("pairs", key_and_value_to_str(&token0, &(token1, fee)))
             .unwrap()
     }
+
+    pub fn pair_fees<T: Into<Key>>(&self, token0: T, token1: T) -> Vec<u32> {
+        let token0: Key = token0.into();
+        let token1: Key = token1.into();
+        self.0
+            .query_dictionary("pair_fees", keys_to_str(&token0, &token1))
+            .unwrap_or_default()
+    }
+
+    pub fn fee_tiers(&self) -> Vec<u32> {
+        self.0.query_named_key(String::from("fee_tiers"))
+    }
 }
 
 pub fn key_to_str(key: &Key) -> String {
@@ -140,3 +452,12 @@ pub fn keys_to_str(key_a: &Key, key_b: &Key) -> String {
     hasher.finalize_variable(|hash| ret.clone_from_slice(hash));
     hex::encode(ret)
 }
+
+pub fn key_and_value_to_str<T: CLTyped + ToBytes>(key: &Key, value: &T) -> String {
+    let mut hasher = VarBlake2b::new(32).unwrap();
+    hasher.update(key.to_bytes().unwrap());
+    hasher.update(value.to_bytes().unwrap());
+    let mut ret = [0u8; 32];
+    hasher.finalize_variable(|hash| ret.clone_from_slice(hash));
+    hex::encode(ret)
+}
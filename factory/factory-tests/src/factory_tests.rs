@@ -8,6 +8,7 @@ use test_env::{TestContract, TestEnv};
 use crate::factory_instance::FACTORYInstance;
 
 const NAME_FACTORY: &str = "Factory";
+const CHAIN_NAME: &str = "casper-test";
 
 fn deploy() -> (TestEnv, FACTORYInstance, AccountHash, TestContract) {
     let env = TestEnv::new();
@@ -16,10 +17,6 @@ fn deploy() -> (TestEnv, FACTORYInstance, AccountHash, TestContract) {
     let token = FACTORYInstance::new(&env, NAME_FACTORY, owner, owner);
     let wcspr = deploy_wcspr(&env);
     let dai = deploy_dai(&env);
-    let name: &str = "ERC20";
-    let symbol: &str = "ERC";
-    let decimals: u8 = 8;
-    let init_total_supply: U256 = 1000.into();
     let callee_contract = TestContract::new(
         //&env_factory,
         &env,
@@ -32,22 +29,67 @@ fn deploy() -> (TestEnv, FACTORYInstance, AccountHash, TestContract) {
             "uniswap_v2_factory" => token.contract_package_hash()
         },
     );
-    let pair_contract = TestContract::new(
+    let pair_contract = deploy_pair(
         &env,
-        "pair-token.wasm",
         "Pair",
         owner,
+        &token,
+        Key::Hash(callee_contract.package_hash()),
+    );
+    (env, token, owner, pair_contract)
+}
+
+fn deploy_pair(
+    env: &TestEnv,
+    contract_name: &str,
+    owner: AccountHash,
+    factory: &FACTORYInstance,
+    callee_package_hash: Key,
+) -> TestContract {
+    let name: &str = "ERC20";
+    let symbol: &str = "ERC";
+    let decimals: u8 = 8;
+    let init_total_supply: U256 = 1000.into();
+    TestContract::new(
+        env,
+        "pair-token.wasm",
+        contract_name,
+        owner,
         runtime_args! {
         "name" => name,
         "symbol" => symbol,
         "decimals" => decimals,
         "initial_supply" => init_total_supply,
-        "callee_package_hash" => Key::Hash(callee_contract.package_hash()),
-        "factory_hash" =>  token.contract_package_hash(),
+        "callee_package_hash" => callee_package_hash,
+        "factory_hash" =>  factory.contract_package_hash(),
+        "chain_name" => CHAIN_NAME
             // contract_name is passed seperately, so we don't need to pass it here.
         },
-    );
-    (env, token, owner, pair_contract)
+    )
+}
+
+/// Installs the pair as released before storage versions, for factories of that release.
+fn deploy_legacy_pair(
+    env: &TestEnv,
+    contract_name: &str,
+    owner: AccountHash,
+    factory: &FACTORYInstance,
+    callee_package_hash: Key,
+) -> TestContract {
+    TestContract::new(
+        env,
+        "pair-token-legacy.wasm",
+        contract_name,
+        owner,
+        runtime_args! {
+            "name" => "ERC20",
+            "symbol" => "ERC",
+            "decimals" => 8u8,
+            "initial_supply" => U256::from(1000),
+            "callee_package_hash" => callee_package_hash,
+            "factory_hash" => factory.contract_package_hash()
+        },
+    )
 }
 
 fn deploy_token0(env: &TestEnv) -> TestContract {
@@ -65,7 +107,8 @@ fn deploy_token0(env: &TestEnv) -> TestContract {
         "initial_supply" => init_total_supply,
         "name" => "token0",
         "symbol" => "tk0",
-        "decimals" => decimals
+        "decimals" => decimals,
+        "chain_name" => CHAIN_NAME
         },
     );
     token0_contract
@@ -85,7 +128,8 @@ fn deploy_token1(env: &TestEnv) -> TestContract {
         "initial_supply" => init_total_supply,
         "name" => "token1",
         "symbol" => "tk1",
-        "decimals" => decimals
+        "decimals" => decimals,
+        "chain_name" => CHAIN_NAME
         },
     );
     token1_contract
@@ -141,7 +185,10 @@ fn test_factory_set_fee_to_setter() {
     let (env, token, owner, _pair_hash) = deploy();
     let user = env.next_user();
     assert_eq!(token.fee_to_setter(), Key::Account(owner));
-    token.set_fee_to_setter(owner, user);
+    token.propose_fee_to_setter(owner, user);
+    assert_eq!(token.fee_to_setter(), Key::Account(owner));
+    assert_eq!(token.pending_fee_to_setter(), Key::Account(user));
+    token.accept_fee_to_setter(user);
     assert_eq!(token.fee_to_setter(), Key::Account(user));
 }
 
@@ -166,18 +213,68 @@ fn test_factory_create_pair() {
     let pair_hash = Key::Hash(pair_hash.package_hash());
     let user = env.next_user();
     token.set_white_list(owner, Key::Account(user));
-    assert_eq!(
-        token.get_white_lists(Key::Account(user)),
-        Key::Account(user)
-    );
-    token.create_pair(user, token0, token1, pair_hash);
-    let pair_0_1: Key = token.get_pair(token0, token1);
-    let pair_1_0: Key = token.get_pair(token1, token0);
-    let all_pairs: Vec<Key> = token.all_pairs();
+    assert!(token.is_white_listed(Key::Account(user)));
+    token.create_pair(user, token0, token1, pair_hash, 30, 0, 0);
+    let pair_0_1: Key = token.get_pair(token0, token1, 30);
+    let pair_1_0: Key = token.get_pair(token1, token0, 30);
     assert_eq!(pair_0_1, pair_1_0);
     assert_eq!(pair_0_1, pair_hash);
     assert_eq!(pair_1_0, pair_hash);
-    assert_eq!(all_pairs.len(), 1);
+    assert_eq!(token.all_pairs_length(), 1.into());
+    assert_eq!(token.pair_at(0.into()), pair_hash);
+    assert_eq!(token.pairs_for_token_length(token0), 1.into());
+    assert_eq!(token.pairs_for_token_length(token1), 1.into());
+    assert_eq!(token.pair_for_token_at(token0, 0.into()), pair_hash);
+    assert_eq!(token.pair_for_token_at(token1, 0.into()), pair_hash);
+}
+
+#[test]
+fn test_factory_sync_fee_on_pushes_fee_on_to_pairs() {
+    let (env, token, owner, pair_hash) = deploy();
+    let token0 = Key::Hash(deploy_token0(&env).package_hash());
+    let token1 = Key::Hash(deploy_token1(&env).package_hash());
+    let pair = Key::Hash(pair_hash.package_hash());
+    let user = env.next_user();
+    token.set_white_list(owner, Key::Account(owner));
+    token.create_pair(owner, token0, token1, pair, 30, 0, 0);
+    let fee_on: bool = pair_hash.query_named_key(String::from("fee_on"));
+    assert!(!fee_on);
+    token.set_fee_to(owner, user);
+    // set_fee_to leaves the pairs alone until they are synced
+    let fee_on: bool = pair_hash.query_named_key(String::from("fee_on"));
+    assert!(!fee_on);
+    // a page past the end of the registry does nothing
+    token.sync_fee_on(user, 1.into(), 10.into());
+    let fee_on: bool = pair_hash.query_named_key(String::from("fee_on"));
+    assert!(!fee_on);
+    token.sync_fee_on(user, 0.into(), 10.into());
+    let fee_on: bool = pair_hash.query_named_key(String::from("fee_on"));
+    assert!(fee_on);
+    token.set_fee_to(owner, AccountHash::new([0u8; 32]));
+    token.sync_fee_on(user, 0.into(), 1.into());
+    let fee_on: bool = pair_hash.query_named_key(String::from("fee_on"));
+    assert!(!fee_on);
+}
+
+#[test]
+fn test_factory_create_pair_after_set_fee_to() {
+    let (env, token, owner, pair_hash) = deploy();
+    let token0 = Key::Hash(deploy_token0(&env).package_hash());
+    let token1 = Key::Hash(deploy_token1(&env).package_hash());
+    let user = env.next_user();
+    token.set_fee_to(owner, user);
+    token.set_white_list(owner, Key::Account(owner));
+    token.create_pair(
+        owner,
+        token0,
+        token1,
+        Key::Hash(pair_hash.package_hash()),
+        30,
+        0,
+        0,
+    );
+    let fee_on: bool = pair_hash.query_named_key(String::from("fee_on"));
+    assert!(fee_on);
 }
 
 #[test]
@@ -187,15 +284,9 @@ fn test_factory_set_white_list() {
     let user = env.next_user();
     token.set_white_list(owner, Key::Account(user));
 
-    assert_eq!(
-        token.get_white_lists(Key::Account(user)),
-        Key::Account(user)
-    );
+    assert!(token.is_white_listed(Key::Account(user)));
     token.set_white_list(owner, Key::Account(owner));
-    assert_eq!(
-        token.get_white_lists(Key::Account(owner)),
-        Key::Account(owner)
-    );
+    assert!(token.is_white_listed(Key::Account(owner)));
 }
 
 #[test]
@@ -207,6 +298,477 @@ fn test_factory_set_white_list_with_non_owner() {
     token.set_white_list(user, Key::Account(user));
 }
 
+#[test]
+fn test_factory_roles_after_deploy() {
+    let (env, token, owner, _pair_hash) = deploy();
+    let user = env.next_user();
+    assert!(token.has_role("DEFAULT_ADMIN", owner));
+    assert!(token.has_role("WHITELISTER", owner));
+    assert!(token.has_role("PAUSER", owner));
+    assert!(token.has_role("FEE_SETTER", owner));
+    assert!(!token.has_role("FEE_SETTER", user));
+}
+
+#[test]
+fn test_factory_set_fee_to_setter_moves_fee_setter_role() {
+    let (env, token, owner, _pair_hash) = deploy();
+    let user = env.next_user();
+    token.propose_fee_to_setter(owner, user);
+    token.accept_fee_to_setter(user);
+    assert!(!token.has_role("FEE_SETTER", owner));
+    assert!(token.has_role("FEE_SETTER", user));
+    token.set_fee_to(user, owner);
+    assert_eq!(token.fee_to(), Key::Account(owner));
+}
+
+#[test]
+#[should_panic]
+fn test_factory_set_fee_to_after_losing_fee_setter_role() {
+    let (env, token, owner, _pair_hash) = deploy();
+    let user = env.next_user();
+    token.propose_fee_to_setter(owner, user);
+    token.accept_fee_to_setter(user);
+    token.set_fee_to(owner, owner);
+}
+
+#[test]
+#[should_panic]
+fn test_factory_accept_fee_to_setter_by_non_nominee() {
+    let (env, token, owner, _pair_hash) = deploy();
+    let user = env.next_user();
+    let other = env.next_user();
+    token.propose_fee_to_setter(owner, user);
+    token.accept_fee_to_setter(other);
+}
+
+#[test]
+#[should_panic]
+fn test_factory_accept_fee_to_setter_after_cancel() {
+    let (env, token, owner, _pair_hash) = deploy();
+    let user = env.next_user();
+    token.propose_fee_to_setter(owner, user);
+    token.cancel_fee_to_setter_proposal(owner);
+    token.accept_fee_to_setter(user);
+}
+
+#[test]
+fn test_factory_two_step_ownership_transfer() {
+    let (env, token, owner, _pair_hash) = deploy();
+    let user = env.next_user();
+    assert_eq!(token.owner(), Key::Account(owner));
+    token.propose_owner(owner, user);
+    assert_eq!(token.owner(), Key::Account(owner));
+    assert_eq!(token.pending_owner(), Key::Account(user));
+    token.accept_ownership(user);
+    assert_eq!(token.owner(), Key::Account(user));
+    for role in ["DEFAULT_ADMIN", "WHITELISTER", "PAUSER"] {
+        assert!(token.has_role(role, user));
+        assert!(!token.has_role(role, owner));
+    }
+    // the fee setter is handed over separately
+    assert!(token.has_role("FEE_SETTER", owner));
+}
+
+#[test]
+#[should_panic]
+fn test_factory_previous_owner_cannot_white_list() {
+    let (env, token, owner, _pair_hash) = deploy();
+    let user = env.next_user();
+    token.propose_owner(owner, user);
+    token.accept_ownership(user);
+    token.set_white_list(owner, Key::Account(owner));
+}
+
+#[test]
+fn test_factory_admin_proposes_owner() {
+    let (env, token, owner, _pair_hash) = deploy();
+    let admin = env.next_user();
+    let user = env.next_user();
+    token.grant_role(owner, "DEFAULT_ADMIN", admin);
+    token.propose_owner(admin, user);
+    token.accept_ownership(user);
+    assert_eq!(token.owner(), Key::Account(user));
+    assert!(!token.has_role("DEFAULT_ADMIN", admin));
+    // roles of the other admin are left alone
+    assert!(token.has_role("DEFAULT_ADMIN", owner));
+    assert!(token.has_role("WHITELISTER", owner));
+}
+
+#[test]
+#[should_panic]
+fn test_factory_accept_ownership_after_proposer_revoked() {
+    let (env, token, owner, _pair_hash) = deploy();
+    let admin = env.next_user();
+    let user = env.next_user();
+    token.grant_role(owner, "DEFAULT_ADMIN", admin);
+    token.propose_owner(admin, user);
+    token.revoke_role(owner, "DEFAULT_ADMIN", admin);
+    token.accept_ownership(user);
+}
+
+#[test]
+#[should_panic]
+fn test_factory_propose_owner_by_non_owner() {
+    let (env, token, _owner, _pair_hash) = deploy();
+    let user = env.next_user();
+    token.propose_owner(user, user);
+}
+
+#[test]
+#[should_panic]
+fn test_factory_accept_ownership_after_cancel() {
+    let (env, token, owner, _pair_hash) = deploy();
+    let user = env.next_user();
+    token.propose_owner(owner, user);
+    token.cancel_owner_proposal(owner);
+    token.accept_ownership(user);
+}
+
+#[test]
+fn test_factory_granted_whitelister_can_set_white_list() {
+    let (env, token, owner, _pair_hash) = deploy();
+    let user = env.next_user();
+    token.grant_role(owner, "WHITELISTER", user);
+    token.set_white_list(user, Key::Account(user));
+    assert!(token.is_white_listed(Key::Account(user)));
+    token.revoke_role(owner, "WHITELISTER", user);
+    assert!(!token.has_role("WHITELISTER", user));
+}
+
+#[test]
+fn test_factory_remove_from_white_list() {
+    let (env, token, owner, _pair_hash) = deploy();
+    let user = env.next_user();
+    let other = env.next_user();
+    token.set_white_list(owner, Key::Account(user));
+    token.set_white_list(owner, Key::Account(other));
+    token.set_white_list(owner, Key::Account(other));
+    assert_eq!(token.white_list_length(), 2.into());
+    token.remove_from_white_list(owner, Key::Account(user));
+    assert!(!token.is_white_listed(Key::Account(user)));
+    assert!(token.is_white_listed(Key::Account(other)));
+    assert_eq!(token.white_list_length(), 1.into());
+    assert_eq!(token.white_list_at(0.into()), Key::Account(other));
+}
+
+#[test]
+#[should_panic]
+fn test_factory_remove_from_white_list_with_non_owner() {
+    let (env, token, owner, _pair_hash) = deploy();
+    let user = env.next_user();
+    token.set_white_list(owner, Key::Account(user));
+    token.remove_from_white_list(user, Key::Account(user));
+}
+
+#[test]
+#[should_panic]
+fn test_factory_create_pair_after_removal_from_white_list() {
+    let (env, token, owner, pair_hash) = deploy();
+    let token0 = Key::Hash(deploy_token0(&env).package_hash());
+    let token1 = Key::Hash(deploy_token1(&env).package_hash());
+    let user = env.next_user();
+    token.set_white_list(owner, Key::Account(user));
+    token.remove_from_white_list(owner, Key::Account(user));
+    token.create_pair(
+        user,
+        token0,
+        token1,
+        Key::Hash(pair_hash.package_hash()),
+        30,
+        0,
+        0,
+    );
+}
+
+#[test]
+fn test_factory_create_pair_when_permissionless() {
+    let (env, token, owner, pair_hash) = deploy();
+    let token0 = Key::Hash(deploy_token0(&env).package_hash());
+    let token1 = Key::Hash(deploy_token1(&env).package_hash());
+    let pair_hash = Key::Hash(pair_hash.package_hash());
+    let user = env.next_user();
+    assert!(!token.is_permissionless());
+    token.set_permissionless(owner, true);
+    assert!(token.is_permissionless());
+    token.create_pair(user, token0, token1, pair_hash, 30, 0, 0);
+    assert_eq!(token.get_pair(token0, token1, 30), pair_hash);
+}
+
+#[test]
+#[should_panic]
+fn test_factory_set_permissionless_with_non_admin() {
+    let (env, token, _owner, _pair_hash) = deploy();
+    let user = env.next_user();
+    token.set_permissionless(user, true);
+}
+
+#[test]
+#[should_panic]
+fn test_factory_create_pair_with_denied_token() {
+    let (env, token, owner, pair_hash) = deploy();
+    let token0 = Key::Hash(deploy_token0(&env).package_hash());
+    let token1 = Key::Hash(deploy_token1(&env).package_hash());
+    token.set_white_list(owner, Key::Account(owner));
+    token.deny_token(owner, token1);
+    assert!(token.is_token_denied(token1));
+    token.create_pair(
+        owner,
+        token0,
+        token1,
+        Key::Hash(pair_hash.package_hash()),
+        30,
+        0,
+        0,
+    );
+}
+
+#[test]
+fn test_factory_create_pair_after_allowing_token() {
+    let (env, token, owner, pair_hash) = deploy();
+    let token0 = Key::Hash(deploy_token0(&env).package_hash());
+    let token1 = Key::Hash(deploy_token1(&env).package_hash());
+    let pair_hash = Key::Hash(pair_hash.package_hash());
+    token.set_white_list(owner, Key::Account(owner));
+    token.deny_token(owner, token1);
+    token.allow_token(owner, token1);
+    assert!(!token.is_token_denied(token1));
+    token.create_pair(owner, token0, token1, pair_hash, 30, 0, 0);
+    assert_eq!(token.get_pair(token0, token1, 30), pair_hash);
+}
+
+#[test]
+fn test_factory_delist_pair() {
+    let (env, token, owner, pair_hash) = deploy();
+    let token0 = Key::Hash(deploy_token0(&env).package_hash());
+    let token1 = Key::Hash(deploy_token1(&env).package_hash());
+    let pair_hash = Key::Hash(pair_hash.package_hash());
+    token.set_white_list(owner, Key::Account(owner));
+    token.create_pair(owner, token0, token1, pair_hash, 30, 0, 0);
+    assert!(!token.is_pair_delisted(pair_hash));
+    token.delist_pair(owner, token1, token0, 30);
+    assert!(token.is_pair_delisted(pair_hash));
+    assert_eq!(token.get_pair(token0, token1, 30), pair_hash);
+    assert_eq!(token.pair_at(0.into()), pair_hash);
+}
+
+#[test]
+#[should_panic]
+fn test_factory_delist_unknown_pair() {
+    let (env, token, owner, _pair_hash) = deploy();
+    let token0 = Key::Hash(deploy_token0(&env).package_hash());
+    let token1 = Key::Hash(deploy_token1(&env).package_hash());
+    token.delist_pair(owner, token0, token1, 30);
+}
+
+#[test]
+fn test_factory_pause() {
+    let (_env, token, owner, _pair_hash) = deploy();
+    assert!(!token.paused());
+    token.pause(owner);
+    assert!(token.paused());
+    token.unpause(owner);
+    assert!(!token.paused());
+}
+
+#[test]
+fn test_factory_upgrade_keeps_pairs_and_roles() {
+    let (env, token, owner, pair_hash) = deploy();
+    let token0 = Key::Hash(deploy_token0(&env).package_hash());
+    let token1 = Key::Hash(deploy_token1(&env).package_hash());
+    let pair_hash = Key::Hash(pair_hash.package_hash());
+    token.set_white_list(owner, Key::Account(owner));
+    token.create_pair(owner, token0, token1, pair_hash, 30, 0, 0);
+    token.pause(owner);
+    let contract_hash = token.self_contract_hash();
+    assert_eq!(token.storage_version(), 1);
+
+    FACTORYInstance::upgrade(&env, NAME_FACTORY, owner);
+
+    assert_ne!(token.self_contract_hash(), contract_hash);
+    assert_eq!(token.storage_version(), 1);
+    assert_eq!(token.get_pair(token0, token1, 30), pair_hash);
+    assert_eq!(token.all_pairs_length(), 1.into());
+    assert_eq!(token.pair_at(0.into()), pair_hash);
+    assert_eq!(token.fee_to_setter(), Key::Account(owner));
+    assert!(token.is_white_listed(Key::Account(owner)));
+    assert!(token.has_role("DEFAULT_ADMIN", owner));
+    assert!(token.paused());
+    token.unpause(owner);
+    assert!(!token.paused());
+}
+
+#[test]
+fn test_factory_upgrade_from_legacy_layout() {
+    let (env, _token, owner, pair_hash) = deploy();
+    let user = env.next_user();
+    let callee_package_hash: Key = pair_hash.query_named_key(String::from("callee_package_hash"));
+    let factory = FACTORYInstance::new_legacy(&env, "legacy_factory", owner, owner);
+    let token0 = Key::Hash(deploy_token0(&env).package_hash());
+    let token1 = Key::Hash(deploy_token1(&env).package_hash());
+    let pair = Key::Hash(
+        deploy_legacy_pair(&env, "legacy_pair", owner, &factory, callee_package_hash)
+            .package_hash(),
+    );
+    factory.set_white_list(owner, Key::Account(owner));
+    factory.set_white_list(owner, Key::Account(user));
+    factory.legacy_create_pair(owner, token0, token1, pair);
+
+    FACTORYInstance::upgrade(&env, "legacy_factory", owner);
+
+    assert_eq!(factory.storage_version(), 1);
+    assert_eq!(factory.fee_to_setter(), Key::Account(owner));
+    assert!(factory.has_role("DEFAULT_ADMIN", owner));
+    assert!(factory.has_role("WHITELISTER", owner));
+    assert!(factory.has_role("PAUSER", owner));
+    assert!(factory.has_role("FEE_SETTER", owner));
+    assert!(!factory.paused());
+    // white listed accounts stay allowed, though nothing lists them yet
+    assert!(factory.is_white_listed(Key::Account(owner)));
+    assert!(factory.is_white_listed(Key::Account(user)));
+    assert_eq!(factory.white_list_length(), 0.into());
+
+    factory.migrate_all_pairs(owner, 0.into(), 10.into());
+    assert_eq!(factory.all_pairs_length(), 1.into());
+    assert_eq!(factory.pair_at(0.into()), pair);
+    assert_eq!(factory.get_pair(token0, token1, 30), pair);
+    assert_eq!(factory.get_pair(token1, token0, 30), pair);
+    assert_eq!(factory.pair_fees(token0, token1), vec![30]);
+
+    factory.remove_from_white_list(owner, Key::Account(user));
+    assert!(!factory.is_white_listed(Key::Account(user)));
+    factory.set_white_list(owner, Key::Account(owner));
+    assert!(factory.is_white_listed(Key::Account(owner)));
+    assert_eq!(factory.white_list_length(), 1.into());
+    assert_eq!(factory.white_list_at(0.into()), Key::Account(owner));
+}
+
+#[test]
+#[should_panic]
+fn test_factory_pause_with_non_pauser() {
+    let (env, token, _owner, _pair_hash) = deploy();
+    let user = env.next_user();
+    token.pause(user);
+}
+
+#[test]
+fn test_factory_pause_pair() {
+    let (_env, token, owner, pair_hash) = deploy();
+    token.pause_pair(owner, Key::Hash(pair_hash.package_hash()));
+    let paused: bool = pair_hash.query_named_key(String::from("paused"));
+    assert!(paused);
+    token.unpause_pair(owner, Key::Hash(pair_hash.package_hash()));
+    let paused: bool = pair_hash.query_named_key(String::from("paused"));
+    assert!(!paused);
+    assert!(!token.paused());
+}
+
+#[test]
+#[should_panic]
+fn test_factory_deny_token_with_non_admin() {
+    let (env, token, _owner, _pair_hash) = deploy();
+    let token0 = Key::Hash(deploy_token0(&env).package_hash());
+    let user = env.next_user();
+    token.deny_token(user, token0);
+}
+
+#[test]
+fn test_factory_create_pairs_in_several_fee_tiers() {
+    let (env, token, owner, pair_hash) = deploy();
+    let token0 = Key::Hash(deploy_token0(&env).package_hash());
+    let token1 = Key::Hash(deploy_token1(&env).package_hash());
+    let pair_30 = Key::Hash(pair_hash.package_hash());
+    let callee_package_hash: Key = pair_hash.query_named_key(String::from("callee_package_hash"));
+    let pair_5 =
+        Key::Hash(deploy_pair(&env, "Pair_5", owner, &token, callee_package_hash).package_hash());
+    assert_eq!(token.fee_tiers(), vec![30]);
+    token.enable_fee_tier(owner, 5);
+    assert_eq!(token.fee_tiers(), vec![30, 5]);
+    token.set_white_list(owner, Key::Account(owner));
+    token.create_pair(owner, token0, token1, pair_30, 30, 0, 0);
+    token.create_pair(owner, token1, token0, pair_5, 5, 0, 0);
+    assert_eq!(token.get_pair(token0, token1, 30), pair_30);
+    assert_eq!(token.get_pair(token1, token0, 5), pair_5);
+    let (sorted0, sorted1) = if token0 < token1 {
+        (token0, token1)
+    } else {
+        (token1, token0)
+    };
+    assert_eq!(token.pair_fees(sorted0, sorted1), vec![30, 5]);
+    assert_eq!(token.all_pairs_length(), 2.into());
+}
+
+#[test]
+#[should_panic]
+fn test_factory_create_pair_in_disabled_fee_tier() {
+    let (env, token, owner, pair_hash) = deploy();
+    let token0 = Key::Hash(deploy_token0(&env).package_hash());
+    let token1 = Key::Hash(deploy_token1(&env).package_hash());
+    token.set_white_list(owner, Key::Account(owner));
+    token.disable_fee_tier(owner, 30);
+    token.create_pair(
+        owner,
+        token0,
+        token1,
+        Key::Hash(pair_hash.package_hash()),
+        30,
+        0,
+        0,
+    );
+}
+
+#[test]
+fn test_factory_create_stable_swap_pair() {
+    let (env, token, owner, pair_hash) = deploy();
+    let token0 = Key::Hash(deploy_token0(&env).package_hash());
+    let token1 = Key::Hash(deploy_token1(&env).package_hash());
+    let callee_package_hash: Key = pair_hash.query_named_key(String::from("callee_package_hash"));
+    let stable_pair = deploy_pair(&env, "Pair_stable", owner, &token, callee_package_hash);
+    token.enable_fee_tier(owner, 4);
+    token.set_white_list(owner, Key::Account(owner));
+    token.create_pair(
+        owner,
+        token0,
+        token1,
+        Key::Hash(stable_pair.package_hash()),
+        4,
+        1,
+        100,
+    );
+    assert_eq!(
+        token.get_pair(token0, token1, 4),
+        Key::Hash(stable_pair.package_hash())
+    );
+    let curve_type: u8 = stable_pair.query_named_key(String::from("curve_type"));
+    let amplification: u64 = stable_pair.query_named_key(String::from("amplification"));
+    assert_eq!(curve_type, 1);
+    assert_eq!(amplification, 100);
+}
+
+#[test]
+#[should_panic]
+fn test_factory_create_pair_with_unknown_curve() {
+    let (env, token, owner, pair_hash) = deploy();
+    let token0 = Key::Hash(deploy_token0(&env).package_hash());
+    let token1 = Key::Hash(deploy_token1(&env).package_hash());
+    token.set_white_list(owner, Key::Account(owner));
+    token.create_pair(
+        owner,
+        token0,
+        token1,
+        Key::Hash(pair_hash.package_hash()),
+        30,
+        2,
+        0,
+    );
+}
+
+#[test]
+#[should_panic]
+fn test_factory_enable_invalid_fee_tier() {
+    let (_env, token, owner, _pair_hash) = deploy();
+    token.enable_fee_tier(owner, 10000);
+}
+
 #[test]
 #[should_panic]
 fn test_calling_construction() {
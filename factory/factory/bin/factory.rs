@@ -4,7 +4,7 @@
 #[macro_use]
 extern crate alloc;
 
-use alloc::{boxed::Box, collections::BTreeSet, format,  vec::Vec};
+use alloc::{boxed::Box, collections::BTreeSet, format, string::String, vec::Vec};
 use casper_contract::{
     contract_api::{runtime, storage},
     unwrap_or_revert::UnwrapOrRevert,
@@ -14,7 +14,7 @@ use casper_types::{
     EntryPoint, EntryPointAccess, EntryPointType, EntryPoints, Group, Key, Parameter, RuntimeArgs,
     URef, U256,
 };
-use contract_utils::{ContractContext, OnChainContractStorage};
+use contract_utils::{AccessControl, ContractContext, OnChainContractStorage};
 use factory::{self, FACTORY};
 
 #[derive(Default)]
@@ -26,19 +26,18 @@ impl ContractContext<OnChainContractStorage> for Factory {
     }
 }
 
+impl AccessControl<OnChainContractStorage> for Factory {}
 impl FACTORY<OnChainContractStorage> for Factory {}
 impl Factory {
     fn constructor(
         &mut self,
         fee_to_setter: Key,
-        all_pairs: Vec<Key>,
         contract_hash: ContractHash,
         package_hash: ContractPackageHash,
     ) {
         FACTORY::init(
             self,
             fee_to_setter,
-            all_pairs,
             Key::from(contract_hash),
             Key::from(package_hash),
         );
@@ -48,11 +47,23 @@ impl Factory {
 #[no_mangle]
 fn constructor() {
     let fee_to_setter: Key = runtime::get_named_arg("fee_to_setter");
-    let all_pairs: Vec<Key> = runtime::get_named_arg("all_pairs");
     let contract_hash: ContractHash = runtime::get_named_arg("contract_hash");
     let package_hash: ContractPackageHash = runtime::get_named_arg("package_hash");
 
-    Factory::default().constructor(fee_to_setter, all_pairs, contract_hash, package_hash);
+    Factory::default().constructor(fee_to_setter, contract_hash, package_hash);
+}
+
+/// This function is to bring the storage of an upgraded Factory up to date, only callable by the installer while upgrading
+///
+/// # Parameters
+///
+/// * `contract_hash` - A ContractHash that holds the hash of the new contract version
+///
+
+#[no_mangle]
+fn migrate() {
+    let contract_hash: ContractHash = runtime::get_named_arg("contract_hash");
+    FACTORY::migrate(&mut Factory::default(), Key::from(contract_hash));
 }
 
 /// This function is to return the fee to's hash
@@ -73,22 +84,94 @@ fn fee_to_setter() {
     runtime::ret(CLValue::from_t(ret).unwrap_or_revert());
 }
 
-/// This function is to return the all Pairs
+/// This function is to return the total length of Pairs
 ///
 
 #[no_mangle]
-fn all_pairs() {
-    let ret: Vec<Key> = Factory::default().get_all_pairs();
+fn all_pairs_length() {
+    let ret: U256 = Factory::default().all_pairs_length();
     runtime::ret(CLValue::from_t(ret).unwrap_or_revert());
 }
 
-/// This function is to return the total length of Pairs
+/// This function is to return the Pair created at the given position
+///
+/// # Parameters
+///
+/// * `index` - A U256 that holds the position of the Pair, starting from 0
 ///
 
 #[no_mangle]
-fn all_pairs_length() {
-    let ret: Vec<Key> = Factory::default().get_all_pairs();
-    runtime::ret(CLValue::from_t(U256::from(ret.len())).unwrap_or_revert());
+fn pair_at() {
+    let index: U256 = runtime::get_named_arg("index");
+    let ret: Key = Factory::default().pair_at(index);
+    runtime::ret(CLValue::from_t(ret).unwrap_or_revert());
+}
+
+/// This function is to return a page of Pairs in creation order
+///
+/// # Parameters
+///
+/// * `start` - A U256 that holds the position of the first Pair to return
+///
+/// * `count` - A U256 that holds the maximum number of Pairs to return
+///
+
+#[no_mangle]
+fn pairs() {
+    let start: U256 = runtime::get_named_arg("start");
+    let count: U256 = runtime::get_named_arg("count");
+    let ret: Vec<Key> = Factory::default().pairs(start, count);
+    runtime::ret(CLValue::from_t(ret).unwrap_or_revert());
+}
+
+/// This function is to return the number of Pairs a token trades in
+///
+/// # Parameters
+///
+/// * `token` - A Key that holds the Hash of the token
+///
+
+#[no_mangle]
+fn pairs_for_token_length() {
+    let token: Key = runtime::get_named_arg("token");
+    let ret: U256 = Factory::default().pairs_for_token_length(token);
+    runtime::ret(CLValue::from_t(ret).unwrap_or_revert());
+}
+
+/// This function is to return a page of the Pairs a token trades in
+///
+/// # Parameters
+///
+/// * `token` - A Key that holds the Hash of the token
+///
+/// * `start` - A U256 that holds the position of the first Pair to return
+///
+/// * `count` - A U256 that holds the maximum number of Pairs to return
+///
+
+#[no_mangle]
+fn pairs_for_token() {
+    let token: Key = runtime::get_named_arg("token");
+    let start: U256 = runtime::get_named_arg("start");
+    let count: U256 = runtime::get_named_arg("count");
+    let ret: Vec<Key> = Factory::default().pairs_for_token(token, start, count);
+    runtime::ret(CLValue::from_t(ret).unwrap_or_revert());
+}
+
+/// This function is to move a page of the all_pairs list of an upgraded factory into the paginated registry, only callable by a DEFAULT_ADMIN
+///
+/// # Parameters
+///
+/// * `start` - A U256 that holds the index of the first pair to move, the number of pairs moved so far
+///
+/// * `count` - A U256 that holds the number of pairs to move
+///
+
+#[no_mangle]
+fn migrate_all_pairs() {
+    let start: U256 = runtime::get_named_arg("start");
+    let count: U256 = runtime::get_named_arg("count");
+    Factory::default().migrate_all_pairs(start, count);
 }
 
 /// This function is to set the fee to address which is only possible if the caller matched with fee to setter's hash
@@ -104,17 +187,105 @@ fn set_fee_to() {
     Factory::default().set_fee_to(fee_to);
 }
 
-/// This function is to set the fee to setter address who is the only one to set fee to.
+/// This function is to push whether the protocol fee is on to a page of the registered pairs, callable by anyone
 ///
 /// # Parameters
 ///
-/// * `fee_to_setter` - A Key that holds the Account Hash of fee_to_setter
+/// * `start` - A U256 that holds the index of the first pair to update
+///
+/// * `count` - A U256 that holds the number of pairs to update
 ///
 
 #[no_mangle]
-fn set_fee_to_setter() {
+fn sync_fee_on() {
+    let start: U256 = runtime::get_named_arg("start");
+    let count: U256 = runtime::get_named_arg("count");
+    Factory::default().sync_fee_on(start, count);
+}
+
+/// This function is to propose the next fee to setter, who has to accept before taking over
+///
+/// # Parameters
+///
+/// * `fee_to_setter` - A Key that holds the Account Hash of the proposed fee_to_setter
+///
+
+#[no_mangle]
+fn propose_fee_to_setter() {
     let fee_to_setter: Key = runtime::get_named_arg("fee_to_setter");
-    Factory::default().set_fee_to_setter(fee_to_setter);
+    Factory::default().propose_fee_to_setter(fee_to_setter);
+}
+
+/// This function is to accept a pending fee to setter proposal, only callable by the proposed fee to setter
+///
+
+#[no_mangle]
+fn accept_fee_to_setter() {
+    Factory::default().accept_fee_to_setter();
+}
+
+/// This function is to cancel a pending fee to setter proposal
+///
+
+#[no_mangle]
+fn cancel_fee_to_setter_proposal() {
+    Factory::default().cancel_fee_to_setter_proposal();
+}
+
+/// This function is to return the pending fee to setter's hash
+///
+
+#[no_mangle]
+fn pending_fee_to_setter() {
+    let ret: Key = Factory::default().get_pending_fee_to_setter();
+    runtime::ret(CLValue::from_t(ret).unwrap_or_revert());
+}
+
+/// This function is to return the owner's hash
+///
+
+#[no_mangle]
+fn owner() {
+    let ret: Key = Factory::default().get_owner();
+    runtime::ret(CLValue::from_t(ret).unwrap_or_revert());
+}
+
+/// This function is to propose the next owner, who has to accept before taking over
+///
+/// # Parameters
+///
+/// * `owner` - A Key that holds the Account Hash of the proposed owner
+///
+
+#[no_mangle]
+fn propose_owner() {
+    let owner: Key = runtime::get_named_arg("owner");
+    Factory::default().propose_owner(owner);
+}
+
+/// This function is to accept a pending ownership proposal, only callable by the proposed owner
+///
+
+#[no_mangle]
+fn accept_ownership() {
+    Factory::default().accept_ownership();
+}
+
+/// This function is to cancel a pending ownership proposal
+///
+
+#[no_mangle]
+fn cancel_owner_proposal() {
+    Factory::default().cancel_owner_proposal();
+}
+
+/// This function is to return the pending owner's hash
+///
+
+#[no_mangle]
+fn pending_owner() {
+    let ret: Key = Factory::default().get_pending_owner();
+    runtime::ret(CLValue::from_t(ret).unwrap_or_revert());
 }
 
 /// This function is to create pair of tokens provided by user agains the pair hash provided by user
@@ -123,47 +294,375 @@ fn set_fee_to_setter() {
 ///
 /// * `token_a` - A Key that holds the Hash of token_a
 ///
-/// * `token_b` - A Key that holds the Hash of token1_b
+/// * `token_b` - A Key that holds the Hash of token1_b
+///
+/// * `pair_hash` - A Key that holds the Hash of Pair Contract
+///
+/// * `fee` - A u32 that holds the fee tier of the Pair in basis points
+///
+/// * `curve_type` - A u8 that holds the curve of the Pair, 0 for constant product and 1 for StableSwap
+///
+/// * `amplification` - A u64 that holds the StableSwap amplification coefficient, 0 for constant product pairs
+///
+
+#[no_mangle]
+fn create_pair() {
+    let token_a: Key = runtime::get_named_arg("token_a");
+    let token_b: Key = runtime::get_named_arg("token_b");
+    let pair_hash: Key = runtime::get_named_arg("pair_hash");
+    let fee: u32 = runtime::get_named_arg("fee");
+    let curve_type: u8 = runtime::get_named_arg("curve_type");
+    let amplification: u64 = runtime::get_named_arg("amplification");
+    Factory::default().create_pair(token_a, token_b, pair_hash, fee, curve_type, amplification);
+}
+
+/// This function is to return the the pair against tokens provided by user. If pair not found it will return hash-0000000000000000000000000000000000000000000000000000000000000000
+///
+/// # Parameters
+///
+/// * `token0` - A Key that holds the Hash of token0
+///
+/// * `token1` - A Key that holds the Hash of token1
+///
+/// * `fee` - A u32 that holds the fee tier of the Pair in basis points
+///
+
+#[no_mangle]
+fn get_pair() {
+    let token0: Key = runtime::get_named_arg("token0");
+    let token1: Key = runtime::get_named_arg("token1");
+    let fee: u32 = runtime::get_named_arg("fee");
+    let ret: Key = Factory::default().get_pair(token0, token1, fee);
+    runtime::ret(CLValue::from_t(ret).unwrap_or_revert());
+}
+
+/// This function is to return the pairs of two tokens in every fee tier
+///
+/// # Parameters
+///
+/// * `token_a` - A Key that holds the Hash of token_a
+///
+/// * `token_b` - A Key that holds the Hash of token_b
+///
+
+#[no_mangle]
+fn get_pairs() {
+    let token_a: Key = runtime::get_named_arg("token_a");
+    let token_b: Key = runtime::get_named_arg("token_b");
+    let ret: Vec<Key> = Factory::default().get_pairs(token_a, token_b);
+    runtime::ret(CLValue::from_t(ret).unwrap_or_revert());
+}
+
+/// This function is to return the fee tiers, in basis points, pairs can be created with
+///
+
+#[no_mangle]
+fn fee_tiers() {
+    let ret: Vec<u32> = Factory::default().get_fee_tiers();
+    runtime::ret(CLValue::from_t(ret).unwrap_or_revert());
+}
+
+/// This function is to allow pairs to be created in a fee tier, only callable by a DEFAULT_ADMIN
+///
+/// # Parameters
+///
+/// * `fee` - A u32 that holds the fee tier in basis points
+///
+
+#[no_mangle]
+fn enable_fee_tier() {
+    let fee: u32 = runtime::get_named_arg("fee");
+    Factory::default().enable_fee_tier(fee);
+}
+
+/// This function is to stop pairs being created in a fee tier, only callable by a DEFAULT_ADMIN
+///
+/// # Parameters
+///
+/// * `fee` - A u32 that holds the fee tier in basis points
+///
+
+#[no_mangle]
+fn disable_fee_tier() {
+    let fee: u32 = runtime::get_named_arg("fee");
+    Factory::default().disable_fee_tier(fee);
+}
+
+/// This function is to add an address to the white list, only callable by a WHITELISTER
+///
+/// # Parameters
+///
+/// * `white_list` - A Key that holds the Account Hash allowed to create pairs
+///
+
+#[no_mangle]
+fn set_white_list() {
+    let white_list: Key = runtime::get_named_arg("white_list");
+    Factory::default().set_white_list(white_list);
+}
+
+/// This function is to remove an address from the white list, only callable by a WHITELISTER
+///
+/// # Parameters
+///
+/// * `white_list` - A Key that holds the Account Hash to remove
+///
+
+#[no_mangle]
+fn remove_from_white_list() {
+    let white_list: Key = runtime::get_named_arg("white_list");
+    Factory::default().remove_from_white_list(white_list);
+}
+
+/// This function is to check whether an address is white listed
+///
+/// # Parameters
+///
+/// * `account` - A Key that holds the Account Hash to check
+///
+
+#[no_mangle]
+fn is_white_listed() {
+    let account: Key = runtime::get_named_arg("account");
+    let ret: bool = Factory::default().is_white_listed(account);
+    runtime::ret(CLValue::from_t(ret).unwrap_or_revert());
+}
+
+/// This function is to return the number of white listed addresses
+///
+
+#[no_mangle]
+fn white_list_length() {
+    let ret: U256 = Factory::default().white_list_length();
+    runtime::ret(CLValue::from_t(ret).unwrap_or_revert());
+}
+
+/// This function is to return a page of white listed addresses
+///
+/// # Parameters
+///
+/// * `start` - A U256 that holds the position of the first address to return
+///
+/// * `count` - A U256 that holds the maximum number of addresses to return
+///
+
+#[no_mangle]
+fn white_lists() {
+    let start: U256 = runtime::get_named_arg("start");
+    let count: U256 = runtime::get_named_arg("count");
+    let ret: Vec<Key> = Factory::default().white_lists(start, count);
+    runtime::ret(CLValue::from_t(ret).unwrap_or_revert());
+}
+
+/// This function is to stop pairs being created with a token, only callable by a DEFAULT_ADMIN
+///
+/// # Parameters
+///
+/// * `token` - A Key that holds the Hash of the token
+///
+
+#[no_mangle]
+fn deny_token() {
+    let token: Key = runtime::get_named_arg("token");
+    Factory::default().deny_token(token);
+}
+
+/// This function is to remove a token from the deny list, only callable by a DEFAULT_ADMIN
+///
+/// # Parameters
+///
+/// * `token` - A Key that holds the Hash of the token
+///
+
+#[no_mangle]
+fn allow_token() {
+    let token: Key = runtime::get_named_arg("token");
+    Factory::default().allow_token(token);
+}
+
+/// This function is to check whether a token is on the deny list
+///
+/// # Parameters
+///
+/// * `token` - A Key that holds the Hash of the token
+///
+
+#[no_mangle]
+fn is_token_denied() {
+    let token: Key = runtime::get_named_arg("token");
+    let ret: bool = Factory::default().is_token_denied(token);
+    runtime::ret(CLValue::from_t(ret).unwrap_or_revert());
+}
+
+/// This function is to flag the pair of two tokens as deprecated, only callable by a DEFAULT_ADMIN
+///
+/// # Parameters
+///
+/// * `token_a` - A Key that holds the Hash of token_a
+///
+/// * `token_b` - A Key that holds the Hash of token_b
+///
+/// * `fee` - A u32 that holds the fee tier of the Pair in basis points
+///
+
+#[no_mangle]
+fn delist_pair() {
+    let token_a: Key = runtime::get_named_arg("token_a");
+    let token_b: Key = runtime::get_named_arg("token_b");
+    let fee: u32 = runtime::get_named_arg("fee");
+    Factory::default().delist_pair(token_a, token_b, fee);
+}
+
+/// This function is to check whether a pair has been delisted
+///
+/// # Parameters
+///
+/// * `pair` - A Key that holds the Hash of the Pair Contract
+///
+
+#[no_mangle]
+fn is_pair_delisted() {
+    let pair: Key = runtime::get_named_arg("pair");
+    let ret: bool = Factory::default().is_pair_delisted(pair);
+    runtime::ret(CLValue::from_t(ret).unwrap_or_revert());
+}
+
+/// This function is to let anyone create pairs instead of white listed addresses only, only callable by a DEFAULT_ADMIN
+///
+/// # Parameters
+///
+/// * `permissionless` - A bool that holds whether pair creation is open to everyone
+///
+
+#[no_mangle]
+fn set_permissionless() {
+    let permissionless: bool = runtime::get_named_arg("permissionless");
+    Factory::default().set_permissionless(permissionless);
+}
+
+/// This function is to check whether pair creation is open to everyone
+///
+
+#[no_mangle]
+fn is_permissionless() {
+    let ret: bool = Factory::default().is_permissionless();
+    runtime::ret(CLValue::from_t(ret).unwrap_or_revert());
+}
+
+/// This function is to stop swaps, flash swaps and mints on every pair at once, only callable by a PAUSER
+///
+
+#[no_mangle]
+fn pause() {
+    Factory::default().pause();
+}
+
+/// This function is to lift the pause set by pause, only callable by a PAUSER
+///
+
+#[no_mangle]
+fn unpause() {
+    Factory::default().unpause();
+}
+
+/// This function is to check whether every pair is paused by the factory
+///
+
+#[no_mangle]
+fn paused() {
+    let ret: bool = Factory::default().is_paused();
+    runtime::ret(CLValue::from_t(ret).unwrap_or_revert());
+}
+
+/// This function is to stop swaps, flash swaps and mints on a single pair, only callable by a PAUSER
+///
+/// # Parameters
+///
+/// * `pair` - A Key that holds the package hash of the pair
+///
+
+#[no_mangle]
+fn pause_pair() {
+    let pair: Key = runtime::get_named_arg("pair");
+    Factory::default().pause_pair(pair);
+}
+
+/// This function is to lift the pause of a single pair, only callable by a PAUSER
+///
+/// # Parameters
+///
+/// * `pair` - A Key that holds the package hash of the pair
+///
+
+#[no_mangle]
+fn unpause_pair() {
+    let pair: Key = runtime::get_named_arg("pair");
+    Factory::default().unpause_pair(pair);
+}
+
+/// This function is to grant a role to an account, only callable by holders of the role's admin role
+///
+/// # Parameters
+///
+/// * `role` - A String that holds the name of the role
+///
+/// * `account` - A Key that holds the account address or package hash receiving the role
+///
+
+#[no_mangle]
+fn grant_role() {
+    let role: String = runtime::get_named_arg("role");
+    let account: Key = runtime::get_named_arg("account");
+    Factory::default().grant_role(role, account);
+}
+
+/// This function is to revoke a role from an account, only callable by holders of the role's admin role
+///
+/// # Parameters
+///
+/// * `role` - A String that holds the name of the role
 ///
-/// * `pair_hash` - A Key that holds the Hash of Pair Contract
+/// * `account` - A Key that holds the account address or package hash losing the role
 ///
 
 #[no_mangle]
-fn create_pair() {
-    let token_a: Key = runtime::get_named_arg("token_a");
-    let token_b: Key = runtime::get_named_arg("token_b");
-    let pair_hash: Key = runtime::get_named_arg("pair_hash");
-    Factory::default().create_pair(token_a, token_b, pair_hash);
+fn revoke_role() {
+    let role: String = runtime::get_named_arg("role");
+    let account: Key = runtime::get_named_arg("account");
+    Factory::default().revoke_role(role, account);
 }
 
-/// This function is to return the the pair against tokens provided by user. If pair not found it will return hash-0000000000000000000000000000000000000000000000000000000000000000
+/// This function is to give up a role held by the caller
 ///
 /// # Parameters
 ///
-/// * `token0` - A Key that holds the Hash of token0
+/// * `role` - A String that holds the name of the role
 ///
-/// * `token1` - A Key that holds the Hash of token1
+/// * `account` - A Key that must be equal to the caller
 ///
 
 #[no_mangle]
-fn get_pair() {
-    let token0: Key = runtime::get_named_arg("token0");
-    let token1: Key = runtime::get_named_arg("token1");
-    let ret: Key = Factory::default().get_pair(token0, token1);
-    runtime::ret(CLValue::from_t(ret).unwrap_or_revert());
+fn renounce_role() {
+    let role: String = runtime::get_named_arg("role");
+    let account: Key = runtime::get_named_arg("account");
+    Factory::default().renounce_role(role, account);
 }
 
-/// This function is to set the white list addresses which is only possible if the caller matched with owners's hash
+/// This function is to check whether an account holds a role
 ///
 /// # Parameters
 ///
-/// * `white_list` - A Key that holds the Account Hash of fee_to_setter
+/// * `role` - A String that holds the name of the role
+///
+/// * `account` - A Key that holds the account address or package hash
 ///
 
 #[no_mangle]
-fn set_white_list() {
-    let white_list: Key = runtime::get_named_arg("white_list");
-    Factory::default().set_white_list(white_list, white_list);
+fn has_role() {
+    let role: String = runtime::get_named_arg("role");
+    let account: Key = runtime::get_named_arg("account");
+    let ret: bool = Factory::default().has_role(&role, account);
+    runtime::ret(CLValue::from_t(ret).unwrap_or_revert());
 }
 
 /// This function is to fetch a Contract Package Hash
@@ -188,12 +687,10 @@ fn call() {
             storage::add_contract_version(package_hash, get_entry_points(), Default::default());
 
         let fee_to_setter: Key = runtime::get_named_arg("fee_to_setter");
-        let all_pairs: Vec<Key> = Vec::new();
 
         // Prepare constructor args
         let constructor_args = runtime_args! {
             "fee_to_setter" => fee_to_setter,
-            "all_pairs" => all_pairs,
             "contract_hash" => contract_hash,
             "package_hash"=> package_hash
         };
@@ -246,8 +743,49 @@ fn call() {
                 .unwrap()
                 .into();
 
+        let previous_contract_hash: ContractHash =
+            runtime::get_key(&format!("{}_contract_hash", contract_name))
+                .unwrap_or_revert()
+                .into_hash()
+                .unwrap_or_revert()
+                .into();
+
+        // The package access token stored at install time authorizes the new version.
+        // Named keys of the previous version, and with them all state, carry over.
         let (contract_hash, _): (ContractHash, _) =
             storage::add_contract_version(package_hash, get_entry_points(), Default::default());
+        storage::disable_contract_version(package_hash, previous_contract_hash).unwrap_or_revert();
+
+        // Provision a single URef of the migration group, creating the group on the first upgrade.
+        let migration_access: URef =
+            match storage::provision_contract_user_group_uref(package_hash, "migration") {
+                Ok(migration_access) => migration_access,
+                Err(_) => storage::create_contract_user_group(
+                    package_hash,
+                    "migration",
+                    1,
+                    Default::default(),
+                )
+                .unwrap_or_revert()
+                .pop()
+                .unwrap_or_revert(),
+            };
+
+        // Run the migration hooks of the new version
+        let _: () = runtime::call_versioned_contract(
+            package_hash,
+            None,
+            "migrate",
+            runtime_args! {
+                "contract_hash" => contract_hash
+            },
+        );
+
+        // Remove the URef again, so migrations only run as part of an upgrade.
+        let mut urefs = BTreeSet::new();
+        urefs.insert(migration_access);
+        storage::remove_contract_user_group_urefs(package_hash, "migration", urefs)
+            .unwrap_or_revert();
 
         // update contract hash
         runtime::put_key(
@@ -267,7 +805,6 @@ fn get_entry_points() -> EntryPoints {
         "constructor",
         vec![
             Parameter::new("fee_to_setter", Key::cl_type()),
-            Parameter::new("all_pairs", CLType::List(Box::new(Key::cl_type()))),
             Parameter::new("contract_hash", ContractHash::cl_type()),
             Parameter::new("package_hash", ContractPackageHash::cl_type()),
         ],
@@ -275,12 +812,22 @@ fn get_entry_points() -> EntryPoints {
         EntryPointAccess::Groups(vec![Group::new("constructor")]),
         EntryPointType::Contract,
     ));
+    entry_points.add_entry_point(EntryPoint::new(
+        "migrate",
+        vec![Parameter::new("contract_hash", ContractHash::cl_type())],
+        <()>::cl_type(),
+        EntryPointAccess::Groups(vec![Group::new("migration")]),
+        EntryPointType::Contract,
+    ));
     entry_points.add_entry_point(EntryPoint::new(
         "create_pair",
         vec![
             Parameter::new("token_a", Key::cl_type()),
             Parameter::new("token_b", Key::cl_type()),
             Parameter::new("pair_hash", Key::cl_type()),
+            Parameter::new("fee", u32::cl_type()),
+            Parameter::new("curve_type", u8::cl_type()),
+            Parameter::new("amplification", u64::cl_type()),
         ],
         <()>::cl_type(),
         EntryPointAccess::Public,
@@ -291,11 +838,43 @@ fn get_entry_points() -> EntryPoints {
         vec![
             Parameter::new("token0", Key::cl_type()),
             Parameter::new("token1", Key::cl_type()),
+            Parameter::new("fee", u32::cl_type()),
         ],
         Key::cl_type(),
         EntryPointAccess::Public,
         EntryPointType::Contract,
     ));
+    entry_points.add_entry_point(EntryPoint::new(
+        "get_pairs",
+        vec![
+            Parameter::new("token_a", Key::cl_type()),
+            Parameter::new("token_b", Key::cl_type()),
+        ],
+        CLType::List(Box::new(Key::cl_type())),
+        EntryPointAccess::Public,
+        EntryPointType::Contract,
+    ));
+    entry_points.add_entry_point(EntryPoint::new(
+        "fee_tiers",
+        vec![],
+        CLType::List(Box::new(u32::cl_type())),
+        EntryPointAccess::Public,
+        EntryPointType::Contract,
+    ));
+    entry_points.add_entry_point(EntryPoint::new(
+        "enable_fee_tier",
+        vec![Parameter::new("fee", u32::cl_type())],
+        CLType::Unit,
+        EntryPointAccess::Public,
+        EntryPointType::Contract,
+    ));
+    entry_points.add_entry_point(EntryPoint::new(
+        "disable_fee_tier",
+        vec![Parameter::new("fee", u32::cl_type())],
+        CLType::Unit,
+        EntryPointAccess::Public,
+        EntryPointType::Contract,
+    ));
     entry_points.add_entry_point(EntryPoint::new(
         "fee_to",
         vec![],
@@ -311,19 +890,57 @@ fn get_entry_points() -> EntryPoints {
         EntryPointType::Contract,
     ));
     entry_points.add_entry_point(EntryPoint::new(
-        "all_pairs",
+        "all_pairs_length",
         vec![],
+        CLType::U256,
+        EntryPointAccess::Public,
+        EntryPointType::Contract,
+    ));
+    entry_points.add_entry_point(EntryPoint::new(
+        "pair_at",
+        vec![Parameter::new("index", U256::cl_type())],
+        Key::cl_type(),
+        EntryPointAccess::Public,
+        EntryPointType::Contract,
+    ));
+    entry_points.add_entry_point(EntryPoint::new(
+        "pairs",
+        vec![
+            Parameter::new("start", U256::cl_type()),
+            Parameter::new("count", U256::cl_type()),
+        ],
         CLType::List(Box::new(Key::cl_type())),
         EntryPointAccess::Public,
         EntryPointType::Contract,
     ));
     entry_points.add_entry_point(EntryPoint::new(
-        "all_pairs_length",
-        vec![],
+        "pairs_for_token_length",
+        vec![Parameter::new("token", Key::cl_type())],
         CLType::U256,
         EntryPointAccess::Public,
         EntryPointType::Contract,
     ));
+    entry_points.add_entry_point(EntryPoint::new(
+        "pairs_for_token",
+        vec![
+            Parameter::new("token", Key::cl_type()),
+            Parameter::new("start", U256::cl_type()),
+            Parameter::new("count", U256::cl_type()),
+        ],
+        CLType::List(Box::new(Key::cl_type())),
+        EntryPointAccess::Public,
+        EntryPointType::Contract,
+    ));
+    entry_points.add_entry_point(EntryPoint::new(
+        "migrate_all_pairs",
+        vec![
+            Parameter::new("start", U256::cl_type()),
+            Parameter::new("count", U256::cl_type()),
+        ],
+        CLType::Unit,
+        EntryPointAccess::Public,
+        EntryPointType::Contract,
+    ));
     entry_points.add_entry_point(EntryPoint::new(
         "set_fee_to",
         vec![Parameter::new("fee_to", Key::cl_type())],
@@ -332,12 +949,78 @@ fn get_entry_points() -> EntryPoints {
         EntryPointType::Contract,
     ));
     entry_points.add_entry_point(EntryPoint::new(
-        "set_fee_to_setter",
+        "sync_fee_on",
+        vec![
+            Parameter::new("start", U256::cl_type()),
+            Parameter::new("count", U256::cl_type()),
+        ],
+        CLType::Unit,
+        EntryPointAccess::Public,
+        EntryPointType::Contract,
+    ));
+    entry_points.add_entry_point(EntryPoint::new(
+        "propose_fee_to_setter",
         vec![Parameter::new("fee_to_setter", Key::cl_type())],
         CLType::Unit,
         EntryPointAccess::Public,
         EntryPointType::Contract,
     ));
+    entry_points.add_entry_point(EntryPoint::new(
+        "accept_fee_to_setter",
+        vec![],
+        CLType::Unit,
+        EntryPointAccess::Public,
+        EntryPointType::Contract,
+    ));
+    entry_points.add_entry_point(EntryPoint::new(
+        "cancel_fee_to_setter_proposal",
+        vec![],
+        CLType::Unit,
+        EntryPointAccess::Public,
+        EntryPointType::Contract,
+    ));
+    entry_points.add_entry_point(EntryPoint::new(
+        "pending_fee_to_setter",
+        vec![],
+        Key::cl_type(),
+        EntryPointAccess::Public,
+        EntryPointType::Contract,
+    ));
+    entry_points.add_entry_point(EntryPoint::new(
+        "owner",
+        vec![],
+        Key::cl_type(),
+        EntryPointAccess::Public,
+        EntryPointType::Contract,
+    ));
+    entry_points.add_entry_point(EntryPoint::new(
+        "propose_owner",
+        vec![Parameter::new("owner", Key::cl_type())],
+        CLType::Unit,
+        EntryPointAccess::Public,
+        EntryPointType::Contract,
+    ));
+    entry_points.add_entry_point(EntryPoint::new(
+        "accept_ownership",
+        vec![],
+        CLType::Unit,
+        EntryPointAccess::Public,
+        EntryPointType::Contract,
+    ));
+    entry_points.add_entry_point(EntryPoint::new(
+        "cancel_owner_proposal",
+        vec![],
+        CLType::Unit,
+        EntryPointAccess::Public,
+        EntryPointType::Contract,
+    ));
+    entry_points.add_entry_point(EntryPoint::new(
+        "pending_owner",
+        vec![],
+        Key::cl_type(),
+        EntryPointAccess::Public,
+        EntryPointType::Contract,
+    ));
     entry_points.add_entry_point(EntryPoint::new(
         "set_white_list",
         vec![Parameter::new("white_list", Key::cl_type())],
@@ -345,6 +1028,125 @@ fn get_entry_points() -> EntryPoints {
         EntryPointAccess::Public,
         EntryPointType::Contract,
     ));
+    entry_points.add_entry_point(EntryPoint::new(
+        "remove_from_white_list",
+        vec![Parameter::new("white_list", Key::cl_type())],
+        CLType::Unit,
+        EntryPointAccess::Public,
+        EntryPointType::Contract,
+    ));
+    entry_points.add_entry_point(EntryPoint::new(
+        "is_white_listed",
+        vec![Parameter::new("account", Key::cl_type())],
+        bool::cl_type(),
+        EntryPointAccess::Public,
+        EntryPointType::Contract,
+    ));
+    entry_points.add_entry_point(EntryPoint::new(
+        "white_list_length",
+        vec![],
+        CLType::U256,
+        EntryPointAccess::Public,
+        EntryPointType::Contract,
+    ));
+    entry_points.add_entry_point(EntryPoint::new(
+        "white_lists",
+        vec![
+            Parameter::new("start", U256::cl_type()),
+            Parameter::new("count", U256::cl_type()),
+        ],
+        CLType::List(Box::new(Key::cl_type())),
+        EntryPointAccess::Public,
+        EntryPointType::Contract,
+    ));
+    entry_points.add_entry_point(EntryPoint::new(
+        "deny_token",
+        vec![Parameter::new("token", Key::cl_type())],
+        CLType::Unit,
+        EntryPointAccess::Public,
+        EntryPointType::Contract,
+    ));
+    entry_points.add_entry_point(EntryPoint::new(
+        "allow_token",
+        vec![Parameter::new("token", Key::cl_type())],
+        CLType::Unit,
+        EntryPointAccess::Public,
+        EntryPointType::Contract,
+    ));
+    entry_points.add_entry_point(EntryPoint::new(
+        "is_token_denied",
+        vec![Parameter::new("token", Key::cl_type())],
+        bool::cl_type(),
+        EntryPointAccess::Public,
+        EntryPointType::Contract,
+    ));
+    entry_points.add_entry_point(EntryPoint::new(
+        "delist_pair",
+        vec![
+            Parameter::new("token_a", Key::cl_type()),
+            Parameter::new("token_b", Key::cl_type()),
+            Parameter::new("fee", u32::cl_type()),
+        ],
+        CLType::Unit,
+        EntryPointAccess::Public,
+        EntryPointType::Contract,
+    ));
+    entry_points.add_entry_point(EntryPoint::new(
+        "is_pair_delisted",
+        vec![Parameter::new("pair", Key::cl_type())],
+        bool::cl_type(),
+        EntryPointAccess::Public,
+        EntryPointType::Contract,
+    ));
+    entry_points.add_entry_point(EntryPoint::new(
+        "set_permissionless",
+        vec![Parameter::new("permissionless", bool::cl_type())],
+        CLType::Unit,
+        EntryPointAccess::Public,
+        EntryPointType::Contract,
+    ));
+    entry_points.add_entry_point(EntryPoint::new(
+        "is_permissionless",
+        vec![],
+        bool::cl_type(),
+        EntryPointAccess::Public,
+        EntryPointType::Contract,
+    ));
+    entry_points.add_entry_point(EntryPoint::new(
+        "pause",
+        vec![],
+        <()>::cl_type(),
+        EntryPointAccess::Public,
+        EntryPointType::Contract,
+    ));
+    entry_points.add_entry_point(EntryPoint::new(
+        "unpause",
+        vec![],
+        <()>::cl_type(),
+        EntryPointAccess::Public,
+        EntryPointType::Contract,
+    ));
+    entry_points.add_entry_point(EntryPoint::new(
+        "paused",
+        vec![],
+        bool::cl_type(),
+        EntryPointAccess::Public,
+        EntryPointType::Contract,
+    ));
+    entry_points.add_entry_point(EntryPoint::new(
+        "pause_pair",
+        vec![Parameter::new("pair", Key::cl_type())],
+        <()>::cl_type(),
+        EntryPointAccess::Public,
+        EntryPointType::Contract,
+    ));
+    entry_points.add_entry_point(EntryPoint::new(
+        "unpause_pair",
+        vec![Parameter::new("pair", Key::cl_type())],
+        <()>::cl_type(),
+        EntryPointAccess::Public,
+        EntryPointType::Contract,
+    ));
     entry_points.add_entry_point(EntryPoint::new(
         "package_hash",
         vec![],
@@ -352,5 +1154,45 @@ fn get_entry_points() -> EntryPoints {
         EntryPointAccess::Public,
         EntryPointType::Contract,
     ));
+    entry_points.add_entry_point(EntryPoint::new(
+        "grant_role",
+        vec![
+            Parameter::new("role", String::cl_type()),
+            Parameter::new("account", Key::cl_type()),
+        ],
+        <()>::cl_type(),
+        EntryPointAccess::Public,
+        EntryPointType::Contract,
+    ));
+    entry_points.add_entry_point(EntryPoint::new(
+        "revoke_role",
+        vec![
+            Parameter::new("role", String::cl_type()),
+            Parameter::new("account", Key::cl_type()),
+        ],
+        <()>::cl_type(),
+        EntryPointAccess::Public,
+        EntryPointType::Contract,
+    ));
+    entry_points.add_entry_point(EntryPoint::new(
+        "renounce_role",
+        vec![
+            Parameter::new("role", String::cl_type()),
+            Parameter::new("account", Key::cl_type()),
+        ],
+        <()>::cl_type(),
+        EntryPointAccess::Public,
+        EntryPointType::Contract,
+    ));
+    entry_points.add_entry_point(EntryPoint::new(
+        "has_role",
+        vec![
+            Parameter::new("role", String::cl_type()),
+            Parameter::new("account", Key::cl_type()),
+        ],
+        bool::cl_type(),
+        EntryPointAccess::Public,
+        EntryPointType::Contract,
+    ));
     entry_points
 }
@@ -1,46 +1,183 @@
-use alloc::vec::Vec;
-use casper_contract::unwrap_or_revert::UnwrapOrRevert;
-use casper_types::Key;
-use contract_utils::{get_key, set_key, Dict};
+use alloc::{string::ToString, vec::Vec};
+use casper_contract::{contract_api::runtime, unwrap_or_revert::UnwrapOrRevert};
+use casper_types::{Key, U256};
+use contract_utils::{get_key, key_and_value_to_str, set_key, zero_address, zero_hash, Dict};
 
 pub const WHITELISTS_DICT: &str = "white_lists";
+pub const WHITELIST_ACCOUNTS_DICT: &str = "white_list_accounts";
+pub const WHITELIST_POSITIONS_DICT: &str = "white_list_positions";
+pub const WHITELIST_LENGTH: &str = "white_list_length";
+pub const PERMISSIONLESS: &str = "permissionless";
+pub const PAUSED: &str = "paused";
+pub const STORAGE_VERSION: &str = "storage_version";
+/// Storage layout written by this version of the contract. Bump it together with a
+/// migration step in `FACTORY::migrate` whenever named keys or dictionaries change.
+pub const CURRENT_STORAGE_VERSION: u32 = 1;
+pub const DENIED_TOKENS_DICT: &str = "denied_tokens";
+pub const DELISTED_PAIRS_DICT: &str = "delisted_pairs";
 pub const PAIRS_DICT: &str = "pairs";
+pub const PAIR_FEES_DICT: &str = "pair_fees";
+pub const FEE_TIERS: &str = "fee_tiers";
+/// Fee tier, in basis points, enabled at install and given to migrated pairs.
+pub const DEFAULT_FEE_TIER: u32 = 30;
 pub const SELF_CONTRACT_HASH: &str = "self_contract_hash";
 pub const FEE_TO: &str = "fee_to";
 pub const FEE_TO_SETTER: &str = "fee_to_setter";
 pub const ALL_PAIRS: &str = "all_pairs";
+pub const PAIR_LIST_DICT: &str = "pair_list";
+pub const ALL_PAIRS_LENGTH: &str = "all_pairs_length";
+pub const TOKEN_PAIRS_DICT: &str = "token_pairs";
+pub const TOKEN_PAIRS_LENGTH_DICT: &str = "token_pairs_length";
 pub const OWNER: &str = "owner";
+pub const PENDING_OWNER: &str = "pending_owner";
+pub const OWNER_PROPOSER: &str = "owner_proposer";
+pub const PENDING_FEE_TO_SETTER: &str = "pending_fee_to_setter";
 pub const CONTRACT_PACKAGE_HASH: &str = "contract_package_hash";
 
+/// Accounts allowed to create pairs. `white_lists` maps an allowed account to
+/// itself and anything else to nothing, as it always has; the accounts are also
+/// kept in a dense list so they can be enumerated, removal moves the last
+/// account into the freed position.
 pub struct Whitelists {
     dict: Dict,
+    accounts: Dict,
+    positions: Dict,
 }
 
 impl Whitelists {
     pub fn instance() -> Whitelists {
         Whitelists {
             dict: Dict::instance(WHITELISTS_DICT),
+            accounts: Dict::instance(WHITELIST_ACCOUNTS_DICT),
+            positions: Dict::instance(WHITELIST_POSITIONS_DICT),
         }
     }
 
     pub fn init() {
-        Dict::init(WHITELISTS_DICT)
+        Dict::init(WHITELISTS_DICT);
+        Dict::init(WHITELIST_ACCOUNTS_DICT);
+        Dict::init(WHITELIST_POSITIONS_DICT);
+        set_key(WHITELIST_LENGTH, U256::zero());
     }
 
-    pub fn get(&self, owner: &Key) -> Key {
-        match self.dict.get_by_key(owner) {
-            Some(whitelist) => whitelist,
-            None => Key::from_formatted_str(
-                "account-hash-0000000000000000000000000000000000000000000000000000000000000000",
-            )
-            .unwrap(),
+    /// Creates the listing dictionaries missing on factories installed before white
+    /// lists were enumerable. `white_lists` itself is kept, so accounts white listed back
+    /// then stay allowed; a dictionary can't be walked on chain, so they are listed once
+    /// a whitelister adds them again, and can be removed either way.
+    pub fn migrate() {
+        if !runtime::has_key(WHITELIST_ACCOUNTS_DICT) {
+            Dict::init(WHITELIST_ACCOUNTS_DICT);
+            Dict::init(WHITELIST_POSITIONS_DICT);
+            set_key(WHITELIST_LENGTH, U256::zero());
         }
     }
 
-    pub fn set(&self, owner: &Key, value: Key) {
-        self.dict.set_by_key(owner, value);
+    pub fn contains(&self, account: &Key) -> bool {
+        match self.dict.get_by_key::<Key>(account) {
+            Some(white_list) => white_list != zero_address(),
+            None => false,
+        }
+    }
+
+    pub fn len(&self) -> U256 {
+        get_key(WHITELIST_LENGTH).unwrap_or_default()
+    }
+
+    pub fn get(&self, index: U256) -> Key {
+        match self.accounts.get(&index.to_string()) {
+            Some(account) => account,
+            None => zero_address(),
+        }
+    }
+
+    /// Returns `false` if `account` was already white listed and listed.
+    pub fn add(&self, account: &Key) -> bool {
+        if self.positions.get_by_key::<U256>(account).is_some() {
+            return false;
+        }
+        let index: U256 = self.len();
+        self.dict.set_by_key(account, *account);
+        self.accounts.set(&index.to_string(), *account);
+        self.positions.set_by_key(account, index);
+        set_key(WHITELIST_LENGTH, index + 1);
+        true
+    }
+
+    /// Returns `false` if `account` was not white listed.
+    pub fn remove(&self, account: &Key) -> bool {
+        if !self.contains(account) {
+            return false;
+        }
+        // accounts white listed before the upgrade may not be listed yet
+        if let Some(index) = self.positions.get_by_key::<U256>(account) {
+            let last_index: U256 = self.len() - 1;
+            if index != last_index {
+                let last_account: Key = self.get(last_index);
+                self.accounts.set(&index.to_string(), last_account);
+                self.positions.set_by_key(&last_account, index);
+            }
+            self.accounts.remove::<Key>(&last_index.to_string());
+            self.positions.remove_by_key::<U256>(account);
+            set_key(WHITELIST_LENGTH, last_index);
+        }
+        self.dict.remove_by_key::<Key>(account);
+        true
+    }
+}
+
+pub struct DeniedTokens {
+    dict: Dict,
+}
+
+impl DeniedTokens {
+    pub fn instance() -> DeniedTokens {
+        DeniedTokens {
+            dict: Dict::instance(DENIED_TOKENS_DICT),
+        }
+    }
+
+    pub fn init() {
+        Dict::init(DENIED_TOKENS_DICT)
+    }
+
+    pub fn contains(&self, token: &Key) -> bool {
+        self.dict.get_by_key(token).unwrap_or_default()
+    }
+
+    pub fn add(&self, token: &Key) {
+        self.dict.set_by_key(token, true);
+    }
+
+    pub fn remove(&self, token: &Key) {
+        self.dict.remove_by_key::<bool>(token);
     }
 }
+
+pub struct DelistedPairs {
+    dict: Dict,
+}
+
+impl DelistedPairs {
+    pub fn instance() -> DelistedPairs {
+        DelistedPairs {
+            dict: Dict::instance(DELISTED_PAIRS_DICT),
+        }
+    }
+
+    pub fn init() {
+        Dict::init(DELISTED_PAIRS_DICT)
+    }
+
+    pub fn contains(&self, pair: &Key) -> bool {
+        self.dict.get_by_key(pair).unwrap_or_default()
+    }
+
+    pub fn add(&self, pair: &Key) {
+        self.dict.set_by_key(pair, true);
+    }
+}
+
+/// Pairs by `(token0, token1, fee)`, stored for both token orders.
 pub struct Pairs {
     dict: Dict,
 }
@@ -56,18 +193,111 @@ impl Pairs {
         Dict::init(PAIRS_DICT)
     }
 
-    pub fn get(&self, token0: &Key, token1: &Key) -> Key {
-        match self.dict.get_by_keys((token0, token1)) {
+    pub fn get(&self, token0: &Key, token1: &Key, fee: u32) -> Key {
+        match self
+            .dict
+            .get(&key_and_value_to_str(token0, &(*token1, fee)))
+        {
+            Some(pair) => pair,
+            None => zero_hash(),
+        }
+    }
+
+    pub fn set(&self, token0: &Key, token1: &Key, fee: u32, value: Key) {
+        self.dict
+            .set(&key_and_value_to_str(token0, &(*token1, fee)), value);
+    }
+}
+
+/// Fee tiers that have a pair, for each sorted `(token0, token1)`.
+pub struct PairFees {
+    dict: Dict,
+}
+
+impl PairFees {
+    pub fn instance() -> PairFees {
+        PairFees {
+            dict: Dict::instance(PAIR_FEES_DICT),
+        }
+    }
+
+    pub fn init() {
+        Dict::init(PAIR_FEES_DICT)
+    }
+
+    pub fn get(&self, token0: &Key, token1: &Key) -> Vec<u32> {
+        self.dict.get_by_keys((token0, token1)).unwrap_or_default()
+    }
+
+    pub fn push(&self, token0: &Key, token1: &Key, fee: u32) {
+        let mut fees: Vec<u32> = self.get(token0, token1);
+        fees.push(fee);
+        self.dict.set_by_keys((token0, token1), fees);
+    }
+}
+
+/// Registry of every pair created by the factory, indexed by creation order.
+pub struct PairList {
+    dict: Dict,
+}
+
+impl PairList {
+    pub fn instance() -> PairList {
+        PairList {
+            dict: Dict::instance(PAIR_LIST_DICT),
+        }
+    }
+
+    pub fn init() {
+        Dict::init(PAIR_LIST_DICT)
+    }
+
+    pub fn get(&self, index: U256) -> Key {
+        match self.dict.get(&index.to_string()) {
+            Some(pair) => pair,
+            None => zero_hash(),
+        }
+    }
+
+    pub fn set(&self, index: U256, value: Key) {
+        self.dict.set(&index.to_string(), value);
+    }
+}
+
+/// Pairs each token trades in, indexed by token and position.
+pub struct TokenPairs {
+    dict: Dict,
+    length_dict: Dict,
+}
+
+impl TokenPairs {
+    pub fn instance() -> TokenPairs {
+        TokenPairs {
+            dict: Dict::instance(TOKEN_PAIRS_DICT),
+            length_dict: Dict::instance(TOKEN_PAIRS_LENGTH_DICT),
+        }
+    }
+
+    pub fn init() {
+        Dict::init(TOKEN_PAIRS_DICT);
+        Dict::init(TOKEN_PAIRS_LENGTH_DICT);
+    }
+
+    pub fn get(&self, token: &Key, index: U256) -> Key {
+        match self.dict.get(&key_and_value_to_str(token, &index)) {
             Some(pair) => pair,
-            None => Key::from_formatted_str(
-                "hash-0000000000000000000000000000000000000000000000000000000000000000",
-            )
-            .unwrap(),
+            None => zero_hash(),
         }
     }
 
-    pub fn set(&self, token0: &Key, token1: &Key, value: Key) {
-        self.dict.set_by_keys((token0, token1), value);
+    pub fn len(&self, token: &Key) -> U256 {
+        self.length_dict.get_by_key(token).unwrap_or_default()
+    }
+
+    pub fn push(&self, token: &Key, pair: Key) {
+        let index: U256 = self.len(token);
+        self.dict.set(&key_and_value_to_str(token, &index), pair);
+        self.length_dict.set_by_key(token, index + 1);
     }
 }
 
@@ -107,12 +337,26 @@ pub fn get_fee_to_setter() -> Key {
     }
 }
 
-pub fn set_all_pairs(all_pairs: Vec<Key>) {
-    set_key(ALL_PAIRS, all_pairs);
+pub fn set_all_pairs_length(all_pairs_length: U256) {
+    set_key(ALL_PAIRS_LENGTH, all_pairs_length);
+}
+
+pub fn get_all_pairs_length() -> U256 {
+    get_key(ALL_PAIRS_LENGTH).unwrap_or_default()
 }
 
-pub fn get_all_pairs() -> Vec<Key> {
-    get_key(ALL_PAIRS).unwrap_or_revert()
+/// Pair list kept as a single `Vec<Key>` by factories installed before the
+/// paginated registry. `None` once it has been migrated or on fresh installs.
+pub fn get_legacy_all_pairs() -> Option<Vec<Key>> {
+    get_key(ALL_PAIRS)
+}
+
+pub fn has_legacy_all_pairs() -> bool {
+    runtime::has_key(ALL_PAIRS)
+}
+
+pub fn remove_legacy_all_pairs() {
+    runtime::remove_key(ALL_PAIRS);
 }
 
 pub fn set_package_hash(package_hash: Key) {
@@ -136,3 +380,62 @@ pub fn get_owner() -> Key {
         .unwrap(),
     }
 }
+
+pub fn set_pending_owner(pending_owner: Key) {
+    set_key(PENDING_OWNER, pending_owner);
+}
+
+pub fn get_pending_owner() -> Key {
+    get_key(PENDING_OWNER).unwrap_or_else(zero_address)
+}
+
+pub fn set_owner_proposer(proposer: Key) {
+    set_key(OWNER_PROPOSER, proposer);
+}
+
+/// Account that proposed the pending owner. Proposals made before it was recorded came from
+/// the owner.
+pub fn get_owner_proposer() -> Key {
+    get_key(OWNER_PROPOSER).unwrap_or_else(get_owner)
+}
+
+pub fn set_pending_fee_to_setter(pending_fee_to_setter: Key) {
+    set_key(PENDING_FEE_TO_SETTER, pending_fee_to_setter);
+}
+
+pub fn get_pending_fee_to_setter() -> Key {
+    get_key(PENDING_FEE_TO_SETTER).unwrap_or_else(zero_address)
+}
+
+pub fn set_permissionless(permissionless: bool) {
+    set_key(PERMISSIONLESS, permissionless);
+}
+
+pub fn get_permissionless() -> bool {
+    get_key(PERMISSIONLESS).unwrap_or_default()
+}
+
+pub fn set_paused(paused: bool) {
+    set_key(PAUSED, paused);
+}
+
+pub fn get_paused() -> bool {
+    get_key(PAUSED).unwrap_or_default()
+}
+
+pub fn set_fee_tiers(fee_tiers: Vec<u32>) {
+    set_key(FEE_TIERS, fee_tiers);
+}
+
+pub fn get_fee_tiers() -> Vec<u32> {
+    get_key(FEE_TIERS).unwrap_or_default()
+}
+
+pub fn set_storage_version(storage_version: u32) {
+    set_key(STORAGE_VERSION, storage_version);
+}
+
+/// Factories installed before storage versioning have no version key and read as 0.
+pub fn get_storage_version() -> u32 {
+    get_key(STORAGE_VERSION).unwrap_or_default()
+}
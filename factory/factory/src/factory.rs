@@ -1,19 +1,74 @@
 use crate::alloc::string::ToString;
-use crate::data::{self, get_all_pairs, Pairs, Whitelists};
+use crate::data::{
+    self, DelistedPairs, DeniedTokens, PairFees, PairList, Pairs, TokenPairs, Whitelists,
+    DEFAULT_FEE_TIER,
+};
 use alloc::collections::BTreeMap;
-use alloc::{string::String, vec::Vec};
+use alloc::{string::String, vec, vec::Vec};
 use casper_contract::contract_api::runtime;
 use casper_contract::contract_api::storage;
 use casper_types::{runtime_args, ApiError, ContractPackageHash, Key, RuntimeArgs, URef, U256};
-use contract_utils::{ContractContext, ContractStorage};
+use contract_utils::{
+    zero_address, AccessControl, ContractStorage, DEFAULT_ADMIN_ROLE, FEE_SETTER_ROLE, PAUSER_ROLE,
+    WHITELISTER_ROLE,
+};
 
 pub enum FACTORYEvent {
     PairCreated {
         token0: Key,
         token1: Key,
+        fee: u32,
+        curve_type: u8,
+        amplification: u64,
         pair: Key,
         all_pairs_length: U256,
     },
+    OwnershipTransferred {
+        ownership: String,
+        previous_owner: Key,
+        new_owner: Key,
+    },
+    FeeToChanged {
+        previous_fee_to: Key,
+        fee_to: Key,
+    },
+    FeeToSetterChanged {
+        previous_fee_to_setter: Key,
+        fee_to_setter: Key,
+    },
+    WhitelistAdded {
+        account: Key,
+    },
+    WhitelistRemoved {
+        account: Key,
+    },
+    PermissionlessChanged {
+        permissionless: bool,
+    },
+    TokenDenied {
+        token: Key,
+    },
+    TokenAllowed {
+        token: Key,
+    },
+    PairDelisted {
+        token0: Key,
+        token1: Key,
+        fee: u32,
+        pair: Key,
+    },
+    FeeTierEnabled {
+        fee: u32,
+    },
+    FeeTierDisabled {
+        fee: u32,
+    },
+    Paused {
+        account: Key,
+    },
+    Unpaused {
+        account: Key,
+    },
 }
 impl FACTORYEvent {
     pub fn type_name(&self) -> String {
@@ -21,9 +76,40 @@ impl FACTORYEvent {
             FACTORYEvent::PairCreated {
                 token0: _,
                 token1: _,
+                fee: _,
+                curve_type: _,
+                amplification: _,
                 pair: _,
                 all_pairs_length: _,
             } => "pair_created",
+            FACTORYEvent::OwnershipTransferred {
+                ownership: _,
+                previous_owner: _,
+                new_owner: _,
+            } => "ownership_transferred",
+            FACTORYEvent::FeeToChanged {
+                previous_fee_to: _,
+                fee_to: _,
+            } => "fee_to_changed",
+            FACTORYEvent::FeeToSetterChanged {
+                previous_fee_to_setter: _,
+                fee_to_setter: _,
+            } => "fee_to_setter_changed",
+            FACTORYEvent::WhitelistAdded { account: _ } => "white_list_added",
+            FACTORYEvent::WhitelistRemoved { account: _ } => "white_list_removed",
+            FACTORYEvent::PermissionlessChanged { permissionless: _ } => "permissionless_changed",
+            FACTORYEvent::TokenDenied { token: _ } => "token_denied",
+            FACTORYEvent::TokenAllowed { token: _ } => "token_allowed",
+            FACTORYEvent::PairDelisted {
+                token0: _,
+                token1: _,
+                fee: _,
+                pair: _,
+            } => "pair_delisted",
+            FACTORYEvent::FeeTierEnabled { fee: _ } => "fee_tier_enabled",
+            FACTORYEvent::FeeTierDisabled { fee: _ } => "fee_tier_disabled",
+            FACTORYEvent::Paused { account: _ } => "paused",
+            FACTORYEvent::Unpaused { account: _ } => "unpaused",
         }
         .to_string()
     }
@@ -46,6 +132,22 @@ pub enum Error {
     UniswapV2FactoryNotInWhiteList = 29,
     /// 65,566 for (UniswapV2 Factory Not Owner)
     UniswapV2FactoryNotOwner = 30,
+    /// 65,665 for (UniswapV2 Factory Not Pending Owner)
+    UniswapV2FactoryNotPendingOwner = 129,
+    /// 65,666 for (UniswapV2 Factory Not Pending Fee To Setter)
+    UniswapV2FactoryNotPendingFeeToSetter = 130,
+    /// 65,667 for (UniswapV2 Factory Pairs Not Migrated)
+    UniswapV2FactoryPairsNotMigrated = 131,
+    /// 65,668 for (UniswapV2 Factory Token Denied)
+    UniswapV2FactoryTokenDenied = 132,
+    /// 65,669 for (UniswapV2 Factory Pair Not Found)
+    UniswapV2FactoryPairNotFound = 133,
+    /// 65,670 for (UniswapV2 Factory Fee Tier Not Enabled)
+    UniswapV2FactoryFeeTierNotEnabled = 134,
+    /// 65,671 for (UniswapV2 Factory Invalid Fee Tier)
+    UniswapV2FactoryInvalidFeeTier = 135,
+    /// 65,696 for (UniswapV2 Factory Migration Out Of Order)
+    UniswapV2FactoryMigrationOutOfOrder = 160,
 }
 
 impl From<Error> for ApiError {
@@ -54,35 +156,98 @@ impl From<Error> for ApiError {
     }
 }
 
-pub trait FACTORY<Storage: ContractStorage>: ContractContext<Storage> {
-    fn init(
-        &mut self,
-        fee_to_setter: Key,
-        all_pairs: Vec<Key>,
-        contract_hash: Key,
-        package_hash: Key,
-    ) {
+pub trait FACTORY<Storage: ContractStorage>: AccessControl<Storage> {
+    fn init(&mut self, fee_to_setter: Key, contract_hash: Key, package_hash: Key) {
         data::set_fee_to_setter(fee_to_setter);
         data::set_owner(self.get_caller());
-        data::set_all_pairs(all_pairs);
+        data::set_pending_owner(zero_address());
+        data::set_pending_fee_to_setter(zero_address());
+        data::set_all_pairs_length(0.into());
         data::set_hash(contract_hash);
         data::set_package_hash(package_hash);
         Pairs::init();
+        PairFees::init();
+        data::set_fee_tiers(vec![DEFAULT_FEE_TIER]);
+        PairList::init();
+        TokenPairs::init();
         Whitelists::init();
+        data::set_permissionless(false);
+        data::set_paused(false);
+        DeniedTokens::init();
+        DelistedPairs::init();
+        AccessControl::init(self);
+        self._grant_role(DEFAULT_ADMIN_ROLE.to_string(), self.get_caller());
+        self._grant_role(WHITELISTER_ROLE.to_string(), self.get_caller());
+        self._grant_role(PAUSER_ROLE.to_string(), self.get_caller());
+        self._grant_role(FEE_SETTER_ROLE.to_string(), fee_to_setter);
+        data::set_storage_version(data::CURRENT_STORAGE_VERSION);
     }
 
-    fn create_pair(&mut self, token_a: Key, token_b: Key, pair_hash: Key) {
-        let white_lists: Whitelists = Whitelists::instance();
-        let white_list_user: Key = white_lists.get(&self.get_caller());
-        if white_list_user
-            != Key::from_formatted_str(
-                "account-hash-0000000000000000000000000000000000000000000000000000000000000000",
-            )
-            .unwrap()
-        {
+    /// Brings the storage of an upgraded factory up to `CURRENT_STORAGE_VERSION`. Called by
+    /// the installer right after a new version is added, each step runs once per factory.
+    /// The pair list of factories installed before the paginated registry is still moved
+    /// by `migrate_all_pairs`, a page per deploy, as it calls every pair.
+    fn migrate(&mut self, contract_hash: Key) {
+        data::set_hash(contract_hash);
+        let storage_version: u32 = data::get_storage_version();
+        if storage_version < 1 {
+            // factories installed before roles, two-step transfers, white list listing,
+            // the deny list, delisting and pausing
+            if !self.access_control_initialized() {
+                AccessControl::init(self);
+                self._grant_role(DEFAULT_ADMIN_ROLE.to_string(), self.get_caller());
+                self._grant_role(WHITELISTER_ROLE.to_string(), self.get_caller());
+                self._grant_role(PAUSER_ROLE.to_string(), self.get_caller());
+                self._grant_role(FEE_SETTER_ROLE.to_string(), data::get_fee_to_setter());
+            }
+            if !runtime::has_key(data::PENDING_OWNER) {
+                data::set_pending_owner(zero_address());
+            }
+            if !runtime::has_key(data::PENDING_FEE_TO_SETTER) {
+                data::set_pending_fee_to_setter(zero_address());
+            }
+            Whitelists::migrate();
+            if !runtime::has_key(data::PERMISSIONLESS) {
+                data::set_permissionless(false);
+            }
+            if !runtime::has_key(data::PAUSED) {
+                data::set_paused(false);
+            }
+            if !runtime::has_key(data::DENIED_TOKENS_DICT) {
+                DeniedTokens::init();
+            }
+            if !runtime::has_key(data::DELISTED_PAIRS_DICT) {
+                DelistedPairs::init();
+            }
+        }
+        data::set_storage_version(data::CURRENT_STORAGE_VERSION);
+    }
+
+    /// Creates the pair of `token_a` and `token_b` in the `fee` tier. `curve_type` and
+    /// `amplification` are handed to the pair, which rejects unknown curves.
+    fn create_pair(
+        &mut self,
+        token_a: Key,
+        token_b: Key,
+        pair_hash: Key,
+        fee: u32,
+        curve_type: u8,
+        amplification: u64,
+    ) {
+        if data::has_legacy_all_pairs() {
+            runtime::revert(Error::UniswapV2FactoryPairsNotMigrated);
+        }
+        if data::get_permissionless() || Whitelists::instance().contains(&self.get_caller()) {
             if token_a == token_b {
                 runtime::revert(Error::UniswapV2FactoryIdenticalAddresses);
             }
+            if !data::get_fee_tiers().contains(&fee) {
+                runtime::revert(Error::UniswapV2FactoryFeeTierNotEnabled);
+            }
+            let denied_tokens: DeniedTokens = DeniedTokens::instance();
+            if denied_tokens.contains(&token_a) || denied_tokens.contains(&token_b) {
+                runtime::revert(Error::UniswapV2FactoryTokenDenied);
+            }
             let token0: Key;
             let token1: Key;
             let address_0: Key = Key::from_formatted_str(
@@ -100,8 +265,8 @@ pub trait FACTORY<Storage: ContractStorage>: ContractContext<Storage> {
             if token0 == address_0 {
                 runtime::revert(Error::UniswapV2FactoryZeroAddress);
             }
-            let pair_0_1_key: Key = self.get_pair(token0, token1);
-            let pair_1_0_key: Key = self.get_pair(token1, token0);
+            let pair_0_1_key: Key = self.get_pair(token0, token1, fee);
+            let pair_1_0_key: Key = self.get_pair(token1, token0, fee);
             if pair_0_1_key != address_0 {
                 runtime::revert(Error::UniswapV2FactoryPairExists1);
             }
@@ -118,71 +283,501 @@ pub trait FACTORY<Storage: ContractStorage>: ContractContext<Storage> {
                 pair_package_hash,
                 None,
                 "initialize",
-                runtime_args! {"token0" => token0, "token1" => token1, "factory_hash" => data::get_package_hash() },
+                runtime_args! {"token0" => token0, "token1" => token1, "factory_hash" => data::get_package_hash(), "swap_fee" => fee, "curve_type" => curve_type, "amplification" => amplification },
             );
+            if data::get_fee_to() != zero_address() {
+                self.push_fee_on(pair_hash, true);
+            }
 
             // handling the pair creation by updating the storage
-            self.set_pair(token0, token1, pair_hash);
-            self.set_pair(token1, token0, pair_hash);
-            let mut pairs: Vec<Key> = get_all_pairs();
-            pairs.push(pair_hash);
-            self.set_all_pairs(pairs);
+            self.set_pair(token0, token1, fee, pair_hash);
+            self.set_pair(token1, token0, fee, pair_hash);
+            PairFees::instance().push(&token0, &token1, fee);
+            let all_pairs_length: U256 = self.add_pair(pair_hash);
+            let token_pairs: TokenPairs = TokenPairs::instance();
+            token_pairs.push(&token0, pair_hash);
+            token_pairs.push(&token1, pair_hash);
             self.emit(&FACTORYEvent::PairCreated {
                 token0: token0,
                 token1: token1,
+                fee,
+                curve_type,
+                amplification,
                 pair: pair_hash,
-                all_pairs_length: (get_all_pairs().len()).into(),
+                all_pairs_length,
             });
         } else {
             runtime::revert(Error::UniswapV2FactoryNotInWhiteList);
         }
     }
 
-    fn get_pair(&mut self, token0: Key, token1: Key) -> Key {
-        Pairs::instance().get(&token0, &token1)
+    fn get_pair(&mut self, token0: Key, token1: Key, fee: u32) -> Key {
+        Pairs::instance().get(&token0, &token1, fee)
     }
 
-    fn set_pair(&mut self, token0: Key, token1: Key, value: Key) {
-        Pairs::instance().set(&token0, &token1, value);
+    fn set_pair(&mut self, token0: Key, token1: Key, fee: u32, value: Key) {
+        Pairs::instance().set(&token0, &token1, fee, value);
     }
 
+    /// Returns the pairs of `token_a` and `token_b` in every fee tier, in creation order.
+    fn get_pairs(&mut self, token_a: Key, token_b: Key) -> Vec<Key> {
+        let (token0, token1) = if token_a < token_b {
+            (token_a, token_b)
+        } else {
+            (token_b, token_a)
+        };
+        let pairs: Pairs = Pairs::instance();
+        PairFees::instance()
+            .get(&token0, &token1)
+            .into_iter()
+            .map(|fee| pairs.get(&token0, &token1, fee))
+            .collect()
+    }
+
+    fn get_fee_tiers(&mut self) -> Vec<u32> {
+        data::get_fee_tiers()
+    }
+
+    /// Allows pairs to be created with a swap fee of `fee` basis points.
+    fn enable_fee_tier(&mut self, fee: u32) {
+        self.assert_role(DEFAULT_ADMIN_ROLE);
+        if fee == 0 || fee >= 10000 {
+            runtime::revert(Error::UniswapV2FactoryInvalidFeeTier);
+        }
+        let mut fee_tiers: Vec<u32> = data::get_fee_tiers();
+        if !fee_tiers.contains(&fee) {
+            fee_tiers.push(fee);
+            data::set_fee_tiers(fee_tiers);
+            self.emit(&FACTORYEvent::FeeTierEnabled { fee });
+        }
+    }
+
+    /// Stops new pairs in the `fee` tier. Pairs already created keep trading.
+    fn disable_fee_tier(&mut self, fee: u32) {
+        self.assert_role(DEFAULT_ADMIN_ROLE);
+        let mut fee_tiers: Vec<u32> = data::get_fee_tiers();
+        if fee_tiers.contains(&fee) {
+            fee_tiers.retain(|fee_tier| *fee_tier != fee);
+            data::set_fee_tiers(fee_tiers);
+            self.emit(&FACTORYEvent::FeeTierDisabled { fee });
+        }
+    }
+
+    /// Sets the receiver of the protocol fee, `zero_address()` turning it off. Pairs cache
+    /// whether the fee is on, so switching it on or off only reaches existing pairs once
+    /// `sync_fee_on` has run over the registry.
     fn set_fee_to(&mut self, fee_to: Key) {
-        if self.get_caller() != self.get_fee_to_setter() {
+        if !self.has_role(FEE_SETTER_ROLE, self.get_caller()) {
             runtime::revert(Error::UniswapV2FactoryForbidden1);
         }
+        let previous_fee_to: Key = data::get_fee_to();
         data::set_fee_to(fee_to);
+        self.emit(&FACTORYEvent::FeeToChanged {
+            previous_fee_to,
+            fee_to,
+        });
+    }
+
+    /// Pushes whether the protocol fee is on to up to `count` pairs of the registry, starting
+    /// at index `start`. Pushing the current flag twice does no harm, so anyone may call it,
+    /// a page at a time, after `set_fee_to` switched the fee on or off.
+    fn sync_fee_on(&mut self, start: U256, count: U256) {
+        if data::has_legacy_all_pairs() {
+            runtime::revert(Error::UniswapV2FactoryPairsNotMigrated);
+        }
+        let fee_on: bool = data::get_fee_to() != zero_address();
+        let pair_list: PairList = PairList::instance();
+        let end: U256 = core::cmp::min(start.saturating_add(count), data::get_all_pairs_length());
+        let mut index: U256 = start;
+        while index < end {
+            self.push_fee_on(pair_list.get(index), fee_on);
+            index = index + 1;
+        }
     }
 
     fn get_fee_to(&mut self) -> Key {
         data::get_fee_to()
     }
 
-    fn set_fee_to_setter(&mut self, fee_to_setter: Key) {
-        if self.get_caller() != self.get_fee_to_setter() {
-            runtime::revert(Error::UniswapV2FactoryForbidden2);
-        }
-        data::set_fee_to_setter(fee_to_setter);
+    fn push_fee_on(&mut self, pair: Key, fee_on: bool) {
+        let pair_hash_add_array = match pair {
+            Key::Hash(package) => package,
+            _ => runtime::revert(ApiError::UnexpectedKeyVariant),
+        };
+        let _ret: () = runtime::call_versioned_contract(
+            ContractPackageHash::new(pair_hash_add_array),
+            None,
+            "set_fee_on",
+            runtime_args! {"fee_on" => fee_on},
+        );
     }
 
     fn get_fee_to_setter(&mut self) -> Key {
         data::get_fee_to_setter()
     }
 
-    fn set_all_pairs(&mut self, all_pairs: Vec<Key>) {
-        data::set_all_pairs(all_pairs);
+    /// Nominates the next `fee_to_setter`. Nothing changes until the nominee
+    /// calls `accept_fee_to_setter`, so a mistyped key can simply be replaced.
+    fn propose_fee_to_setter(&mut self, fee_to_setter: Key) {
+        if !self.has_role(FEE_SETTER_ROLE, self.get_caller()) {
+            runtime::revert(Error::UniswapV2FactoryForbidden2);
+        }
+        data::set_pending_fee_to_setter(fee_to_setter);
     }
 
-    fn get_all_pairs(&mut self) -> Vec<Key> {
-        data::get_all_pairs()
+    fn accept_fee_to_setter(&mut self) {
+        let new_fee_to_setter: Key = self.get_caller();
+        if new_fee_to_setter != data::get_pending_fee_to_setter() {
+            runtime::revert(Error::UniswapV2FactoryNotPendingFeeToSetter);
+        }
+        let previous_fee_to_setter: Key = self.get_fee_to_setter();
+        self._revoke_role(FEE_SETTER_ROLE.to_string(), previous_fee_to_setter);
+        self._grant_role(FEE_SETTER_ROLE.to_string(), new_fee_to_setter);
+        data::set_fee_to_setter(new_fee_to_setter);
+        data::set_pending_fee_to_setter(zero_address());
+        self.emit(&FACTORYEvent::OwnershipTransferred {
+            ownership: "fee_to_setter".to_string(),
+            previous_owner: previous_fee_to_setter,
+            new_owner: new_fee_to_setter,
+        });
+        self.emit(&FACTORYEvent::FeeToSetterChanged {
+            previous_fee_to_setter,
+            fee_to_setter: new_fee_to_setter,
+        });
     }
 
-    fn set_white_list(&mut self, white_list: Key, value: Key) {
-        if self.get_caller() == data::get_owner() {
-            Whitelists::instance().set(&white_list, value);
-        } else {
+    fn cancel_fee_to_setter_proposal(&mut self) {
+        if !self.has_role(FEE_SETTER_ROLE, self.get_caller()) {
+            runtime::revert(Error::UniswapV2FactoryForbidden2);
+        }
+        data::set_pending_fee_to_setter(zero_address());
+    }
+
+    fn get_pending_fee_to_setter(&mut self) -> Key {
+        data::get_pending_fee_to_setter()
+    }
+
+    fn get_owner(&mut self) -> Key {
+        data::get_owner()
+    }
+
+    /// Nominates the next owner. Any `DEFAULT_ADMIN` may propose, and becomes the previous
+    /// owner whose roles `accept_ownership` hands over.
+    fn propose_owner(&mut self, owner: Key) {
+        if !self.has_role(DEFAULT_ADMIN_ROLE, self.get_caller()) {
+            runtime::revert(Error::UniswapV2FactoryNotOwner);
+        }
+        data::set_pending_owner(owner);
+        data::set_owner_proposer(self.get_caller());
+    }
+
+    /// Moves `DEFAULT_ADMIN`, and `WHITELISTER` and `PAUSER` where the proposer holds them,
+    /// from the proposer to the pending owner. Reverts if the proposer is no longer an admin.
+    fn accept_ownership(&mut self) {
+        let new_owner: Key = self.get_caller();
+        if new_owner != data::get_pending_owner() {
+            runtime::revert(Error::UniswapV2FactoryNotPendingOwner);
+        }
+        let previous_owner: Key = data::get_owner_proposer();
+        if !self.has_role(DEFAULT_ADMIN_ROLE, previous_owner) {
+            runtime::revert(Error::UniswapV2FactoryNotOwner);
+        }
+        for role in [DEFAULT_ADMIN_ROLE, WHITELISTER_ROLE, PAUSER_ROLE] {
+            if self.has_role(role, previous_owner) {
+                self._revoke_role(role.to_string(), previous_owner);
+                self._grant_role(role.to_string(), new_owner);
+            }
+        }
+        data::set_owner(new_owner);
+        data::set_pending_owner(zero_address());
+        self.emit(&FACTORYEvent::OwnershipTransferred {
+            ownership: "owner".to_string(),
+            previous_owner,
+            new_owner,
+        });
+    }
+
+    fn cancel_owner_proposal(&mut self) {
+        if !self.has_role(DEFAULT_ADMIN_ROLE, self.get_caller()) {
+            runtime::revert(Error::UniswapV2FactoryNotOwner);
+        }
+        data::set_pending_owner(zero_address());
+    }
+
+    fn get_pending_owner(&mut self) -> Key {
+        data::get_pending_owner()
+    }
+
+    /// Appends `pair` to the registry and returns the new number of pairs.
+    fn add_pair(&mut self, pair: Key) -> U256 {
+        let index: U256 = data::get_all_pairs_length();
+        PairList::instance().set(index, pair);
+        let all_pairs_length: U256 = index + 1;
+        data::set_all_pairs_length(all_pairs_length);
+        all_pairs_length
+    }
+
+    fn all_pairs_length(&mut self) -> U256 {
+        data::get_all_pairs_length()
+    }
+
+    fn pair_at(&mut self, index: U256) -> Key {
+        PairList::instance().get(index)
+    }
+
+    /// Returns up to `count` pairs starting at `start`, stopping at the end of the registry.
+    fn pairs(&mut self, start: U256, count: U256) -> Vec<Key> {
+        let pair_list: PairList = PairList::instance();
+        let end: U256 = self.all_pairs_length().min(start.saturating_add(count));
+        let mut pairs: Vec<Key> = Vec::new();
+        let mut index: U256 = start;
+        while index < end {
+            pairs.push(pair_list.get(index));
+            index = index + 1;
+        }
+        pairs
+    }
+
+    fn pairs_for_token_length(&mut self, token: Key) -> U256 {
+        TokenPairs::instance().len(&token)
+    }
+
+    /// Returns up to `count` of the pairs `token` trades in, starting at `start`.
+    fn pairs_for_token(&mut self, token: Key, start: U256, count: U256) -> Vec<Key> {
+        let token_pairs: TokenPairs = TokenPairs::instance();
+        let end: U256 = token_pairs.len(&token).min(start.saturating_add(count));
+        let mut pairs: Vec<Key> = Vec::new();
+        let mut index: U256 = start;
+        while index < end {
+            pairs.push(token_pairs.get(&token, index));
+            index = index + 1;
+        }
+        pairs
+    }
+
+    /// Moves `count` pairs of the `all_pairs` vector of a factory installed before the
+    /// paginated registry into `pair_list`, from index `start`, indexing each pair under its
+    /// two tokens and registering it in the default fee tier. Every pair costs two calls, so
+    /// a large vector is moved over several deploys. `start` must be the number of pairs
+    /// moved so far, `all_pairs_length`, so a repeated deploy cannot move a pair twice.
+    /// `create_pair` is blocked until the last pair has been moved and the vector removed.
+    fn migrate_all_pairs(&mut self, start: U256, count: U256) {
+        self.assert_role(DEFAULT_ADMIN_ROLE);
+        if let Some(legacy_pairs) = data::get_legacy_all_pairs() {
+            if start != data::get_all_pairs_length() {
+                runtime::revert(Error::UniswapV2FactoryMigrationOutOfOrder);
+            }
+            if !runtime::has_key(data::PAIR_LIST_DICT) {
+                PairList::init();
+                TokenPairs::init();
+                PairFees::init();
+                data::set_fee_tiers(vec![DEFAULT_FEE_TIER]);
+            }
+            let token_pairs: TokenPairs = TokenPairs::instance();
+            let pair_fees: PairFees = PairFees::instance();
+            let legacy_length: U256 = legacy_pairs.len().into();
+            let end: U256 = core::cmp::min(start.saturating_add(count), legacy_length);
+            for pair in legacy_pairs
+                .into_iter()
+                .skip(start.as_usize())
+                .take((end - start).as_usize())
+            {
+                let pair_package_hash: ContractPackageHash = match pair {
+                    Key::Hash(package) => ContractPackageHash::new(package),
+                    _ => runtime::revert(ApiError::UnexpectedKeyVariant),
+                };
+                let token0: Key = runtime::call_versioned_contract(
+                    pair_package_hash,
+                    None,
+                    "token0",
+                    runtime_args! {},
+                );
+                let token1: Key = runtime::call_versioned_contract(
+                    pair_package_hash,
+                    None,
+                    "token1",
+                    runtime_args! {},
+                );
+                self.add_pair(pair);
+                token_pairs.push(&token0, pair);
+                token_pairs.push(&token1, pair);
+                self.set_pair(token0, token1, DEFAULT_FEE_TIER, pair);
+                self.set_pair(token1, token0, DEFAULT_FEE_TIER, pair);
+                pair_fees.push(&token0, &token1, DEFAULT_FEE_TIER);
+            }
+            if end == legacy_length {
+                data::remove_legacy_all_pairs();
+            }
+        }
+    }
+
+    fn set_white_list(&mut self, white_list: Key) {
+        if !self.has_role(WHITELISTER_ROLE, self.get_caller()) {
+            runtime::revert(Error::UniswapV2FactoryNotOwner);
+        }
+        if Whitelists::instance().add(&white_list) {
+            self.emit(&FACTORYEvent::WhitelistAdded {
+                account: white_list,
+            });
+        }
+    }
+
+    fn remove_from_white_list(&mut self, white_list: Key) {
+        if !self.has_role(WHITELISTER_ROLE, self.get_caller()) {
             runtime::revert(Error::UniswapV2FactoryNotOwner);
         }
+        if Whitelists::instance().remove(&white_list) {
+            self.emit(&FACTORYEvent::WhitelistRemoved {
+                account: white_list,
+            });
+        }
+    }
+
+    fn is_white_listed(&mut self, account: Key) -> bool {
+        Whitelists::instance().contains(&account)
+    }
+
+    fn white_list_length(&mut self) -> U256 {
+        Whitelists::instance().len()
+    }
+
+    /// Returns up to `count` white listed accounts starting at `start`. Removals
+    /// reorder the list, so pages are only consistent between two removals.
+    fn white_lists(&mut self, start: U256, count: U256) -> Vec<Key> {
+        let white_lists: Whitelists = Whitelists::instance();
+        let end: U256 = white_lists.len().min(start.saturating_add(count));
+        let mut accounts: Vec<Key> = Vec::new();
+        let mut index: U256 = start;
+        while index < end {
+            accounts.push(white_lists.get(index));
+            index = index + 1;
+        }
+        accounts
+    }
+
+    /// Stops `create_pair` from accepting `token`. Existing pairs are unaffected.
+    fn deny_token(&mut self, token: Key) {
+        self.assert_role(DEFAULT_ADMIN_ROLE);
+        let denied_tokens: DeniedTokens = DeniedTokens::instance();
+        if !denied_tokens.contains(&token) {
+            denied_tokens.add(&token);
+            self.emit(&FACTORYEvent::TokenDenied { token });
+        }
+    }
+
+    fn allow_token(&mut self, token: Key) {
+        self.assert_role(DEFAULT_ADMIN_ROLE);
+        let denied_tokens: DeniedTokens = DeniedTokens::instance();
+        if denied_tokens.contains(&token) {
+            denied_tokens.remove(&token);
+            self.emit(&FACTORYEvent::TokenAllowed { token });
+        }
+    }
+
+    fn is_token_denied(&mut self, token: Key) -> bool {
+        DeniedTokens::instance().contains(&token)
+    }
+
+    /// Flags the pair of `token_a` and `token_b` as deprecated. The pair stays in
+    /// the registry and keeps working, so liquidity providers can still redeem,
+    /// but routers and frontends are expected to skip it.
+    fn delist_pair(&mut self, token_a: Key, token_b: Key, fee: u32) {
+        self.assert_role(DEFAULT_ADMIN_ROLE);
+        let pair: Key = self.get_pair(token_a, token_b, fee);
+        if pair
+            == Key::from_formatted_str(
+                "hash-0000000000000000000000000000000000000000000000000000000000000000",
+            )
+            .unwrap()
+        {
+            runtime::revert(Error::UniswapV2FactoryPairNotFound);
+        }
+        let delisted_pairs: DelistedPairs = DelistedPairs::instance();
+        if !delisted_pairs.contains(&pair) {
+            delisted_pairs.add(&pair);
+            let (token0, token1) = if token_a < token_b {
+                (token_a, token_b)
+            } else {
+                (token_b, token_a)
+            };
+            self.emit(&FACTORYEvent::PairDelisted {
+                token0,
+                token1,
+                fee,
+                pair,
+            });
+        }
+    }
+
+    fn is_pair_delisted(&mut self, pair: Key) -> bool {
+        DelistedPairs::instance().contains(&pair)
+    }
+
+    /// Lets anyone call `create_pair` when `permissionless` is true, instead of
+    /// white listed accounts only.
+    fn set_permissionless(&mut self, permissionless: bool) {
+        self.assert_role(DEFAULT_ADMIN_ROLE);
+        data::set_permissionless(permissionless);
+        self.emit(&FACTORYEvent::PermissionlessChanged { permissionless });
+    }
+
+    fn is_permissionless(&mut self) -> bool {
+        data::get_permissionless()
+    }
+
+    /// Pauses every pair of the factory at once; pairs read the flag on each swap and mint.
+    fn pause(&mut self) {
+        self.assert_role(PAUSER_ROLE);
+        if !data::get_paused() {
+            data::set_paused(true);
+            self.emit(&FACTORYEvent::Paused {
+                account: self.get_caller(),
+            });
+        }
+    }
+
+    fn unpause(&mut self) {
+        self.assert_role(PAUSER_ROLE);
+        if data::get_paused() {
+            data::set_paused(false);
+            self.emit(&FACTORYEvent::Unpaused {
+                account: self.get_caller(),
+            });
+        }
+    }
+
+    fn is_paused(&mut self) -> bool {
+        data::get_paused()
     }
+
+    fn pause_pair(&mut self, pair: Key) {
+        self.assert_role(PAUSER_ROLE);
+        let pair_hash_add_array = match pair {
+            Key::Hash(package) => package,
+            _ => runtime::revert(ApiError::UnexpectedKeyVariant),
+        };
+        let _ret: () = runtime::call_versioned_contract(
+            ContractPackageHash::new(pair_hash_add_array),
+            None,
+            "pause",
+            runtime_args! {},
+        );
+    }
+
+    fn unpause_pair(&mut self, pair: Key) {
+        self.assert_role(PAUSER_ROLE);
+        let pair_hash_add_array = match pair {
+            Key::Hash(package) => package,
+            _ => runtime::revert(ApiError::UnexpectedKeyVariant),
+        };
+        let _ret: () = runtime::call_versioned_contract(
+            ContractPackageHash::new(pair_hash_add_array),
+            None,
+            "unpause",
+            runtime_args! {},
+        );
+    }
+
     fn emit(&mut self, factory_event: &FACTORYEvent) {
         let mut events = Vec::new();
         let package = self.get_package_hash();
@@ -190,6 +785,9 @@ pub trait FACTORY<Storage: ContractStorage>: ContractContext<Storage> {
             FACTORYEvent::PairCreated {
                 token0,
                 token1,
+                fee,
+                curve_type,
+                amplification,
                 pair,
                 all_pairs_length,
             } => {
@@ -198,10 +796,99 @@ pub trait FACTORY<Storage: ContractStorage>: ContractContext<Storage> {
                 event.insert("event_type", factory_event.type_name());
                 event.insert("token0", token0.to_string());
                 event.insert("token1", token1.to_string());
+                event.insert("fee", fee.to_string());
+                event.insert("curve_type", curve_type.to_string());
+                event.insert("amplification", amplification.to_string());
                 event.insert("pair", pair.to_string());
                 event.insert("all_pairs_length", all_pairs_length.to_string());
                 events.push(event);
             }
+            FACTORYEvent::OwnershipTransferred {
+                ownership,
+                previous_owner,
+                new_owner,
+            } => {
+                let mut event = BTreeMap::new();
+                event.insert("contract_package_hash", package.to_string());
+                event.insert("event_type", factory_event.type_name());
+                event.insert("ownership", ownership.clone());
+                event.insert("previous_owner", previous_owner.to_string());
+                event.insert("new_owner", new_owner.to_string());
+                events.push(event);
+            }
+            FACTORYEvent::FeeToChanged {
+                previous_fee_to,
+                fee_to,
+            } => {
+                let mut event = BTreeMap::new();
+                event.insert("contract_package_hash", package.to_string());
+                event.insert("event_type", factory_event.type_name());
+                event.insert("previous_fee_to", previous_fee_to.to_string());
+                event.insert("fee_to", fee_to.to_string());
+                events.push(event);
+            }
+            FACTORYEvent::FeeToSetterChanged {
+                previous_fee_to_setter,
+                fee_to_setter,
+            } => {
+                let mut event = BTreeMap::new();
+                event.insert("contract_package_hash", package.to_string());
+                event.insert("event_type", factory_event.type_name());
+                event.insert("previous_fee_to_setter", previous_fee_to_setter.to_string());
+                event.insert("fee_to_setter", fee_to_setter.to_string());
+                events.push(event);
+            }
+            FACTORYEvent::WhitelistAdded { account }
+            | FACTORYEvent::WhitelistRemoved { account } => {
+                let mut event = BTreeMap::new();
+                event.insert("contract_package_hash", package.to_string());
+                event.insert("event_type", factory_event.type_name());
+                event.insert("account", account.to_string());
+                events.push(event);
+            }
+            FACTORYEvent::PermissionlessChanged { permissionless } => {
+                let mut event = BTreeMap::new();
+                event.insert("contract_package_hash", package.to_string());
+                event.insert("event_type", factory_event.type_name());
+                event.insert("permissionless", permissionless.to_string());
+                events.push(event);
+            }
+            FACTORYEvent::TokenDenied { token } | FACTORYEvent::TokenAllowed { token } => {
+                let mut event = BTreeMap::new();
+                event.insert("contract_package_hash", package.to_string());
+                event.insert("event_type", factory_event.type_name());
+                event.insert("token", token.to_string());
+                events.push(event);
+            }
+            FACTORYEvent::PairDelisted {
+                token0,
+                token1,
+                fee,
+                pair,
+            } => {
+                let mut event = BTreeMap::new();
+                event.insert("contract_package_hash", package.to_string());
+                event.insert("event_type", factory_event.type_name());
+                event.insert("token0", token0.to_string());
+                event.insert("token1", token1.to_string());
+                event.insert("fee", fee.to_string());
+                event.insert("pair", pair.to_string());
+                events.push(event);
+            }
+            FACTORYEvent::FeeTierEnabled { fee } | FACTORYEvent::FeeTierDisabled { fee } => {
+                let mut event = BTreeMap::new();
+                event.insert("contract_package_hash", package.to_string());
+                event.insert("event_type", factory_event.type_name());
+                event.insert("fee", fee.to_string());
+                events.push(event);
+            }
+            FACTORYEvent::Paused { account } | FACTORYEvent::Unpaused { account } => {
+                let mut event = BTreeMap::new();
+                event.insert("contract_package_hash", package.to_string());
+                event.insert("event_type", factory_event.type_name());
+                event.insert("account", account.to_string());
+                events.push(event);
+            }
         };
 
         for event in events {
@@ -124,6 +124,9 @@ impl TESTInstance {
         token_a: Key,
         token_b: Key,
         pair_hash: Key,
+        fee: u32,
+        curve_type: u8,
+        amplification: u64,
         factory_hash: Key,
     ) {
         self.0.call_contract(
@@ -133,6 +136,9 @@ impl TESTInstance {
                 "token_a" => token_a,
                 "token_b" => token_b,
                 "pair_hash" => pair_hash,
+                "fee" => fee,
+                "curve_type" => curve_type,
+                "amplification" => amplification,
                 "factory_hash" => factory_hash
             },
         );
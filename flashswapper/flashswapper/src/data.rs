@@ -13,6 +13,8 @@ pub const UNISWAP_V2_FACTROY: &str = "uniswap_v2_factory";
 pub const UNISWAP_V2_PAIR: &str = "uniswap_v2_pair";
 pub const SELF_PURSE: &str = "self_purse";
 pub const CONTRACT_PACKAGE_HASH: &str = "contract_package_hash";
+// The repayment maths assumes the 0.3% fee, so pairs are looked up in that tier.
+pub const PAIR_FEE: u32 = 30;
 
 #[repr(u16)]
 pub enum ErrorCodes {
@@ -188,7 +188,7 @@ pub trait FLASHSWAPPER<Storage: ContractStorage>: ContractContext<Storage> {
             uniswap_v2_factory_hash_add,
             None,
             "get_pair",
-            runtime_args! {"token0" => _token_borrow, "token1"  => other_token },
+            runtime_args! {"token0" => _token_borrow, "token1"  => other_token, "fee" => data::PAIR_FEE },
         );
         data::set_permissioned_pair_address(permissioned_pair_address);
         let pair_address: Key = data::get_permissioned_pair_address();
@@ -385,7 +385,7 @@ pub trait FLASHSWAPPER<Storage: ContractStorage>: ContractContext<Storage> {
             uniswap_v2_factory_package_hash,
             None,
             "get_pair",
-            runtime_args! {"token0" => token_borrow, "token1" => token_pay},
+            runtime_args! {"token0" => token_borrow, "token1" => token_pay, "fee" => data::PAIR_FEE},
         );
         data::set_permissioned_pair_address(token_borrow_token_pay_pair_address);
         let pair_address: Key = token_borrow_token_pay_pair_address; // gas efficiency
@@ -510,19 +510,7 @@ pub trait FLASHSWAPPER<Storage: ContractStorage>: ContractContext<Storage> {
         }
         // compute the amount of _tokenPay that needs to be repaid
         let pair_address: Key = data::get_permissioned_pair_address(); // gas efficiency
-                                                                       //convert Key to ContractPackageHash
-        let token_borrow_address_hash_add_array = match token_borrow {
-            Key::Hash(package) => package,
-            _ => runtime::revert(ApiError::UnexpectedKeyVariant),
-        };
-        let token_borrow_package_hash: ContractPackageHash =
-            ContractPackageHash::new(token_borrow_address_hash_add_array);
-        let pair_balance_token_borrow: U256 = runtime::call_versioned_contract(
-            token_borrow_package_hash,
-            None,
-            "balance_of",
-            runtime_args! {"owner" => pair_address},
-        );
+        let amount_to_repay: U256 = self.get_amount_in(pair_address, amount, token_borrow);
         //convert Key to ContractPackageHash
         let token_pay_address_hash_add_array = match token_pay {
             Key::Hash(package) => package,
@@ -530,20 +518,6 @@ pub trait FLASHSWAPPER<Storage: ContractStorage>: ContractContext<Storage> {
         };
         let token_pay_package_hash: ContractPackageHash =
             ContractPackageHash::new(token_pay_address_hash_add_array);
-        let pair_balance_token_pay: U256 = runtime::call_versioned_contract(
-            token_pay_package_hash,
-            None,
-            "balance_of",
-            runtime_args! {"owner" => pair_address},
-        );
-        let amount_1000: U256 = U256::from(1000);
-        let amount_997: U256 = 997.into();
-        let amount_1: U256 = 1.into();
-        let amount_to_repay: U256 = ((amount_1000 * pair_balance_token_pay * amount)
-            / (amount_997 * pair_balance_token_borrow))
-            .checked_add(amount_1)
-            .ok_or(Error::UniswapV2CoreFlashSwapperOverFlow3)
-            .unwrap_or_revert();
         // get the orignal tokens the user requested
         let mut _token_borrowed: Key = Key::from_formatted_str(
             "hash-0000000000000000000000000000000000000000000000000000000000000000",
@@ -627,7 +601,7 @@ pub trait FLASHSWAPPER<Storage: ContractStorage>: ContractContext<Storage> {
             uniswap_v2_factory_package_hash,
             None,
             "get_pair",
-            runtime_args! {"token0" => token_borrow, "token1" => wcspr},
+            runtime_args! {"token0" => token_borrow, "token1" => wcspr, "fee" => data::PAIR_FEE},
         );
         let address_0: Key = Key::from_formatted_str(
             "hash-0000000000000000000000000000000000000000000000000000000000000000",
@@ -638,7 +612,7 @@ pub trait FLASHSWAPPER<Storage: ContractStorage>: ContractContext<Storage> {
                 uniswap_v2_factory_package_hash,
                 None,
                 "get_pair",
-                runtime_args! {"token0" => token_pay, "token1" => wcspr},
+                runtime_args! {"token0" => token_pay, "token1" => wcspr, "fee" => data::PAIR_FEE},
             );
             data::set_permissioned_pair_address(permissioned_pair_address);
             let pay_pair_address: Key = permissioned_pair_address; // gas efficiency
@@ -659,29 +633,8 @@ pub trait FLASHSWAPPER<Storage: ContractStorage>: ContractContext<Storage> {
                 );
 
                 if pair_balance_token_borrow_before >= amount {
-                    let pair_balance_token_borrow_after: U256 = pair_balance_token_borrow_before
-                        .checked_sub(amount)
-                        .ok_or(Error::UniswapV2CoreFlashSwapperUnderFlow)
-                        .unwrap_or_revert();
-                    //convert Key to ContractPackageHash
-                    let wcspr_address_hash_add_array = match wcspr {
-                        Key::Hash(package) => package,
-                        _ => runtime::revert(ApiError::UnexpectedKeyVariant),
-                    };
-                    let wcspr_package_hash: ContractPackageHash =
-                        ContractPackageHash::new(wcspr_address_hash_add_array);
-                    let pair_balance_wcspr: U256 = runtime::call_versioned_contract(
-                        wcspr_package_hash,
-                        None,
-                        "balance_of",
-                        runtime_args! {"owner" => borrow_pair_address},
-                    );
-                    let amount_1000: U256 = 1000.into();
-                    let amount_997: U256 = 997.into();
-                    let amount_1: U256 = 1.into();
-                    let amount_of_wcspr: U256 = ((amount_1000 * pair_balance_wcspr * amount)
-                        / (amount_997 * pair_balance_token_borrow_after))
-                        + amount_1;
+                    let amount_of_wcspr: U256 =
+                        self.get_amount_in(borrow_pair_address, amount, token_borrow);
                     // using a helper function here to avoid "stack too deep" :(
                     self.traingular_flash_swap_helper(
                         token_borrow,
@@ -879,12 +832,7 @@ pub trait FLASHSWAPPER<Storage: ContractStorage>: ContractContext<Storage> {
         );
         // compute the amount of _tokenPay that needs to be repaid
         let pay_pair_address: Key = data::get_permissioned_pair_address(); // gas efficiency
-        let pair_balance_wcspr: U256 = runtime::call_versioned_contract(
-            wcspr_package_hash,
-            None,
-            "balance_of",
-            runtime_args! {"owner" => pay_pair_address},
-        );
+        let amount_to_repay: U256 = self.get_amount_in(pay_pair_address, amount_of_wcspr, wcspr);
         //convert Key to ContractPackageHash
         let token_pay_address_hash_add_array = match token_pay {
             Key::Hash(package) => package,
@@ -892,18 +840,6 @@ pub trait FLASHSWAPPER<Storage: ContractStorage>: ContractContext<Storage> {
         };
         let token_pay_package_hash: ContractPackageHash =
             ContractPackageHash::new(token_pay_address_hash_add_array);
-        let pair_balance_token_pay: U256 = runtime::call_versioned_contract(
-            token_pay_package_hash,
-            None,
-            "balance_of",
-            runtime_args! {"owner" => pay_pair_address},
-        );
-        let amount_1000: U256 = 1000.into();
-        let amount_997: U256 = 997.into();
-        let amount_1: U256 = 1.into();
-        let amount_to_repay: U256 = ((amount_1000 * pair_balance_token_pay * amount_of_wcspr)
-            / (amount_997 * pair_balance_wcspr))
-            + amount_1;
         // Step 4: Do whatever the user wants (arb, liqudiation, etc)
         self.execute(token_borrow, amount, token_pay, amount_to_repay, user_data);
         // Step 5: Pay back the flash-borrow to the _tokenPay/wcspr pool
@@ -937,6 +873,17 @@ pub trait FLASHSWAPPER<Storage: ContractStorage>: ContractContext<Storage> {
     ) {
     }
 
+    /// Returns how much of the other token `pair` takes in for `amount_out` of `token_out`,
+    /// at the pair's own swap fee and curve.
+    fn get_amount_in(&mut self, pair: Key, amount_out: U256, token_out: Key) -> U256 {
+        runtime::call_versioned_contract(
+            pair.into_hash().unwrap_or_revert().into(),
+            None,
+            "get_amount_in",
+            runtime_args! {"amount_out" => amount_out, "token_out" => token_out},
+        )
+    }
+
     fn purse(&mut self) -> URef {
         data::get_self_purse()
     }
@@ -76,8 +76,19 @@ fn create_pair() {
     let token_a: Key = runtime::get_named_arg("token_a");
     let token_b: Key = runtime::get_named_arg("token_b");
     let pair_hash: Key = runtime::get_named_arg("pair_hash");
+    let fee: u32 = runtime::get_named_arg("fee");
+    let curve_type: u8 = runtime::get_named_arg("curve_type");
+    let amplification: u64 = runtime::get_named_arg("amplification");
     let factory_hash: Key = runtime::get_named_arg("factory_hash");
-    Test::default().create_pair(token_a, token_b, pair_hash, factory_hash);
+    Test::default().create_pair(
+        token_a,
+        token_b,
+        pair_hash,
+        fee,
+        curve_type,
+        amplification,
+        factory_hash,
+    );
 }
 
 // PAIR METHOD
@@ -166,6 +177,9 @@ fn get_entry_points() -> EntryPoints {
             Parameter::new("token_a", Key::cl_type()),
             Parameter::new("token_b", Key::cl_type()),
             Parameter::new("pair_hash", Key::cl_type()),
+            Parameter::new("fee", u32::cl_type()),
+            Parameter::new("curve_type", u8::cl_type()),
+            Parameter::new("amplification", u64::cl_type()),
             Parameter::new("factory_hash", Key::cl_type()),
         ],
         <()>::cl_type(),
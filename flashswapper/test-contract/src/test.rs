@@ -85,7 +85,16 @@ pub trait TEST<Storage: ContractStorage>: ContractContext<Storage> {
     //
 
     // FACTORY METHOD
-    fn create_pair(&mut self, token_a: Key, token_b: Key, pair_hash: Key, factory_hash: Key) {
+    fn create_pair(
+        &mut self,
+        token_a: Key,
+        token_b: Key,
+        pair_hash: Key,
+        fee: u32,
+        curve_type: u8,
+        amplification: u64,
+        factory_hash: Key,
+    ) {
         let factory_hash_add_array = match factory_hash {
             Key::Hash(package) => package,
             _ => runtime::revert(ApiError::UnexpectedKeyVariant),
@@ -97,7 +106,10 @@ pub trait TEST<Storage: ContractStorage>: ContractContext<Storage> {
             runtime_args! {
                 "token_a" => token_a,
                 "token_b" => token_b,
-                "pair_hash" => pair_hash
+                "pair_hash" => pair_hash,
+                "fee" => fee,
+                "curve_type" => curve_type,
+                "amplification" => amplification
             },
         );
     }
@@ -0,0 +1,5 @@
+#[cfg(test)]
+pub mod migrator_instance;
+
+#[cfg(test)]
+pub mod migrator_tests;
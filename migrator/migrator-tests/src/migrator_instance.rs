@@ -0,0 +1,59 @@
+use casper_types::{account::AccountHash, runtime_args, Key, RuntimeArgs, U256};
+use test_env::{balance_key, TestContract, TestEnv};
+
+pub struct MIGRATORInstance(TestContract);
+
+impl MIGRATORInstance {
+    pub fn new(
+        env: &TestEnv,
+        contract_name: &str,
+        sender: AccountHash,
+        factory: Key,
+    ) -> MIGRATORInstance {
+        MIGRATORInstance(TestContract::new(
+            env,
+            "migrator.wasm",
+            contract_name,
+            sender,
+            runtime_args! {
+                "factory" => factory
+            },
+        ))
+    }
+
+    pub fn migrate<T: Into<Key>>(
+        &self,
+        sender: AccountHash,
+        pair: Key,
+        liquidity: U256,
+        fee: u32,
+        min_liquidity: U256,
+        to: T,
+    ) {
+        self.0.call_contract(
+            sender,
+            "migrate",
+            runtime_args! {
+                "pair" => pair,
+                "liquidity" => liquidity,
+                "fee" => fee,
+                "min_liquidity" => min_liquidity,
+                "to" => to.into()
+            },
+        );
+    }
+
+    pub fn factory(&self) -> Key {
+        self.0.query_named_key(String::from("factory"))
+    }
+
+    pub fn package_hash(&self) -> Key {
+        Key::Hash(self.0.package_hash())
+    }
+}
+
+pub fn balance_of<T: Into<Key>>(token: &TestContract, account: T) -> U256 {
+    token
+        .query_dictionary("balances", balance_key(&account.into()))
+        .unwrap_or_default()
+}
@@ -0,0 +1,293 @@
+use casper_types::{account::AccountHash, runtime_args, Key, RuntimeArgs, U256};
+use test_env::{TestContract, TestEnv};
+use uniswap_types::Reserves;
+
+use crate::migrator_instance::{balance_of, MIGRATORInstance};
+
+const LIQUIDITY: u64 = 10000;
+// sqrt(10000 * 10000) minus the 1000 the first mint locks
+const OLD_LP: u64 = 9000;
+const CHAIN_NAME: &str = "casper-test";
+
+fn deploy_token(env: &TestEnv, contract_name: &str, owner: AccountHash) -> TestContract {
+    let decimals: u8 = 18;
+    let init_total_supply: U256 = 100000.into();
+    TestContract::new(
+        env,
+        "erc20-token.wasm",
+        contract_name,
+        owner,
+        runtime_args! {
+            "initial_supply" => init_total_supply,
+            "name" => contract_name,
+            "symbol" => "tk",
+            "decimals" => decimals,
+            "chain_name" => CHAIN_NAME
+        },
+    )
+}
+
+fn deploy_factory(env: &TestEnv, contract_name: &str, owner: AccountHash) -> TestContract {
+    let factory = TestContract::new(
+        env,
+        "factory.wasm",
+        contract_name,
+        owner,
+        runtime_args! {
+            "fee_to_setter" => Key::from(owner)
+        },
+    );
+    factory.call_contract(
+        owner,
+        "set_white_list",
+        runtime_args! {
+            "white_list" => Key::from(owner)
+        },
+    );
+    factory
+}
+
+fn deploy_pair(
+    env: &TestEnv,
+    contract_name: &str,
+    owner: AccountHash,
+    factory: &TestContract,
+    token_a: &TestContract,
+    token_b: &TestContract,
+) -> TestContract {
+    let decimals: u8 = 8;
+    let init_total_supply: U256 = 0.into();
+    let pair = TestContract::new(
+        env,
+        "pair-token.wasm",
+        contract_name,
+        owner,
+        runtime_args! {
+            "name" => "ERC20",
+            "symbol" => "ERC",
+            "decimals" => decimals,
+            "initial_supply" => init_total_supply,
+            "callee_package_hash" => Key::from(owner),
+            "factory_hash" => Key::Hash(factory.package_hash()),
+            "chain_name" => CHAIN_NAME
+        },
+    );
+    factory.call_contract(
+        owner,
+        "create_pair",
+        runtime_args! {
+            "token_a" => Key::Hash(token_a.package_hash()),
+            "token_b" => Key::Hash(token_b.package_hash()),
+            "pair_hash" => Key::Hash(pair.package_hash()),
+            "fee" => 30u32,
+            "curve_type" => 0u8,
+            "amplification" => 0u64
+        },
+    );
+    pair
+}
+
+fn transfer(token: &TestContract, sender: AccountHash, recipient: Key, amount: U256) {
+    token.call_contract(
+        sender,
+        "transfer",
+        runtime_args! {
+            "recipient" => recipient,
+            "amount" => amount
+        },
+    );
+}
+
+fn add_liquidity(
+    owner: AccountHash,
+    pair: &TestContract,
+    token_a: &TestContract,
+    amount_a: U256,
+    token_b: &TestContract,
+    amount_b: U256,
+) {
+    transfer(token_a, owner, Key::Hash(pair.package_hash()), amount_a);
+    transfer(token_b, owner, Key::Hash(pair.package_hash()), amount_b);
+    pair.call_contract(
+        owner,
+        "mint_no_ret",
+        runtime_args! {
+            "to" => Key::from(owner)
+        },
+    );
+}
+
+fn deploy() -> (
+    TestEnv,
+    AccountHash,
+    TestContract,
+    TestContract,
+    TestContract,
+    TestContract,
+    MIGRATORInstance,
+) {
+    let env = TestEnv::new();
+    let owner = env.next_user();
+    let token_a = deploy_token(&env, "token_a", owner);
+    let token_b = deploy_token(&env, "token_b", owner);
+    let old_factory = deploy_factory(&env, "old_factory", owner);
+    let new_factory = deploy_factory(&env, "new_factory", owner);
+    let old_pair = deploy_pair(&env, "old_pair", owner, &old_factory, &token_a, &token_b);
+    let new_pair = deploy_pair(&env, "new_pair", owner, &new_factory, &token_a, &token_b);
+    add_liquidity(
+        owner,
+        &old_pair,
+        &token_a,
+        LIQUIDITY.into(),
+        &token_b,
+        LIQUIDITY.into(),
+    );
+    let migrator = MIGRATORInstance::new(
+        &env,
+        "migrator",
+        owner,
+        Key::Hash(new_factory.package_hash()),
+    );
+    (env, owner, token_a, token_b, old_pair, new_pair, migrator)
+}
+
+fn approve(pair: &TestContract, owner: AccountHash, migrator: &MIGRATORInstance, amount: U256) {
+    pair.call_contract(
+        owner,
+        "approve",
+        runtime_args! {
+            "spender" => migrator.package_hash(),
+            "amount" => amount
+        },
+    );
+}
+
+#[test]
+fn test_migrator_deploy() {
+    let (_env, _owner, _token_a, _token_b, _old_pair, new_pair, migrator) = deploy();
+    let factory: Key = new_pair.query_named_key(String::from("factory_hash"));
+    assert_eq!(migrator.factory(), factory);
+}
+
+#[test]
+fn test_migrator_migrate_into_empty_pair() {
+    let (_env, owner, token_a, token_b, old_pair, new_pair, migrator) = deploy();
+    assert_eq!(balance_of(&old_pair, owner), OLD_LP.into());
+    approve(&old_pair, owner, &migrator, OLD_LP.into());
+
+    migrator.migrate(
+        owner,
+        Key::Hash(old_pair.package_hash()),
+        OLD_LP.into(),
+        30,
+        0.into(),
+        owner,
+    );
+
+    assert_eq!(balance_of(&old_pair, owner), 0.into());
+    // 9000 of each token, less the 1000 the new pair locks
+    assert_eq!(balance_of(&new_pair, owner), 8000.into());
+    let reserves: Reserves = new_pair.query_named_key(String::from("reserves"));
+    assert_eq!(reserves.reserve0, 9000.into());
+    assert_eq!(reserves.reserve1, 9000.into());
+    assert_eq!(balance_of(&token_a, migrator.package_hash()), 0.into());
+    assert_eq!(balance_of(&token_b, migrator.package_hash()), 0.into());
+}
+
+#[test]
+fn test_migrator_refunds_dust() {
+    let (_env, owner, token_a, token_b, old_pair, new_pair, migrator) = deploy();
+    // the new pair prices token_a at half a token_b
+    add_liquidity(
+        owner,
+        &new_pair,
+        &token_a,
+        2000.into(),
+        &token_b,
+        1000.into(),
+    );
+    let balance_a: U256 = balance_of(&token_a, owner);
+    let balance_b: U256 = balance_of(&token_b, owner);
+    approve(&old_pair, owner, &migrator, OLD_LP.into());
+
+    migrator.migrate(
+        owner,
+        Key::Hash(old_pair.package_hash()),
+        OLD_LP.into(),
+        30,
+        0.into(),
+        owner,
+    );
+
+    // 9000 token_a only pair with 4500 token_b, the rest comes back
+    assert_eq!(balance_of(&token_a, owner), balance_a);
+    assert_eq!(balance_of(&token_b, owner), balance_b + U256::from(4500));
+    assert_eq!(balance_of(&token_a, migrator.package_hash()), 0.into());
+    assert_eq!(balance_of(&token_b, migrator.package_hash()), 0.into());
+}
+
+#[test]
+#[should_panic]
+fn test_migrator_below_min_liquidity() {
+    let (_env, owner, _token_a, _token_b, old_pair, _new_pair, migrator) = deploy();
+    approve(&old_pair, owner, &migrator, OLD_LP.into());
+    migrator.migrate(
+        owner,
+        Key::Hash(old_pair.package_hash()),
+        OLD_LP.into(),
+        30,
+        8001.into(),
+        owner,
+    );
+}
+
+#[test]
+#[should_panic]
+fn test_migrator_without_new_pair() {
+    let (_env, owner, _token_a, _token_b, old_pair, _new_pair, migrator) = deploy();
+    approve(&old_pair, owner, &migrator, OLD_LP.into());
+    migrator.migrate(
+        owner,
+        Key::Hash(old_pair.package_hash()),
+        OLD_LP.into(),
+        100,
+        0.into(),
+        owner,
+    );
+}
+
+#[test]
+#[should_panic]
+fn test_migrator_without_approval() {
+    let (_env, owner, _token_a, _token_b, old_pair, _new_pair, migrator) = deploy();
+    migrator.migrate(
+        owner,
+        Key::Hash(old_pair.package_hash()),
+        OLD_LP.into(),
+        30,
+        0.into(),
+        owner,
+    );
+}
+
+#[test]
+#[should_panic]
+fn test_migrator_into_factory_without_pair() {
+    let (env, owner, _token_a, _token_b, old_pair, _new_pair, _migrator) = deploy();
+    let empty_factory = deploy_factory(&env, "empty_factory", owner);
+    let migrator = MIGRATORInstance::new(
+        &env,
+        "empty_migrator",
+        owner,
+        Key::Hash(empty_factory.package_hash()),
+    );
+    approve(&old_pair, owner, &migrator, OLD_LP.into());
+    migrator.migrate(
+        owner,
+        Key::Hash(old_pair.package_hash()),
+        OLD_LP.into(),
+        30,
+        0.into(),
+        owner,
+    );
+}
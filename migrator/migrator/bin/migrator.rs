@@ -0,0 +1,223 @@
+#![no_main]
+#![no_std]
+
+#[macro_use]
+extern crate alloc;
+
+use alloc::{collections::BTreeSet, format};
+use casper_contract::{
+    contract_api::{runtime, storage},
+    unwrap_or_revert::UnwrapOrRevert,
+};
+use casper_types::{
+    runtime_args, CLTyped, CLValue, ContractHash, ContractPackageHash, EntryPoint,
+    EntryPointAccess, EntryPointType, EntryPoints, Group, Key, Parameter, RuntimeArgs, URef, U256,
+};
+use contract_utils::{ContractContext, OnChainContractStorage};
+use migrator::MIGRATOR;
+
+#[derive(Default)]
+struct Migrator(OnChainContractStorage);
+
+impl ContractContext<OnChainContractStorage> for Migrator {
+    fn storage(&self) -> &OnChainContractStorage {
+        &self.0
+    }
+}
+
+impl MIGRATOR<OnChainContractStorage> for Migrator {}
+
+impl Migrator {
+    fn constructor(
+        &mut self,
+        factory: Key,
+        contract_hash: ContractHash,
+        package_hash: ContractPackageHash,
+    ) {
+        MIGRATOR::init(
+            self,
+            factory,
+            Key::from(contract_hash),
+            Key::from(package_hash),
+        );
+    }
+}
+
+#[no_mangle]
+fn constructor() {
+    let factory: Key = runtime::get_named_arg("factory");
+    let contract_hash: ContractHash = runtime::get_named_arg("contract_hash");
+    let package_hash: ContractPackageHash = runtime::get_named_arg("package_hash");
+    Migrator::default().constructor(factory, contract_hash, package_hash);
+}
+
+/// This function is to move liquidity from an old Pair into the Factory's Pair of the same tokens in one deploy
+///
+/// # Parameters
+///
+/// * `pair` - A Key that holds the package hash of the old Pair
+///
+/// * `liquidity` - A U256 that holds the amount of old LP tokens to move, approved to the Migrator beforehand
+///
+/// * `fee` - A u32 that holds the fee tier of the new Pair
+///
+/// * `min_liquidity` - A U256 that holds the least amount of new LP tokens to accept
+///
+/// * `to` - A Key that holds the account address receiving the new LP tokens
+///
+
+#[no_mangle]
+fn migrate() {
+    let pair: Key = runtime::get_named_arg("pair");
+    let liquidity: U256 = runtime::get_named_arg("liquidity");
+    let fee: u32 = runtime::get_named_arg("fee");
+    let min_liquidity: U256 = runtime::get_named_arg("min_liquidity");
+    let to: Key = runtime::get_named_arg("to");
+    let ret: U256 = Migrator::default().migrate(pair, liquidity, fee, min_liquidity, to);
+    runtime::ret(CLValue::from_t(ret).unwrap_or_revert());
+}
+
+/// This function is to return the package hash of the Factory pairs are looked up in
+///
+
+#[no_mangle]
+fn factory() {
+    let ret: Key = Migrator::default().get_factory();
+    runtime::ret(CLValue::from_t(ret).unwrap_or_revert());
+}
+
+/// This function is to fetch a Contract Package Hash
+///
+
+#[no_mangle]
+fn package_hash() {
+    let ret: Key = Migrator::default().get_package_hash();
+    runtime::ret(CLValue::from_t(ret).unwrap_or_revert());
+}
+
+#[no_mangle]
+fn call() {
+    // Contract name must be same for all new versions of the contracts
+    let contract_name: alloc::string::String = runtime::get_named_arg("contract_name");
+
+    // If this is the first deployment
+    if !runtime::has_key(&format!("{}_package_hash", contract_name)) {
+        // Build new package with initial a first version of the contract.
+        let (package_hash, access_token) = storage::create_contract_package_at_hash();
+        let (contract_hash, _) =
+            storage::add_contract_version(package_hash, get_entry_points(), Default::default());
+
+        let factory: Key = runtime::get_named_arg("factory");
+
+        // Prepare constructor args
+        let constructor_args = runtime_args! {
+            "factory" => factory,
+            "contract_hash" => contract_hash,
+            "package_hash"=> package_hash
+        };
+
+        // Add the constructor group to the package hash with a single URef.
+        let constructor_access: URef =
+            storage::create_contract_user_group(package_hash, "constructor", 1, Default::default())
+                .unwrap_or_revert()
+                .pop()
+                .unwrap_or_revert();
+
+        // Call the constructor entry point
+        let _: () =
+            runtime::call_versioned_contract(package_hash, None, "constructor", constructor_args);
+
+        // Remove all URefs from the constructor group, so no one can call it for the second time.
+        let mut urefs = BTreeSet::new();
+        urefs.insert(constructor_access);
+        storage::remove_contract_user_group_urefs(package_hash, "constructor", urefs)
+            .unwrap_or_revert();
+
+        // Store contract in the account's named keys.
+        runtime::put_key(
+            &format!("{}_package_hash", contract_name),
+            package_hash.into(),
+        );
+        runtime::put_key(
+            &format!("{}_package_hash_wrapped", contract_name),
+            storage::new_uref(package_hash).into(),
+        );
+        runtime::put_key(
+            &format!("{}_contract_hash", contract_name),
+            contract_hash.into(),
+        );
+        runtime::put_key(
+            &format!("{}_contract_hash_wrapped", contract_name),
+            storage::new_uref(contract_hash).into(),
+        );
+        runtime::put_key(
+            &format!("{}_package_access_token", contract_name),
+            access_token.into(),
+        );
+    } else {
+        // this is a contract upgrade
+
+        let package_hash: ContractPackageHash =
+            runtime::get_key(&format!("{}_package_hash", contract_name))
+                .unwrap_or_revert()
+                .into_hash()
+                .unwrap()
+                .into();
+
+        let (contract_hash, _): (ContractHash, _) =
+            storage::add_contract_version(package_hash, get_entry_points(), Default::default());
+
+        // update contract hash
+        runtime::put_key(
+            &format!("{}_contract_hash", contract_name),
+            contract_hash.into(),
+        );
+        runtime::put_key(
+            &format!("{}_contract_hash_wrapped", contract_name),
+            storage::new_uref(contract_hash).into(),
+        );
+    }
+}
+
+fn get_entry_points() -> EntryPoints {
+    let mut entry_points = EntryPoints::new();
+    entry_points.add_entry_point(EntryPoint::new(
+        "constructor",
+        vec![
+            Parameter::new("factory", Key::cl_type()),
+            Parameter::new("contract_hash", ContractHash::cl_type()),
+            Parameter::new("package_hash", ContractPackageHash::cl_type()),
+        ],
+        <()>::cl_type(),
+        EntryPointAccess::Groups(vec![Group::new("constructor")]),
+        EntryPointType::Contract,
+    ));
+    entry_points.add_entry_point(EntryPoint::new(
+        "migrate",
+        vec![
+            Parameter::new("pair", Key::cl_type()),
+            Parameter::new("liquidity", U256::cl_type()),
+            Parameter::new("fee", u32::cl_type()),
+            Parameter::new("min_liquidity", U256::cl_type()),
+            Parameter::new("to", Key::cl_type()),
+        ],
+        U256::cl_type(),
+        EntryPointAccess::Public,
+        EntryPointType::Contract,
+    ));
+    entry_points.add_entry_point(EntryPoint::new(
+        "factory",
+        vec![],
+        Key::cl_type(),
+        EntryPointAccess::Public,
+        EntryPointType::Contract,
+    ));
+    entry_points.add_entry_point(EntryPoint::new(
+        "package_hash",
+        vec![],
+        Key::cl_type(),
+        EntryPointAccess::Public,
+        EntryPointType::Contract,
+    ));
+    entry_points
+}
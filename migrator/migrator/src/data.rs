@@ -0,0 +1,32 @@
+use casper_contract::unwrap_or_revert::UnwrapOrRevert;
+use casper_types::Key;
+use contract_utils::{get_key, set_key};
+
+pub const SELF_CONTRACT_HASH: &str = "self_contract_hash";
+pub const CONTRACT_PACKAGE_HASH: &str = "contract_package_hash";
+pub const FACTORY: &str = "factory";
+
+pub fn set_hash(contract_hash: Key) {
+    set_key(SELF_CONTRACT_HASH, contract_hash);
+}
+
+pub fn get_hash() -> Key {
+    get_key(SELF_CONTRACT_HASH).unwrap_or_revert()
+}
+
+pub fn set_package_hash(package_hash: Key) {
+    set_key(CONTRACT_PACKAGE_HASH, package_hash);
+}
+
+pub fn get_package_hash() -> Key {
+    get_key(CONTRACT_PACKAGE_HASH).unwrap_or_revert()
+}
+
+/// Package hash of the factory whose pairs liquidity is moved into.
+pub fn set_factory(factory: Key) {
+    set_key(FACTORY, factory);
+}
+
+pub fn get_factory() -> Key {
+    get_key(FACTORY).unwrap_or_revert()
+}
@@ -0,0 +1,8 @@
+#![no_std]
+extern crate alloc;
+
+pub mod data;
+mod migrator;
+
+pub use contract_utils;
+pub use migrator::{Error, MIGRATOREvent, MIGRATOR};
@@ -0,0 +1,246 @@
+use crate::alloc::string::ToString;
+use crate::data;
+use alloc::collections::BTreeMap;
+use alloc::{string::String, vec::Vec};
+use casper_contract::{
+    contract_api::{runtime, storage},
+    unwrap_or_revert::UnwrapOrRevert,
+};
+use casper_types::{
+    runtime_args, ApiError, ContractPackageHash, Key, RuntimeArgs, URef, U128, U256,
+};
+use contract_utils::{zero_hash, ContractContext, ContractStorage};
+
+pub enum MIGRATOREvent {
+    Migrated {
+        sender: Key,
+        old_pair: Key,
+        new_pair: Key,
+        liquidity: U256,
+        amount0: U256,
+        amount1: U256,
+        minted: U256,
+    },
+}
+
+impl MIGRATOREvent {
+    pub fn type_name(&self) -> String {
+        match self {
+            MIGRATOREvent::Migrated {
+                sender: _,
+                old_pair: _,
+                new_pair: _,
+                liquidity: _,
+                amount0: _,
+                amount1: _,
+                minted: _,
+            } => "migrated",
+        }
+        .to_string()
+    }
+}
+
+#[repr(u16)]
+pub enum Error {
+    /// 65,675 for (UniswapV2 Migrator Pair Not Found)
+    UniswapV2MigratorPairNotFound = 139,
+    /// 65,676 for (UniswapV2 Migrator Same Pair)
+    UniswapV2MigratorSamePair = 140,
+    /// 65,677 for (UniswapV2 Migrator Zero Liquidity)
+    UniswapV2MigratorZeroLiquidity = 141,
+    /// 65,678 for (UniswapV2 Migrator Insufficient Liquidity Minted)
+    UniswapV2MigratorInsufficientLiquidityMinted = 142,
+    /// 65,697 for (UniswapV2 Migrator Multiplication OverFlow)
+    UniswapV2MigratorMultiplicationOverFlow = 161,
+}
+
+impl From<Error> for ApiError {
+    fn from(error: Error) -> ApiError {
+        ApiError::User(error as u16)
+    }
+}
+
+fn package_hash(key: Key) -> ContractPackageHash {
+    match key {
+        Key::Hash(package) => ContractPackageHash::new(package),
+        _ => runtime::revert(ApiError::UnexpectedKeyVariant),
+    }
+}
+
+fn transfer(token: Key, recipient: Key, amount: U256) {
+    if amount.is_zero() {
+        return;
+    }
+    let res: Result<(), u32> = runtime::call_versioned_contract(
+        package_hash(token),
+        None,
+        "transfer",
+        runtime_args! {"recipient" => recipient, "amount" => amount},
+    );
+    match res {
+        Ok(()) => (),
+        Err(err) => runtime::revert(err),
+    }
+}
+
+pub trait MIGRATOR<Storage: ContractStorage>: ContractContext<Storage> {
+    fn init(&mut self, factory: Key, contract_hash: Key, package_hash: Key) {
+        data::set_factory(factory);
+        data::set_hash(contract_hash);
+        data::set_package_hash(package_hash);
+    }
+
+    /// Moves `liquidity` LP tokens of `pair` held by the caller into the factory's pair of
+    /// the same tokens in the `fee` tier, minting the new LP tokens to `to`. The caller must
+    /// have approved this contract for `liquidity` on `pair`. Whatever the new pair's price
+    /// leaves of either token is refunded to the caller. Reverts unless at least
+    /// `min_liquidity` new LP tokens are minted.
+    fn migrate(
+        &mut self,
+        pair: Key,
+        liquidity: U256,
+        fee: u32,
+        min_liquidity: U256,
+        to: Key,
+    ) -> U256 {
+        if liquidity.is_zero() {
+            runtime::revert(Error::UniswapV2MigratorZeroLiquidity);
+        }
+        let sender: Key = self.get_caller();
+        let old_pair: ContractPackageHash = package_hash(pair);
+        let token0: Key =
+            runtime::call_versioned_contract(old_pair, None, "token0", runtime_args! {});
+        let token1: Key =
+            runtime::call_versioned_contract(old_pair, None, "token1", runtime_args! {});
+        let new_pair_key: Key = runtime::call_versioned_contract(
+            package_hash(data::get_factory()),
+            None,
+            "get_pair",
+            runtime_args! {"token0" => token0, "token1" => token1, "fee" => fee},
+        );
+        if new_pair_key == zero_hash() {
+            runtime::revert(Error::UniswapV2MigratorPairNotFound);
+        }
+        if new_pair_key == pair {
+            runtime::revert(Error::UniswapV2MigratorSamePair);
+        }
+        let new_pair: ContractPackageHash = package_hash(new_pair_key);
+
+        // the LP tokens go straight to the old pair, which burns what it holds
+        let res: Result<(), u32> = runtime::call_versioned_contract(
+            old_pair,
+            None,
+            "transfer_from",
+            runtime_args! {"owner" => sender, "recipient" => pair, "amount" => liquidity},
+        );
+        if let Err(err) = res {
+            runtime::revert(err);
+        }
+        let (amount0, amount1): (U256, U256) = runtime::call_versioned_contract(
+            old_pair,
+            None,
+            "burn",
+            runtime_args! {"to" => data::get_package_hash()},
+        );
+
+        let (reserve0, reserve1, _): (U128, U128, u64) =
+            runtime::call_versioned_contract(new_pair, None, "get_reserves", runtime_args! {});
+        let new_token0: Key =
+            runtime::call_versioned_contract(new_pair, None, "token0", runtime_args! {});
+        let (reserve0, reserve1): (U256, U256) = if new_token0 == token0 {
+            (reserve0.as_u128().into(), reserve1.as_u128().into())
+        } else {
+            (reserve1.as_u128().into(), reserve0.as_u128().into())
+        };
+        let (deposit0, deposit1): (U256, U256) =
+            self.deposit_amounts(amount0, amount1, reserve0, reserve1);
+        transfer(token0, new_pair_key, deposit0);
+        transfer(token1, new_pair_key, deposit1);
+        let minted: U256 =
+            runtime::call_versioned_contract(new_pair, None, "mint", runtime_args! {"to" => to});
+        if minted < min_liquidity {
+            runtime::revert(Error::UniswapV2MigratorInsufficientLiquidityMinted);
+        }
+
+        transfer(token0, sender, amount0 - deposit0);
+        transfer(token1, sender, amount1 - deposit1);
+        self.emit(&MIGRATOREvent::Migrated {
+            sender,
+            old_pair: pair,
+            new_pair: new_pair_key,
+            liquidity,
+            amount0: deposit0,
+            amount1: deposit1,
+            minted,
+        });
+        minted
+    }
+
+    /// Returns how much of `amount0` and `amount1` a pair with reserves `reserve0` and
+    /// `reserve1` takes without moving its price. An empty pair takes everything.
+    fn deposit_amounts(
+        &self,
+        amount0: U256,
+        amount1: U256,
+        reserve0: U256,
+        reserve1: U256,
+    ) -> (U256, U256) {
+        if reserve0.is_zero() || reserve1.is_zero() {
+            return (amount0, amount1);
+        }
+        let amount1_optimal: U256 = amount0
+            .checked_mul(reserve1)
+            .ok_or(Error::UniswapV2MigratorMultiplicationOverFlow)
+            .unwrap_or_revert()
+            / reserve0;
+        if amount1_optimal <= amount1 {
+            (amount0, amount1_optimal)
+        } else {
+            let amount0_optimal: U256 = amount1
+                .checked_mul(reserve0)
+                .ok_or(Error::UniswapV2MigratorMultiplicationOverFlow)
+                .unwrap_or_revert()
+                / reserve1;
+            (amount0_optimal, amount1)
+        }
+    }
+
+    fn get_factory(&self) -> Key {
+        data::get_factory()
+    }
+
+    fn get_package_hash(&self) -> Key {
+        data::get_package_hash()
+    }
+
+    fn emit(&mut self, migrator_event: &MIGRATOREvent) {
+        let mut events = Vec::new();
+        let package = data::get_package_hash();
+        match migrator_event {
+            MIGRATOREvent::Migrated {
+                sender,
+                old_pair,
+                new_pair,
+                liquidity,
+                amount0,
+                amount1,
+                minted,
+            } => {
+                let mut event = BTreeMap::new();
+                event.insert("contract_package_hash", package.to_string());
+                event.insert("event_type", migrator_event.type_name());
+                event.insert("sender", sender.to_string());
+                event.insert("old_pair", old_pair.to_string());
+                event.insert("new_pair", new_pair.to_string());
+                event.insert("liquidity", liquidity.to_string());
+                event.insert("amount0", amount0.to_string());
+                event.insert("amount1", amount1.to_string());
+                event.insert("minted", minted.to_string());
+                events.push(event);
+            }
+        };
+        for event in events {
+            let _: URef = storage::new_uref(event);
+        }
+    }
+}
@@ -0,0 +1,140 @@
+use casper_types::U256;
+use pair::constant_product::{get_amount_in, get_amount_out, invariant_holds, quote};
+
+const CASES: usize = 500;
+const SWAP_FEES: [u32; 4] = [1, 4, 30, 100];
+
+/// xorshift64* so every run checks the same cases and failures reproduce.
+struct Rng(u64);
+
+impl Rng {
+    fn next_u64(&mut self) -> u64 {
+        self.0 ^= self.0 >> 12;
+        self.0 ^= self.0 << 25;
+        self.0 ^= self.0 >> 27;
+        self.0.wrapping_mul(0x2545_f491_4f6c_dd1d)
+    }
+
+    fn below(&mut self, max: u128) -> u128 {
+        let value = (u128::from(self.next_u64()) << 64) | u128::from(self.next_u64());
+        value % max
+    }
+
+    fn pick<T: Copy>(&mut self, values: &[T]) -> T {
+        values[(self.next_u64() % values.len() as u64) as usize]
+    }
+
+    /// A reserve between 1,000 and 10^digits, with digits up to 30.
+    fn reserve(&mut self) -> U256 {
+        let digits = 4 + (self.next_u64() % 27) as u32;
+        U256::from(1000 + self.below(10u128.pow(digits)))
+    }
+}
+
+/// Whether `PAIR::swap` accepts `amount_out` for `amount_in`.
+fn accepted(
+    reserve_in: U256,
+    reserve_out: U256,
+    amount_in: U256,
+    amount_out: U256,
+    swap_fee: u32,
+) -> bool {
+    let balance_in_adjusted = (reserve_in + amount_in) * 10000 - amount_in * swap_fee;
+    let balance_out_adjusted = (reserve_out - amount_out) * 10000;
+    balance_in_adjusted * balance_out_adjusted >= reserve_in * reserve_out * 100_000_000
+}
+
+#[test]
+fn test_amount_out_quotes_are_exact() {
+    let mut rng = Rng(0x5eed_0101);
+    for _ in 0..CASES {
+        let reserve_in = rng.reserve();
+        let reserve_out = rng.reserve();
+        let swap_fee = rng.pick(&SWAP_FEES);
+        let amount_in = U256::from(1 + rng.below(reserve_in.as_u128() * 2));
+        let amount_out = get_amount_out(amount_in, reserve_in, reserve_out, swap_fee).unwrap();
+        assert!(amount_out < reserve_out);
+        assert!(accepted(
+            reserve_in,
+            reserve_out,
+            amount_in,
+            amount_out,
+            swap_fee
+        ));
+        assert!(!accepted(
+            reserve_in,
+            reserve_out,
+            amount_in,
+            amount_out + 1,
+            swap_fee
+        ));
+    }
+}
+
+#[test]
+fn test_amount_in_quotes_are_exact() {
+    let mut rng = Rng(0x5eed_0102);
+    for _ in 0..CASES {
+        let reserve_in = rng.reserve();
+        let reserve_out = rng.reserve();
+        let swap_fee = rng.pick(&SWAP_FEES);
+        let amount_out = U256::from(1 + rng.below(reserve_out.as_u128() - 1));
+        let amount_in = get_amount_in(amount_out, reserve_in, reserve_out, swap_fee).unwrap();
+        assert!(accepted(
+            reserve_in,
+            reserve_out,
+            amount_in,
+            amount_out,
+            swap_fee
+        ));
+        assert!(!accepted(
+            reserve_in,
+            reserve_out,
+            amount_in - 1,
+            amount_out,
+            swap_fee
+        ));
+    }
+}
+
+#[test]
+fn test_amount_in_for_whole_reserve() {
+    assert_eq!(
+        get_amount_in(1000.into(), 2000.into(), 1000.into(), 30),
+        None
+    );
+}
+
+#[test]
+fn test_quote_keeps_price() {
+    assert_eq!(quote(100.into(), 2000.into(), 1000.into()), Some(50.into()));
+    assert_eq!(
+        quote(100.into(), 1000.into(), 2000.into()),
+        Some(200.into())
+    );
+}
+
+#[test]
+fn test_quotes_near_u128_max() {
+    let mut rng = Rng(0x5eed_0103);
+    for _ in 0..CASES {
+        let reserve_in = u128::MAX / 2 + rng.below(u128::MAX / 2);
+        let reserve_out = u128::MAX / 2 + rng.below(u128::MAX / 2);
+        let swap_fee = rng.pick(&SWAP_FEES);
+        // the balance after the swap still fits the U128 reserves
+        let amount_in = U256::from(1 + rng.below(u128::MAX - reserve_in));
+        let (reserve_in, reserve_out) = (U256::from(reserve_in), U256::from(reserve_out));
+        let amount_out = get_amount_out(amount_in, reserve_in, reserve_out, swap_fee).unwrap();
+        assert!(invariant_holds(
+            (reserve_in + amount_in) * 10000 - amount_in * swap_fee,
+            (reserve_out - amount_out) * 10000,
+            reserve_in * 10000,
+            reserve_out * 10000,
+        ));
+        if amount_out.is_zero() {
+            continue;
+        }
+        let amount_in_again = get_amount_in(amount_out, reserve_in, reserve_out, swap_fee).unwrap();
+        assert!(amount_in_again <= amount_in);
+    }
+}
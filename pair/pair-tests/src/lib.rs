@@ -1,5 +1,11 @@
+#[cfg(test)]
+pub mod constant_product_tests;
+
 #[cfg(test)]
 pub mod pair_tests;
 
 #[cfg(test)]
 pub mod pair_instance;
+
+#[cfg(test)]
+pub mod stable_swap_tests;
@@ -3,10 +3,14 @@ use blake2::{
     VarBlake2b,
 };
 use casper_types::{
-    account::AccountHash, bytesrepr::ToBytes, runtime_args, ContractPackageHash, Key, RuntimeArgs,
-    U128, U256,
+    account::AccountHash, bytesrepr::ToBytes, runtime_args, CLTyped, ContractHash,
+    ContractPackageHash, Key, PublicKey, RuntimeArgs, Signature, U128, U256,
 };
-use test_env::{TestContract, TestEnv};
+use test_env::{balance_key, TestContract, TestEnv};
+use uniswap_types::{PairState, PriceCumulatives, Reserves};
+
+/// Chain name the test tokens bind their permit domain to.
+pub const CHAIN_NAME: &str = "casper-test";
 
 pub struct PAIRInstance(TestContract);
 
@@ -54,6 +58,64 @@ impl PAIRInstance {
             "pair-token.wasm",
             contract_name,
             sender,
+            runtime_args! {
+                "initial_supply" => supply,
+                "name" => name,
+                "symbol" => symbol,
+                "decimals" => decimals,
+                "callee_package_hash" => callee_package_hash,
+                "factory_hash" => factory_hash,
+                "chain_name" => CHAIN_NAME
+            },
+        )
+    }
+
+    /// Installs the pair built with `uncached-lookups`, for gas comparisons.
+    pub fn new_uncached(
+        env: &TestEnv,
+        contract_name: &str,
+        sender: AccountHash,
+        name: &str,
+        symbol: &str,
+        decimals: u8,
+        supply: U256,
+        callee_package_hash: Key,
+        factory_hash: Key,
+    ) -> TestContract {
+        TestContract::new(
+            env,
+            "pair-token-uncached.wasm",
+            contract_name,
+            sender,
+            runtime_args! {
+                "initial_supply" => supply,
+                "name" => name,
+                "symbol" => symbol,
+                "decimals" => decimals,
+                "callee_package_hash" => callee_package_hash,
+                "factory_hash" => factory_hash,
+                "chain_name" => CHAIN_NAME
+            },
+        )
+    }
+
+    /// Installs the pair as released before storage versions, to be upgraded in tests.
+    pub fn new_legacy(
+        env: &TestEnv,
+        contract_name: &str,
+        sender: AccountHash,
+        name: &str,
+        symbol: &str,
+        decimals: u8,
+        supply: U256,
+        callee_package_hash: Key,
+        factory_hash: Key,
+    ) -> TestContract {
+        TestContract::new(
+            env,
+            "pair-token-legacy.wasm",
+            contract_name,
+            sender,
             runtime_args! {
                 "initial_supply" => supply,
                 "name" => name,
@@ -65,6 +127,19 @@ impl PAIRInstance {
         )
     }
 
+    /// Adds a new version to the package installed under `contract_name` by `sender`.
+    pub fn upgrade(env: &TestEnv, contract_name: &str, sender: AccountHash) -> TestContract {
+        TestContract::new(
+            env,
+            "pair-token.wasm",
+            contract_name,
+            sender,
+            runtime_args! {
+                "chain_name" => CHAIN_NAME
+            },
+        )
+    }
+
     pub fn constructor(
         &self,
         sender: AccountHash,
@@ -89,7 +164,18 @@ impl PAIRInstance {
         );
     }
 
+    pub fn proxy_package_hash(&self) -> Key {
+        Key::Hash(self.0.package_hash())
+    }
+
     pub fn balance_of<T: Into<Key>>(&self, account: T) -> U256 {
+        self.0
+            .query_dictionary("balances", balance_key(&account.into()))
+            .unwrap_or_default()
+    }
+
+    /// Balance written under the `key_to_str` item by pairs installed before the shared core.
+    pub fn legacy_balance_of<T: Into<Key>>(&self, account: T) -> U256 {
         self.0
             .query_dictionary("balances", key_to_str(&account.into()))
             .unwrap_or_default()
@@ -120,6 +206,42 @@ impl PAIRInstance {
         );
     }
 
+    pub fn transfer_and_call<T: Into<Key>>(
+        &self,
+        sender: AccountHash,
+        recipient: T,
+        amount: U256,
+        data: &str,
+    ) {
+        self.0.call_contract(
+            sender,
+            "transfer_and_call",
+            runtime_args! {
+                "recipient" => recipient.into(),
+                "amount" => amount,
+                "data" => data.to_string()
+            },
+        );
+    }
+
+    pub fn transfer_and_call_expecting_failure<T: Into<Key>>(
+        &self,
+        sender: AccountHash,
+        recipient: T,
+        amount: U256,
+        data: &str,
+    ) {
+        self.0.call_contract_expecting_failure(
+            sender,
+            "transfer_and_call",
+            runtime_args! {
+                "recipient" => recipient.into(),
+                "amount" => amount,
+                "data" => data.to_string()
+            },
+        );
+    }
+
     pub fn transfer_from(&self, sender: AccountHash, owner: Key, recipient: Key, amount: U256) {
         self.0.call_contract(
             sender,
@@ -132,6 +254,154 @@ impl PAIRInstance {
         );
     }
 
+    #[allow(clippy::too_many_arguments)]
+    pub fn transfer_with_authorization(
+        &self,
+        sender: AccountHash,
+        public_key: PublicKey,
+        signature: Signature,
+        from: Key,
+        to: Key,
+        value: U256,
+        valid_after: u64,
+        valid_before: u64,
+        nonce: [u8; 32],
+        block_time: u64,
+    ) {
+        self.0.call_contract_with_block_time(
+            sender,
+            "transfer_with_authorization",
+            runtime_args! {
+                "public" => public_key,
+                "signature" => signature,
+                "from" => from,
+                "to" => to,
+                "value" => value,
+                "valid_after" => valid_after,
+                "valid_before" => valid_before,
+                "nonce" => nonce
+            },
+            block_time,
+        );
+    }
+
+    #[allow(clippy::too_many_arguments)]
+    pub fn receive_with_authorization(
+        &self,
+        sender: AccountHash,
+        public_key: PublicKey,
+        signature: Signature,
+        from: Key,
+        to: Key,
+        value: U256,
+        valid_after: u64,
+        valid_before: u64,
+        nonce: [u8; 32],
+        block_time: u64,
+    ) {
+        self.0.call_contract_with_block_time(
+            sender,
+            "receive_with_authorization",
+            runtime_args! {
+                "public" => public_key,
+                "signature" => signature,
+                "from" => from,
+                "to" => to,
+                "value" => value,
+                "valid_after" => valid_after,
+                "valid_before" => valid_before,
+                "nonce" => nonce
+            },
+            block_time,
+        );
+    }
+
+    pub fn cancel_authorization(
+        &self,
+        sender: AccountHash,
+        public_key: PublicKey,
+        signature: Signature,
+        authorizer: Key,
+        nonce: [u8; 32],
+    ) {
+        self.0.call_contract(
+            sender,
+            "cancel_authorization",
+            runtime_args! {
+                "public" => public_key,
+                "signature" => signature,
+                "authorizer" => authorizer,
+                "nonce" => nonce
+            },
+        );
+    }
+
+    pub fn authorization_used<T: Into<Key>>(&self, authorizer: T, nonce: [u8; 32]) -> bool {
+        self.0
+            .query_dictionary(
+                "authorizations",
+                key_and_value_to_str(&authorizer.into(), &nonce),
+            )
+            .unwrap_or_default()
+    }
+
+    pub fn transfer_at<T: Into<Key>>(
+        &self,
+        sender: AccountHash,
+        recipient: T,
+        amount: U256,
+        block_time: u64,
+    ) {
+        self.0.call_contract_with_block_time(
+            sender,
+            "transfer",
+            runtime_args! {
+                "recipient" => recipient.into(),
+                "amount" => amount
+            },
+            block_time,
+        );
+    }
+
+    pub fn delegate<T: Into<Key>>(&self, sender: AccountHash, delegatee: T, block_time: u64) {
+        self.0.call_contract_with_block_time(
+            sender,
+            "delegate",
+            runtime_args! {
+                "delegatee" => delegatee.into()
+            },
+            block_time,
+        );
+    }
+
+    pub fn get_votes_fn<T: Into<Key>>(&self, sender: AccountHash, account: T) {
+        self.0.call_contract(
+            sender,
+            "get_votes",
+            runtime_args! {
+                "account" => account.into()
+            },
+        );
+    }
+
+    pub fn get_past_votes_fn<T: Into<Key>>(
+        &self,
+        sender: AccountHash,
+        account: T,
+        timestamp: u64,
+        block_time: u64,
+    ) {
+        self.0.call_contract_with_block_time(
+            sender,
+            "get_past_votes",
+            runtime_args! {
+                "account" => account.into(),
+                "timestamp" => timestamp
+            },
+            block_time,
+        );
+    }
+
     pub fn allowance_fn(&self, sender: AccountHash, owner: Key, spender: Key) {
         self.0.call_contract(
             sender,
@@ -220,6 +490,9 @@ impl PAIRInstance {
         token0: T,
         token1: T,
         factory_hash: T,
+        swap_fee: u32,
+        curve_type: u8,
+        amplification: u64,
     ) {
         self.0.call_contract(
             sender,
@@ -227,7 +500,10 @@ impl PAIRInstance {
             runtime_args! {
                 "token0" => token0.into(),
                 "token1" => token1.into(),
-                "factory_hash" => factory_hash.into()
+                "factory_hash" => factory_hash.into(),
+                "swap_fee" => swap_fee,
+                "curve_type" => curve_type,
+                "amplification" => amplification
             },
         );
     }
@@ -253,6 +529,35 @@ impl PAIRInstance {
         );
     }
 
+    pub fn migrate(&self, sender: AccountHash, contract_hash: ContractHash) {
+        self.0.call_contract(
+            sender,
+            "migrate",
+            runtime_args! {
+                "contract_hash" => contract_hash,
+                "chain_name" => CHAIN_NAME
+            },
+        );
+    }
+
+    pub fn pause(&self, sender: AccountHash) {
+        self.0.call_contract(sender, "pause", runtime_args! {});
+    }
+
+    pub fn unpause(&self, sender: AccountHash) {
+        self.0.call_contract(sender, "unpause", runtime_args! {});
+    }
+
+    pub fn set_fee_on(&self, sender: AccountHash, fee_on: bool) {
+        self.0.call_contract(
+            sender,
+            "set_fee_on",
+            runtime_args! {
+                "fee_on" => fee_on
+            },
+        );
+    }
+
     pub fn sync(&self, sender: AccountHash) {
         self.0.call_contract(sender, "sync", runtime_args! {});
     }
@@ -308,6 +613,48 @@ impl PAIRInstance {
         );
     }
 
+    pub fn get_amount_out<T: Into<Key>>(&self, sender: AccountHash, amount_in: U256, token_in: T) {
+        self.0.call_contract(
+            sender,
+            "get_amount_out",
+            runtime_args! {
+                "amount_in" => amount_in,
+                "token_in" => token_in.into()
+            },
+        );
+    }
+
+    pub fn get_amount_in<T: Into<Key>>(&self, sender: AccountHash, amount_out: U256, token_out: T) {
+        self.0.call_contract(
+            sender,
+            "get_amount_in",
+            runtime_args! {
+                "amount_out" => amount_out,
+                "token_out" => token_out.into()
+            },
+        );
+    }
+
+    pub fn quote<T: Into<Key>>(&self, sender: AccountHash, amount: U256, token: T) {
+        self.0.call_contract(
+            sender,
+            "quote",
+            runtime_args! {
+                "amount" => amount,
+                "token" => token.into()
+            },
+        );
+    }
+
+    pub fn get_state(&self, sender: AccountHash) {
+        self.0.call_contract(sender, "get_state", runtime_args! {});
+    }
+
+    pub fn get_reserves(&self, sender: AccountHash) {
+        self.0
+            .call_contract(sender, "get_reserves", runtime_args! {});
+    }
+
     pub fn name(&self) -> String {
         self.0.query_named_key(String::from("name"))
     }
@@ -335,32 +682,72 @@ impl PAIRInstance {
         self.0.query_named_key(String::from("balance"))
     }
 
+    pub fn domain_separator(&self) -> String {
+        self.0.query_named_key(String::from("domain_separator"))
+    }
+
+    pub fn reserves(&self) -> Reserves {
+        self.0.query_named_key(String::from("reserves"))
+    }
+
     pub fn reserve0(&self) -> U128 {
-        self.0.query_named_key(String::from("reserve0"))
+        self.reserves().reserve0
     }
 
     pub fn reserve1(&self) -> U128 {
-        self.0.query_named_key(String::from("reserve1"))
+        self.reserves().reserve1
     }
 
     pub fn block_timestamp_last(&self) -> u64 {
-        self.0.query_named_key(String::from("block_timestamp_last"))
+        self.reserves().block_timestamp_last
+    }
+
+    /// Reserves and timestamp kept in their own named keys by pairs installed before
+    /// storage version `3`.
+    pub fn legacy_reserves(&self) -> (U128, U128, u64) {
+        (
+            self.0.query_named_key(String::from("reserve0")),
+            self.0.query_named_key(String::from("reserve1")),
+            self.0.query_named_key(String::from("block_timestamp_last")),
+        )
+    }
+
+    pub fn price_cumulatives(&self) -> PriceCumulatives {
+        self.0.query_named_key(String::from("price_cumulatives"))
     }
 
     pub fn price0_cumulative_last(&self) -> U256 {
-        self.0
-            .query_named_key(String::from("price0_cumulative_last"))
+        self.price_cumulatives().price0_cumulative_last
     }
 
     pub fn price1_cumulative_last(&self) -> U256 {
-        self.0
-            .query_named_key(String::from("price1_cumulative_last"))
+        self.price_cumulatives().price1_cumulative_last
     }
 
     pub fn k_last(&self) -> U256 {
         self.0.query_named_key(String::from("k_last"))
     }
 
+    pub fn swap_fee(&self) -> u32 {
+        self.0.query_named_key(String::from("swap_fee"))
+    }
+
+    pub fn paused(&self) -> bool {
+        self.0.query_named_key(String::from("paused"))
+    }
+
+    pub fn fee_on(&self) -> bool {
+        self.0.query_named_key(String::from("fee_on"))
+    }
+
+    pub fn curve_type(&self) -> u8 {
+        self.0.query_named_key(String::from("curve_type"))
+    }
+
+    pub fn amplification(&self) -> u64 {
+        self.0.query_named_key(String::from("amplification"))
+    }
+
     pub fn treasury_fee(&self) -> U256 {
         self.0.query_named_key(String::from("treasury_fee"))
     }
@@ -385,6 +772,35 @@ impl PAIRInstance {
         self.0.query_named_key(String::from("callee_package_hash"))
     }
 
+    /// `initialize` of pairs installed before fee tiers and curves.
+    pub fn legacy_initialize<T: Into<Key>>(
+        &self,
+        sender: AccountHash,
+        token0: T,
+        token1: T,
+        factory_hash: T,
+    ) {
+        self.0.call_contract(
+            sender,
+            "initialize",
+            runtime_args! {
+                "token0" => token0.into(),
+                "token1" => token1.into(),
+                "factory_hash" => factory_hash.into()
+            },
+        );
+    }
+
+    pub fn migrate_balances(&self, sender: AccountHash, owners: Vec<Key>) {
+        self.0.call_contract(
+            sender,
+            "migrate_balances",
+            runtime_args! {
+                "owners" => owners
+            },
+        );
+    }
+
     pub fn factory_hash(&self) -> Key {
         self.0.query_named_key(String::from("factory_hash"))
     }
@@ -393,6 +809,24 @@ impl PAIRInstance {
         self.0.query_named_key(String::from("fee_to"))
     }
 
+    pub fn storage_version(&self) -> u32 {
+        self.0.query_named_key(String::from("storage_version"))
+    }
+
+    pub fn has_role<T: Into<Key>>(&self, role: &str, account: T) -> bool {
+        self.0
+            .query_dictionary(
+                "roles",
+                key_and_value_to_str(&account.into(), &role.to_string()),
+            )
+            .unwrap_or_default()
+    }
+
+    /// Names of the pair's named keys.
+    pub fn named_keys(&self) -> Vec<String> {
+        self.0.named_keys()
+    }
+
     pub fn self_contract_hash(&self) -> Key {
         self.0.query_named_key(String::from("self_contract_hash"))
     }
@@ -405,6 +839,52 @@ impl PAIRInstance {
         self.0.query_named_key("transfer_result".to_string())
     }
 
+    pub fn received_from(&self) -> Key {
+        self.0.query_named_key("received_from".to_string())
+    }
+
+    pub fn received_amount(&self) -> U256 {
+        self.0.query_named_key("received_amount".to_string())
+    }
+
+    pub fn received_data(&self) -> String {
+        self.0.query_named_key("received_data".to_string())
+    }
+
+    pub fn delegates<T: Into<Key>>(&self, account: T) -> Option<Key> {
+        self.0
+            .query_dictionary("delegates", key_to_str(&account.into()))
+    }
+
+    pub fn checkpoints<T: Into<Key>>(&self, account: T) -> Vec<(u64, U256)> {
+        let account: Key = account.into();
+        let len: u32 = self
+            .0
+            .query_dictionary("num_checkpoints", key_to_str(&account))
+            .unwrap_or_default();
+        (0..len)
+            .map(|index| {
+                self.0
+                    .query_dictionary("checkpoints", key_and_value_to_str(&account, &index))
+                    .unwrap()
+            })
+            .collect()
+    }
+
+    pub fn votes<T: Into<Key>>(&self, account: T) -> U256 {
+        self.checkpoints(account)
+            .last()
+            .map_or_else(U256::zero, |(_, votes)| *votes)
+    }
+
+    pub fn get_votes_res(&self) -> U256 {
+        self.0.query_named_key("get_votes".to_string())
+    }
+
+    pub fn get_past_votes_res(&self) -> U256 {
+        self.0.query_named_key("get_past_votes".to_string())
+    }
+
     pub fn package_hash_result(&self) -> ContractPackageHash {
         self.0.query_named_key("package_hash".to_string())
     }
@@ -415,6 +895,22 @@ impl PAIRInstance {
     pub fn allowance_res(&self) -> U256 {
         self.0.query_named_key("allowance".to_string())
     }
+    pub fn amount_out_result(&self) -> U256 {
+        self.0.query_named_key("amount_out".to_string())
+    }
+    pub fn amount_in_result(&self) -> U256 {
+        self.0.query_named_key("amount_in".to_string())
+    }
+    pub fn quote_result(&self) -> U256 {
+        self.0.query_named_key("quote".to_string())
+    }
+    pub fn state_result(&self) -> PairState {
+        self.0.query_named_key("state".to_string())
+    }
+
+    pub fn reserves_result(&self) -> (U128, U128, u64) {
+        self.0.query_named_key("reserves".to_string())
+    }
 }
 
 pub fn key_to_str(key: &Key) -> String {
@@ -433,3 +929,12 @@ pub fn keys_to_str(key_a: &Key, key_b: &Key) -> String {
     hasher.finalize_variable(|hash| ret.clone_from_slice(hash));
     hex::encode(ret)
 }
+
+pub fn key_and_value_to_str<T: CLTyped + ToBytes>(key: &Key, value: &T) -> String {
+    let mut hasher = VarBlake2b::new(32).unwrap();
+    hasher.update(key.to_bytes().unwrap());
+    hasher.update(value.to_bytes().unwrap());
+    let mut ret = [0u8; 32];
+    hasher.finalize_variable(|hash| ret.clone_from_slice(hash));
+    hex::encode(ret)
+}
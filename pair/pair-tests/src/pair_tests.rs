@@ -1,7 +1,15 @@
-use casper_types::{account::AccountHash, runtime_args, Key, RuntimeArgs, U256};
+use casper_types::{
+    account::AccountHash,
+    bytesrepr::{FromBytes, ToBytes},
+    crypto, runtime_args, ContractHash, Key, PublicKey, RuntimeArgs, SecretKey, Signature, U128,
+    U256, U512,
+};
+use pair::{constant_product, stable_swap};
+use renvm_sig::{hash_message, keccak256};
 use test_env::{TestContract, TestEnv};
+use uniswap_types::{PairState, PriceCumulatives, Reserves};
 
-use crate::pair_instance::PAIRInstance;
+use crate::pair_instance::{PAIRInstance, CHAIN_NAME};
 
 const NAME: &str = "ERC20";
 const SYMBOL: &str = "ERC";
@@ -33,12 +41,11 @@ fn deploy_wcspr(env: &TestEnv) -> TestContract {
 
 fn deploy_factory(env: &TestEnv, owner: AccountHash) -> TestContract {
     // deploy factory contract
-    let owner_factory = env.next_user();
     let factory = TestContract::new(
         &env,
         "factory.wasm",
         "factory",
-        owner_factory,
+        owner,
         runtime_args! {
             "fee_to_setter" => Key::from(owner)
             // contract_name is passed seperately, so we don't need to pass it here.
@@ -108,6 +115,20 @@ fn deploy1() -> (
     PAIRInstance,
     AccountHash,
     TestContract,
+) {
+    deploy_without_supply(false)
+}
+
+/// Deploys a pair without initial supply, built with `uncached-lookups` if `uncached`.
+fn deploy_without_supply(
+    uncached: bool,
+) -> (
+    TestEnv,
+    PAIRInstance,
+    PAIRInstance,
+    PAIRInstance,
+    AccountHash,
+    TestContract,
 ) {
     let env = TestEnv::new();
     let owner = env.next_user();
@@ -130,7 +151,12 @@ fn deploy1() -> (
         },
     );
 
-    let token = PAIRInstance::new(
+    let install = if uncached {
+        PAIRInstance::new_uncached
+    } else {
+        PAIRInstance::new
+    };
+    let token = install(
         &env,
         NAME,
         owner,
@@ -155,7 +181,7 @@ fn deploy1() -> (
         factory_contract,
     )
 }
-fn deploy_token0(env: &TestEnv) -> TestContract {
+fn deploy_token0(env: &TestEnv, minter: Key) -> TestContract {
     let decimals: u8 = 18;
     let init_total_supply: U256 = 0.into();
 
@@ -169,13 +195,22 @@ fn deploy_token0(env: &TestEnv) -> TestContract {
             "initial_supply" => init_total_supply,
             "name" => "token0",
             "symbol" => "tk0",
-            "decimals" => decimals
+            "decimals" => decimals,
+            "chain_name" => CHAIN_NAME
+        },
+    );
+    token0_contract.call_contract(
+        token0_owner,
+        "grant_role",
+        runtime_args! {
+            "role" => "MINTER".to_string(),
+            "account" => minter
         },
     );
     token0_contract
 }
 
-fn deploy_token1(env: &TestEnv) -> TestContract {
+fn deploy_token1(env: &TestEnv, minter: Key) -> TestContract {
     let decimals: u8 = 18;
     let init_total_supply: U256 = 0.into();
 
@@ -189,7 +224,16 @@ fn deploy_token1(env: &TestEnv) -> TestContract {
             "initial_supply" => init_total_supply,
             "name" => "token1",
             "symbol" => "tk1",
-            "decimals" => decimals
+            "decimals" => decimals,
+            "chain_name" => CHAIN_NAME
+        },
+    );
+    token1_contract.call_contract(
+        token1_owner,
+        "grant_role",
+        runtime_args! {
+            "role" => "MINTER".to_string(),
+            "account" => minter
         },
     );
     token1_contract
@@ -279,6 +323,37 @@ fn test_pair_transfer_too_much() {
     token.transfer(owner, user, amount);
 }
 
+#[test]
+fn test_pair_transfer_and_call() {
+    let (_env, proxy, _proxy2, token, owner, _factory_hash) = deploy();
+    let package_hash = proxy.package_hash_result();
+    let amount: U256 = 100.into();
+
+    // the proxy's on_token_received records what it was called with
+    token.transfer_and_call(owner, package_hash, amount, "deposit");
+
+    assert_eq!(token.balance_of(package_hash), amount);
+    assert_eq!(
+        token.balance_of(owner),
+        U256::from(INIT_TOTAL_SUPPLY) - amount
+    );
+    assert_eq!(proxy.received_from(), Key::from(owner));
+    assert_eq!(proxy.received_amount(), amount);
+    assert_eq!(proxy.received_data(), "deposit");
+}
+
+#[test]
+fn test_pair_transfer_and_call_rejected_by_hook() {
+    let (_env, proxy, _proxy2, token, owner, _factory_hash) = deploy();
+    let package_hash = proxy.package_hash_result();
+
+    // the proxy's on_token_received reverts, taking the transfer with it
+    token.transfer_and_call_expecting_failure(owner, package_hash, 100.into(), "reject");
+
+    assert_eq!(token.balance_of(package_hash), 0.into());
+    assert_eq!(token.balance_of(owner), INIT_TOTAL_SUPPLY.into());
+}
+
 #[test]
 fn test_pair_approve() {
     let (env, _proxy, _proxy2, token, owner, _factory_hash) = deploy();
@@ -293,16 +368,45 @@ fn test_pair_approve() {
 
 #[test]
 fn test_pair_initialize() {
-    let (env, _proxy, _proxy2, token, owner, factory_hash) = deploy();
-    let token0 = deploy_token0(&env);
-    let token1 = deploy_token1(&env);
+    let (env, proxy, _proxy2, token, owner, factory_hash) = deploy();
+    let token0 = deploy_token0(&env, proxy.proxy_package_hash());
+    let token1 = deploy_token1(&env, proxy.proxy_package_hash());
     let token0 = Key::Hash(token0.package_hash());
     let token1 = Key::Hash(token1.package_hash());
     let factory_hash = Key::Hash(factory_hash.package_hash());
-    token.initialize(owner, token0, token1, factory_hash);
+    token.initialize(owner, token0, token1, factory_hash, 30, 0, 0);
     assert_eq!(token.factory_hash(), factory_hash);
     assert_eq!(token.token0(), token0);
     assert_eq!(token.token1(), token1);
+    assert_eq!(token.swap_fee(), 30);
+    assert_eq!(token.curve_type(), 0);
+    assert_eq!(token.amplification(), 0);
+}
+
+#[test]
+fn test_pair_initialize_stable_swap() {
+    let (env, proxy, _proxy2, token, owner, factory_hash) = deploy();
+    let token0 = deploy_token0(&env, proxy.proxy_package_hash());
+    let token1 = deploy_token1(&env, proxy.proxy_package_hash());
+    let token0 = Key::Hash(token0.package_hash());
+    let token1 = Key::Hash(token1.package_hash());
+    let factory_hash = Key::Hash(factory_hash.package_hash());
+    token.initialize(owner, token0, token1, factory_hash, 4, 1, 100);
+    assert_eq!(token.swap_fee(), 4);
+    assert_eq!(token.curve_type(), 1);
+    assert_eq!(token.amplification(), 100);
+}
+
+#[test]
+#[should_panic]
+fn test_pair_initialize_stable_swap_without_amplification() {
+    let (env, proxy, _proxy2, token, owner, factory_hash) = deploy();
+    let token0 = deploy_token0(&env, proxy.proxy_package_hash());
+    let token1 = deploy_token1(&env, proxy.proxy_package_hash());
+    let token0 = Key::Hash(token0.package_hash());
+    let token1 = Key::Hash(token1.package_hash());
+    let factory_hash = Key::Hash(factory_hash.package_hash());
+    token.initialize(owner, token0, token1, factory_hash, 4, 1, 0);
 }
 
 #[test]
@@ -322,12 +426,28 @@ fn test_pair_set_treasury_fee_percent() {
     assert_eq!(token.treasury_fee(), 3.into());
 }
 
+#[test]
+#[should_panic]
+fn test_pair_set_treasury_fee_percent_without_fee_setter_role() {
+    let (env, _proxy, _proxy2, token, _owner, _factory_hash) = deploy();
+    let user = env.next_user();
+    token.set_treasury_fee_percent(user, 10.into());
+}
+
+#[test]
+#[should_panic]
+fn test_pair_erc20_mint_without_minter_role() {
+    let (env, _proxy, _proxy2, token, _owner, _factory_hash) = deploy();
+    let user = env.next_user();
+    token.erc20_mint(user, user, 10.into());
+}
+
 #[test]
 fn test_pair_skim() {
     let (env, proxy, _proxy2, token, owner, factory_hash) = deploy();
     let user = env.next_user();
-    let token0 = deploy_token0(&env);
-    let token1 = deploy_token1(&env);
+    let token0 = deploy_token0(&env, proxy.proxy_package_hash());
+    let token1 = deploy_token1(&env, proxy.proxy_package_hash());
     let token0_contract_hash = Key::Hash(token0.contract_hash());
     let token1_contract_hash = Key::Hash(token1.contract_hash());
     let token0_package_hash = Key::Hash(token0.package_hash());
@@ -341,6 +461,9 @@ fn test_pair_skim() {
         token0_package_hash,
         token1_package_hash,
         factory_hash,
+        30,
+        0,
+        0,
     );
     assert_eq!(token.token0(), token0_package_hash);
     assert_eq!(token.token1(), token1_package_hash);
@@ -365,8 +488,8 @@ fn test_pair_skim() {
 fn test_pair_mint() {
     let (env, proxy, _proxy2, token, owner, factory_hash) = deploy1();
     let user = env.next_user();
-    let token0 = deploy_token0(&env);
-    let token1 = deploy_token1(&env);
+    let token0 = deploy_token0(&env, proxy.proxy_package_hash());
+    let token1 = deploy_token1(&env, proxy.proxy_package_hash());
 
     let token0_contract_hash = Key::Hash(token0.contract_hash());
     let token1_contract_hash = Key::Hash(token1.contract_hash());
@@ -381,6 +504,9 @@ fn test_pair_mint() {
         token0_package_hash,
         token1_package_hash,
         factory_hash,
+        30,
+        0,
+        0,
     );
     assert_eq!(token.token0(), token0_package_hash);
     assert_eq!(token.token1(), token1_package_hash);
@@ -405,8 +531,8 @@ fn test_pair_mint() {
 fn test_pair_burn() {
     let (env, proxy, _proxy2, token, owner, factory_hash) = deploy1();
     let user = env.next_user();
-    let token0 = deploy_token0(&env);
-    let token1 = deploy_token1(&env);
+    let token0 = deploy_token0(&env, proxy.proxy_package_hash());
+    let token1 = deploy_token1(&env, proxy.proxy_package_hash());
     let token0_contract_hash = Key::Hash(token0.contract_hash());
     let token1_contract_hash = Key::Hash(token1.contract_hash());
     let token0_package_hash = Key::Hash(token0.package_hash());
@@ -420,6 +546,9 @@ fn test_pair_burn() {
         token0_package_hash,
         token1_package_hash,
         factory_hash,
+        30,
+        0,
+        0,
     );
     assert_eq!(token.token0(), token0_package_hash);
     assert_eq!(token.token1(), token1_package_hash);
@@ -484,8 +613,8 @@ fn test_pair_burn() {
 fn test_pair_sync() {
     let (env, proxy, _, token, owner, factory_hash) = deploy();
     let user = env.next_user();
-    let token0 = deploy_token0(&env);
-    let token1 = deploy_token1(&env);
+    let token0 = deploy_token0(&env, proxy.proxy_package_hash());
+    let token1 = deploy_token1(&env, proxy.proxy_package_hash());
 
     let token0_contract_hash = Key::Hash(token0.contract_hash());
     let token1_contract_hash = Key::Hash(token1.contract_hash());
@@ -498,6 +627,9 @@ fn test_pair_sync() {
         token0_package_hash,
         token1_package_hash,
         factory_hash,
+        30,
+        0,
+        0,
     );
     assert_eq!(token.factory_hash(), factory_hash);
     assert_eq!(token.token0(), token0_package_hash);
@@ -526,8 +658,8 @@ fn test_pair_sync() {
 fn test_pair_swap() {
     let (env, proxy, _proxy2, token, owner, factory_hash) = deploy();
     let user = env.next_user();
-    let token0 = deploy_token0(&env);
-    let token1 = deploy_token1(&env);
+    let token0 = deploy_token0(&env, proxy.proxy_package_hash());
+    let token1 = deploy_token1(&env, proxy.proxy_package_hash());
     let token0_contract_hash = Key::Hash(token0.contract_hash());
     let token1_contract_hash = Key::Hash(token1.contract_hash());
     let token0_package_hash = Key::Hash(token0.package_hash());
@@ -545,6 +677,9 @@ fn test_pair_swap() {
         token0_package_hash,
         token1_package_hash,
         factory_hash,
+        30,
+        0,
+        0,
     );
     assert_eq!(token.token0(), token0_package_hash);
     assert_eq!(token.token1(), token1_package_hash);
@@ -581,6 +716,728 @@ fn test_pair_swap() {
     token.swap(owner, amount2, amount3, user, data);
 }
 
+fn swap_into_pair(
+    curve_type: u8,
+    amplification: u64,
+    amount1_out: U256,
+    paused: bool,
+) -> PAIRInstance {
+    let (env, proxy, _proxy2, token, owner, factory_hash) = deploy();
+    let user = env.next_user();
+    let token0 = deploy_token0(&env, proxy.proxy_package_hash());
+    let token1 = deploy_token1(&env, proxy.proxy_package_hash());
+    let token0_contract_hash = Key::Hash(token0.contract_hash());
+    let token1_contract_hash = Key::Hash(token1.contract_hash());
+    let token0_package_hash = Key::Hash(token0.package_hash());
+    let token1_package_hash = Key::Hash(token1.package_hash());
+    let factory_hash = Key::Hash(factory_hash.package_hash());
+    token.initialize(
+        owner,
+        token0_package_hash,
+        token1_package_hash,
+        factory_hash,
+        4,
+        curve_type,
+        amplification,
+    );
+    proxy.mint_with_caller(
+        owner,
+        token0_contract_hash,
+        Key::from(token.self_package_hash()),
+        2000.into(),
+    );
+    proxy.mint_with_caller(
+        owner,
+        token1_contract_hash,
+        Key::from(token.self_package_hash()),
+        2000.into(),
+    );
+    token.sync(owner);
+    proxy.mint_with_caller(
+        owner,
+        token0_contract_hash,
+        Key::from(token.self_package_hash()),
+        1000.into(),
+    );
+    if paused {
+        token.pause(owner);
+    }
+    token.swap(owner, 0.into(), amount1_out, user, "");
+    token
+}
+
+#[test]
+fn test_pair_stable_swap() {
+    let amount1_out: U256 =
+        stable_swap::get_amount_out(1000.into(), 2000.into(), 2000.into(), 100, 4).unwrap();
+    // a constant product pair pays out at most 666 here
+    assert!(amount1_out > 900.into());
+    let token = swap_into_pair(1, 100, amount1_out, false);
+    assert_eq!(token.reserve0(), 3000.into());
+    assert_eq!(
+        U256::from(token.reserve1().as_u128()),
+        U256::from(2000) - amount1_out
+    );
+    let d_before = stable_swap::compute_d(2000.into(), 2000.into(), 100).unwrap();
+    let d_after =
+        stable_swap::compute_d(3000.into(), U256::from(token.reserve1().as_u128()), 100).unwrap();
+    assert!(d_after >= d_before);
+}
+
+#[test]
+#[should_panic]
+fn test_pair_stable_swap_too_much() {
+    let amount1_out: U256 =
+        stable_swap::get_amount_out(1000.into(), 2000.into(), 2000.into(), 100, 4).unwrap();
+    swap_into_pair(1, 100, amount1_out + 2, false);
+}
+
+#[test]
+#[should_panic]
+fn test_pair_swap_beyond_constant_product() {
+    let amount1_out: U256 =
+        stable_swap::get_amount_out(1000.into(), 2000.into(), 2000.into(), 100, 4).unwrap();
+    swap_into_pair(0, 0, amount1_out, false);
+}
+
+fn pair_with_reserves(
+    curve_type: u8,
+    amplification: u64,
+    reserve0: U256,
+    reserve1: U256,
+) -> (
+    TestEnv,
+    PAIRInstance,
+    PAIRInstance,
+    AccountHash,
+    TestContract,
+    TestContract,
+) {
+    let (env, proxy, _proxy2, token, owner, factory_hash) = deploy();
+    let token0 = deploy_token0(&env, proxy.proxy_package_hash());
+    let token1 = deploy_token1(&env, proxy.proxy_package_hash());
+    token.initialize(
+        owner,
+        Key::Hash(token0.package_hash()),
+        Key::Hash(token1.package_hash()),
+        Key::Hash(factory_hash.package_hash()),
+        30,
+        curve_type,
+        amplification,
+    );
+    proxy.mint_with_caller(
+        owner,
+        Key::Hash(token0.contract_hash()),
+        Key::from(token.self_package_hash()),
+        reserve0,
+    );
+    proxy.mint_with_caller(
+        owner,
+        Key::Hash(token1.contract_hash()),
+        Key::from(token.self_package_hash()),
+        reserve1,
+    );
+    token.sync(owner);
+    (env, proxy, token, owner, token0, token1)
+}
+
+fn swap_quoted_amount_out(extra: U256) {
+    let (env, proxy, token, owner, token0, _token1) =
+        pair_with_reserves(0, 0, 2000.into(), 2000.into());
+    let user = env.next_user();
+    proxy.get_amount_out(owner, 1000.into(), Key::Hash(token0.package_hash()));
+    let amount1_out: U256 = proxy.amount_out_result();
+    assert_eq!(amount1_out, 665.into());
+    assert_eq!(
+        Some(amount1_out),
+        constant_product::get_amount_out(1000.into(), 2000.into(), 2000.into(), 30)
+    );
+    proxy.mint_with_caller(
+        owner,
+        Key::Hash(token0.contract_hash()),
+        Key::from(token.self_package_hash()),
+        1000.into(),
+    );
+    token.swap(owner, 0.into(), amount1_out + extra, user, "");
+    assert_eq!(token.reserve0(), 3000.into());
+    assert_eq!(
+        U256::from(token.reserve1().as_u128()),
+        U256::from(2000) - amount1_out
+    );
+}
+
+#[test]
+fn test_pair_get_amount_out() {
+    swap_quoted_amount_out(0.into());
+}
+
+#[test]
+#[should_panic]
+fn test_pair_get_amount_out_too_much() {
+    swap_quoted_amount_out(1.into());
+}
+
+#[test]
+fn test_pair_get_amount_in_stable_swap() {
+    let (env, proxy, token, owner, token0, token1) =
+        pair_with_reserves(1, 100, 2000.into(), 2000.into());
+    let user = env.next_user();
+    proxy.get_amount_in(owner, 900.into(), Key::Hash(token1.package_hash()));
+    let amount0_in: U256 = proxy.amount_in_result();
+    assert_eq!(
+        Some(amount0_in),
+        stable_swap::get_amount_in(900.into(), 2000.into(), 2000.into(), 100, 30)
+    );
+    // a constant product pair takes 1642 here
+    assert_eq!(amount0_in, 907.into());
+    proxy.mint_with_caller(
+        owner,
+        Key::Hash(token0.contract_hash()),
+        Key::from(token.self_package_hash()),
+        amount0_in,
+    );
+    token.swap(owner, 0.into(), 900.into(), user, "");
+    assert_eq!(
+        U256::from(token.reserve0().as_u128()),
+        U256::from(2000) + amount0_in
+    );
+    assert_eq!(token.reserve1(), 1100.into());
+}
+
+#[test]
+fn test_pair_quote() {
+    let (_env, proxy, _token, owner, token0, token1) =
+        pair_with_reserves(0, 0, 2000.into(), 1000.into());
+    proxy.quote(owner, 100.into(), Key::Hash(token0.package_hash()));
+    assert_eq!(proxy.quote_result(), 50.into());
+    proxy.quote(owner, 100.into(), Key::Hash(token1.package_hash()));
+    assert_eq!(proxy.quote_result(), 200.into());
+}
+
+#[test]
+#[should_panic]
+fn test_pair_quote_unknown_token() {
+    let (_env, proxy, _token, owner, _token0, _token1) =
+        pair_with_reserves(0, 0, 2000.into(), 1000.into());
+    proxy.quote(owner, 100.into(), Key::from(owner));
+}
+
+#[test]
+fn test_pair_get_state() {
+    let (_env, proxy, token, owner, token0, token1) =
+        pair_with_reserves(1, 100, 2000.into(), 1000.into());
+    proxy.get_state(owner);
+    let state: PairState = proxy.state_result();
+    assert_eq!(state.token0, Key::Hash(token0.package_hash()));
+    assert_eq!(state.token1, Key::Hash(token1.package_hash()));
+    assert_eq!(state.reserve0, 2000.into());
+    assert_eq!(state.reserve1, 1000.into());
+    assert_eq!(state.block_timestamp_last, token.block_timestamp_last());
+    assert_eq!(state.total_supply, token.total_supply());
+    assert_eq!(state.treasury_fee, token.treasury_fee());
+    assert_eq!(state.k_last, token.k_last());
+    assert_eq!(state.price0_cumulative_last, token.price0_cumulative_last());
+    assert_eq!(state.price1_cumulative_last, token.price1_cumulative_last());
+    assert_eq!(state.factory_hash, token.factory_hash());
+    assert_eq!(state.swap_fee, 30);
+    assert_eq!(state.curve_type, 1);
+    assert_eq!(state.amplification, 100);
+}
+
+#[test]
+fn test_pair_state_bytesrepr_roundtrip() {
+    let state = PairState {
+        token0: Key::Hash([1u8; 32]),
+        token1: Key::Hash([2u8; 32]),
+        reserve0: 2000.into(),
+        reserve1: 1000.into(),
+        block_timestamp_last: 1_650_000_000_000,
+        total_supply: 1414.into(),
+        treasury_fee: 3.into(),
+        k_last: 2_000_000.into(),
+        price0_cumulative_last: U256::MAX,
+        price1_cumulative_last: 0.into(),
+        factory_hash: Key::Hash([3u8; 32]),
+        swap_fee: 30,
+        curve_type: 1,
+        amplification: 100,
+    };
+    let bytes = state.to_bytes().unwrap();
+    assert_eq!(bytes.len(), state.serialized_length());
+    let (decoded, rest) = PairState::from_bytes(&bytes).unwrap();
+    assert_eq!(decoded, state);
+    assert!(rest.is_empty());
+}
+
+#[test]
+fn test_pair_get_reserves() {
+    let (_env, proxy, token, owner, _token0, _token1) =
+        pair_with_reserves(0, 0, 2000.into(), 1000.into());
+    proxy.get_reserves(owner);
+    assert_eq!(
+        proxy.reserves_result(),
+        (2000.into(), 1000.into(), token.block_timestamp_last())
+    );
+}
+
+#[test]
+fn test_pair_reserves_bytesrepr_roundtrip() {
+    let reserves = Reserves {
+        reserve0: U128::MAX,
+        reserve1: 1000.into(),
+        block_timestamp_last: 1_650_000_000_000,
+    };
+    let bytes = reserves.to_bytes().unwrap();
+    assert_eq!(bytes.len(), reserves.serialized_length());
+    let (decoded, rest) = Reserves::from_bytes(&bytes).unwrap();
+    assert_eq!(decoded, reserves);
+    assert!(rest.is_empty());
+
+    let cumulatives = PriceCumulatives {
+        price0_cumulative_last: U256::MAX,
+        price1_cumulative_last: 42.into(),
+    };
+    let bytes = cumulatives.to_bytes().unwrap();
+    assert_eq!(bytes.len(), cumulatives.serialized_length());
+    let (decoded, rest) = PriceCumulatives::from_bytes(&bytes).unwrap();
+    assert_eq!(decoded, cumulatives);
+    assert!(rest.is_empty());
+}
+
+fn mint_into_pair(
+    proxy: &PAIRInstance,
+    owner: AccountHash,
+    pair: &PAIRInstance,
+    token: &TestContract,
+    amount: U256,
+) {
+    proxy.mint_with_caller(
+        owner,
+        Key::Hash(token.contract_hash()),
+        Key::from(pair.self_package_hash()),
+        amount,
+    );
+}
+
+#[test]
+#[should_panic]
+fn test_pair_sync_beyond_u128() {
+    let (_env, proxy, token, owner, token0, _token1) =
+        pair_with_reserves(0, 0, 2000.into(), 2000.into());
+    mint_into_pair(&proxy, owner, &token, &token0, u128::MAX.into());
+    token.sync(owner);
+}
+
+#[test]
+#[should_panic]
+fn test_pair_swap_beyond_u128() {
+    let (env, proxy, token, owner, token0, _token1) =
+        pair_with_reserves(0, 0, 2000.into(), 2000.into());
+    let user = env.next_user();
+    mint_into_pair(&proxy, owner, &token, &token0, u128::MAX.into());
+    token.swap(owner, 0.into(), 1.into(), user, "");
+}
+
+#[test]
+fn test_pair_skim_beyond_u128() {
+    let (env, proxy, token, owner, token0, _token1) =
+        pair_with_reserves(0, 0, 2000.into(), 2000.into());
+    let user = env.next_user();
+    let excess: U256 = U256::from(u128::MAX) * 3;
+    mint_into_pair(&proxy, owner, &token, &token0, excess);
+    token.skim(owner, user);
+    proxy.balance_with_caller(owner, Key::Hash(token0.contract_hash()), Key::from(user));
+    assert_eq!(proxy.balance(), excess);
+    proxy.balance_with_caller(
+        owner,
+        Key::Hash(token0.contract_hash()),
+        Key::from(token.self_package_hash()),
+    );
+    assert_eq!(proxy.balance(), 2000.into());
+    token.sync(owner);
+    assert_eq!(token.reserve0(), 2000.into());
+}
+
+#[test]
+fn test_pair_swap_near_u128_max() {
+    // reserve0 * reserve1 scaled by the fee base no longer fits in U256
+    let reserve: U256 = U256::from(u128::MAX) / 2;
+    let (env, proxy, token, owner, token0, _token1) = pair_with_reserves(0, 0, reserve, reserve);
+    let user = env.next_user();
+    let amount0_in: U256 = U256::exp10(30);
+    proxy.get_amount_out(owner, amount0_in, Key::Hash(token0.package_hash()));
+    let amount1_out: U256 = proxy.amount_out_result();
+    mint_into_pair(&proxy, owner, &token, &token0, amount0_in);
+    token.swap(owner, 0.into(), amount1_out, user, "");
+    assert_eq!(U256::from(token.reserve0().as_u128()), reserve + amount0_in);
+    assert_eq!(
+        U256::from(token.reserve1().as_u128()),
+        reserve - amount1_out
+    );
+}
+
+fn create_pair(
+    owner: AccountHash,
+    factory: &TestContract,
+    pair: &PAIRInstance,
+    token0: &TestContract,
+    token1: &TestContract,
+) {
+    factory.call_contract(
+        owner,
+        "set_white_list",
+        runtime_args! {
+            "white_list" => Key::from(owner)
+        },
+    );
+    factory.call_contract(
+        owner,
+        "create_pair",
+        runtime_args! {
+            "token_a" => Key::Hash(token0.package_hash()),
+            "token_b" => Key::Hash(token1.package_hash()),
+            "pair_hash" => Key::from(pair.self_package_hash()),
+            "fee" => 30u32,
+            "curve_type" => 0u8,
+            "amplification" => 0u64
+        },
+    );
+}
+
+fn set_fee_to(owner: AccountHash, factory: &TestContract, fee_to: Key) {
+    factory.call_contract(
+        owner,
+        "set_fee_to",
+        runtime_args! {
+            "fee_to" => fee_to
+        },
+    );
+}
+
+fn sync_fee_on(owner: AccountHash, factory: &TestContract) {
+    factory.call_contract(
+        owner,
+        "sync_fee_on",
+        runtime_args! {
+            "start" => U256::from(0),
+            "count" => U256::from(1)
+        },
+    );
+}
+
+#[test]
+fn test_pair_mint_fee_with_large_reserves() {
+    let (env, proxy, _proxy2, token, owner, factory) = deploy();
+    let fee_to = env.next_user();
+    let token0 = deploy_token0(&env, proxy.proxy_package_hash());
+    let token1 = deploy_token1(&env, proxy.proxy_package_hash());
+    set_fee_to(owner, &factory, Key::from(fee_to));
+    create_pair(owner, &factory, &token, &token0, &token1);
+    assert!(token.fee_on());
+    // 10^12 tokens of 18 decimals, whose product no longer fits in U128
+    let amount: U256 = U256::exp10(30);
+    mint_into_pair(&proxy, owner, &token, &token0, amount);
+    mint_into_pair(&proxy, owner, &token, &token1, amount);
+    token.sync(owner);
+    mint_into_pair(&proxy, owner, &token, &token0, amount);
+    mint_into_pair(&proxy, owner, &token, &token1, amount);
+    token.mint_no_ret(owner, owner);
+    assert_eq!(token.k_last(), amount * amount);
+    mint_into_pair(&proxy, owner, &token, &token0, amount);
+    mint_into_pair(&proxy, owner, &token, &token1, amount);
+    token.mint_no_ret(owner, owner);
+    assert!(token.balance_of(fee_to) > 0.into());
+}
+
+#[test]
+fn test_pair_fee_on_follows_factory() {
+    let (env, proxy, _proxy2, token, owner, factory) = deploy();
+    let fee_to = env.next_user();
+    let token0 = deploy_token0(&env, proxy.proxy_package_hash());
+    let token1 = deploy_token1(&env, proxy.proxy_package_hash());
+    create_pair(owner, &factory, &token, &token0, &token1);
+    assert!(!token.fee_on());
+    set_fee_to(owner, &factory, Key::from(fee_to));
+    sync_fee_on(owner, &factory);
+    assert!(token.fee_on());
+    set_fee_to(owner, &factory, Key::from(AccountHash::new([0u8; 32])));
+    sync_fee_on(owner, &factory);
+    assert!(!token.fee_on());
+}
+
+#[test]
+#[should_panic]
+fn test_pair_set_fee_on_by_non_factory() {
+    let (_env, _proxy, _proxy2, token, owner, _factory_hash) = deploy();
+    token.set_fee_on(owner, true);
+}
+
+/// Gas of the second mint, a swap and a burn on a fresh constant product pair.
+fn mint_swap_burn_gas(uncached: bool, fee_to: Key) -> (U512, U512, U512) {
+    let (env, proxy, _proxy2, token, owner, factory) = deploy_without_supply(uncached);
+    let user = env.next_user();
+    let token0 = deploy_token0(&env, proxy.proxy_package_hash());
+    let token1 = deploy_token1(&env, proxy.proxy_package_hash());
+    set_fee_to(owner, &factory, fee_to);
+    create_pair(owner, &factory, &token, &token0, &token1);
+    let amount: U256 = 10000.into();
+    mint_into_pair(&proxy, owner, &token, &token0, amount);
+    mint_into_pair(&proxy, owner, &token, &token1, amount);
+    token.mint_no_ret(owner, owner);
+    // grows k, so the next mint takes the protocol fee when it is on
+    let amount_out: U256 =
+        constant_product::get_amount_out(1000.into(), amount, amount, 30).unwrap();
+    mint_into_pair(&proxy, owner, &token, &token0, 1000.into());
+    token.swap(owner, 0.into(), amount_out, user, "");
+
+    mint_into_pair(&proxy, owner, &token, &token0, amount);
+    mint_into_pair(&proxy, owner, &token, &token1, amount);
+    token.mint_no_ret(owner, Key::from(token.self_package_hash()));
+    let mint_gas: U512 = env.last_gas_cost();
+    mint_into_pair(&proxy, owner, &token, &token1, 1000.into());
+    token.swap(owner, amount_out, 0.into(), user, "");
+    let swap_gas: U512 = env.last_gas_cost();
+    token.burn_no_ret(owner, user);
+    let burn_gas: U512 = env.last_gas_cost();
+    (mint_gas, swap_gas, burn_gas)
+}
+
+#[test]
+fn test_pair_gas_with_cached_lookups() {
+    // the same fee setting for both pairs; with the fee off the cached pair no longer calls
+    // the factory on mints and burns, and every call skips converting the stored keys
+    let fee_to = Key::from(AccountHash::new([0u8; 32]));
+    let (mint_uncached, swap_uncached, burn_uncached) = mint_swap_burn_gas(true, fee_to);
+    let (mint_cached, swap_cached, burn_cached) = mint_swap_burn_gas(false, fee_to);
+    assert!(mint_cached < mint_uncached);
+    assert!(swap_cached < swap_uncached);
+    assert!(burn_cached < burn_uncached);
+}
+
+#[test]
+fn test_pair_pause() {
+    let (_env, _proxy, _proxy2, token, owner, _factory_hash) = deploy();
+    assert!(!token.paused());
+    token.pause(owner);
+    assert!(token.paused());
+    token.unpause(owner);
+    assert!(!token.paused());
+}
+
+#[test]
+#[should_panic]
+fn test_pair_pause_without_pauser_role() {
+    let (env, _proxy, _proxy2, token, _owner, _factory_hash) = deploy();
+    let user = env.next_user();
+    token.pause(user);
+}
+
+#[test]
+#[should_panic]
+fn test_pair_swap_when_paused() {
+    swap_into_pair(0, 0, 600.into(), true);
+}
+
+#[test]
+#[should_panic]
+fn test_pair_mint_when_paused() {
+    let (env, proxy, _proxy2, token, owner, factory_hash) = deploy1();
+    let user = env.next_user();
+    let token0 = deploy_token0(&env, proxy.proxy_package_hash());
+    let token1 = deploy_token1(&env, proxy.proxy_package_hash());
+    token.initialize(
+        owner,
+        Key::Hash(token0.package_hash()),
+        Key::Hash(token1.package_hash()),
+        Key::Hash(factory_hash.package_hash()),
+        30,
+        0,
+        0,
+    );
+    proxy.mint_with_caller(
+        owner,
+        Key::Hash(token0.contract_hash()),
+        Key::from(token.self_package_hash()),
+        30000.into(),
+    );
+    proxy.mint_with_caller(
+        owner,
+        Key::Hash(token1.contract_hash()),
+        Key::from(token.self_package_hash()),
+        30000.into(),
+    );
+    token.pause(owner);
+    token.mint_no_ret(owner, user);
+}
+
+#[test]
+fn test_pair_burn_when_paused() {
+    let (env, proxy, _proxy2, token, owner, factory_hash) = deploy1();
+    let user = env.next_user();
+    let token0 = deploy_token0(&env, proxy.proxy_package_hash());
+    let token1 = deploy_token1(&env, proxy.proxy_package_hash());
+    token.initialize(
+        owner,
+        Key::Hash(token0.package_hash()),
+        Key::Hash(token1.package_hash()),
+        Key::Hash(factory_hash.package_hash()),
+        30,
+        0,
+        0,
+    );
+    proxy.mint_with_caller(
+        owner,
+        Key::Hash(token0.contract_hash()),
+        Key::from(token.self_package_hash()),
+        30000.into(),
+    );
+    proxy.mint_with_caller(
+        owner,
+        Key::Hash(token1.contract_hash()),
+        Key::from(token.self_package_hash()),
+        30000.into(),
+    );
+    token.mint_no_ret(owner, Key::from(token.self_package_hash()));
+    token.pause(owner);
+    // liquidity providers can always exit
+    token.burn_no_ret(owner, user);
+    assert_eq!(token.total_supply(), 1000.into());
+    assert_eq!(token.reserve0(), 1000.into());
+}
+
+#[test]
+fn test_pair_upgrade_keeps_reserves_and_balances() {
+    let (env, proxy, _proxy2, token, owner, factory_hash) = deploy1();
+    let user = env.next_user();
+    let token0 = deploy_token0(&env, proxy.proxy_package_hash());
+    let token1 = deploy_token1(&env, proxy.proxy_package_hash());
+    token.initialize(
+        owner,
+        Key::Hash(token0.package_hash()),
+        Key::Hash(token1.package_hash()),
+        Key::Hash(factory_hash.package_hash()),
+        30,
+        0,
+        0,
+    );
+    proxy.mint_with_caller(
+        owner,
+        Key::Hash(token0.contract_hash()),
+        Key::from(token.self_package_hash()),
+        30000.into(),
+    );
+    proxy.mint_with_caller(
+        owner,
+        Key::Hash(token1.contract_hash()),
+        Key::from(token.self_package_hash()),
+        30000.into(),
+    );
+    token.mint_no_ret(owner, user);
+    let reserve0 = token.reserve0();
+    let reserve1 = token.reserve1();
+    let block_timestamp_last = token.block_timestamp_last();
+    let domain_separator = token.domain_separator();
+    let total_supply = token.total_supply();
+    let owner_balance = token.balance_of(owner);
+    let user_balance = token.balance_of(user);
+    let contract_hash = token.self_contract_hash();
+    assert_eq!(token.storage_version(), 5);
+
+    PAIRInstance::upgrade(&env, NAME, owner);
+
+    assert_ne!(token.self_contract_hash(), contract_hash);
+    assert_eq!(token.storage_version(), 5);
+    assert_eq!(token.reserve0(), reserve0);
+    assert_eq!(token.reserve1(), reserve1);
+    assert_eq!(token.block_timestamp_last(), block_timestamp_last);
+    // permits are bound to the package, so signatures survive the upgrade
+    assert_eq!(token.domain_separator(), domain_separator);
+    assert_eq!(token.total_supply(), total_supply);
+    assert_eq!(token.balance_of(owner), owner_balance);
+    assert_eq!(token.balance_of(user), user_balance);
+    assert_eq!(token.token0(), Key::Hash(token0.package_hash()));
+    assert_eq!(token.token1(), Key::Hash(token1.package_hash()));
+    assert_eq!(token.swap_fee(), 30);
+
+    // the new version serves calls with the migrated state
+    token.sync(owner);
+    assert_eq!(token.reserve0(), reserve0);
+    token.pause(owner);
+    assert!(token.paused());
+}
+
+#[test]
+fn test_pair_upgrade_from_legacy_layout() {
+    let (env, proxy, _proxy2, token, owner, factory_hash) = deploy1();
+    let user = env.next_user();
+    let token0 = deploy_token0(&env, proxy.proxy_package_hash());
+    let token1 = deploy_token1(&env, proxy.proxy_package_hash());
+    let legacy = PAIRInstance::instance(PAIRInstance::new_legacy(
+        &env,
+        "legacy_pair",
+        owner,
+        NAME,
+        SYMBOL,
+        DECIMALS,
+        INIT_TOTAL_SUPPLY_ZERO.into(),
+        token.callee_package_hash(),
+        Key::Hash(factory_hash.package_hash()),
+    ));
+    legacy.legacy_initialize(
+        owner,
+        Key::Hash(token0.package_hash()),
+        Key::Hash(token1.package_hash()),
+        Key::Hash(factory_hash.package_hash()),
+    );
+    mint_into_pair(&proxy, owner, &legacy, &token0, 30000.into());
+    mint_into_pair(&proxy, owner, &legacy, &token1, 30000.into());
+    legacy.mint_no_ret(owner, user);
+    let (reserve0, reserve1, block_timestamp_last) = legacy.legacy_reserves();
+    let domain_separator = legacy.domain_separator();
+    assert_eq!(reserve0, 30000.into());
+    assert_eq!(legacy.legacy_balance_of(user), 29000.into());
+    assert!(!legacy
+        .named_keys()
+        .contains(&String::from("storage_version")));
+
+    PAIRInstance::upgrade(&env, "legacy_pair", owner);
+
+    assert_eq!(legacy.storage_version(), 5);
+    // version 3 packs the reserves and drops their old keys
+    assert_eq!(legacy.reserve0(), reserve0);
+    assert_eq!(legacy.reserve1(), reserve1);
+    assert_eq!(legacy.block_timestamp_last(), block_timestamp_last);
+    assert!(!legacy.named_keys().contains(&String::from("reserve0")));
+    // version 4 binds permits to the package and chain name
+    assert_ne!(legacy.domain_separator(), domain_separator);
+    assert_eq!(legacy.total_supply(), 30000.into());
+    assert_eq!(legacy.swap_fee(), 30);
+    assert_eq!(legacy.curve_type(), 0);
+    assert!(!legacy.paused());
+    assert!(!legacy.fee_on());
+    assert!(legacy.has_role("DEFAULT_ADMIN", owner));
+    assert!(legacy.has_role("PAUSER", owner));
+
+    legacy.migrate_balances(user, vec![user.into()]);
+    assert_eq!(legacy.balance_of(user), 29000.into());
+    assert_eq!(legacy.legacy_balance_of(user), 0.into());
+
+    // liquidity added before the upgrade can be removed with the new version
+    legacy.transfer(user, Key::from(legacy.self_package_hash()), 29000.into());
+    legacy.burn_no_ret(owner, user);
+    assert_eq!(legacy.total_supply(), 1000.into());
+    assert_eq!(legacy.reserve0(), 1000.into());
+    assert_eq!(legacy.reserve1(), 1000.into());
+}
+
+#[test]
+#[should_panic]
+fn test_pair_migrate_outside_upgrade() {
+    let (_env, _proxy, _proxy2, token, owner, _factory_hash) = deploy();
+    let contract_hash = ContractHash::new(token.self_contract_hash().into_hash().unwrap());
+    token.migrate(owner, contract_hash);
+}
+
 #[test]
 fn test_pair_transfer_from() {
     let (env, proxy, proxy2, token, owner, _factory_hash) = deploy();
@@ -639,6 +1496,435 @@ fn test_pair_transfer_from_too_much() {
     proxy2.transfer_from(owner, package_hash.into(), user.into(), amount);
 }
 
+const TRANSFER_WITH_AUTHORIZATION: &str = "TransferWithAuthorization(address from,address to,uint256 value,uint256 validAfter,uint256 validBefore,bytes32 nonce)";
+const RECEIVE_WITH_AUTHORIZATION: &str = "ReceiveWithAuthorization(address from,address to,uint256 value,uint256 validAfter,uint256 validBefore,bytes32 nonce)";
+const VALID_BEFORE: u64 = 4_000_000_000;
+// block time, in milliseconds, authorizations are submitted at
+const NOW: u64 = 1_000_000_000;
+
+/// Signs the digest an authorization of `authorization_type` is checked against, returning the
+/// public key and signature along with the account the LP tokens are moved from.
+#[allow(clippy::too_many_arguments)]
+fn sign_authorization(
+    token: &PAIRInstance,
+    secret_key: &SecretKey,
+    authorization_type: &str,
+    to: Key,
+    value: U256,
+    valid_after: u64,
+    valid_before: u64,
+    nonce: [u8; 32],
+) -> (PublicKey, Signature, Key) {
+    let public_key: PublicKey = secret_key.into();
+    let from = Key::from(public_key.to_account_hash());
+    let data = format!(
+        "{}{}{}{}{}{}{}",
+        hex::encode(keccak256(authorization_type.as_bytes())),
+        from,
+        to,
+        value,
+        valid_after,
+        valid_before,
+        hex::encode(nonce)
+    );
+    let hash = hex::encode(keccak256(data.as_bytes()));
+    let digest = hash_message(format!("{}{}", token.domain_separator(), hash));
+    let signature = crypto::sign(digest, secret_key, &public_key);
+    (public_key, signature, from)
+}
+
+/// Deploys the pair and mints LP tokens to the account of an ed25519 key that signs
+/// authorizations.
+fn deploy_with_authorizer() -> (TestEnv, PAIRInstance, SecretKey, Key) {
+    let (env, _proxy, _proxy2, token, owner, _factory_hash) = deploy();
+    let secret_key = SecretKey::ed25519_from_bytes([42u8; 32]).unwrap();
+    let public_key: PublicKey = (&secret_key).into();
+    let authorizer = Key::from(public_key.to_account_hash());
+    token.erc20_mint(owner, authorizer, 500.into());
+    (env, token, secret_key, authorizer)
+}
+
+#[test]
+fn test_pair_transfer_with_authorization() {
+    let (env, token, secret_key, authorizer) = deploy_with_authorizer();
+    let relayer = env.next_user();
+    let recipient = env.next_user();
+    let nonce = [1u8; 32];
+    let (public_key, signature, from) = sign_authorization(
+        &token,
+        &secret_key,
+        TRANSFER_WITH_AUTHORIZATION,
+        recipient.into(),
+        200.into(),
+        0,
+        VALID_BEFORE,
+        nonce,
+    );
+    assert!(!token.authorization_used(authorizer, nonce));
+    token.transfer_with_authorization(
+        relayer,
+        public_key,
+        signature,
+        from,
+        recipient.into(),
+        200.into(),
+        0,
+        VALID_BEFORE,
+        nonce,
+        NOW,
+    );
+    assert_eq!(token.balance_of(authorizer), 300.into());
+    assert_eq!(token.balance_of(recipient), 200.into());
+    assert!(token.authorization_used(authorizer, nonce));
+    // authorizations leave the permit nonce alone
+    assert_eq!(token.nonce(authorizer), 0.into());
+}
+
+#[test]
+#[should_panic]
+fn test_pair_transfer_with_authorization_replayed() {
+    let (env, token, secret_key, _) = deploy_with_authorizer();
+    let relayer = env.next_user();
+    let recipient = env.next_user();
+    let nonce = [1u8; 32];
+    let (public_key, signature, from) = sign_authorization(
+        &token,
+        &secret_key,
+        TRANSFER_WITH_AUTHORIZATION,
+        recipient.into(),
+        100.into(),
+        0,
+        VALID_BEFORE,
+        nonce,
+    );
+    for _ in 0..2 {
+        token.transfer_with_authorization(
+            relayer,
+            public_key.clone(),
+            signature.clone(),
+            from,
+            recipient.into(),
+            100.into(),
+            0,
+            VALID_BEFORE,
+            nonce,
+            NOW,
+        );
+    }
+}
+
+#[test]
+#[should_panic]
+fn test_pair_transfer_with_authorization_expired() {
+    let (env, token, secret_key, _) = deploy_with_authorizer();
+    let relayer = env.next_user();
+    let recipient = env.next_user();
+    let nonce = [1u8; 32];
+    let valid_before = NOW / 1000;
+    let (public_key, signature, from) = sign_authorization(
+        &token,
+        &secret_key,
+        TRANSFER_WITH_AUTHORIZATION,
+        recipient.into(),
+        100.into(),
+        0,
+        valid_before,
+        nonce,
+    );
+    token.transfer_with_authorization(
+        relayer,
+        public_key,
+        signature,
+        from,
+        recipient.into(),
+        100.into(),
+        0,
+        valid_before,
+        nonce,
+        NOW,
+    );
+}
+
+#[test]
+#[should_panic]
+fn test_pair_transfer_with_authorization_not_yet_valid() {
+    let (env, token, secret_key, _) = deploy_with_authorizer();
+    let relayer = env.next_user();
+    let recipient = env.next_user();
+    let nonce = [1u8; 32];
+    let valid_after = NOW / 1000;
+    let (public_key, signature, from) = sign_authorization(
+        &token,
+        &secret_key,
+        TRANSFER_WITH_AUTHORIZATION,
+        recipient.into(),
+        100.into(),
+        valid_after,
+        VALID_BEFORE,
+        nonce,
+    );
+    token.transfer_with_authorization(
+        relayer,
+        public_key,
+        signature,
+        from,
+        recipient.into(),
+        100.into(),
+        valid_after,
+        VALID_BEFORE,
+        nonce,
+        NOW,
+    );
+}
+
+#[test]
+fn test_pair_receive_with_authorization() {
+    let (env, token, secret_key, authorizer) = deploy_with_authorizer();
+    let recipient = env.next_user();
+    let nonce = [1u8; 32];
+    let (public_key, signature, from) = sign_authorization(
+        &token,
+        &secret_key,
+        RECEIVE_WITH_AUTHORIZATION,
+        recipient.into(),
+        200.into(),
+        0,
+        VALID_BEFORE,
+        nonce,
+    );
+    token.receive_with_authorization(
+        recipient,
+        public_key,
+        signature,
+        from,
+        recipient.into(),
+        200.into(),
+        0,
+        VALID_BEFORE,
+        nonce,
+        NOW,
+    );
+    assert_eq!(token.balance_of(authorizer), 300.into());
+    assert_eq!(token.balance_of(recipient), 200.into());
+    assert!(token.authorization_used(authorizer, nonce));
+}
+
+#[test]
+#[should_panic]
+fn test_pair_receive_with_authorization_by_relayer() {
+    let (env, token, secret_key, _) = deploy_with_authorizer();
+    let relayer = env.next_user();
+    let recipient = env.next_user();
+    let nonce = [1u8; 32];
+    let (public_key, signature, from) = sign_authorization(
+        &token,
+        &secret_key,
+        RECEIVE_WITH_AUTHORIZATION,
+        recipient.into(),
+        200.into(),
+        0,
+        VALID_BEFORE,
+        nonce,
+    );
+    token.receive_with_authorization(
+        relayer,
+        public_key,
+        signature,
+        from,
+        recipient.into(),
+        200.into(),
+        0,
+        VALID_BEFORE,
+        nonce,
+        NOW,
+    );
+}
+
+#[test]
+#[should_panic]
+fn test_pair_receive_with_authorization_expired() {
+    let (env, token, secret_key, _) = deploy_with_authorizer();
+    let recipient = env.next_user();
+    let nonce = [1u8; 32];
+    let valid_before = NOW / 1000;
+    let (public_key, signature, from) = sign_authorization(
+        &token,
+        &secret_key,
+        RECEIVE_WITH_AUTHORIZATION,
+        recipient.into(),
+        200.into(),
+        0,
+        valid_before,
+        nonce,
+    );
+    token.receive_with_authorization(
+        recipient,
+        public_key,
+        signature,
+        from,
+        recipient.into(),
+        200.into(),
+        0,
+        valid_before,
+        nonce,
+        NOW,
+    );
+}
+
+/// Cancels `nonce` of the authorizer with its signature of a `CancelAuthorization`.
+fn cancel_authorization(
+    token: &PAIRInstance,
+    sender: AccountHash,
+    secret_key: &SecretKey,
+    authorizer: Key,
+    nonce: [u8; 32],
+) {
+    let public_key: PublicKey = secret_key.into();
+    let data = format!(
+        "{}{}{}",
+        hex::encode(keccak256(
+            "CancelAuthorization(address authorizer,bytes32 nonce)".as_bytes()
+        )),
+        authorizer,
+        hex::encode(nonce)
+    );
+    let hash = hex::encode(keccak256(data.as_bytes()));
+    let digest = hash_message(format!("{}{}", token.domain_separator(), hash));
+    let signature = crypto::sign(digest, secret_key, &public_key);
+    token.cancel_authorization(sender, public_key, signature, authorizer, nonce);
+}
+
+#[test]
+fn test_pair_cancel_authorization() {
+    let (env, token, secret_key, authorizer) = deploy_with_authorizer();
+    let relayer = env.next_user();
+    let nonce = [1u8; 32];
+    cancel_authorization(&token, relayer, &secret_key, authorizer, nonce);
+    assert!(token.authorization_used(authorizer, nonce));
+    assert_eq!(token.balance_of(authorizer), 500.into());
+}
+
+#[test]
+#[should_panic]
+fn test_pair_cancel_authorization_twice() {
+    let (env, token, secret_key, authorizer) = deploy_with_authorizer();
+    let relayer = env.next_user();
+    let nonce = [1u8; 32];
+    cancel_authorization(&token, relayer, &secret_key, authorizer, nonce);
+    cancel_authorization(&token, relayer, &secret_key, authorizer, nonce);
+}
+
+#[test]
+#[should_panic]
+fn test_pair_transfer_with_canceled_authorization() {
+    let (env, token, secret_key, authorizer) = deploy_with_authorizer();
+    let relayer = env.next_user();
+    let recipient = env.next_user();
+    let nonce = [1u8; 32];
+    let (public_key, signature, from) = sign_authorization(
+        &token,
+        &secret_key,
+        TRANSFER_WITH_AUTHORIZATION,
+        recipient.into(),
+        100.into(),
+        0,
+        VALID_BEFORE,
+        nonce,
+    );
+    cancel_authorization(&token, relayer, &secret_key, authorizer, nonce);
+    token.transfer_with_authorization(
+        relayer,
+        public_key,
+        signature,
+        from,
+        recipient.into(),
+        100.into(),
+        0,
+        VALID_BEFORE,
+        nonce,
+        NOW,
+    );
+}
+
+#[test]
+fn test_pair_delegate() {
+    let (env, proxy, _proxy2, token, owner, _factory_hash) = deploy();
+    let user = env.next_user();
+
+    assert_eq!(token.votes(owner), 0.into());
+    token.delegate(owner, owner, NOW);
+    assert_eq!(token.delegates(owner), Some(owner.into()));
+    assert_eq!(token.votes(owner), INIT_TOTAL_SUPPLY.into());
+    proxy.get_votes_fn(owner, owner);
+    assert_eq!(proxy.get_votes_res(), INIT_TOTAL_SUPPLY.into());
+
+    // LP tokens that were never delegated carry no votes
+    token.transfer_at(owner, user, 300.into(), NOW + 1000);
+    assert_eq!(token.votes(owner), (INIT_TOTAL_SUPPLY - 300).into());
+    assert_eq!(token.votes(user), 0.into());
+
+    token.delegate(user, owner, NOW + 2000);
+    assert_eq!(token.votes(owner), INIT_TOTAL_SUPPLY.into());
+    assert_eq!(token.checkpoints(owner).len(), 3);
+}
+
+#[test]
+fn test_pair_get_past_votes() {
+    let (env, proxy, _proxy2, token, owner, _factory_hash) = deploy();
+    let user = env.next_user();
+
+    token.delegate(owner, owner, NOW);
+    token.transfer_at(owner, user, 300.into(), NOW + 1000);
+    token.delegate(user, user, NOW + 2000);
+
+    proxy.get_past_votes_fn(owner, owner, NOW - 1, NOW + 3000);
+    assert_eq!(proxy.get_past_votes_res(), 0.into());
+    proxy.get_past_votes_fn(owner, owner, NOW, NOW + 3000);
+    assert_eq!(proxy.get_past_votes_res(), INIT_TOTAL_SUPPLY.into());
+    proxy.get_past_votes_fn(owner, owner, NOW + 999, NOW + 3000);
+    assert_eq!(proxy.get_past_votes_res(), INIT_TOTAL_SUPPLY.into());
+    proxy.get_past_votes_fn(owner, owner, NOW + 1000, NOW + 3000);
+    assert_eq!(proxy.get_past_votes_res(), (INIT_TOTAL_SUPPLY - 300).into());
+    proxy.get_past_votes_fn(owner, user, NOW + 1999, NOW + 3000);
+    assert_eq!(proxy.get_past_votes_res(), 0.into());
+    proxy.get_past_votes_fn(owner, user, NOW + 2000, NOW + 3000);
+    assert_eq!(proxy.get_past_votes_res(), 300.into());
+}
+
+#[test]
+#[should_panic]
+fn test_pair_get_past_votes_at_current_time() {
+    let (_env, proxy, _proxy2, token, owner, _factory_hash) = deploy();
+
+    token.delegate(owner, owner, NOW);
+    proxy.get_past_votes_fn(owner, owner, NOW, NOW);
+}
+
+#[test]
+fn test_pair_liquidity_moves_votes() {
+    let (env, proxy, _proxy2, token, owner, factory) = deploy1();
+    let token0 = deploy_token0(&env, proxy.proxy_package_hash());
+    let token1 = deploy_token1(&env, proxy.proxy_package_hash());
+    create_pair(owner, &factory, &token, &token0, &token1);
+    // mints and burns run at the default block time, so the delegation does too
+    token.delegate(owner, owner, 0);
+
+    mint_into_pair(&proxy, owner, &token, &token0, 10000.into());
+    mint_into_pair(&proxy, owner, &token, &token1, 10000.into());
+    token.mint_no_ret(owner, owner);
+    // sqrt(10000 * 10000) less the 1000 locked for good
+    assert_eq!(token.votes(owner), 9000.into());
+
+    // removing liquidity sends the LP tokens to the pair, which has no delegate, and burns
+    // them there
+    token.transfer(owner, Key::from(token.self_package_hash()), 4000.into());
+    assert_eq!(token.votes(owner), 5000.into());
+    token.burn_no_ret(owner, owner);
+    assert_eq!(token.votes(owner), 5000.into());
+    assert_eq!(token.balance_of(owner), 5000.into());
+    assert_eq!(token.checkpoints(owner).len(), 1);
+}
+
 #[test]
 #[should_panic]
 fn test_calling_construction() {
@@ -0,0 +1,211 @@
+use casper_types::U256;
+use pair::stable_swap::{compute_d, get_amount_in, get_amount_out, invariant_holds};
+
+const CASES: usize = 500;
+const SWAPS_PER_CASE: usize = 20;
+const AMPLIFICATIONS: [u64; 6] = [1, 2, 10, 100, 1000, 1_000_000];
+const SWAP_FEES: [u32; 4] = [1, 4, 30, 100];
+
+/// xorshift64* so every run checks the same cases and failures reproduce.
+struct Rng(u64);
+
+impl Rng {
+    fn next_u64(&mut self) -> u64 {
+        self.0 ^= self.0 >> 12;
+        self.0 ^= self.0 << 25;
+        self.0 ^= self.0 >> 27;
+        self.0.wrapping_mul(0x2545_f491_4f6c_dd1d)
+    }
+
+    fn below(&mut self, max: u128) -> u128 {
+        let value = (u128::from(self.next_u64()) << 64) | u128::from(self.next_u64());
+        value % max
+    }
+
+    fn pick<T: Copy>(&mut self, values: &[T]) -> T {
+        values[(self.next_u64() % values.len() as u64) as usize]
+    }
+
+    /// A reserve between 1,000 and 10^digits, with digits up to 30.
+    fn reserve(&mut self) -> U256 {
+        let digits = 4 + (self.next_u64() % 27) as u32;
+        U256::from(1000 + self.below(10u128.pow(digits)))
+    }
+}
+
+fn scaled(value: U256) -> U256 {
+    value * 10000
+}
+
+/// Checks `amount_out` for `amount_in` the way `PAIR::swap` does and returns
+/// the new reserves if the pair would accept it.
+fn swap(
+    reserve_in: U256,
+    reserve_out: U256,
+    amount_in: U256,
+    amount_out: U256,
+    amplification: u64,
+    swap_fee: u32,
+) -> Option<(U256, U256)> {
+    let balance_in = reserve_in + amount_in;
+    let balance_out = reserve_out - amount_out;
+    let accepted = invariant_holds(
+        scaled(balance_in) - amount_in * swap_fee,
+        scaled(balance_out),
+        scaled(reserve_in),
+        scaled(reserve_out),
+        amplification,
+    )
+    .unwrap();
+    if accepted {
+        Some((balance_in, balance_out))
+    } else {
+        None
+    }
+}
+
+#[test]
+fn test_balanced_invariant_is_sum_of_reserves() {
+    let mut rng = Rng(0x5eed_0001);
+    for _ in 0..CASES {
+        let reserve = rng.reserve();
+        let amplification = rng.pick(&AMPLIFICATIONS);
+        let d = compute_d(reserve, reserve, amplification).unwrap();
+        assert!(d + 1 >= reserve * 2 && d <= reserve * 2 + 1);
+    }
+}
+
+#[test]
+fn test_invariant_grows_with_reserves() {
+    let mut rng = Rng(0x5eed_0002);
+    for _ in 0..CASES {
+        let reserve0 = rng.reserve();
+        let reserve1 = rng.reserve();
+        let amplification = rng.pick(&AMPLIFICATIONS);
+        let added = reserve0 / 100 + 1 + rng.below(reserve0.as_u128());
+        assert!(
+            compute_d(reserve0 + added, reserve1, amplification).unwrap()
+                >= compute_d(reserve0, reserve1, amplification).unwrap()
+        );
+    }
+}
+
+#[test]
+fn test_quoted_swaps_never_decrease_invariant() {
+    let mut rng = Rng(0x5eed_0003);
+    for _ in 0..CASES {
+        let mut reserve0 = rng.reserve();
+        let mut reserve1 = rng.reserve();
+        let amplification = rng.pick(&AMPLIFICATIONS);
+        let swap_fee = rng.pick(&SWAP_FEES);
+        for _ in 0..SWAPS_PER_CASE {
+            let zero_for_one = rng.next_u64() % 2 == 0;
+            let (reserve_in, reserve_out) = if zero_for_one {
+                (reserve0, reserve1)
+            } else {
+                (reserve1, reserve0)
+            };
+            let amount_in = U256::from(1 + rng.below(reserve_out.as_u128()));
+            let amount_out =
+                get_amount_out(amount_in, reserve_in, reserve_out, amplification, swap_fee)
+                    .unwrap();
+            if amount_out.is_zero() {
+                continue;
+            }
+            let d_before = compute_d(reserve0, reserve1, amplification).unwrap();
+            let (balance_in, balance_out) = swap(
+                reserve_in,
+                reserve_out,
+                amount_in,
+                amount_out,
+                amplification,
+                swap_fee,
+            )
+            .expect("quoted swap rejected");
+            if zero_for_one {
+                reserve0 = balance_in;
+                reserve1 = balance_out;
+            } else {
+                reserve0 = balance_out;
+                reserve1 = balance_in;
+            }
+            assert!(compute_d(reserve0, reserve1, amplification).unwrap() >= d_before);
+        }
+    }
+}
+
+#[test]
+fn test_accepted_swaps_never_decrease_invariant() {
+    let mut rng = Rng(0x5eed_0004);
+    for _ in 0..CASES {
+        let reserve_in = rng.reserve();
+        let reserve_out = rng.reserve();
+        let amplification = rng.pick(&AMPLIFICATIONS);
+        let swap_fee = rng.pick(&SWAP_FEES);
+        let amount_in = U256::from(1 + rng.below(reserve_in.as_u128() * 2));
+        let quote =
+            get_amount_out(amount_in, reserve_in, reserve_out, amplification, swap_fee).unwrap();
+        // ask for up to a few units more than quoted so rejected swaps are covered
+        let amount_out = (quote + rng.below(4)).min(reserve_out - 1);
+        if amount_out.is_zero() {
+            continue;
+        }
+        if let Some((balance_in, balance_out)) = swap(
+            reserve_in,
+            reserve_out,
+            amount_in,
+            amount_out,
+            amplification,
+            swap_fee,
+        ) {
+            assert!(
+                compute_d(balance_in, balance_out, amplification).unwrap()
+                    >= compute_d(reserve_in, reserve_out, amplification).unwrap()
+            );
+        }
+    }
+}
+
+#[test]
+fn test_swap_output_is_bounded_by_reserve() {
+    let mut rng = Rng(0x5eed_0005);
+    for _ in 0..CASES {
+        let reserve_in = rng.reserve();
+        let reserve_out = rng.reserve();
+        let amplification = rng.pick(&AMPLIFICATIONS);
+        let swap_fee = rng.pick(&SWAP_FEES);
+        let amount_in = U256::from(1 + rng.below(reserve_in.as_u128() * 100));
+        let amount_out =
+            get_amount_out(amount_in, reserve_in, reserve_out, amplification, swap_fee).unwrap();
+        assert!(amount_out < reserve_out);
+    }
+}
+
+#[test]
+fn test_amount_in_quotes_are_accepted() {
+    let mut rng = Rng(0x5eed_0006);
+    for _ in 0..CASES {
+        let reserve_in = rng.reserve();
+        let reserve_out = rng.reserve();
+        let amplification = rng.pick(&AMPLIFICATIONS);
+        let swap_fee = rng.pick(&SWAP_FEES);
+        let amount_out = U256::from(1 + rng.below(reserve_out.as_u128() - 1));
+        let amount_in =
+            get_amount_in(amount_out, reserve_in, reserve_out, amplification, swap_fee).unwrap();
+        swap(
+            reserve_in,
+            reserve_out,
+            amount_in,
+            amount_out,
+            amplification,
+            swap_fee,
+        )
+        .expect("quoted swap rejected");
+        // paying in the quote buys back at least what was asked for
+        assert!(
+            get_amount_out(amount_in, reserve_in, reserve_out, amplification, swap_fee).unwrap()
+                + 3
+                >= amount_out
+        );
+    }
+}
@@ -3,7 +3,7 @@
 
 extern crate alloc;
 
-use alloc::{boxed::Box, collections::BTreeSet, format, string::String, vec};
+use alloc::{boxed::Box, collections::BTreeSet, format, string::String, vec, vec::Vec};
 
 use casper_contract::{
     contract_api::{runtime, storage},
@@ -11,13 +11,14 @@ use casper_contract::{
 };
 use casper_types::{
     runtime_args, CLType, CLTyped, CLValue, ContractHash, ContractPackageHash, EntryPoint,
-    EntryPointAccess, EntryPointType, EntryPoints, Group, Key, Parameter, RuntimeArgs, URef, U128,
-    U256,
+    EntryPointAccess, EntryPointType, EntryPoints, Group, Key, Parameter, PublicKey, RuntimeArgs,
+    Signature, URef, U128, U256,
+};
+use contract_utils::{
+    AccessControl, CEP18Event, ContractContext, OnChainContractStorage, Votes, CEP18, MINTER_ROLE,
 };
-use contract_utils::{ContractContext, OnChainContractStorage};
-use hex::encode;
 use pair::{self, PAIR};
-use renvm_sig::keccak256;
+use uniswap_types::PairState;
 
 #[derive(Default)]
 struct Pair(OnChainContractStorage);
@@ -28,6 +29,20 @@ impl ContractContext<OnChainContractStorage> for Pair {
     }
 }
 
+impl AccessControl<OnChainContractStorage> for Pair {}
+
+impl CEP18<OnChainContractStorage> for Pair {
+    fn emit_cep18_event(&mut self, cep18_event: &CEP18Event) {
+        self.emit_token_event(cep18_event);
+    }
+
+    fn after_transfer(&mut self, from: Key, to: Key, amount: U256) {
+        Votes::move_voting_power(self, from, to, amount);
+    }
+}
+
+impl Votes<OnChainContractStorage> for Pair {}
+
 impl PAIR<OnChainContractStorage> for Pair {}
 
 impl Pair {
@@ -74,7 +89,7 @@ impl Pair {
             callee_package_hash,
             lock,
         );
-        PAIR::mint(self, self.get_caller(), initial_supply);
+        CEP18::mint(self, self.get_caller(), initial_supply);
     }
 }
 
@@ -122,6 +137,22 @@ fn constructor() {
     );
 }
 
+/// This function is to bring the storage of an upgraded Pair up to date, only callable by the installer while upgrading
+///
+/// # Parameters
+///
+/// * `contract_hash` - A ContractHash that holds the hash of the new contract version
+///
+/// * `chain_name` - A String that holds the name of the chain permits are signed for
+///
+
+#[no_mangle]
+fn migrate() {
+    let contract_hash: ContractHash = runtime::get_named_arg("contract_hash");
+    let chain_name: String = runtime::get_named_arg("chain_name");
+    PAIR::migrate(&mut Pair::default(), Key::from(contract_hash), chain_name);
+}
+
 /// This function is to transfer tokens against the address that user provided
 ///
 /// # Parameters
@@ -139,6 +170,26 @@ fn transfer() {
     runtime::ret(CLValue::from_t(ret).unwrap_or_revert());
 }
 
+/// This function is to transfer tokens to a recipient and, if it is a contract, call its `on_token_received` entry point in the same deploy
+///
+/// # Parameters
+///
+/// * `recipient` - A Key that holds the account address or contract package hash of the recipient
+///
+/// * `amount` - A U256 that holds the amount for transfer
+///
+/// * `data` - A String that holds data passed on to `on_token_received`
+///
+
+#[no_mangle]
+fn transfer_and_call() {
+    let recipient: Key = runtime::get_named_arg("recipient");
+    let amount: U256 = runtime::get_named_arg("amount");
+    let data: String = runtime::get_named_arg("data");
+    let ret = Pair::default().transfer_and_call(recipient, amount, data);
+    runtime::ret(CLValue::from_t(ret).unwrap_or_revert());
+}
+
 /// This function is to transfer tokens against the address that has been approved before by owner
 ///
 /// # Parameters
@@ -185,6 +236,53 @@ fn sync() {
     Pair::default().sync();
 }
 
+/// This function is to stop swaps, flash swaps and mints on the Pair, only callable by the factory or a PAUSER
+///
+
+#[no_mangle]
+fn pause() {
+    Pair::default().pause();
+}
+
+/// This function is to resume swaps, flash swaps and mints on the Pair, only callable by the factory or a PAUSER
+///
+
+#[no_mangle]
+fn unpause() {
+    Pair::default().unpause();
+}
+
+/// This function is to return whether the Pair is paused, either on its own or by the factory for all pairs
+///
+
+#[no_mangle]
+fn paused() {
+    let ret: bool = Pair::default().paused();
+    runtime::ret(CLValue::from_t(ret).unwrap_or_revert());
+}
+
+/// This function is to cache whether the Factory has a fee_to, only callable by the factory whenever set_fee_to turns the protocol fee on or off
+///
+/// # Parameters
+///
+/// * `fee_on` - A bool that holds whether the protocol fee is on
+///
+
+#[no_mangle]
+fn set_fee_on() {
+    let fee_on: bool = runtime::get_named_arg("fee_on");
+    Pair::default().set_fee_on(fee_on);
+}
+
+/// This function is to return whether the protocol fee is on, as last pushed by the factory
+///
+
+#[no_mangle]
+fn fee_on() {
+    let ret: bool = Pair::default().get_fee_on();
+    runtime::ret(CLValue::from_t(ret).unwrap_or_revert());
+}
+
 /// this low-level function should be called from a contract which performs important safety checks
 
 #[no_mangle]
@@ -201,9 +299,9 @@ fn swap() {
 ///
 /// # Parameters
 ///
-/// * `public_key` - A string slice that holds the public key of the meta transaction signer,  Subscriber have to get it from running cryptoxide project externally.
+/// * `public` - A PublicKey that holds the ed25519 or secp256k1 key of the owner
 ///
-/// * `signature` - A string slice that holds the signature of the meta transaction,  Subscriber have to get it from running cryptoxide project externally.
+/// * `signature` - A Signature that holds the owner's signature of the permit digest
 ///
 /// * `owner` - A Key that holds the account address of the owner
 ///
@@ -216,8 +314,8 @@ fn swap() {
 
 #[no_mangle]
 fn permit() {
-    let public_key: String = runtime::get_named_arg("public");
-    let signature: String = runtime::get_named_arg("signature");
+    let public_key: PublicKey = runtime::get_named_arg("public");
+    let signature: Signature = runtime::get_named_arg("signature");
     let owner: Key = runtime::get_named_arg("owner");
     let spender: Key = runtime::get_named_arg("spender");
     let value: U256 = runtime::get_named_arg("value");
@@ -225,6 +323,211 @@ fn permit() {
     Pair::default().permit(public_key, signature, owner, spender, value, deadline);
 }
 
+/// This function is to move tokens from one account to another with the sender's signed authorization, callable by anyone
+///
+/// # Parameters
+///
+/// * `public` - A PublicKey that holds the ed25519 or secp256k1 key of `from`
+///
+/// * `signature` - A Signature that holds the signature of the authorization
+///
+/// * `from` - A Key that holds the account address LP tokens are moved from
+///
+/// * `to` - A Key that holds the account address LP tokens are moved to
+///
+/// * `value` - A U256 that holds the amount to move
+///
+/// * `valid_after` - A u64 that holds the time in seconds after which the authorization is valid
+///
+/// * `valid_before` - A u64 that holds the time in seconds before which the authorization is valid
+///
+/// * `nonce` - A 32 byte array that holds the unique nonce of the authorization
+///
+
+#[no_mangle]
+fn transfer_with_authorization() {
+    let public_key: PublicKey = runtime::get_named_arg("public");
+    let signature: Signature = runtime::get_named_arg("signature");
+    let from: Key = runtime::get_named_arg("from");
+    let to: Key = runtime::get_named_arg("to");
+    let value: U256 = runtime::get_named_arg("value");
+    let valid_after: u64 = runtime::get_named_arg("valid_after");
+    let valid_before: u64 = runtime::get_named_arg("valid_before");
+    let nonce: [u8; 32] = runtime::get_named_arg("nonce");
+    Pair::default().transfer_with_authorization(
+        public_key,
+        signature,
+        from,
+        to,
+        value,
+        valid_after,
+        valid_before,
+        nonce,
+    );
+}
+
+/// This function is to move tokens with the sender's signed authorization, only callable by the recipient
+///
+/// # Parameters
+///
+/// * `public` - A PublicKey that holds the ed25519 or secp256k1 key of `from`
+///
+/// * `signature` - A Signature that holds the signature of the authorization
+///
+/// * `from` - A Key that holds the account address LP tokens are moved from
+///
+/// * `to` - A Key that holds the account address LP tokens are moved to, which must be the caller
+///
+/// * `value` - A U256 that holds the amount to move
+///
+/// * `valid_after` - A u64 that holds the time in seconds after which the authorization is valid
+///
+/// * `valid_before` - A u64 that holds the time in seconds before which the authorization is valid
+///
+/// * `nonce` - A 32 byte array that holds the unique nonce of the authorization
+///
+
+#[no_mangle]
+fn receive_with_authorization() {
+    let public_key: PublicKey = runtime::get_named_arg("public");
+    let signature: Signature = runtime::get_named_arg("signature");
+    let from: Key = runtime::get_named_arg("from");
+    let to: Key = runtime::get_named_arg("to");
+    let value: U256 = runtime::get_named_arg("value");
+    let valid_after: u64 = runtime::get_named_arg("valid_after");
+    let valid_before: u64 = runtime::get_named_arg("valid_before");
+    let nonce: [u8; 32] = runtime::get_named_arg("nonce");
+    Pair::default().receive_with_authorization(
+        public_key,
+        signature,
+        from,
+        to,
+        value,
+        valid_after,
+        valid_before,
+        nonce,
+    );
+}
+
+/// This function is to cancel an authorization that has not been used yet
+///
+/// # Parameters
+///
+/// * `public` - A PublicKey that holds the ed25519 or secp256k1 key of `authorizer`
+///
+/// * `signature` - A Signature that holds the signature of the cancellation
+///
+/// * `authorizer` - A Key that holds the account address that signed the authorization
+///
+/// * `nonce` - A 32 byte array that holds the nonce of the authorization to cancel
+///
+
+#[no_mangle]
+fn cancel_authorization() {
+    let public_key: PublicKey = runtime::get_named_arg("public");
+    let signature: Signature = runtime::get_named_arg("signature");
+    let authorizer: Key = runtime::get_named_arg("authorizer");
+    let nonce: [u8; 32] = runtime::get_named_arg("nonce");
+    Pair::default().cancel_authorization(public_key, signature, authorizer, nonce);
+}
+
+/// This function is to return whether an authorizer has used or canceled a nonce
+///
+/// # Parameters
+///
+/// * `authorizer` - A Key that holds the account address of the authorizer
+///
+/// * `nonce` - A 32 byte array that holds the nonce
+///
+
+#[no_mangle]
+fn authorization_state() {
+    let authorizer: Key = runtime::get_named_arg("authorizer");
+    let nonce: [u8; 32] = runtime::get_named_arg("nonce");
+    let ret: bool = Pair::default().authorization_state(authorizer, nonce);
+    runtime::ret(CLValue::from_t(ret).unwrap_or_revert());
+}
+
+/// This function is to delegate the caller's votes to delegatee
+///
+/// # Parameters
+///
+/// * `delegatee` - A Key that holds the account address that will vote with the caller's balance
+///
+
+#[no_mangle]
+fn delegate() {
+    let delegatee: Key = runtime::get_named_arg("delegatee");
+    Pair::default().delegate(delegatee);
+}
+
+/// This function is to delegate the votes of the signer to delegatee with their signed delegation
+///
+/// # Parameters
+///
+/// * `public` - A PublicKey that holds the ed25519 or secp256k1 key of the delegator
+///
+/// * `signature` - A Signature that holds the signature of the delegation
+///
+/// * `delegatee` - A Key that holds the account address that will vote with the delegator's balance
+///
+/// * `expiry` - A u64 that holds the time in seconds after which the signature is no longer valid
+///
+
+#[no_mangle]
+fn delegate_by_sig() {
+    let public_key: PublicKey = runtime::get_named_arg("public");
+    let signature: Signature = runtime::get_named_arg("signature");
+    let delegatee: Key = runtime::get_named_arg("delegatee");
+    let expiry: u64 = runtime::get_named_arg("expiry");
+    Pair::default().delegate_by_sig(public_key, signature, delegatee, expiry);
+}
+
+/// This function is to return the account an account has delegated its votes to
+///
+/// # Parameters
+///
+/// * `account` - A Key that holds the account address of the delegator
+///
+
+#[no_mangle]
+fn delegates() {
+    let account: Key = runtime::get_named_arg("account");
+    let ret: Key = Pair::default().delegates(account);
+    runtime::ret(CLValue::from_t(ret).unwrap_or_revert());
+}
+
+/// This function is to return the current votes of an account
+///
+/// # Parameters
+///
+/// * `account` - A Key that holds the account address of the delegate
+///
+
+#[no_mangle]
+fn get_votes() {
+    let account: Key = runtime::get_named_arg("account");
+    let ret: U256 = Pair::default().get_votes(account);
+    runtime::ret(CLValue::from_t(ret).unwrap_or_revert());
+}
+
+/// This function is to return the votes an account had at a past block time
+///
+/// # Parameters
+///
+/// * `account` - A Key that holds the account address of the delegate
+///
+/// * `timestamp` - A u64 that holds the block time in milliseconds, which must be in the past
+///
+
+#[no_mangle]
+fn get_past_votes() {
+    let account: Key = runtime::get_named_arg("account");
+    let timestamp: u64 = runtime::get_named_arg("timestamp");
+    let ret: U256 = Pair::default().get_past_votes(account, timestamp);
+    runtime::ret(CLValue::from_t(ret).unwrap_or_revert());
+}
+
 /// This function is to approve tokens against the address that user provided so the address can transfer on his behalf
 ///
 /// # Parameters
@@ -345,7 +648,7 @@ fn mint_no_ret() {
     let _liquidity: U256 = Pair::default().mint_helper(to);
 }
 
-/// This function is to mint token against the address that user provided with the amount
+/// This function is to mint token against the address that user provided with the amount, only callable by a MINTER
 ///
 /// # Parameters
 ///
@@ -358,7 +661,9 @@ fn mint_no_ret() {
 fn erc20_mint() {
     let to: Key = runtime::get_named_arg("to");
     let amount: U256 = runtime::get_named_arg("amount");
-    Pair::default().mint(to, amount);
+    let mut pair = Pair::default();
+    pair.assert_role(MINTER_ROLE);
+    pair.mint(to, amount);
 }
 
 /// This function is to burn token against the address that user provided
@@ -402,6 +707,19 @@ fn balance_of() {
     runtime::ret(CLValue::from_t(ret).unwrap_or_revert());
 }
 
+/// This function is to move balances written before the CEP-18 item keys to them
+///
+/// # Parameters
+///
+/// * `owners` - A Vec<Key> that holds the account addresses whose balances are moved
+///
+
+#[no_mangle]
+fn migrate_balances() {
+    let owners: Vec<Key> = runtime::get_named_arg("owners");
+    Pair::default().migrate_balances(owners);
+}
+
 /// This function is to get the reserves like Reserve0, Reserve1 and Block Time Stamp
 ///
 
@@ -412,6 +730,15 @@ fn get_reserves() {
     runtime::ret(CLValue::from_t((reserve0, reserve1, block_timestamp_last)).unwrap_or_revert());
 }
 
+/// This function is to get a snapshot of the Pair, its tokens, reserves, total supply, fees, cumulative prices and factory hash, as a PairState
+///
+
+#[no_mangle]
+fn get_state() {
+    let ret: PairState = Pair::default().get_state();
+    runtime::ret(CLValue::from_t(ret).unwrap_or_revert());
+}
+
 /// This function is to get a nonce of a owner provided by user
 ///
 /// # Parameters
@@ -443,6 +770,33 @@ fn allowance() {
     runtime::ret(CLValue::from_t(ret).unwrap_or_revert());
 }
 
+/// This function is to get a Name
+///
+
+#[no_mangle]
+fn name() {
+    let ret: String = Pair::default().name();
+    runtime::ret(CLValue::from_t(ret).unwrap_or_revert());
+}
+
+/// This function is to get a Symbol
+///
+
+#[no_mangle]
+fn symbol() {
+    let ret: String = Pair::default().symbol();
+    runtime::ret(CLValue::from_t(ret).unwrap_or_revert());
+}
+
+/// This function is to get a Decimals
+///
+
+#[no_mangle]
+fn decimals() {
+    let ret: u8 = Pair::default().decimals();
+    runtime::ret(CLValue::from_t(ret).unwrap_or_revert());
+}
+
 /// This function is to get a Total Supply
 ///
 
@@ -481,7 +835,15 @@ fn token1() {
 
 /// This method will be called once by the factory at time of create_pair() method
 ///
-/// This function is to Initialize Pair Contract with Token0 and Token1 and called in Factory Contract method create_pair()
+/// This function is to Initialize Pair Contract with Token0, Token1, the swap fee of its fee tier and its curve and called in Factory Contract method create_pair()
+///
+/// # Parameters
+///
+/// * `swap_fee` - A u32 that holds the swap fee in basis points
+///
+/// * `curve_type` - A u8 that holds the curve of the pair, 0 for constant product and 1 for StableSwap
+///
+/// * `amplification` - A u64 that holds the StableSwap amplification coefficient, 0 for constant product pairs
 ///
 
 #[no_mangle]
@@ -489,11 +851,99 @@ pub extern "C" fn initialize() {
     let token0: Key = runtime::get_named_arg("token0");
     let token1: Key = runtime::get_named_arg("token1");
     let factory_hash: Key = runtime::get_named_arg("factory_hash");
+    let swap_fee: u32 = runtime::get_named_arg("swap_fee");
+    let curve_type: u8 = runtime::get_named_arg("curve_type");
+    let amplification: u64 = runtime::get_named_arg("amplification");
+
+    Pair::default().initialize(
+        token0,
+        token1,
+        factory_hash,
+        swap_fee,
+        curve_type,
+        amplification,
+    );
+}
+
+/// This function is to return the swap fee of the Pair in basis points
+///
+
+#[no_mangle]
+fn swap_fee() {
+    let ret: u32 = Pair::default().get_swap_fee();
+    runtime::ret(CLValue::from_t(ret).unwrap_or_revert());
+}
+
+/// This function is to return the curve of the Pair, 0 for constant product and 1 for StableSwap
+///
+
+#[no_mangle]
+fn curve_type() {
+    let ret: u8 = Pair::default().get_curve_type();
+    runtime::ret(CLValue::from_t(ret).unwrap_or_revert());
+}
+
+/// This function is to return the StableSwap amplification coefficient of the Pair
+///
+
+#[no_mangle]
+fn amplification() {
+    let ret: u64 = Pair::default().get_amplification();
+    runtime::ret(CLValue::from_t(ret).unwrap_or_revert());
+}
+
+/// This function is to return how much of the other token a swap pays out, at the swap fee and curve of the Pair
+///
+/// # Parameters
+///
+/// * `amount_in` - A U256 that holds the amount of token_in paid into the Pair
+///
+/// * `token_in` - A Key that holds the package hash of the token paid in, Token0 or Token1
+///
+
+#[no_mangle]
+fn get_amount_out() {
+    let amount_in: U256 = runtime::get_named_arg("amount_in");
+    let token_in: Key = runtime::get_named_arg("token_in");
+    let ret: U256 = Pair::default().get_amount_out(amount_in, token_in);
+    runtime::ret(CLValue::from_t(ret).unwrap_or_revert());
+}
+
+/// This function is to return how much of the other token a swap has to pay in, at the swap fee and curve of the Pair
+///
+/// # Parameters
+///
+/// * `amount_out` - A U256 that holds the amount of token_out taken out of the Pair
+///
+/// * `token_out` - A Key that holds the package hash of the token taken out, Token0 or Token1
+///
+
+#[no_mangle]
+fn get_amount_in() {
+    let amount_out: U256 = runtime::get_named_arg("amount_out");
+    let token_out: Key = runtime::get_named_arg("token_out");
+    let ret: U256 = Pair::default().get_amount_in(amount_out, token_out);
+    runtime::ret(CLValue::from_t(ret).unwrap_or_revert());
+}
+
+/// This function is to return how much of the other token matches an amount at the current reserves of the Pair
+///
+/// # Parameters
+///
+/// * `amount` - A U256 that holds the amount of token
+///
+/// * `token` - A Key that holds the package hash of the token, Token0 or Token1
+///
 
-    Pair::default().initialize(token0, token1, factory_hash);
+#[no_mangle]
+fn quote() {
+    let amount: U256 = runtime::get_named_arg("amount");
+    let token: Key = runtime::get_named_arg("token");
+    let ret: U256 = Pair::default().quote(amount, token);
+    runtime::ret(CLValue::from_t(ret).unwrap_or_revert());
 }
 
-/// This function is to set a treasury_fee
+/// This function is to set a treasury_fee, only callable by a FEE_SETTER
 ///
 /// # Parameters
 ///
@@ -506,6 +956,71 @@ pub extern "C" fn set_treasury_fee_percent() {
     Pair::default().set_treasury_fee_percent(treasury_fee);
 }
 
+/// This function is to grant a role to an account, only callable by holders of the role's admin role
+///
+/// # Parameters
+///
+/// * `role` - A String that holds the name of the role
+///
+/// * `account` - A Key that holds the account address or package hash receiving the role
+///
+
+#[no_mangle]
+fn grant_role() {
+    let role: String = runtime::get_named_arg("role");
+    let account: Key = runtime::get_named_arg("account");
+    Pair::default().grant_role(role, account);
+}
+
+/// This function is to revoke a role from an account, only callable by holders of the role's admin role
+///
+/// # Parameters
+///
+/// * `role` - A String that holds the name of the role
+///
+/// * `account` - A Key that holds the account address or package hash losing the role
+///
+
+#[no_mangle]
+fn revoke_role() {
+    let role: String = runtime::get_named_arg("role");
+    let account: Key = runtime::get_named_arg("account");
+    Pair::default().revoke_role(role, account);
+}
+
+/// This function is to give up a role held by the caller
+///
+/// # Parameters
+///
+/// * `role` - A String that holds the name of the role
+///
+/// * `account` - A Key that must be equal to the caller
+///
+
+#[no_mangle]
+fn renounce_role() {
+    let role: String = runtime::get_named_arg("role");
+    let account: Key = runtime::get_named_arg("account");
+    Pair::default().renounce_role(role, account);
+}
+
+/// This function is to check whether an account holds a role
+///
+/// # Parameters
+///
+/// * `role` - A String that holds the name of the role
+///
+/// * `account` - A Key that holds the account address or package hash
+///
+
+#[no_mangle]
+fn has_role() {
+    let role: String = runtime::get_named_arg("role");
+    let account: Key = runtime::get_named_arg("account");
+    let ret: bool = Pair::default().has_role(&role, account);
+    runtime::ret(CLValue::from_t(ret).unwrap_or_revert());
+}
+
 /// This function is to fetch a Contract Package Hash
 ///
 
@@ -515,6 +1030,15 @@ fn package_hash() {
     runtime::ret(CLValue::from_t(ret).unwrap_or_revert());
 }
 
+/// This function is to return the hex encoded domain separator permits are signed over
+///
+
+#[no_mangle]
+fn domain_separator() {
+    let ret: String = Pair::default().get_domain_separator();
+    runtime::ret(CLValue::from_t(ret).unwrap_or_revert());
+}
+
 fn get_entry_points() -> EntryPoints {
     let mut entry_points = EntryPoints::new();
     entry_points.add_entry_point(EntryPoint::new(
@@ -544,6 +1068,16 @@ fn get_entry_points() -> EntryPoints {
         EntryPointAccess::Groups(vec![Group::new("constructor")]),
         EntryPointType::Contract,
     ));
+    entry_points.add_entry_point(EntryPoint::new(
+        "migrate",
+        vec![
+            Parameter::new("contract_hash", ContractHash::cl_type()),
+            Parameter::new("chain_name", String::cl_type()),
+        ],
+        <()>::cl_type(),
+        EntryPointAccess::Groups(vec![Group::new("migration")]),
+        EntryPointType::Contract,
+    ));
     entry_points.add_entry_point(EntryPoint::new(
         "transfer",
         vec![
@@ -557,6 +1091,20 @@ fn get_entry_points() -> EntryPoints {
         EntryPointAccess::Public,
         EntryPointType::Contract,
     ));
+    entry_points.add_entry_point(EntryPoint::new(
+        "transfer_and_call",
+        vec![
+            Parameter::new("recipient", Key::cl_type()),
+            Parameter::new("amount", U256::cl_type()),
+            Parameter::new("data", String::cl_type()),
+        ],
+        CLType::Result {
+            ok: Box::new(CLType::Unit),
+            err: Box::new(CLType::U32),
+        },
+        EntryPointAccess::Public,
+        EntryPointType::Contract,
+    ));
     entry_points.add_entry_point(EntryPoint::new(
         "transfer_from",
         vec![
@@ -597,11 +1145,46 @@ fn get_entry_points() -> EntryPoints {
         EntryPointAccess::Public,
         EntryPointType::Contract,
     ));
+    entry_points.add_entry_point(EntryPoint::new(
+        "pause",
+        vec![],
+        <()>::cl_type(),
+        EntryPointAccess::Public,
+        EntryPointType::Contract,
+    ));
+    entry_points.add_entry_point(EntryPoint::new(
+        "unpause",
+        vec![],
+        <()>::cl_type(),
+        EntryPointAccess::Public,
+        EntryPointType::Contract,
+    ));
+    entry_points.add_entry_point(EntryPoint::new(
+        "set_fee_on",
+        vec![Parameter::new("fee_on", bool::cl_type())],
+        <()>::cl_type(),
+        EntryPointAccess::Public,
+        EntryPointType::Contract,
+    ));
+    entry_points.add_entry_point(EntryPoint::new(
+        "fee_on",
+        vec![],
+        bool::cl_type(),
+        EntryPointAccess::Public,
+        EntryPointType::Contract,
+    ));
+    entry_points.add_entry_point(EntryPoint::new(
+        "paused",
+        vec![],
+        bool::cl_type(),
+        EntryPointAccess::Public,
+        EntryPointType::Contract,
+    ));
     entry_points.add_entry_point(EntryPoint::new(
         "permit",
         vec![
-            Parameter::new("public", String::cl_type()),
-            Parameter::new("signature", String::cl_type()),
+            Parameter::new("public", PublicKey::cl_type()),
+            Parameter::new("signature", Signature::cl_type()),
             Parameter::new("owner", Key::cl_type()),
             Parameter::new("spender", Key::cl_type()),
             Parameter::new("value", U256::cl_type()),
@@ -611,6 +1194,103 @@ fn get_entry_points() -> EntryPoints {
         EntryPointAccess::Public,
         EntryPointType::Contract,
     ));
+    entry_points.add_entry_point(EntryPoint::new(
+        "transfer_with_authorization",
+        vec![
+            Parameter::new("public", PublicKey::cl_type()),
+            Parameter::new("signature", Signature::cl_type()),
+            Parameter::new("from", Key::cl_type()),
+            Parameter::new("to", Key::cl_type()),
+            Parameter::new("value", U256::cl_type()),
+            Parameter::new("valid_after", u64::cl_type()),
+            Parameter::new("valid_before", u64::cl_type()),
+            Parameter::new("nonce", <[u8; 32]>::cl_type()),
+        ],
+        <()>::cl_type(),
+        EntryPointAccess::Public,
+        EntryPointType::Contract,
+    ));
+    entry_points.add_entry_point(EntryPoint::new(
+        "receive_with_authorization",
+        vec![
+            Parameter::new("public", PublicKey::cl_type()),
+            Parameter::new("signature", Signature::cl_type()),
+            Parameter::new("from", Key::cl_type()),
+            Parameter::new("to", Key::cl_type()),
+            Parameter::new("value", U256::cl_type()),
+            Parameter::new("valid_after", u64::cl_type()),
+            Parameter::new("valid_before", u64::cl_type()),
+            Parameter::new("nonce", <[u8; 32]>::cl_type()),
+        ],
+        <()>::cl_type(),
+        EntryPointAccess::Public,
+        EntryPointType::Contract,
+    ));
+    entry_points.add_entry_point(EntryPoint::new(
+        "cancel_authorization",
+        vec![
+            Parameter::new("public", PublicKey::cl_type()),
+            Parameter::new("signature", Signature::cl_type()),
+            Parameter::new("authorizer", Key::cl_type()),
+            Parameter::new("nonce", <[u8; 32]>::cl_type()),
+        ],
+        <()>::cl_type(),
+        EntryPointAccess::Public,
+        EntryPointType::Contract,
+    ));
+    entry_points.add_entry_point(EntryPoint::new(
+        "authorization_state",
+        vec![
+            Parameter::new("authorizer", Key::cl_type()),
+            Parameter::new("nonce", <[u8; 32]>::cl_type()),
+        ],
+        bool::cl_type(),
+        EntryPointAccess::Public,
+        EntryPointType::Contract,
+    ));
+    entry_points.add_entry_point(EntryPoint::new(
+        "delegate",
+        vec![Parameter::new("delegatee", Key::cl_type())],
+        <()>::cl_type(),
+        EntryPointAccess::Public,
+        EntryPointType::Contract,
+    ));
+    entry_points.add_entry_point(EntryPoint::new(
+        "delegate_by_sig",
+        vec![
+            Parameter::new("public", PublicKey::cl_type()),
+            Parameter::new("signature", Signature::cl_type()),
+            Parameter::new("delegatee", Key::cl_type()),
+            Parameter::new("expiry", u64::cl_type()),
+        ],
+        <()>::cl_type(),
+        EntryPointAccess::Public,
+        EntryPointType::Contract,
+    ));
+    entry_points.add_entry_point(EntryPoint::new(
+        "delegates",
+        vec![Parameter::new("account", Key::cl_type())],
+        Key::cl_type(),
+        EntryPointAccess::Public,
+        EntryPointType::Contract,
+    ));
+    entry_points.add_entry_point(EntryPoint::new(
+        "get_votes",
+        vec![Parameter::new("account", Key::cl_type())],
+        U256::cl_type(),
+        EntryPointAccess::Public,
+        EntryPointType::Contract,
+    ));
+    entry_points.add_entry_point(EntryPoint::new(
+        "get_past_votes",
+        vec![
+            Parameter::new("account", Key::cl_type()),
+            Parameter::new("timestamp", u64::cl_type()),
+        ],
+        U256::cl_type(),
+        EntryPointAccess::Public,
+        EntryPointType::Contract,
+    ));
     entry_points.add_entry_point(EntryPoint::new(
         "approve",
         vec![
@@ -674,6 +1354,13 @@ fn get_entry_points() -> EntryPoints {
         EntryPointAccess::Public,
         EntryPointType::Contract,
     ));
+    entry_points.add_entry_point(EntryPoint::new(
+        "migrate_balances",
+        vec![Parameter::new("owners", Vec::<Key>::cl_type())],
+        <()>::cl_type(),
+        EntryPointAccess::Public,
+        EntryPointType::Contract,
+    ));
     entry_points.add_entry_point(EntryPoint::new(
         "nonce",
         vec![Parameter::new("owner", Key::cl_type())],
@@ -691,6 +1378,27 @@ fn get_entry_points() -> EntryPoints {
         EntryPointAccess::Public,
         EntryPointType::Contract,
     ));
+    entry_points.add_entry_point(EntryPoint::new(
+        "name",
+        vec![],
+        String::cl_type(),
+        EntryPointAccess::Public,
+        EntryPointType::Contract,
+    ));
+    entry_points.add_entry_point(EntryPoint::new(
+        "symbol",
+        vec![],
+        String::cl_type(),
+        EntryPointAccess::Public,
+        EntryPointType::Contract,
+    ));
+    entry_points.add_entry_point(EntryPoint::new(
+        "decimals",
+        vec![],
+        u8::cl_type(),
+        EntryPointAccess::Public,
+        EntryPointType::Contract,
+    ));
     entry_points.add_entry_point(EntryPoint::new(
         "total_supply",
         vec![],
@@ -761,6 +1469,13 @@ fn get_entry_points() -> EntryPoints {
         EntryPointAccess::Public,
         EntryPointType::Contract,
     ));
+    entry_points.add_entry_point(EntryPoint::new(
+        "domain_separator",
+        vec![],
+        String::cl_type(),
+        EntryPointAccess::Public,
+        EntryPointType::Contract,
+    ));
 
     entry_points.add_entry_point(EntryPoint::new(
         "initialize",
@@ -768,11 +1483,65 @@ fn get_entry_points() -> EntryPoints {
             Parameter::new("token0", Key::cl_type()),
             Parameter::new("token1", Key::cl_type()),
             Parameter::new("factory_hash", Key::cl_type()),
+            Parameter::new("swap_fee", u32::cl_type()),
+            Parameter::new("curve_type", u8::cl_type()),
+            Parameter::new("amplification", u64::cl_type()),
         ],
         <()>::cl_type(),
         EntryPointAccess::Public,
         EntryPointType::Contract,
     ));
+    entry_points.add_entry_point(EntryPoint::new(
+        "swap_fee",
+        vec![],
+        u32::cl_type(),
+        EntryPointAccess::Public,
+        EntryPointType::Contract,
+    ));
+    entry_points.add_entry_point(EntryPoint::new(
+        "curve_type",
+        vec![],
+        u8::cl_type(),
+        EntryPointAccess::Public,
+        EntryPointType::Contract,
+    ));
+    entry_points.add_entry_point(EntryPoint::new(
+        "amplification",
+        vec![],
+        u64::cl_type(),
+        EntryPointAccess::Public,
+        EntryPointType::Contract,
+    ));
+    entry_points.add_entry_point(EntryPoint::new(
+        "get_amount_out",
+        vec![
+            Parameter::new("amount_in", U256::cl_type()),
+            Parameter::new("token_in", Key::cl_type()),
+        ],
+        U256::cl_type(),
+        EntryPointAccess::Public,
+        EntryPointType::Contract,
+    ));
+    entry_points.add_entry_point(EntryPoint::new(
+        "get_amount_in",
+        vec![
+            Parameter::new("amount_out", U256::cl_type()),
+            Parameter::new("token_out", Key::cl_type()),
+        ],
+        U256::cl_type(),
+        EntryPointAccess::Public,
+        EntryPointType::Contract,
+    ));
+    entry_points.add_entry_point(EntryPoint::new(
+        "quote",
+        vec![
+            Parameter::new("amount", U256::cl_type()),
+            Parameter::new("token", Key::cl_type()),
+        ],
+        U256::cl_type(),
+        EntryPointAccess::Public,
+        EntryPointType::Contract,
+    ));
     entry_points.add_entry_point(EntryPoint::new(
         "get_reserves",
         vec![],
@@ -784,6 +1553,13 @@ fn get_entry_points() -> EntryPoints {
         EntryPointAccess::Public,
         EntryPointType::Contract,
     ));
+    entry_points.add_entry_point(EntryPoint::new(
+        "get_state",
+        vec![],
+        PairState::cl_type(),
+        EntryPointAccess::Public,
+        EntryPointType::Contract,
+    ));
     entry_points.add_entry_point(EntryPoint::new(
         "erc20_mint",
         vec![
@@ -794,6 +1570,46 @@ fn get_entry_points() -> EntryPoints {
         EntryPointAccess::Public,
         EntryPointType::Contract,
     ));
+    entry_points.add_entry_point(EntryPoint::new(
+        "grant_role",
+        vec![
+            Parameter::new("role", String::cl_type()),
+            Parameter::new("account", Key::cl_type()),
+        ],
+        <()>::cl_type(),
+        EntryPointAccess::Public,
+        EntryPointType::Contract,
+    ));
+    entry_points.add_entry_point(EntryPoint::new(
+        "revoke_role",
+        vec![
+            Parameter::new("role", String::cl_type()),
+            Parameter::new("account", Key::cl_type()),
+        ],
+        <()>::cl_type(),
+        EntryPointAccess::Public,
+        EntryPointType::Contract,
+    ));
+    entry_points.add_entry_point(EntryPoint::new(
+        "renounce_role",
+        vec![
+            Parameter::new("role", String::cl_type()),
+            Parameter::new("account", Key::cl_type()),
+        ],
+        <()>::cl_type(),
+        EntryPointAccess::Public,
+        EntryPointType::Contract,
+    ));
+    entry_points.add_entry_point(EntryPoint::new(
+        "has_role",
+        vec![
+            Parameter::new("role", String::cl_type()),
+            Parameter::new("account", Key::cl_type()),
+        ],
+        bool::cl_type(),
+        EntryPointAccess::Public,
+        EntryPointType::Contract,
+    ));
     entry_points
 }
 
@@ -814,25 +1630,9 @@ fn call() {
         let initial_supply: U256 = runtime::get_named_arg("initial_supply");
         let callee_package_hash: Key = runtime::get_named_arg("callee_package_hash");
         let factory_hash: Key = runtime::get_named_arg("factory_hash");
-        let eip_712_domain: &str =
-            "EIP712Domain(string name,string version,uint256 chainId,address verifyingContract)";
-        let permit_type: &str =
-            "Permit(address owner,address spender,uint256 value,uint256 nonce,uint256 deadline)";
-        let chain_id: &str = "101";
-        let eip_domain_hash = keccak256(eip_712_domain.as_bytes()); // to take a byte hash of EIP712Domain
-        let name_hash = keccak256(name.as_bytes()); // to take a byte hash of name
-        let one_hash = keccak256("1".as_bytes()); // to take a byte hash of "1"
-        let eip_domain_hash = encode(eip_domain_hash); // to encode and convert eip_domain_hash into string
-        let name_hash = encode(name_hash); // to encode and convert name_hash into string
-        let one_hash = encode(one_hash); // to encode and convert one_hash into string
-        let concatenated_data: String = format!(
-            "{}{}{}{}{}",
-            eip_domain_hash, name_hash, one_hash, chain_id, contract_hash
-        ); //string contactination
-        let domain_separator = keccak256(concatenated_data.as_bytes()); //to take a byte hash of concatenated Data
-        let permit_type_hash = keccak256(permit_type.as_bytes()); // to take a byte hash of Permit Type
-        let domain_separator = encode(domain_separator);
-        let permit_type_hash = encode(permit_type_hash);
+        let chain_name: String = runtime::get_named_arg("chain_name");
+        let (domain_separator, permit_type_hash) =
+            Pair::default().get_permit_type_and_domain_separator(&name, &chain_name, package_hash);
         let base: i32 = 10;
         let minimum_liquidity: U256 = (base.pow(3)).into();
         let reserve0: U128 = 0.into();
@@ -914,8 +1714,51 @@ fn call() {
                 .unwrap()
                 .into();
 
+        let previous_contract_hash: ContractHash =
+            runtime::get_key(&format!("{}_contract_hash", contract_name))
+                .unwrap_or_revert()
+                .into_hash()
+                .unwrap_or_revert()
+                .into();
+
+        // The package access token stored at install time authorizes the new version.
+        // Named keys of the previous version, and with them all state, carry over.
         let (contract_hash, _): (ContractHash, _) =
             storage::add_contract_version(package_hash, get_entry_points(), Default::default());
+        storage::disable_contract_version(package_hash, previous_contract_hash).unwrap_or_revert();
+
+        // Provision a single URef of the migration group, creating the group on the first upgrade.
+        let migration_access: URef =
+            match storage::provision_contract_user_group_uref(package_hash, "migration") {
+                Ok(migration_access) => migration_access,
+                Err(_) => storage::create_contract_user_group(
+                    package_hash,
+                    "migration",
+                    1,
+                    Default::default(),
+                )
+                .unwrap_or_revert()
+                .pop()
+                .unwrap_or_revert(),
+            };
+
+        // Run the migration hooks of the new version
+        let chain_name: String = runtime::get_named_arg("chain_name");
+        let _: () = runtime::call_versioned_contract(
+            package_hash,
+            None,
+            "migrate",
+            runtime_args! {
+                "contract_hash" => contract_hash,
+                "chain_name" => chain_name
+            },
+        );
+
+        // Remove the URef again, so migrations only run as part of an upgrade.
+        let mut urefs = BTreeSet::new();
+        urefs.insert(migration_access);
+        storage::remove_contract_user_group_urefs(package_hash, "migration", urefs)
+            .unwrap_or_revert();
 
         // update contract hash
         runtime::put_key(
@@ -0,0 +1,65 @@
+//! Constant product `x * y = k` for two coins, checked the way `PAIR::swap`
+//! does with the swap fee taken in basis points of the input. Products are
+//! taken in U512 so reserves up to U128 scaled by the swap fee base cannot
+//! overflow.
+
+use crate::stable_swap::{to_u256, to_u512, FEE_BASE};
+use casper_types::{U256, U512};
+
+/// Returns whether fee adjusted balances keep at least the product of the
+/// reserves they replace. Both sides must be scaled by the same factor.
+pub fn invariant_holds(
+    balance0_adjusted: U256,
+    balance1_adjusted: U256,
+    reserve0: U256,
+    reserve1: U256,
+) -> bool {
+    to_u512(balance0_adjusted) * to_u512(balance1_adjusted) >= to_u512(reserve0) * to_u512(reserve1)
+}
+
+/// Returns how much of `reserve_out`'s token a swap of `amount_in` pays out.
+pub fn get_amount_out(
+    amount_in: U256,
+    reserve_in: U256,
+    reserve_out: U256,
+    swap_fee: u32,
+) -> Option<U256> {
+    let fee_base = U512::from(FEE_BASE);
+    let amount_in_with_fee =
+        to_u512(amount_in).checked_mul(fee_base.checked_sub(swap_fee.into())?)?;
+    let numerator = amount_in_with_fee.checked_mul(to_u512(reserve_out))?;
+    let denominator = to_u512(reserve_in)
+        .checked_mul(fee_base)?
+        .checked_add(amount_in_with_fee)?;
+    to_u256(numerator.checked_div(denominator)?)
+}
+
+/// Returns how much of `reserve_in`'s token a swap must pay in for
+/// `amount_out`, rounded up so the swap is accepted.
+pub fn get_amount_in(
+    amount_out: U256,
+    reserve_in: U256,
+    reserve_out: U256,
+    swap_fee: u32,
+) -> Option<U256> {
+    if amount_out >= reserve_out {
+        return None;
+    }
+    let fee_base = U512::from(FEE_BASE);
+    let numerator = to_u512(reserve_in)
+        .checked_mul(to_u512(amount_out))?
+        .checked_mul(fee_base)?;
+    let denominator =
+        to_u512(reserve_out - amount_out).checked_mul(fee_base.checked_sub(swap_fee.into())?)?;
+    to_u256(numerator.checked_div(denominator)?)?.checked_add(U256::one())
+}
+
+/// Returns how much of the other token matches `amount` at the current price,
+/// which is what adding liquidity without moving the price takes.
+pub fn quote(amount: U256, reserve: U256, reserve_other: U256) -> Option<U256> {
+    to_u256(
+        to_u512(amount)
+            .checked_mul(to_u512(reserve_other))?
+            .checked_div(to_u512(reserve))?,
+    )
+}
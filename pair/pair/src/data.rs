@@ -1,21 +1,30 @@
 use alloc::string::String;
 
-use casper_contract::unwrap_or_revert::UnwrapOrRevert;
+use casper_contract::{contract_api::runtime, unwrap_or_revert::UnwrapOrRevert};
 use casper_types::{ContractPackageHash, Key, U128, U256};
-use contract_utils::{get_key, set_key, Dict};
+use contract_utils::{get_key, key_and_value_to_str, set_key, Dict};
+use uniswap_types::{PriceCumulatives, Reserves};
+
+#[cfg(feature = "uncached-lookups")]
+use crate::pair::to_package_hash;
+#[cfg(feature = "uncached-lookups")]
+use casper_types::{runtime_args, RuntimeArgs};
+#[cfg(feature = "uncached-lookups")]
+use contract_utils::zero_address;
 
-pub const BALANCES_DICT: &str = "balances";
 pub const NONCES_DICT: &str = "nonces";
-pub const ALLOWANCES_DICT: &str = "allowances";
-pub const NAME: &str = "name";
-pub const SYMBOL: &str = "symbol";
-pub const DECIMALS: &str = "decimals";
-pub const TOTAL_SUPPLY: &str = "total_supply";
+pub const AUTHORIZATIONS_DICT: &str = "authorizations";
 pub const SELF_CONTRACT_HASH: &str = "self_contract_hash";
 pub const SELF_PACKAGE_HASH: &str = "self_package_hash";
 pub const DOMAIN_SEPARATOR: &str = "domain_separator";
 pub const PERMIT_TYPE_HASH: &str = "permit_type_hash";
+/// Version in the permit domain. It is bumped by hand when the permit format changes rather
+/// than following the package's contract version, as every upgrade would then void
+/// outstanding signatures, which binding the package hash is meant to avoid.
+pub const PERMIT_VERSION: &str = "1";
 pub const FACTORY: &str = "factory_hash";
+pub const RESERVES: &str = "reserves";
+pub const PRICE_CUMULATIVES: &str = "price_cumulatives";
 pub const RESERVE0: &str = "reserve0";
 pub const RESERVE1: &str = "reserve1";
 pub const BLOCK_TIMESTAMP_LAST: &str = "block_timestamp_last";
@@ -23,38 +32,33 @@ pub const PRICE0_CUMULATIVE_LAST: &str = "price0_cumulative_last";
 pub const PRICE1_CUMULATIVE_LAST: &str = "price1_cumulative_last";
 pub const K_LAST: &str = "k_last";
 pub const TREASURY_FEE: &str = "treasury_fee";
+pub const SWAP_FEE: &str = "swap_fee";
+/// Swap fee in basis points of pairs initialized without one, the original 0.3%.
+pub const DEFAULT_SWAP_FEE: u32 = 30;
+pub const CURVE_TYPE: &str = "curve_type";
+pub const AMPLIFICATION: &str = "amplification";
+/// Curve of pairs checking `reserve0 * reserve1` on swaps, the default.
+pub const CONSTANT_PRODUCT: u8 = 0;
+/// Curve of pairs checking the StableSwap invariant on swaps.
+pub const STABLE_SWAP: u8 = 1;
 pub const MINIMUM_LIQUIDITY: &str = "minimum_liquidity";
 pub const TOKEN0: &str = "token0";
 pub const TOKEN1: &str = "token1";
+pub const TOKEN0_PACKAGE_HASH: &str = "token0_package_hash";
+pub const TOKEN1_PACKAGE_HASH: &str = "token1_package_hash";
+pub const FACTORY_PACKAGE_HASH: &str = "factory_package_hash";
+/// Whether the factory has a `fee_to`, pushed by the factory whenever that changes.
+pub const FEE_ON: &str = "fee_on";
 pub const LIQUIDITY: &str = "liquidity";
 pub const AMOUNT0: &str = "amount0";
 pub const AMOUNT1: &str = "amount1";
 pub const CALLEE_PACKAGE_HASH: &str = "callee_package_hash";
 pub const LOCK: &str = "lock";
-
-pub struct Balances {
-    dict: Dict,
-}
-
-impl Balances {
-    pub fn instance() -> Balances {
-        Balances {
-            dict: Dict::instance(BALANCES_DICT),
-        }
-    }
-
-    pub fn init() {
-        Dict::init(BALANCES_DICT)
-    }
-
-    pub fn get(&self, owner: &Key) -> U256 {
-        self.dict.get_by_key(owner).unwrap_or_default()
-    }
-
-    pub fn set(&self, owner: &Key, value: U256) {
-        self.dict.set_by_key(owner, value);
-    }
-}
+pub const PAUSED: &str = "paused";
+pub const STORAGE_VERSION: &str = "storage_version";
+/// Storage layout written by this version of the contract. Bump it together with a
+/// migration step in `PAIR::migrate` whenever named keys or dictionaries change.
+pub const CURRENT_STORAGE_VERSION: u32 = 5;
 
 pub struct Nonces {
     dict: Dict,
@@ -80,62 +84,34 @@ impl Nonces {
     }
 }
 
-pub struct Allowances {
+/// Nonces of signed authorizations each authorizer has used or canceled.
+pub struct Authorizations {
     dict: Dict,
 }
 
-impl Allowances {
-    pub fn instance() -> Allowances {
-        Allowances {
-            dict: Dict::instance(ALLOWANCES_DICT),
+impl Authorizations {
+    pub fn instance() -> Authorizations {
+        Authorizations {
+            dict: Dict::instance(AUTHORIZATIONS_DICT),
         }
     }
 
     pub fn init() {
-        Dict::init(ALLOWANCES_DICT)
+        Dict::init(AUTHORIZATIONS_DICT)
     }
 
-    pub fn get(&self, owner: &Key, spender: &Key) -> U256 {
-        self.dict.get_by_keys((owner, spender)).unwrap_or_default()
+    pub fn is_used(&self, authorizer: &Key, nonce: &[u8; 32]) -> bool {
+        self.dict
+            .get(&key_and_value_to_str(authorizer, nonce))
+            .unwrap_or_default()
     }
 
-    pub fn set(&self, owner: &Key, spender: &Key, value: U256) {
-        self.dict.set_by_keys((owner, spender), value);
+    pub fn set_used(&self, authorizer: &Key, nonce: &[u8; 32]) {
+        self.dict
+            .set(&key_and_value_to_str(authorizer, nonce), true);
     }
 }
 
-pub fn name() -> String {
-    get_key(NAME).unwrap_or_revert()
-}
-
-pub fn set_name(name: String) {
-    set_key(NAME, name);
-}
-
-pub fn symbol() -> String {
-    get_key(SYMBOL).unwrap_or_revert()
-}
-
-pub fn set_symbol(symbol: String) {
-    set_key(SYMBOL, symbol);
-}
-
-pub fn decimals() -> u8 {
-    get_key(DECIMALS).unwrap_or_revert()
-}
-
-pub fn set_decimals(decimals: u8) {
-    set_key(DECIMALS, decimals);
-}
-
-pub fn total_supply() -> U256 {
-    get_key(TOTAL_SUPPLY).unwrap_or_default()
-}
-
-pub fn set_total_supply(total_supply: U256) {
-    set_key(TOTAL_SUPPLY, total_supply);
-}
-
 pub fn set_hash(contract_hash: Key) {
     set_key(SELF_CONTRACT_HASH, contract_hash);
 }
@@ -175,60 +151,129 @@ pub fn get_token1() -> Key {
     get_key(TOKEN1).unwrap_or_revert()
 }
 
-pub fn set_domain_separator(domain_separator: String) {
-    set_key(DOMAIN_SEPARATOR, domain_separator);
+pub fn set_token0_package_hash(token0: ContractPackageHash) {
+    set_key(TOKEN0_PACKAGE_HASH, token0);
 }
 
-pub fn get_domain_separator() -> String {
-    get_key(DOMAIN_SEPARATOR).unwrap_or_revert()
+#[cfg(not(feature = "uncached-lookups"))]
+pub fn get_token0_package_hash() -> ContractPackageHash {
+    get_key(TOKEN0_PACKAGE_HASH).unwrap_or_revert()
 }
 
-pub fn set_permit_type_hash(permit_type_hash: String) {
-    set_key(PERMIT_TYPE_HASH, permit_type_hash);
+#[cfg(feature = "uncached-lookups")]
+pub fn get_token0_package_hash() -> ContractPackageHash {
+    to_package_hash(get_token0())
 }
 
-pub fn get_permit_type_hash() -> String {
-    get_key(PERMIT_TYPE_HASH).unwrap_or_revert()
+pub fn set_token1_package_hash(token1: ContractPackageHash) {
+    set_key(TOKEN1_PACKAGE_HASH, token1);
+}
+
+#[cfg(not(feature = "uncached-lookups"))]
+pub fn get_token1_package_hash() -> ContractPackageHash {
+    get_key(TOKEN1_PACKAGE_HASH).unwrap_or_revert()
+}
+
+#[cfg(feature = "uncached-lookups")]
+pub fn get_token1_package_hash() -> ContractPackageHash {
+    to_package_hash(get_token1())
+}
+
+pub fn set_factory_package_hash(factory_hash: ContractPackageHash) {
+    set_key(FACTORY_PACKAGE_HASH, factory_hash);
+}
+
+#[cfg(not(feature = "uncached-lookups"))]
+pub fn get_factory_package_hash() -> ContractPackageHash {
+    get_key(FACTORY_PACKAGE_HASH).unwrap_or_revert()
+}
+
+#[cfg(feature = "uncached-lookups")]
+pub fn get_factory_package_hash() -> ContractPackageHash {
+    to_package_hash(get_factory_hash())
+}
+
+pub fn set_fee_on(fee_on: bool) {
+    set_key(FEE_ON, fee_on);
 }
 
-pub fn set_reserve0(reserve0: U128) {
-    set_key(RESERVE0, reserve0);
+#[cfg(not(feature = "uncached-lookups"))]
+pub fn get_fee_on() -> bool {
+    get_key(FEE_ON).unwrap_or_default()
 }
 
-pub fn get_reserve0() -> U128 {
-    get_key(RESERVE0).unwrap_or_revert()
+/// Asks the factory for `fee_to` on every call, as pairs did before `fee_on` was cached.
+#[cfg(feature = "uncached-lookups")]
+pub fn get_fee_on() -> bool {
+    let fee_to: Key = runtime::call_versioned_contract(
+        get_factory_package_hash(),
+        None,
+        "fee_to",
+        runtime_args! {},
+    );
+    fee_to != zero_address()
 }
 
-pub fn set_reserve1(reserve1: U128) {
-    set_key(RESERVE1, reserve1);
+pub fn set_domain_separator(domain_separator: String) {
+    set_key(DOMAIN_SEPARATOR, domain_separator);
+}
+
+pub fn get_domain_separator() -> String {
+    get_key(DOMAIN_SEPARATOR).unwrap_or_revert()
 }
 
-pub fn get_reserve1() -> U128 {
-    get_key(RESERVE1).unwrap_or_revert()
+pub fn set_permit_type_hash(permit_type_hash: String) {
+    set_key(PERMIT_TYPE_HASH, permit_type_hash);
+}
+
+pub fn get_permit_type_hash() -> String {
+    get_key(PERMIT_TYPE_HASH).unwrap_or_revert()
 }
 
-pub fn set_block_timestamp_last(block_timestamp_last: u64) {
-    set_key(BLOCK_TIMESTAMP_LAST, block_timestamp_last);
+pub fn set_reserves(reserves: Reserves) {
+    set_key(RESERVES, reserves);
 }
 
-pub fn get_block_timestamp_last() -> u64 {
-    get_key(BLOCK_TIMESTAMP_LAST).unwrap_or_revert()
+pub fn get_reserves() -> Reserves {
+    get_key(RESERVES).unwrap_or_revert()
 }
 
-pub fn set_price0_cumulative_last(price0_cumulative_last: U256) {
-    set_key(PRICE0_CUMULATIVE_LAST, price0_cumulative_last);
+pub fn set_price_cumulatives(price_cumulatives: PriceCumulatives) {
+    set_key(PRICE_CUMULATIVES, price_cumulatives);
 }
 
-pub fn get_price0_cumulative_last() -> U256 {
-    get_key(PRICE0_CUMULATIVE_LAST).unwrap_or_revert()
+pub fn get_price_cumulatives() -> PriceCumulatives {
+    get_key(PRICE_CUMULATIVES).unwrap_or_revert()
 }
 
-pub fn set_price1_cumulative_last(price1_cumulative_last: U256) {
-    set_key(PRICE1_CUMULATIVE_LAST, price1_cumulative_last);
+/// Reserves, timestamp and cumulative prices kept in a named key each by pairs
+/// installed before the packed keys. `None` once migrated or on fresh installs.
+pub fn get_legacy_reserves() -> Option<(Reserves, PriceCumulatives)> {
+    let reserve0: U128 = get_key(RESERVE0)?;
+    let reserves = Reserves {
+        reserve0,
+        reserve1: get_key(RESERVE1).unwrap_or_revert(),
+        block_timestamp_last: get_key(BLOCK_TIMESTAMP_LAST).unwrap_or_revert(),
+    };
+    let price_cumulatives = PriceCumulatives {
+        price0_cumulative_last: get_key(PRICE0_CUMULATIVE_LAST).unwrap_or_revert(),
+        price1_cumulative_last: get_key(PRICE1_CUMULATIVE_LAST).unwrap_or_revert(),
+    };
+    Some((reserves, price_cumulatives))
 }
 
-pub fn get_price1_cumulative_last() -> U256 {
-    get_key(PRICE1_CUMULATIVE_LAST).unwrap_or_revert()
+pub fn remove_legacy_reserves() {
+    for key in [
+        RESERVE0,
+        RESERVE1,
+        BLOCK_TIMESTAMP_LAST,
+        PRICE0_CUMULATIVE_LAST,
+        PRICE1_CUMULATIVE_LAST,
+    ]
+    .iter()
+    {
+        runtime::remove_key(key);
+    }
 }
 
 pub fn set_k_last(k_last: U256) {
@@ -247,6 +292,30 @@ pub fn get_treasury_fee() -> U256 {
     get_key(TREASURY_FEE).unwrap_or_revert()
 }
 
+pub fn set_swap_fee(swap_fee: u32) {
+    set_key(SWAP_FEE, swap_fee);
+}
+
+pub fn get_swap_fee() -> u32 {
+    get_key(SWAP_FEE).unwrap_or(DEFAULT_SWAP_FEE)
+}
+
+pub fn set_curve_type(curve_type: u8) {
+    set_key(CURVE_TYPE, curve_type);
+}
+
+pub fn get_curve_type() -> u8 {
+    get_key(CURVE_TYPE).unwrap_or(CONSTANT_PRODUCT)
+}
+
+pub fn set_amplification(amplification: u64) {
+    set_key(AMPLIFICATION, amplification);
+}
+
+pub fn get_amplification() -> u64 {
+    get_key(AMPLIFICATION).unwrap_or_default()
+}
+
 pub fn set_minimum_liquidity(minimum_liquidity: U256) {
     set_key(MINIMUM_LIQUIDITY, minimum_liquidity);
 }
@@ -294,3 +363,20 @@ pub fn set_lock(lock: u64) {
 pub fn get_lock() -> u64 {
     get_key(LOCK).unwrap_or_revert()
 }
+
+pub fn set_paused(paused: bool) {
+    set_key(PAUSED, paused);
+}
+
+pub fn get_paused() -> bool {
+    get_key(PAUSED).unwrap_or_default()
+}
+
+pub fn set_storage_version(storage_version: u32) {
+    set_key(STORAGE_VERSION, storage_version);
+}
+
+/// Pairs installed before storage versioning have no version key and read as 0.
+pub fn get_storage_version() -> u32 {
+    get_key(STORAGE_VERSION).unwrap_or_default()
+}
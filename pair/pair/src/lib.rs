@@ -2,7 +2,9 @@
 
 extern crate alloc;
 
+pub mod constant_product;
 pub mod data;
 mod pair;
+pub mod stable_swap;
 
 pub use pair::PAIR;
@@ -1,21 +1,28 @@
 use alloc::{format, string::String, vec::Vec};
 use casper_contract::unwrap_or_revert::UnwrapOrRevert;
 
-use crate::data::{self, Allowances, Balances, Nonces};
+use crate::constant_product;
+use crate::data::{self, Authorizations, Nonces};
+use crate::stable_swap;
 
 use casper_contract::contract_api::runtime;
 use casper_contract::contract_api::storage;
 
 use crate::alloc::string::ToString;
 use alloc::collections::BTreeMap;
-use casper_types::system::mint::Error as MintError;
 use casper_types::{
-    runtime_args, ApiError, BlockTime, ContractPackageHash, Key, RuntimeArgs, URef, U128, U256,
+    runtime_args, ApiError, BlockTime, ContractPackageHash, Key, PublicKey, RuntimeArgs, Signature,
+    URef, U128, U256,
+};
+#[cfg(feature = "debug-keys")]
+use contract_utils::set_key;
+use contract_utils::{
+    verify_signature, zero_address, AccessControl, CEP18Event, ContractStorage, Votes, CEP18,
+    DEFAULT_ADMIN_ROLE, FEE_SETTER_ROLE, MINTER_ROLE, PAUSER_ROLE,
 };
-use contract_utils::{set_key, ContractContext, ContractStorage};
-use cryptoxide::ed25519;
 use renvm_sig::hash_message;
 use renvm_sig::keccak256;
+use uniswap_types::{PairState, PriceCumulatives, Reserves};
 
 pub enum PAIREvent {
     Approval {
@@ -57,6 +64,31 @@ pub enum PAIREvent {
         reserve1: U128,
         pair: Key,
     },
+    Paused {
+        account: Key,
+        pair: Key,
+    },
+    Unpaused {
+        account: Key,
+        pair: Key,
+    },
+    PermitUsed {
+        owner: Key,
+        spender: Key,
+        value: U256,
+        nonce: U256,
+        pair: Key,
+    },
+    AuthorizationUsed {
+        authorizer: Key,
+        nonce: [u8; 32],
+        pair: Key,
+    },
+    AuthorizationCanceled {
+        authorizer: Key,
+        nonce: [u8; 32],
+        pair: Key,
+    },
 }
 
 impl PAIREvent {
@@ -101,6 +133,31 @@ impl PAIREvent {
                 reserve1: _,
                 pair: _,
             } => "sync",
+            PAIREvent::Paused {
+                account: _,
+                pair: _,
+            } => "paused",
+            PAIREvent::Unpaused {
+                account: _,
+                pair: _,
+            } => "unpaused",
+            PAIREvent::PermitUsed {
+                owner: _,
+                spender: _,
+                value: _,
+                nonce: _,
+                pair: _,
+            } => "permit_used",
+            PAIREvent::AuthorizationUsed {
+                authorizer: _,
+                nonce: _,
+                pair: _,
+            } => "authorization_used",
+            PAIREvent::AuthorizationCanceled {
+                authorizer: _,
+                nonce: _,
+                pair: _,
+            } => "authorization_canceled",
         }
         .to_string()
     }
@@ -262,6 +319,24 @@ pub enum Error {
     UniswapV2CorePairForbidden = 106,
     /// 65,643 for (UniswapV2 Core Pair Failed Verification)
     UniswapV2CorePairFailedVerification = 107,
+    /// 65,672 for (UniswapV2 Core Pair Invalid Curve)
+    UniswapV2CorePairInvalidCurve = 136,
+    /// 65,673 for (UniswapV2 Core Pair Invariant Not Converged)
+    UniswapV2CorePairInvariantNotConverged = 137,
+    /// 65,674 for (UniswapV2 Core Pair Paused)
+    UniswapV2CorePairPaused = 138,
+    /// 65,679 for (UniswapV2 Core Pair Invalid Token)
+    UniswapV2CorePairInvalidToken = 143,
+    /// 65,680 for (UniswapV2 Core Pair Quote OverFlow)
+    UniswapV2CorePairQuoteOverFlow = 144,
+    /// 65,685 for (UniswapV2 Core Pair Authorization Not Yet Valid)
+    UniswapV2CorePairAuthorizationNotYetValid = 149,
+    /// 65,686 for (UniswapV2 Core Pair Authorization Expired)
+    UniswapV2CorePairAuthorizationExpired = 150,
+    /// 65,687 for (UniswapV2 Core Pair Authorization Used)
+    UniswapV2CorePairAuthorizationUsed = 151,
+    /// 65,688 for (UniswapV2 Core Pair Caller Must Be Payee)
+    UniswapV2CorePairCallerMustBePayee = 152,
 }
 
 impl From<Error> for ApiError {
@@ -270,7 +345,16 @@ impl From<Error> for ApiError {
     }
 }
 
-pub trait PAIR<Storage: ContractStorage>: ContractContext<Storage> {
+pub(crate) fn to_package_hash(key: Key) -> ContractPackageHash {
+    match key {
+        Key::Hash(package) => ContractPackageHash::new(package),
+        _ => runtime::revert(ApiError::UnexpectedKeyVariant),
+    }
+}
+
+pub trait PAIR<Storage: ContractStorage>:
+    CEP18<Storage> + AccessControl<Storage> + Votes<Storage>
+{
     fn init(
         &mut self,
         name: String,
@@ -292,120 +376,113 @@ pub trait PAIR<Storage: ContractStorage>: ContractContext<Storage> {
         callee_package_hash: Key,
         lock: u64,
     ) {
-        data::set_name(name);
-        data::set_symbol(symbol);
-        data::set_decimals(decimals);
+        CEP18::init(self, name, symbol, decimals);
         data::set_domain_separator(domain_separator);
         data::set_permit_type_hash(permit_type_hash);
         data::set_hash(contract_hash);
         data::set_package_hash(package_hash);
         data::set_factory_hash(factory_hash);
-        data::set_reserve0(reserve0);
-        data::set_reserve1(reserve1);
-        data::set_block_timestamp_last(block_timestamp_last);
-        data::set_price0_cumulative_last(price0_cumulative_last);
-        data::set_price1_cumulative_last(price1_cumulative_last);
+        data::set_factory_package_hash(to_package_hash(factory_hash));
+        data::set_fee_on(false);
+        data::set_reserves(Reserves {
+            reserve0,
+            reserve1,
+            block_timestamp_last,
+        });
+        data::set_price_cumulatives(PriceCumulatives {
+            price0_cumulative_last,
+            price1_cumulative_last,
+        });
         data::set_k_last(k_last);
         data::set_treasury_fee(treasury_fee);
         data::set_minimum_liquidity(minimum_liquidity);
         data::set_callee_package_hash(callee_package_hash);
         data::set_lock(lock);
+        data::set_paused(false);
         Nonces::init();
+        Authorizations::init();
         let nonces = Nonces::instance();
         nonces.set(&Key::from(self.get_caller()), U256::from(0));
-        Balances::init();
-        Allowances::init();
+        AccessControl::init(self);
+        self._grant_role(DEFAULT_ADMIN_ROLE.to_string(), self.get_caller());
+        self._grant_role(MINTER_ROLE.to_string(), self.get_caller());
+        self._grant_role(FEE_SETTER_ROLE.to_string(), self.get_caller());
+        self._grant_role(PAUSER_ROLE.to_string(), self.get_caller());
+        data::set_storage_version(data::CURRENT_STORAGE_VERSION);
     }
 
-    fn balance_of(&mut self, owner: Key) -> U256 {
-        Balances::instance().get(&owner)
-    }
-
-    fn nonce(&mut self, owner: Key) -> U256 {
-        Nonces::instance().get(&owner)
-    }
-
-    fn transfer(&mut self, recipient: Key, amount: U256) -> Result<(), u32> {
-        self.make_transfer(self.get_caller(), recipient, amount)
-    }
-
-    fn approve(&mut self, spender: Key, amount: U256) {
-        self._approve(self.get_caller(), spender, amount);
-    }
-
-    fn _approve(&mut self, owner: Key, spender: Key, amount: U256) {
-        Allowances::instance().set(&owner, &spender, amount);
-        self.emit(&PAIREvent::Approval {
-            owner: owner,
-            spender: spender,
-            value: amount,
-        });
-    }
-
-    fn increase_allowance(&mut self, spender: Key, amount: U256) -> Result<(), u32> {
-        let allowances = Allowances::instance();
-        let owner: Key = self.get_caller();
-
-        let spender_allowance: U256 = allowances.get(&owner, &spender);
-        let new_allowance: U256 = spender_allowance
-            .checked_add(amount)
-            .ok_or(Error::UniswapV2CorePairOverFlow1)
-            .unwrap_or_revert();
-
-        if owner != spender {
-            self._approve(owner, spender, new_allowance);
-            return Ok(());
-        } else {
-            return Err(4);
-        }
-    }
-
-    fn decrease_allowance(&mut self, spender: Key, amount: U256) -> Result<(), u32> {
-        let allowances = Allowances::instance();
-
-        let owner: Key = self.get_caller();
-
-        let spender_allowance: U256 = allowances.get(&owner, &spender);
-
-        let new_allowance: U256 = spender_allowance
-            .checked_sub(amount)
-            .ok_or(Error::UniswapV2CorePairUnderFlow1)
-            .unwrap_or_revert();
-
-        if new_allowance >= 0.into() && new_allowance < spender_allowance && owner != spender {
-            self._approve(owner, spender, new_allowance);
-            return Ok(());
-        } else {
-            return Err(4);
-        }
-    }
-
-    fn transfer_from(&mut self, owner: Key, recipient: Key, amount: U256) -> Result<(), u32> {
-        if owner != recipient && amount != 0.into() {
-            let ret: Result<(), u32> = self.make_transfer(owner, recipient, amount);
-            if ret.is_ok() {
-                let allowances = Allowances::instance();
-                let spender_allowance: U256 = allowances.get(&owner, &self.get_caller());
-                let new_allowance: U256 = spender_allowance
-                    .checked_sub(amount)
-                    .ok_or(Error::UniswapV2CorePairUnderFlow2)
-                    .unwrap_or_revert();
-                if new_allowance >= 0.into()
-                    && new_allowance < spender_allowance
-                    && owner != self.get_caller()
+    /// Brings the storage of an upgraded pair up to `CURRENT_STORAGE_VERSION`. Called by the
+    /// installer right after a new version is added, each step runs once per pair.
+    fn migrate(&mut self, contract_hash: Key, chain_name: String) {
+        data::set_hash(contract_hash);
+        let storage_version: u32 = data::get_storage_version();
+        if storage_version < 1 {
+            // pairs installed before roles, fee tiers, curves and pausing
+            if !self.access_control_initialized() {
+                AccessControl::init(self);
+                for role in [
+                    DEFAULT_ADMIN_ROLE,
+                    MINTER_ROLE,
+                    FEE_SETTER_ROLE,
+                    PAUSER_ROLE,
+                ]
+                .iter()
                 {
-                    self._approve(owner, self.get_caller(), new_allowance);
-                    return Ok(());
-                } else {
-                    return Err(4);
+                    self._grant_role(role.to_string(), self.get_caller());
                 }
             }
+            if !runtime::has_key(data::SWAP_FEE) {
+                data::set_swap_fee(data::DEFAULT_SWAP_FEE);
+            }
+            if !runtime::has_key(data::CURVE_TYPE) {
+                data::set_curve_type(data::CONSTANT_PRODUCT);
+                data::set_amplification(0);
+            }
+            if !runtime::has_key(data::PAUSED) {
+                data::set_paused(false);
+            }
+        }
+        if storage_version < 2 {
+            // pairs converting token keys and asking the factory for fee_to on every call
+            let factory_hash: ContractPackageHash = to_package_hash(data::get_factory_hash());
+            data::set_factory_package_hash(factory_hash);
+            if runtime::has_key(data::TOKEN0) {
+                data::set_token0_package_hash(to_package_hash(data::get_token0()));
+                data::set_token1_package_hash(to_package_hash(data::get_token1()));
+            }
+            let fee_to: Key =
+                runtime::call_versioned_contract(factory_hash, None, "fee_to", runtime_args! {});
+            data::set_fee_on(fee_to != zero_address());
+        }
+        if storage_version < 3 {
+            // pairs keeping each reserve, the timestamp and each cumulative price in its own key
+            if let Some((reserves, price_cumulatives)) = data::get_legacy_reserves() {
+                data::set_reserves(reserves);
+                data::set_price_cumulatives(price_cumulatives);
+                data::remove_legacy_reserves();
+            }
+        }
+        if storage_version < 4 {
+            // pairs binding permits to the contract hash and a hard-coded chain id
+            let name: String = self.name();
+            let (domain_separator, _) = self.get_permit_type_and_domain_separator(
+                &name,
+                &chain_name,
+                data::get_package_hash(),
+            );
+            data::set_domain_separator(domain_separator);
         }
-        Ok(())
+        if storage_version < 5 {
+            // pairs without signed transfer authorizations
+            if !runtime::has_key(data::AUTHORIZATIONS_DICT) {
+                Authorizations::init();
+            }
+        }
+        data::set_storage_version(data::CURRENT_STORAGE_VERSION);
     }
 
-    fn allowance(&mut self, owner: Key, spender: Key) -> U256 {
-        Allowances::instance().get(&owner, &spender)
+    fn nonce(&mut self, owner: Key) -> U256 {
+        Nonces::instance().get(&owner)
     }
 
     fn skim(&mut self, to: Key) {
@@ -415,23 +492,10 @@ pub trait PAIR<Storage: ContractStorage>: ContractContext<Storage> {
             runtime::revert(Error::UniswapV2CorePairLocked1);
         }
         data::set_lock(1);
-        let token0: Key = self.get_token0();
-        let token1: Key = self.get_token1();
-        let reserve0: U128 = data::get_reserve0();
-        let reserve1: U128 = data::get_reserve1();
+        let token0_package_hash: ContractPackageHash = data::get_token0_package_hash();
+        let token1_package_hash: ContractPackageHash = data::get_token1_package_hash();
+        let (reserve0, reserve1, _block_timestamp_last) = self.get_reserves();
         let pair_address: Key = Key::from(data::get_package_hash());
-        //convert Key to ContractPackageHash
-        let token0_hash_add_array = match token0 {
-            Key::Hash(package) => package,
-            _ => runtime::revert(ApiError::UnexpectedKeyVariant),
-        };
-        let token0_package_hash = ContractPackageHash::new(token0_hash_add_array);
-        //convert Key to ContractPackageHash
-        let token1_hash_add_array = match token1 {
-            Key::Hash(package) => package,
-            _ => runtime::revert(ApiError::UnexpectedKeyVariant),
-        };
-        let token1_package_hash = ContractPackageHash::new(token1_hash_add_array);
         let balance0: U256 = runtime::call_versioned_contract(
             token0_package_hash,
             None,
@@ -445,16 +509,21 @@ pub trait PAIR<Storage: ContractStorage>: ContractContext<Storage> {
             runtime_args! {"owner" => pair_address},
         );
 
-        let balance0_conversion: U128 = U128::from(balance0.as_u128());
-        let balance1_conversion: U128 = U128::from(balance1.as_u128());
+        // a balance beyond U128 is what skim is there to bring back in range
+        let excess0: U256 = balance0
+            .checked_sub(U256::from(reserve0.as_u128()))
+            .ok_or(Error::UniswapV2CorePairUnderFlow3)
+            .unwrap_or_revert();
+        let excess1: U256 = balance1
+            .checked_sub(U256::from(reserve1.as_u128()))
+            .ok_or(Error::UniswapV2CorePairUnderFlow4)
+            .unwrap_or_revert();
 
         let _ret: Result<(), u32> = runtime::call_versioned_contract(
             token0_package_hash,
             None,
             "transfer",
-            runtime_args! {"recipient" => to,"amount" => U256::from((balance0_conversion.checked_sub(reserve0)
-            .ok_or(Error::UniswapV2CorePairUnderFlow3)
-            .unwrap_or_revert()).as_u128())},
+            runtime_args! {"recipient" => to,"amount" => excess0},
         );
         match _ret {
             Ok(()) => {
@@ -462,9 +531,7 @@ pub trait PAIR<Storage: ContractStorage>: ContractContext<Storage> {
                     token1_package_hash,
                     None,
                     "transfer",
-                    runtime_args! {"recipient" => to,"amount" => U256::from((balance1_conversion.checked_sub(reserve1)
-                    .ok_or(Error::UniswapV2CorePairUnderFlow4)
-                    .unwrap_or_revert()).as_u128()), },
+                    runtime_args! {"recipient" => to,"amount" => excess1},
                 );
                 match _ret {
                     Ok(()) => data::set_lock(0),
@@ -482,23 +549,10 @@ pub trait PAIR<Storage: ContractStorage>: ContractContext<Storage> {
             runtime::revert(Error::UniswapV2CorePairLocked2);
         }
         data::set_lock(1);
-        let token0: Key = self.get_token0();
-        let token1: Key = self.get_token1();
-        let reserve0: U128 = data::get_reserve0();
-        let reserve1: U128 = data::get_reserve1();
+        let token0_package_hash: ContractPackageHash = data::get_token0_package_hash();
+        let token1_package_hash: ContractPackageHash = data::get_token1_package_hash();
+        let (reserve0, reserve1, block_timestamp_last) = self.get_reserves();
         let pair_address: Key = Key::from(data::get_package_hash());
-        //convert Key to ContractPackageHash
-        let token0_hash_add_array = match token0 {
-            Key::Hash(package) => package,
-            _ => runtime::revert(ApiError::UnexpectedKeyVariant),
-        };
-        let token0_package_hash = ContractPackageHash::new(token0_hash_add_array);
-        //convert Key to ContractPackageHash
-        let token1_hash_add_array = match token1 {
-            Key::Hash(package) => package,
-            _ => runtime::revert(ApiError::UnexpectedKeyVariant),
-        };
-        let token1_package_hash = ContractPackageHash::new(token1_hash_add_array);
         let balance0: U256 = runtime::call_versioned_contract(
             token0_package_hash,
             None,
@@ -511,31 +565,27 @@ pub trait PAIR<Storage: ContractStorage>: ContractContext<Storage> {
             "balance_of",
             runtime_args! {"owner" => pair_address},
         );
-        self.update(balance0, balance1, reserve0, reserve1);
+        self.update(balance0, balance1, reserve0, reserve1, block_timestamp_last);
         data::set_lock(0);
     }
 
     fn swap(&mut self, amount0_out: U256, amount1_out: U256, to: Key, data: String) {
+        self.when_not_paused();
         let pair_address: Key = Key::from(data::get_package_hash());
         let zero: U256 = 0.into();
         if amount0_out > zero || amount1_out > zero {
-            let (reserve0, reserve1, _block_timestamp_last) = self.get_reserves(); // gas savings
+            let (reserve0, reserve1, block_timestamp_last) = self.get_reserves(); // gas savings
             if amount0_out < U256::from(reserve0.as_u128())
                 && amount1_out < U256::from(reserve1.as_u128())
             {
                 let token0: Key = self.get_token0();
                 let token1: Key = self.get_token1();
                 if to != token0 && to != token1 {
+                    let token0_package_hash: ContractPackageHash = data::get_token0_package_hash();
+                    let token1_package_hash: ContractPackageHash = data::get_token1_package_hash();
                     if amount0_out > zero {
-                        //convert Key to ContractPackageHash
-                        // let token0_hash_add_array = match token0 {
-                        //     Key::Hash(package) => package,
-                        //     _ => runtime::revert(ApiError::UnexpectedKeyVariant),
-                        // };
-                        // let token0_package_hash = ContractPackageHash::new(token0_hash_add_array);
                         let ret: Result<(), u32> = runtime::call_versioned_contract(
-                            // token0_package_hash,
-                            token0.into_hash().unwrap_or_revert().into(),
+                            token0_package_hash,
                             None,
                             "transfer",
                             runtime_args! {
@@ -549,12 +599,6 @@ pub trait PAIR<Storage: ContractStorage>: ContractContext<Storage> {
                         }
                     }
                     if amount1_out > zero {
-                        //convert Key to ContractPackageHash
-                        let token1_hash_add_array = match token1 {
-                            Key::Hash(package) => package,
-                            _ => runtime::revert(ApiError::UnexpectedKeyVariant),
-                        };
-                        let token1_package_hash = ContractPackageHash::new(token1_hash_add_array);
                         let _ret: Result<(), u32> = runtime::call_versioned_contract(
                             token1_package_hash,
                             None,
@@ -584,18 +628,6 @@ pub trait PAIR<Storage: ContractStorage>: ContractContext<Storage> {
                             runtime_args! {"sender" => data::get_callee_package_hash(),"amount0" => amount0_out,"amount1" => amount1_out,"data" => data},
                         );
                     }
-                    //convert Key to ContractPackageHash
-                    let token0_hash_add_array = match token0 {
-                        Key::Hash(package) => package,
-                        _ => runtime::revert(ApiError::UnexpectedKeyVariant),
-                    };
-                    let token0_package_hash = ContractPackageHash::new(token0_hash_add_array);
-                    //convert Key to ContractPackageHash
-                    let token1_hash_add_array = match token1 {
-                        Key::Hash(package) => package,
-                        _ => runtime::revert(ApiError::UnexpectedKeyVariant),
-                    };
-                    let token1_package_hash = ContractPackageHash::new(token1_hash_add_array);
                     let balance0: U256 = runtime::call_versioned_contract(
                         token0_package_hash,
                         None,
@@ -618,50 +650,67 @@ pub trait PAIR<Storage: ContractStorage>: ContractContext<Storage> {
                         amount1_in = balance1 - (U256::from(reserve1.as_u128()) - amount1_out);
                     }
                     if amount0_in > zero || amount1_in > zero {
-                        let amount_1000: U256 = 1000.into();
-                        let amount_3: U256 = 3.into();
+                        // the swap fee is expressed in basis points
+                        let amount_10000: U256 = 10000.into();
+                        let swap_fee: U256 = data::get_swap_fee().into();
                         let balance0_adjusted: U256 = (balance0
-                            .checked_mul(amount_1000)
+                            .checked_mul(amount_10000)
                             .ok_or(Error::UniswapV2CorePairMultiplicationOverFlow1)
                             .unwrap_or_revert())
                         .checked_sub(
                             amount0_in
-                                .checked_mul(amount_3)
+                                .checked_mul(swap_fee)
                                 .ok_or(Error::UniswapV2CorePairMultiplicationOverFlow2)
                                 .unwrap_or_revert(),
                         )
                         .ok_or(Error::UniswapV2CorePairUnderFlow11)
                         .unwrap_or_revert();
                         let balance1_adjusted: U256 = (balance1
-                            .checked_mul(amount_1000)
+                            .checked_mul(amount_10000)
                             .ok_or(Error::UniswapV2CorePairMultiplicationOverFlow3)
                             .unwrap_or_revert())
                         .checked_sub(
                             amount1_in
-                                .checked_mul(amount_3)
+                                .checked_mul(swap_fee)
                                 .ok_or(Error::UniswapV2CorePairMultiplicationOverFlow4)
                                 .unwrap_or_revert(),
                         )
                         .ok_or(Error::UniswapV2CorePairUnderFlow12)
                         .unwrap_or_revert();
-                        let reserve0_conversion: U256 = U256::from(reserve0.as_u128());
-                        let reserve1_conversion: U256 = U256::from(reserve1.as_u128());
-                        let base: i32 = 1000;
-                        let reserve_multiply: U256 = (base.pow(2)).into();
-                        // let reserve_multiply: U256 = (1000 ^ 2).into();
-                        if (balance0_adjusted
-                            .checked_mul(balance1_adjusted)
-                            .ok_or(Error::UniswapV2CorePairMultiplicationOverFlow5)
-                            .unwrap_or_revert())
-                            >= (reserve0_conversion
-                                .checked_mul(reserve1_conversion)
-                                .ok_or(Error::UniswapV2CorePairMultiplicationOverFlow6)
-                                .unwrap_or_revert()
-                                .checked_mul(reserve_multiply)
-                                .ok_or(Error::UniswapV2CorePairMultiplicationOverFlow7)
-                                .unwrap_or_revert())
-                        {
-                            self.update(balance0, balance1, reserve0, reserve1);
+                        let reserve0_adjusted: U256 = U256::from(reserve0.as_u128())
+                            .checked_mul(amount_10000)
+                            .ok_or(Error::UniswapV2CorePairMultiplicationOverFlow6)
+                            .unwrap_or_revert();
+                        let reserve1_adjusted: U256 = U256::from(reserve1.as_u128())
+                            .checked_mul(amount_10000)
+                            .ok_or(Error::UniswapV2CorePairMultiplicationOverFlow7)
+                            .unwrap_or_revert();
+                        let invariant_holds: bool = if data::get_curve_type() == data::STABLE_SWAP {
+                            stable_swap::invariant_holds(
+                                balance0_adjusted,
+                                balance1_adjusted,
+                                reserve0_adjusted,
+                                reserve1_adjusted,
+                                data::get_amplification(),
+                            )
+                            .ok_or(Error::UniswapV2CorePairInvariantNotConverged)
+                            .unwrap_or_revert()
+                        } else {
+                            constant_product::invariant_holds(
+                                balance0_adjusted,
+                                balance1_adjusted,
+                                reserve0_adjusted,
+                                reserve1_adjusted,
+                            )
+                        };
+                        if invariant_holds {
+                            self.update(
+                                balance0,
+                                balance1,
+                                reserve0,
+                                reserve1,
+                                block_timestamp_last,
+                            );
                             let eventpair: Key = Key::from(data::get_package_hash());
                             self.emit(&PAIREvent::Swap {
                                 sender: self.get_caller(),
@@ -700,48 +749,27 @@ pub trait PAIR<Storage: ContractStorage>: ContractContext<Storage> {
     ///
     /// # Parameters
     ///
-    /// * `public_key` - A string slice that holds the public key of the meta transaction signer
+    /// * `public_key` - A PublicKey that holds the ed25519 or secp256k1 key of the meta transaction signer
     ///
-    /// * `signature` - A string slice that holds the signature of the meta transaction
+    /// * `signature` - A Signature that holds the ed25519 or secp256k1 signature of the meta transaction
     ///
     /// * `digest` - A u8 array that holds the digest
     ///
-    /// * `owner` - An Accounthash that holds the account address of the signer
+    /// * `owner` - A Key that holds the account address the public key must belong to
     ///
 
     fn ecrecover(
         &mut self,
-        public_key: String,
-        signature: String,
+        public_key: PublicKey,
+        signature: Signature,
         digest: [u8; 32],
         owner: Key,
     ) -> bool {
-        let public_key_without_spaces: String = public_key.split_whitespace().collect();
-        let public_key_string: Vec<&str> = public_key_without_spaces.split(',').collect();
-        let mut public_key_vec: Vec<u8> = Vec::new();
-        let mut public_counter: usize = 0;
-        while public_counter < 32 {
-            public_key_vec.push(public_key_string[public_counter].parse::<u8>().unwrap());
-            public_counter = public_counter
-                .checked_add(1)
-                .ok_or(Error::UniswapV2CorePairOverFlow2)
-                .unwrap_or_revert();
-        }
-        let signature_without_spaces: String = signature.split_whitespace().collect();
-        let signature_string: Vec<&str> = signature_without_spaces.split(',').collect();
-        let mut signature_vec: Vec<u8> = Vec::new();
-        let mut signature_counter: usize = 0;
-        while signature_counter < 64 {
-            signature_vec.push(signature_string[signature_counter].parse::<u8>().unwrap());
-            signature_counter = signature_counter
-                .checked_add(1)
-                .ok_or(Error::UniswapV2CorePairOverFlow3)
-                .unwrap_or_revert();
-        }
-        let result: bool = ed25519::verify(&digest, &public_key_vec, &signature_vec);
-        let verify_key: String = format!("{}{}", "VERIFY", owner);
-        set_key(&verify_key, result);
-        return result;
+        let result: bool = Key::from(public_key.to_account_hash()) == owner
+            && verify_signature(&public_key, &signature, &digest);
+        #[cfg(feature = "debug-keys")]
+        set_key(&format!("{}{}", "VERIFY", owner), result);
+        result
     }
 
     /// This function is to get meta transaction signer and verify if it is equal
@@ -749,9 +777,9 @@ pub trait PAIR<Storage: ContractStorage>: ContractContext<Storage> {
     ///
     /// # Parameters
     ///
-    /// * `public_key` - A string slice that holds the public key of the meta transaction signer,  Subscriber have to get it from running cryptoxide project externally.
+    /// * `public_key` - A PublicKey that holds the ed25519 or secp256k1 key of the owner
     ///
-    /// * `signature` - A string slice that holds the signature of the meta transaction,  Subscriber have to get it from running cryptoxide project externally.
+    /// * `signature` - A Signature that holds the owner's signature of the permit digest
     ///
     /// * `owner` - A Key that holds the account address of the owner
     ///
@@ -764,8 +792,8 @@ pub trait PAIR<Storage: ContractStorage>: ContractContext<Storage> {
 
     fn permit(
         &mut self,
-        public_key: String,
-        signature: String,
+        public_key: PublicKey,
+        signature: Signature,
         owner: Key,
         spender: Key,
         value: U256,
@@ -773,7 +801,7 @@ pub trait PAIR<Storage: ContractStorage>: ContractContext<Storage> {
     ) {
         let domain_separator: String = data::get_domain_separator();
         let permit_type_hash: String = data::get_permit_type_hash();
-        let nonce: U256 = self.nonce(Key::from(self.get_caller()));
+        let nonce: U256 = self.nonce(owner);
         let deadline_into_blocktime: BlockTime = BlockTime::new(
             deadline
                 .checked_mul(1000)
@@ -790,18 +818,18 @@ pub trait PAIR<Storage: ContractStorage>: ContractContext<Storage> {
             let hash_string: String = hex::encode(hash);
             let encode_packed: String = format!("{}{}", domain_separator, hash_string);
             let digest: [u8; 32] = hash_message(encode_packed);
-            let digest_string: String = hex::encode(digest);
-            let digest_key: String = format!("{}{}", "digest_", owner);
-            set_key(&digest_key, digest_string);
-            self.set_nonce(Key::from(self.get_caller()));
-            let result: bool =
-                self.ecrecover(public_key, signature, digest, Key::from(self.get_caller()));
+            #[cfg(feature = "debug-keys")]
+            set_key(&format!("{}{}", "digest_", owner), hex::encode(digest));
+            self.set_nonce(owner);
+            let result: bool = self.ecrecover(public_key, signature, digest, owner);
             if result == true {
-                Allowances::instance().set(&owner, &spender, value);
-                self.emit(&PAIREvent::Approval {
-                    owner: owner,
-                    spender: spender,
-                    value: value,
+                self._approve(owner, spender, value);
+                self.emit(&PAIREvent::PermitUsed {
+                    owner,
+                    spender,
+                    value,
+                    nonce,
+                    pair: Key::from(data::get_package_hash()),
                 });
             } else {
                 //signature verification failed
@@ -813,66 +841,178 @@ pub trait PAIR<Storage: ContractStorage>: ContractContext<Storage> {
         }
     }
 
-    fn mint(&mut self, recipient: Key, amount: U256) {
-        let balances = Balances::instance();
-        let balance = balances.get(&recipient);
-        balances.set(
-            &recipient,
-            balance
-                .checked_add(amount)
-                .ok_or(Error::UniswapV2CorePairOverFlow4)
-                .unwrap_or_revert(),
+    fn authorization_state(&self, authorizer: Key, nonce: [u8; 32]) -> bool {
+        Authorizations::instance().is_used(&authorizer, &nonce)
+    }
+
+    /// Moves `value` from `from` to `to` with `from`'s signature of a `TransferWithAuthorization`
+    /// over the permit domain, so anyone can relay it. `nonce` is any 32 bytes `from` has not
+    /// used or canceled before, and the transfer is only valid after `valid_after` and before
+    /// `valid_before`, both in seconds.
+    fn transfer_with_authorization(
+        &mut self,
+        public_key: PublicKey,
+        signature: Signature,
+        from: Key,
+        to: Key,
+        value: U256,
+        valid_after: u64,
+        valid_before: u64,
+        nonce: [u8; 32],
+    ) {
+        self.use_authorization(
+            "TransferWithAuthorization(address from,address to,uint256 value,uint256 validAfter,uint256 validBefore,bytes32 nonce)",
+            public_key,
+            signature,
+            from,
+            to,
+            value,
+            valid_after,
+            valid_before,
+            nonce,
         );
-        data::set_total_supply(
-            self.total_supply()
-                .checked_add(amount)
-                .ok_or(Error::UniswapV2CorePairOverFlow5)
-                .unwrap_or_revert(),
+    }
+
+    /// Same as `transfer_with_authorization` for a `ReceiveWithAuthorization`, which only `to`
+    /// may submit. This keeps a relayer from front-running a contract that pulls the tokens
+    /// as part of a larger call.
+    fn receive_with_authorization(
+        &mut self,
+        public_key: PublicKey,
+        signature: Signature,
+        from: Key,
+        to: Key,
+        value: U256,
+        valid_after: u64,
+        valid_before: u64,
+        nonce: [u8; 32],
+    ) {
+        if self.get_caller() != to {
+            runtime::revert(Error::UniswapV2CorePairCallerMustBePayee);
+        }
+        self.use_authorization(
+            "ReceiveWithAuthorization(address from,address to,uint256 value,uint256 validAfter,uint256 validBefore,bytes32 nonce)",
+            public_key,
+            signature,
+            from,
+            to,
+            value,
+            valid_after,
+            valid_before,
+            nonce,
         );
-        let address_0: Key = Key::from_formatted_str(
-            "account-hash-0000000000000000000000000000000000000000000000000000000000000000",
-        )
-        .unwrap();
-        let eventpair: Key = Key::from(data::get_package_hash());
-        self.emit(&PAIREvent::Transfer {
-            from: address_0,
-            to: recipient,
-            value: amount,
-            pair: eventpair,
+    }
+
+    /// Marks `nonce` of `authorizer` as used with their signature of a `CancelAuthorization`,
+    /// so an authorization signed with it can no longer be submitted.
+    fn cancel_authorization(
+        &mut self,
+        public_key: PublicKey,
+        signature: Signature,
+        authorizer: Key,
+        nonce: [u8; 32],
+    ) {
+        let authorizations: Authorizations = Authorizations::instance();
+        if authorizations.is_used(&authorizer, &nonce) {
+            runtime::revert(Error::UniswapV2CorePairAuthorizationUsed);
+        }
+        let type_hash: String = hex::encode(keccak256(
+            "CancelAuthorization(address authorizer,bytes32 nonce)".as_bytes(),
+        ));
+        let encoded: String = format!("{}{}{}", type_hash, authorizer, hex::encode(nonce));
+        let digest: [u8; 32] = self.authorization_digest(encoded);
+        if !self.ecrecover(public_key, signature, digest, authorizer) {
+            runtime::revert(Error::UniswapV2CorePairFailedVerification);
+        }
+        authorizations.set_used(&authorizer, &nonce);
+        self.emit(&PAIREvent::AuthorizationCanceled {
+            authorizer,
+            nonce,
+            pair: Key::from(data::get_package_hash()),
         });
     }
 
-    fn burn(&mut self, recipient: Key, amount: U256) {
-        let balances = Balances::instance();
-        let balance = balances.get(&recipient);
-        if balance >= amount {
-            balances.set(
-                &recipient,
-                balance
-                    .checked_sub(amount)
-                    .ok_or(Error::UniswapV2CorePairUnderFlow13)
-                    .unwrap_or_revert(),
-            );
-            data::set_total_supply(
-                self.total_supply()
-                    .checked_sub(amount)
-                    .ok_or(Error::UniswapV2CorePairUnderFlow14)
-                    .unwrap_or_revert(),
-            );
-            let address_0: Key = Key::from_formatted_str(
-                "account-hash-0000000000000000000000000000000000000000000000000000000000000000",
-            )
-            .unwrap();
-            let eventpair: Key = Key::from(data::get_package_hash());
-            self.emit(&PAIREvent::Transfer {
-                from: recipient,
-                to: address_0,
-                value: amount,
-                pair: eventpair,
-            });
-        } else {
-            runtime::revert(MintError::InsufficientFunds)
+    fn use_authorization(
+        &mut self,
+        authorization_type: &str,
+        public_key: PublicKey,
+        signature: Signature,
+        from: Key,
+        to: Key,
+        value: U256,
+        valid_after: u64,
+        valid_before: u64,
+        nonce: [u8; 32],
+    ) {
+        let blocktime: BlockTime = runtime::get_blocktime();
+        if BlockTime::new(valid_after.saturating_mul(1000)) >= blocktime {
+            runtime::revert(Error::UniswapV2CorePairAuthorizationNotYetValid);
+        }
+        if BlockTime::new(valid_before.saturating_mul(1000)) <= blocktime {
+            runtime::revert(Error::UniswapV2CorePairAuthorizationExpired);
+        }
+        let authorizations: Authorizations = Authorizations::instance();
+        if authorizations.is_used(&from, &nonce) {
+            runtime::revert(Error::UniswapV2CorePairAuthorizationUsed);
+        }
+        let type_hash: String = hex::encode(keccak256(authorization_type.as_bytes()));
+        let encoded: String = format!(
+            "{}{}{}{}{}{}{}",
+            type_hash,
+            from,
+            to,
+            value,
+            valid_after,
+            valid_before,
+            hex::encode(nonce)
+        );
+        let digest: [u8; 32] = self.authorization_digest(encoded);
+        if !self.ecrecover(public_key, signature, digest, from) {
+            runtime::revert(Error::UniswapV2CorePairFailedVerification);
+        }
+        authorizations.set_used(&from, &nonce);
+        self.emit(&PAIREvent::AuthorizationUsed {
+            authorizer: from,
+            nonce,
+            pair: Key::from(data::get_package_hash()),
+        });
+        if let Err(e) = self.make_transfer(from, to, value) {
+            runtime::revert(e);
+        }
+    }
+
+    /// Delegates the LP votes of the `public_key` account to `delegatee` with its signature of a
+    /// `Delegation`, so anyone can relay it. The signature uses up the account's permit nonce
+    /// and is only valid until `expiry`, in seconds.
+    fn delegate_by_sig(
+        &mut self,
+        public_key: PublicKey,
+        signature: Signature,
+        delegatee: Key,
+        expiry: u64,
+    ) {
+        if BlockTime::new(expiry.saturating_mul(1000)) < runtime::get_blocktime() {
+            runtime::revert(Error::UniswapV2CorePairExpire);
+        }
+        let delegator: Key = Key::from(public_key.to_account_hash());
+        let nonce: U256 = self.nonce(delegator);
+        let type_hash: String = hex::encode(keccak256(
+            "Delegation(address delegatee,uint256 nonce,uint256 expiry)".as_bytes(),
+        ));
+        let encoded: String = format!("{}{}{}{}", type_hash, delegatee, nonce, expiry);
+        let digest: [u8; 32] = self.authorization_digest(encoded);
+        if !self.ecrecover(public_key, signature, digest, delegator) {
+            runtime::revert(Error::UniswapV2CorePairFailedVerification);
         }
+        self.set_nonce(delegator);
+        self._delegate(delegator, delegatee);
+    }
+
+    /// Returns the digest signed for an authorization whose fields are concatenated in
+    /// `encoded`, hashed under the permit domain the same way `permit` is.
+    fn authorization_digest(&self, encoded: String) -> [u8; 32] {
+        let hash: String = hex::encode(keccak256(encoded.as_bytes()));
+        hash_message(format!("{}{}", data::get_domain_separator(), hash))
     }
 
     fn set_nonce(&mut self, recipient: Key) {
@@ -887,37 +1027,8 @@ pub trait PAIR<Storage: ContractStorage>: ContractContext<Storage> {
         );
     }
 
-    fn make_transfer(&mut self, sender: Key, recipient: Key, amount: U256) -> Result<(), u32> {
-        if sender != recipient && amount != 0.into() {
-            let balances: Balances = Balances::instance();
-            let sender_balance: U256 = balances.get(&sender);
-            let recipient_balance: U256 = balances.get(&recipient);
-            balances.set(
-                &sender,
-                sender_balance
-                    .checked_sub(amount)
-                    .ok_or(Error::UniswapV2CorePairUnderFlow15)
-                    .unwrap_or_revert(),
-            );
-            balances.set(
-                &recipient,
-                recipient_balance
-                    .checked_add(amount)
-                    .ok_or(Error::UniswapV2CorePairOverFlow7)
-                    .unwrap_or_revert(),
-            );
-            let eventpair: Key = Key::from(data::get_package_hash());
-            self.emit(&PAIREvent::Transfer {
-                from: sender,
-                to: recipient,
-                value: amount,
-                pair: eventpair,
-            });
-        }
-        Ok(())
-    }
-
     fn set_treasury_fee_percent(&mut self, treasury_fee: U256) {
+        self.assert_role(FEE_SETTER_ROLE);
         if treasury_fee < 30.into() && treasury_fee > 3.into() {
             data::set_treasury_fee(treasury_fee);
         } else if treasury_fee >= 30.into() {
@@ -928,21 +1039,189 @@ pub trait PAIR<Storage: ContractStorage>: ContractContext<Storage> {
     }
 
     fn set_reserve0(&mut self, reserve0: U128) {
-        data::set_reserve0(reserve0);
+        let reserves: Reserves = data::get_reserves();
+        data::set_reserves(Reserves {
+            reserve0,
+            ..reserves
+        });
     }
 
     fn set_reserve1(&mut self, reserve1: U128) {
-        data::set_reserve1(reserve1);
-    }
-
-    fn total_supply(&mut self) -> U256 {
-        data::total_supply()
+        let reserves: Reserves = data::get_reserves();
+        data::set_reserves(Reserves {
+            reserve1,
+            ..reserves
+        });
     }
 
     fn get_treasury_fee(&mut self) -> U256 {
         data::get_treasury_fee()
     }
 
+    fn get_swap_fee(&mut self) -> u32 {
+        data::get_swap_fee()
+    }
+
+    fn get_curve_type(&mut self) -> u8 {
+        data::get_curve_type()
+    }
+
+    fn get_amplification(&mut self) -> u64 {
+        data::get_amplification()
+    }
+
+    /// Returns the reserve of `token` and the reserve of the other token of the pair.
+    fn reserves_of(&mut self, token: Key) -> (U256, U256) {
+        let (reserve0, reserve1, _block_timestamp_last) = self.get_reserves();
+        let reserve0: U256 = U256::from(reserve0.as_u128());
+        let reserve1: U256 = U256::from(reserve1.as_u128());
+        if token == data::get_token0() {
+            (reserve0, reserve1)
+        } else if token == data::get_token1() {
+            (reserve1, reserve0)
+        } else {
+            runtime::revert(Error::UniswapV2CorePairInvalidToken)
+        }
+    }
+
+    /// Returns how much of the other token a swap of `amount_in` of `token_in` pays out at
+    /// the pair's swap fee and curve, which `swap` accepts as the amount out.
+    fn get_amount_out(&mut self, amount_in: U256, token_in: Key) -> U256 {
+        if amount_in.is_zero() {
+            runtime::revert(Error::UniswapV2CorePairInsufficientInputAmount);
+        }
+        let (reserve_in, reserve_out) = self.reserves_of(token_in);
+        if reserve_in.is_zero() || reserve_out.is_zero() {
+            runtime::revert(Error::UniswapV2CorePairInsufficientLiquidity);
+        }
+        let swap_fee: u32 = data::get_swap_fee();
+        if data::get_curve_type() == data::STABLE_SWAP {
+            stable_swap::get_amount_out(
+                amount_in,
+                reserve_in,
+                reserve_out,
+                data::get_amplification(),
+                swap_fee,
+            )
+            .ok_or(Error::UniswapV2CorePairInvariantNotConverged)
+        } else {
+            constant_product::get_amount_out(amount_in, reserve_in, reserve_out, swap_fee)
+                .ok_or(Error::UniswapV2CorePairQuoteOverFlow)
+        }
+        .unwrap_or_revert()
+    }
+
+    /// Returns how much of the other token a swap must pay in for `amount_out` of
+    /// `token_out` at the pair's swap fee and curve, rounded up so `swap` accepts it.
+    fn get_amount_in(&mut self, amount_out: U256, token_out: Key) -> U256 {
+        if amount_out.is_zero() {
+            runtime::revert(Error::UniswapV2CorePairInsufficientOutputAmount);
+        }
+        let (reserve_out, reserve_in) = self.reserves_of(token_out);
+        if reserve_in.is_zero() || amount_out >= reserve_out {
+            runtime::revert(Error::UniswapV2CorePairInsufficientLiquidity);
+        }
+        let swap_fee: u32 = data::get_swap_fee();
+        if data::get_curve_type() == data::STABLE_SWAP {
+            stable_swap::get_amount_in(
+                amount_out,
+                reserve_in,
+                reserve_out,
+                data::get_amplification(),
+                swap_fee,
+            )
+            .ok_or(Error::UniswapV2CorePairInvariantNotConverged)
+        } else {
+            constant_product::get_amount_in(amount_out, reserve_in, reserve_out, swap_fee)
+                .ok_or(Error::UniswapV2CorePairQuoteOverFlow)
+        }
+        .unwrap_or_revert()
+    }
+
+    /// Returns how much of the other token matches `amount` of `token` at the current
+    /// reserves, which is what `mint` takes without losing part of either deposit.
+    fn quote(&mut self, amount: U256, token: Key) -> U256 {
+        if amount.is_zero() {
+            runtime::revert(Error::UniswapV2CorePairInsufficientInputAmount);
+        }
+        let (reserve, reserve_other) = self.reserves_of(token);
+        if reserve.is_zero() || reserve_other.is_zero() {
+            runtime::revert(Error::UniswapV2CorePairInsufficientLiquidity);
+        }
+        constant_product::quote(amount, reserve, reserve_other)
+            .ok_or(Error::UniswapV2CorePairQuoteOverFlow)
+            .unwrap_or_revert()
+    }
+
+    /// Caches whether the factory has a `fee_to`, so mints and burns don't ask it each time.
+    /// Callable by the factory only, which pushes the flag from `sync_fee_on` after
+    /// `set_fee_to` turns the protocol fee on or off.
+    fn set_fee_on(&mut self, fee_on: bool) {
+        if self.get_caller() != data::get_factory_hash() {
+            runtime::revert(Error::UniswapV2CorePairForbidden);
+        }
+        data::set_fee_on(fee_on);
+    }
+
+    fn get_fee_on(&mut self) -> bool {
+        data::get_fee_on()
+    }
+
+    /// Stops swaps, flash swaps and mints on this pair. Callable by the factory or a PAUSER.
+    fn pause(&mut self) {
+        self.assert_pauser();
+        if !data::get_paused() {
+            data::set_paused(true);
+            self.emit(&PAIREvent::Paused {
+                account: self.get_caller(),
+                pair: Key::from(data::get_package_hash()),
+            });
+        }
+    }
+
+    fn unpause(&mut self) {
+        self.assert_pauser();
+        if data::get_paused() {
+            data::set_paused(false);
+            self.emit(&PAIREvent::Unpaused {
+                account: self.get_caller(),
+                pair: Key::from(data::get_package_hash()),
+            });
+        }
+    }
+
+    fn assert_pauser(&mut self) {
+        let caller: Key = self.get_caller();
+        if caller != data::get_factory_hash() && !self.has_role(PAUSER_ROLE, caller) {
+            runtime::revert(Error::UniswapV2CorePairForbidden);
+        }
+    }
+
+    /// Returns whether the pair is paused on its own or all pairs are paused by the factory.
+    ///
+    /// Unlike `fee_on`, the factory's flag is read with a call on every swap and mint rather
+    /// than pushed to the pairs. Pushing it would take one call per pair, over several deploys
+    /// on a large registry, and pairs not reached yet would keep trading during an exploit.
+    /// A single `pause` on the factory stops every pair in the same block, which is worth the
+    /// one extra call on the paths it guards. A pair paused on its own skips the call.
+    fn paused(&mut self) -> bool {
+        if data::get_paused() {
+            return true;
+        }
+        runtime::call_versioned_contract(
+            data::get_factory_package_hash(),
+            None,
+            "paused",
+            runtime_args! {},
+        )
+    }
+
+    fn when_not_paused(&mut self) {
+        if self.paused() {
+            runtime::revert(Error::UniswapV2CorePairPaused);
+        }
+    }
+
     fn get_minimum_liquidity(&mut self) -> U256 {
         data::get_minimum_liquidity()
     }
@@ -963,22 +1242,47 @@ pub trait PAIR<Storage: ContractStorage>: ContractContext<Storage> {
         data::get_package_hash()
     }
 
+    fn get_domain_separator(&self) -> String {
+        data::get_domain_separator()
+    }
+
+    /// Returns the hex encoded domain separator and permit type hash permits are signed over.
+    /// The domain binds the token name, the permit version, the chain name and the package
+    /// hash, so signatures survive upgrades but not a move to another chain or package.
+    fn get_permit_type_and_domain_separator(
+        &mut self,
+        name: &str,
+        chain_name: &str,
+        package_hash: ContractPackageHash,
+    ) -> (String, String) {
+        let eip_712_domain: &str =
+            "EIP712Domain(string name,string version,string chainName,bytes32 verifyingPackage)";
+        let permit_type: &str =
+            "Permit(address owner,address spender,uint256 value,uint256 nonce,uint256 deadline)";
+        let eip_domain_hash: String = hex::encode(keccak256(eip_712_domain.as_bytes()));
+        let name_hash: String = hex::encode(keccak256(name.as_bytes()));
+        let version_hash: String = hex::encode(keccak256(data::PERMIT_VERSION.as_bytes()));
+        let chain_name_hash: String = hex::encode(keccak256(chain_name.as_bytes()));
+        let concatenated_data: String = format!(
+            "{}{}{}{}{}",
+            eip_domain_hash,
+            name_hash,
+            version_hash,
+            chain_name_hash,
+            hex::encode(package_hash.value())
+        );
+        let domain_separator: String = hex::encode(keccak256(concatenated_data.as_bytes()));
+        let permit_type_hash: String = hex::encode(keccak256(permit_type.as_bytes()));
+        (domain_separator, permit_type_hash)
+    }
+
     fn mint_helper(&mut self, to: Key) -> U256 {
-        let (reserve0, reserve1, _block_timestamp_last) = self.get_reserves(); // gas savings
-        let token0: Key = data::get_token0();
-        let token1: Key = data::get_token1();
+        self.when_not_paused();
+        let (reserve0, reserve1, block_timestamp_last) = self.get_reserves(); // gas savings
+        let token0_hash_add: ContractPackageHash = data::get_token0_package_hash();
+        let token1_hash_add: ContractPackageHash = data::get_token1_package_hash();
         let pair_package_hash1: Key = Key::from(data::get_package_hash());
         let pair_package_hash2: Key = Key::from(data::get_package_hash());
-        let token0_hash_add_array = match token0 {
-            Key::Hash(package) => package,
-            _ => runtime::revert(ApiError::UnexpectedKeyVariant),
-        };
-        let token0_hash_add = ContractPackageHash::new(token0_hash_add_array);
-        let token1_hash_add_array = match token1 {
-            Key::Hash(package) => package,
-            _ => runtime::revert(ApiError::UnexpectedKeyVariant),
-        };
-        let token1_hash_add = ContractPackageHash::new(token1_hash_add_array);
         let balance0: U256 = runtime::call_versioned_contract(
             token0_hash_add,
             None,
@@ -1036,15 +1340,12 @@ pub trait PAIR<Storage: ContractStorage>: ContractContext<Storage> {
         }
         if liquidity > 0.into() {
             self.mint(to, liquidity);
-            self.update(balance0, balance1, reserve0, reserve1);
+            self.update(balance0, balance1, reserve0, reserve1, block_timestamp_last);
             if fee_on {
-                let k_last: U256 = U256::from(
-                    (reserve0
-                        .checked_mul(reserve1)
-                        .ok_or(Error::UniswapV2CorePairMultiplicationOverFlow12)
-                        .unwrap_or_revert())
-                    .as_u128(),
-                ); // reserve0 and reserve1 are up-to-date
+                let k_last: U256 = self
+                    .invariant_k(reserve0, reserve1)
+                    .ok_or(Error::UniswapV2CorePairMultiplicationOverFlow12)
+                    .unwrap_or_revert(); // reserve0 and reserve1 are up-to-date
                 data::set_k_last(k_last);
             }
             data::set_liquidity(liquidity); // return liquidity
@@ -1063,19 +1364,9 @@ pub trait PAIR<Storage: ContractStorage>: ContractContext<Storage> {
     }
 
     fn burn_helper(&mut self, to: Key) -> (U256, U256) {
-        let (reserve0, reserve1, _block_timestamp_last) = self.get_reserves(); // gas savings
-        let token0: Key = data::get_token0();
-        let token1: Key = data::get_token1();
-        let token0_hash_add_array = match token0 {
-            Key::Hash(package) => package,
-            _ => runtime::revert(ApiError::UnexpectedKeyVariant),
-        };
-        let token0_hash_add = ContractPackageHash::new(token0_hash_add_array);
-        let token1_hash_add_array = match token1 {
-            Key::Hash(package) => package,
-            _ => runtime::revert(ApiError::UnexpectedKeyVariant),
-        };
-        let token1_hash_add = ContractPackageHash::new(token1_hash_add_array);
+        let (reserve0, reserve1, block_timestamp_last) = self.get_reserves(); // gas savings
+        let token0_hash_add: ContractPackageHash = data::get_token0_package_hash();
+        let token1_hash_add: ContractPackageHash = data::get_token1_package_hash();
         let balance0: U256 = runtime::call_versioned_contract(
             token0_hash_add,
             None,
@@ -1124,16 +1415,6 @@ pub trait PAIR<Storage: ContractStorage>: ContractContext<Storage> {
                 Err(e) => runtime::revert(e),
             }
 
-            let token0_hash_add_array = match token0 {
-                Key::Hash(package) => package,
-                _ => runtime::revert(ApiError::UnexpectedKeyVariant),
-            };
-            let token0_hash_add = ContractPackageHash::new(token0_hash_add_array);
-            let token1_hash_add_array = match token1 {
-                Key::Hash(package) => package,
-                _ => runtime::revert(ApiError::UnexpectedKeyVariant),
-            };
-            let token1_hash_add = ContractPackageHash::new(token1_hash_add_array);
             let balance0: U256 = runtime::call_versioned_contract(
                 token0_hash_add,
                 None,
@@ -1146,15 +1427,12 @@ pub trait PAIR<Storage: ContractStorage>: ContractContext<Storage> {
                 "balance_of",
                 runtime_args! {"owner" => Key::from(data::get_package_hash())},
             );
-            self.update(balance0, balance1, reserve0, reserve1);
+            self.update(balance0, balance1, reserve0, reserve1, block_timestamp_last);
             if fee_on {
-                let k_last: U256 = U256::from(
-                    (reserve0
-                        .checked_mul(reserve1)
-                        .ok_or(Error::UniswapV2CorePairMultiplicationOverFlow15)
-                        .unwrap_or_revert())
-                    .as_u128(),
-                ); // reserve0 and reserve1 are up-to-date
+                let k_last: U256 = self
+                    .invariant_k(reserve0, reserve1)
+                    .ok_or(Error::UniswapV2CorePairMultiplicationOverFlow15)
+                    .unwrap_or_revert(); // reserve0 and reserve1 are up-to-date
                 data::set_k_last(k_last);
             }
             data::set_amount0(amount0);
@@ -1175,35 +1453,17 @@ pub trait PAIR<Storage: ContractStorage>: ContractContext<Storage> {
     }
 
     // if fee is on, mint liquidity equivalent to 1/6th of the growth in sqrt(k)
+    // fee_to is only looked up when there is liquidity to mint to it
     fn mint_fee(&mut self, reserve0: U128, reserve1: U128) -> bool {
-        let factory_hash: Key = self.get_factory_hash();
-        let factory_hash_add_array = match factory_hash {
-            Key::Hash(package) => package,
-            _ => runtime::revert(ApiError::UnexpectedKeyVariant),
-        };
-        let factory_hash_add = ContractPackageHash::new(factory_hash_add_array);
-        let fee_to: Key =
-            runtime::call_versioned_contract(factory_hash_add, None, "fee_to", runtime_args! {});
-        let mut fee_on: bool = false;
-        if fee_to
-            != Key::from_formatted_str(
-                "account-hash-0000000000000000000000000000000000000000000000000000000000000000",
-            )
-            .unwrap()
-        {
-            fee_on = true;
-        }
+        let fee_on: bool = data::get_fee_on();
         let k_last: U256 = data::get_k_last(); // gas savings
         let treasury_fee: U256 = data::get_treasury_fee();
         if fee_on {
             if k_last != 0.into() {
-                let mul_val: U256 = U256::from(
-                    (reserve1
-                        .checked_mul(reserve0)
-                        .ok_or(Error::UniswapV2CorePairMultiplicationOverFlow16)
-                        .unwrap_or_revert())
-                    .as_u128(),
-                );
+                let mul_val: U256 = self
+                    .invariant_k(reserve0, reserve1)
+                    .ok_or(Error::UniswapV2CorePairMultiplicationOverFlow16)
+                    .unwrap_or_revert();
                 let root_k: U256 = self.sqrt(mul_val);
                 let root_k_last: U256 = self.sqrt(k_last);
                 if root_k > root_k_last {
@@ -1226,7 +1486,16 @@ pub trait PAIR<Storage: ContractStorage>: ContractContext<Storage> {
                     if denominator > U256::from(0) {
                         let liquidity: U256 = numerator / denominator;
                         if liquidity > 0.into() {
-                            self.mint(fee_to, liquidity)
+                            let fee_to: Key = runtime::call_versioned_contract(
+                                data::get_factory_package_hash(),
+                                None,
+                                "fee_to",
+                                runtime_args! {},
+                            );
+                            // fee_to is cleared but sync_fee_on has not reached this pair yet
+                            if fee_to != zero_address() {
+                                self.mint(fee_to, liquidity)
+                            }
                         }
                     } else {
                         //UniswapV2: DENOMINATOR IS ZERO
@@ -1240,22 +1509,86 @@ pub trait PAIR<Storage: ContractStorage>: ContractContext<Storage> {
         return fee_on;
     }
 
-    fn initialize(&mut self, token0: Key, token1: Key, factory_hash: Key) {
+    /// k tracked by the protocol fee, reserve0 * reserve1 on constant product
+    /// pairs and the square of the StableSwap invariant on stable pairs
+    fn invariant_k(&mut self, reserve0: U128, reserve1: U128) -> Option<U256> {
+        if data::get_curve_type() == data::STABLE_SWAP {
+            let d: U256 = stable_swap::compute_d(
+                U256::from(reserve0.as_u128()),
+                U256::from(reserve1.as_u128()),
+                data::get_amplification(),
+            )?;
+            d.checked_mul(d)
+        } else {
+            // both reserves fit in U128, so their product always fits in U256
+            U256::from(reserve0.as_u128()).checked_mul(U256::from(reserve1.as_u128()))
+        }
+    }
+
+    fn initialize(
+        &mut self,
+        token0: Key,
+        token1: Key,
+        factory_hash: Key,
+        swap_fee: u32,
+        curve_type: u8,
+        amplification: u64,
+    ) {
         let factory_hash_getter: Key = self.get_factory_hash();
         if factory_hash == factory_hash_getter {
+            let valid_curve: bool = match curve_type {
+                data::CONSTANT_PRODUCT => amplification == 0,
+                data::STABLE_SWAP => {
+                    amplification > 0 && amplification <= stable_swap::MAX_AMPLIFICATION
+                }
+                _ => false,
+            };
+            if !valid_curve {
+                runtime::revert(Error::UniswapV2CorePairInvalidCurve);
+            }
             data::set_token0(token0);
             data::set_token1(token1);
+            data::set_token0_package_hash(to_package_hash(token0));
+            data::set_token1_package_hash(to_package_hash(token1));
+            data::set_swap_fee(swap_fee);
+            data::set_curve_type(curve_type);
+            data::set_amplification(amplification);
         } else {
             //(UniswapV2: FORBIDDEN)
             runtime::revert(Error::UniswapV2CorePairForbidden);
         }
     }
 
+    /// Reads the reserves and their timestamp with a single storage read.
     fn get_reserves(&mut self) -> (U128, U128, u64) {
-        let reserve0: U128 = data::get_reserve0();
-        let reserve1: U128 = data::get_reserve1();
-        let block_timestamp_last: u64 = data::get_block_timestamp_last();
-        return (reserve0, reserve1, block_timestamp_last);
+        let reserves: Reserves = data::get_reserves();
+        (
+            reserves.reserve0,
+            reserves.reserve1,
+            reserves.block_timestamp_last,
+        )
+    }
+
+    /// Returns everything a client needs to read the pair in one consistent snapshot.
+    fn get_state(&mut self) -> PairState {
+        let (reserve0, reserve1, block_timestamp_last) = self.get_reserves();
+        let price_cumulatives: PriceCumulatives = data::get_price_cumulatives();
+        PairState {
+            token0: data::get_token0(),
+            token1: data::get_token1(),
+            reserve0,
+            reserve1,
+            block_timestamp_last,
+            total_supply: self.total_supply(),
+            treasury_fee: data::get_treasury_fee(),
+            k_last: data::get_k_last(),
+            price0_cumulative_last: price_cumulatives.price0_cumulative_last,
+            price1_cumulative_last: price_cumulatives.price1_cumulative_last,
+            factory_hash: data::get_factory_hash(),
+            swap_fee: data::get_swap_fee(),
+            curve_type: data::get_curve_type(),
+            amplification: data::get_amplification(),
+        }
     }
 
     fn sqrt(&mut self, y: U256) -> U256 {
@@ -1326,35 +1659,48 @@ pub trait PAIR<Storage: ContractStorage>: ContractContext<Storage> {
         return general_price_cumulative_last;
     }
 
-    fn update(&mut self, balance0: U256, balance1: U256, reserve0: U128, reserve1: U128) {
-        let one: U128 = 1.into();
-        let overflow_check: U256 = U256::from(
-            ((U128::MAX)
-                .checked_sub(one)
-                .ok_or(Error::UniswapV2CorePairUnderFlow20)
-                .unwrap_or_revert())
-            .as_u128(),
-        );
+    /// Stores the new reserves and accumulates prices over the time since `block_timestamp_last`,
+    /// writing one storage key for the reserves and, when prices moved on, one for the cumulatives.
+    fn update(
+        &mut self,
+        balance0: U256,
+        balance1: U256,
+        reserve0: U128,
+        reserve1: U128,
+        block_timestamp_last: u64,
+    ) {
+        // reserves are stored as U128, larger balances have to be skimmed first
+        let overflow_check: U256 = U256::from(u128::MAX);
         if balance0 <= overflow_check && balance1 <= overflow_check {
             let block_timestamp: u64 = runtime::get_blocktime().into();
-            let block_timestamp_last: u64 = data::get_block_timestamp_last();
             let time_elapsed: u64 = block_timestamp - block_timestamp_last; // overflow is desired
             if time_elapsed > 0 && reserve0 != 0.into() && reserve1 != 0.into() {
                 // * never overflows, and + overflow is desired
-                let price0_cumulative_last: U256 = data::get_price0_cumulative_last();
-                let price1_cumulative_last: U256 = data::get_price1_cumulative_last();
-                let price0_cumulative_last_result: U256 =
-                    self.encode_uqdiv(reserve1, reserve0, price0_cumulative_last, time_elapsed);
-                data::set_price0_cumulative_last(price0_cumulative_last_result);
-                let price1_cumulative_last_result: U256 =
-                    self.encode_uqdiv(reserve0, reserve1, price1_cumulative_last, time_elapsed);
-                data::set_price1_cumulative_last(price1_cumulative_last_result);
+                let price_cumulatives: PriceCumulatives = data::get_price_cumulatives();
+                let price0_cumulative_last_result: U256 = self.encode_uqdiv(
+                    reserve1,
+                    reserve0,
+                    price_cumulatives.price0_cumulative_last,
+                    time_elapsed,
+                );
+                let price1_cumulative_last_result: U256 = self.encode_uqdiv(
+                    reserve0,
+                    reserve1,
+                    price_cumulatives.price1_cumulative_last,
+                    time_elapsed,
+                );
+                data::set_price_cumulatives(PriceCumulatives {
+                    price0_cumulative_last: price0_cumulative_last_result,
+                    price1_cumulative_last: price1_cumulative_last_result,
+                });
             }
             let reserve0_conversion: U128 = U128::from(balance0.as_u128());
             let reserve1_conversion: U128 = U128::from(balance1.as_u128());
-            data::set_reserve0(reserve0_conversion);
-            data::set_reserve1(reserve1_conversion);
-            data::set_block_timestamp_last(block_timestamp);
+            data::set_reserves(Reserves {
+                reserve0: reserve0_conversion,
+                reserve1: reserve1_conversion,
+                block_timestamp_last: block_timestamp,
+            });
             let eventpair: Key = Key::from(data::get_package_hash());
             self.emit(&PAIREvent::Sync {
                 reserve0: reserve0_conversion,
@@ -1366,6 +1712,43 @@ pub trait PAIR<Storage: ContractStorage>: ContractContext<Storage> {
             runtime::revert(Error::UniswapV2CorePairOverFlow12);
         }
     }
+    /// Maps the shared token events onto the pair's own event layout, which
+    /// carries the `pair` field and names transfers `transfer`.
+    fn emit_token_event(&mut self, cep18_event: &CEP18Event) {
+        match cep18_event {
+            CEP18Event::Approval {
+                owner,
+                spender,
+                value,
+            } => self.emit(&PAIREvent::Approval {
+                owner: *owner,
+                spender: *spender,
+                value: *value,
+            }),
+            CEP18Event::Transfer { from, to, value } => self.emit(&PAIREvent::Transfer {
+                from: *from,
+                to: *to,
+                value: *value,
+                pair: Key::from(data::get_package_hash()),
+            }),
+            // LP tokens keep one transfer event per recipient
+            CEP18Event::BatchTransfer {
+                from,
+                recipients,
+                amounts,
+            } => {
+                for (to, value) in recipients.iter().zip(amounts.iter()) {
+                    self.emit(&PAIREvent::Transfer {
+                        from: *from,
+                        to: *to,
+                        value: *value,
+                        pair: Key::from(data::get_package_hash()),
+                    });
+                }
+            }
+        }
+    }
+
     fn emit(&mut self, pair_event: &PAIREvent) {
         let mut events = Vec::new();
         let package = data::get_package_hash();
@@ -1466,6 +1849,57 @@ pub trait PAIR<Storage: ContractStorage>: ContractContext<Storage> {
                 event.insert("pair", pair.to_string());
                 events.push(event);
             }
+            PAIREvent::Paused { account, pair } => {
+                let mut event = BTreeMap::new();
+                event.insert("contract_package_hash", package.to_string());
+                event.insert("event_type", pair_event.type_name());
+                event.insert("account", account.to_string());
+                event.insert("pair", pair.to_string());
+                events.push(event);
+            }
+            PAIREvent::Unpaused { account, pair } => {
+                let mut event = BTreeMap::new();
+                event.insert("contract_package_hash", package.to_string());
+                event.insert("event_type", pair_event.type_name());
+                event.insert("account", account.to_string());
+                event.insert("pair", pair.to_string());
+                events.push(event);
+            }
+            PAIREvent::PermitUsed {
+                owner,
+                spender,
+                value,
+                nonce,
+                pair,
+            } => {
+                let mut event = BTreeMap::new();
+                event.insert("contract_package_hash", package.to_string());
+                event.insert("event_type", pair_event.type_name());
+                event.insert("owner", owner.to_string());
+                event.insert("spender", spender.to_string());
+                event.insert("value", value.to_string());
+                event.insert("nonce", nonce.to_string());
+                event.insert("pair", pair.to_string());
+                events.push(event);
+            }
+            PAIREvent::AuthorizationUsed {
+                authorizer,
+                nonce,
+                pair,
+            }
+            | PAIREvent::AuthorizationCanceled {
+                authorizer,
+                nonce,
+                pair,
+            } => {
+                let mut event = BTreeMap::new();
+                event.insert("contract_package_hash", package.to_string());
+                event.insert("event_type", pair_event.type_name());
+                event.insert("authorizer", authorizer.to_string());
+                event.insert("nonce", hex::encode(nonce));
+                event.insert("pair", pair.to_string());
+                events.push(event);
+            }
         };
         for event in events {
             let _: URef = storage::new_uref(event);
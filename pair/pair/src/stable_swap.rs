@@ -0,0 +1,173 @@
+//! Curve StableSwap invariant for two coins.
+//!
+//! `A * n^n * (x + y) + D = A * D * n^n + D^(n + 1) / (n^n * x * y)` with `n = 2`.
+//! Intermediate products, up to `D^3`, are taken in U512 so reserves up to U128
+//! scaled by the swap fee base cannot overflow.
+
+use casper_types::{U256, U512};
+
+/// Number of coins in a pair.
+const N_COINS: u64 = 2;
+/// Newton iterations before giving up on convergence.
+const MAX_ITERATIONS: usize = 255;
+/// Highest amplification coefficient a stable pair accepts.
+pub const MAX_AMPLIFICATION: u64 = 1_000_000;
+/// Denominator of the swap fee, which is given in basis points.
+pub const FEE_BASE: u64 = 10000;
+
+pub(crate) fn to_u512(value: U256) -> U512 {
+    let mut bytes = [0u8; 32];
+    value.to_little_endian(&mut bytes);
+    U512::from_little_endian(&bytes)
+}
+
+pub(crate) fn to_u256(value: U512) -> Option<U256> {
+    let mut bytes = [0u8; 64];
+    value.to_little_endian(&mut bytes);
+    if bytes[32..].iter().any(|byte| *byte != 0) {
+        return None;
+    }
+    Some(U256::from_little_endian(&bytes[..32]))
+}
+
+fn abs_diff(a: U512, b: U512) -> U512 {
+    if a > b {
+        a - b
+    } else {
+        b - a
+    }
+}
+
+/// Returns the invariant `D` of balances `x` and `y`, or `None` if a balance is
+/// zero while the other is not, the amplification is zero or Newton's method
+/// does not converge.
+pub fn compute_d(x: U256, y: U256, amplification: u64) -> Option<U256> {
+    if x.is_zero() && y.is_zero() {
+        return Some(U256::zero());
+    }
+    if x.is_zero() || y.is_zero() || amplification == 0 {
+        return None;
+    }
+    let n = U512::from(N_COINS);
+    let x = to_u512(x);
+    let y = to_u512(y);
+    let sum = x.checked_add(y)?;
+    let ann = U512::from(amplification).checked_mul(n * n)?;
+    let mut d = sum;
+    for _ in 0..MAX_ITERATIONS {
+        // one division keeps Newton's method from oscillating on very uneven balances
+        let d_p = d
+            .checked_mul(d)?
+            .checked_mul(d)?
+            .checked_div(x.checked_mul(y)?.checked_mul(n * n)?)?;
+        let d_prev = d;
+        let numerator = ann
+            .checked_mul(sum)?
+            .checked_add(d_p.checked_mul(n)?)?
+            .checked_mul(d)?;
+        let denominator = (ann - U512::one())
+            .checked_mul(d)?
+            .checked_add((n + U512::one()).checked_mul(d_p)?)?;
+        d = numerator.checked_div(denominator)?;
+        if abs_diff(d, d_prev) <= U512::one() {
+            return to_u256(d);
+        }
+    }
+    None
+}
+
+/// Returns the balance of the other coin that keeps the invariant at `d` when
+/// one coin's balance is `x`.
+pub fn compute_y(x: U256, d: U256, amplification: u64) -> Option<U256> {
+    if x.is_zero() || amplification == 0 {
+        return None;
+    }
+    let n = U512::from(N_COINS);
+    let x = to_u512(x);
+    let d = to_u512(d);
+    let ann = U512::from(amplification).checked_mul(n * n)?;
+    let c = d
+        .checked_mul(d)?
+        .checked_mul(d)?
+        .checked_div(x.checked_mul(n)?.checked_mul(ann)?.checked_mul(n)?)?;
+    let b = x.checked_add(d.checked_div(ann)?)?;
+    let mut y = d;
+    for _ in 0..MAX_ITERATIONS {
+        let y_prev = y;
+        let numerator = y.checked_mul(y)?.checked_add(c)?;
+        let denominator = y.checked_mul(n)?.checked_add(b)?.checked_sub(d)?;
+        y = numerator.checked_div(denominator)?;
+        if abs_diff(y, y_prev) <= U512::one() {
+            return to_u256(y);
+        }
+    }
+    None
+}
+
+/// Returns whether fee adjusted balances keep at least the invariant of the
+/// reserves they replace. Both sides must be scaled by the same factor.
+pub fn invariant_holds(
+    balance0_adjusted: U256,
+    balance1_adjusted: U256,
+    reserve0: U256,
+    reserve1: U256,
+    amplification: u64,
+) -> Option<bool> {
+    let d_after = compute_d(balance0_adjusted, balance1_adjusted, amplification)?;
+    let d_before = compute_d(reserve0, reserve1, amplification)?;
+    Some(d_after >= d_before)
+}
+
+/// Returns how much of the other coin a swap of `amount_in` pays out. The
+/// invariant is solved at the same scale as the pair's check and the result is
+/// lowered by one unit to absorb rounding.
+pub fn get_amount_out(
+    amount_in: U256,
+    reserve_in: U256,
+    reserve_out: U256,
+    amplification: u64,
+    swap_fee: u32,
+) -> Option<U256> {
+    let fee_base = U256::from(FEE_BASE);
+    let reserve_in = reserve_in.checked_mul(fee_base)?;
+    let reserve_out = reserve_out.checked_mul(fee_base)?;
+    let d = compute_d(reserve_in, reserve_out, amplification)?;
+    let amount_in_after_fee = amount_in.checked_mul(fee_base.checked_sub(swap_fee.into())?)?;
+    let y = compute_y(
+        reserve_in.checked_add(amount_in_after_fee)?,
+        d,
+        amplification,
+    )?;
+    let amount_out = reserve_out.saturating_sub(y) / fee_base;
+    Some(amount_out.saturating_sub(U256::one()))
+}
+
+/// Returns how much of one coin a swap must pay in for `amount_out` of the
+/// other. The invariant is solved at the same scale as the pair's check and the
+/// result is raised by one unit to absorb rounding.
+pub fn get_amount_in(
+    amount_out: U256,
+    reserve_in: U256,
+    reserve_out: U256,
+    amplification: u64,
+    swap_fee: u32,
+) -> Option<U256> {
+    if amount_out >= reserve_out {
+        return None;
+    }
+    let fee_base = U256::from(FEE_BASE);
+    let reserve_in = reserve_in.checked_mul(fee_base)?;
+    let reserve_out = reserve_out.checked_mul(fee_base)?;
+    let d = compute_d(reserve_in, reserve_out, amplification)?;
+    let x = compute_y(
+        reserve_out.checked_sub(amount_out.checked_mul(fee_base)?)?,
+        d,
+        amplification,
+    )?;
+    let fee_factor = fee_base.checked_sub(swap_fee.into())?;
+    let amount_in_after_fee = x.saturating_sub(reserve_in);
+    let amount_in = amount_in_after_fee
+        .checked_add(fee_factor.checked_sub(U256::one())?)?
+        .checked_div(fee_factor)?;
+    amount_in.checked_add(U256::one())
+}
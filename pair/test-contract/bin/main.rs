@@ -2,7 +2,7 @@
 #![no_std]
 
 extern crate alloc;
-use alloc::{collections::BTreeSet, format, vec};
+use alloc::{collections::BTreeSet, format, string::String, vec};
 
 use casper_contract::{
     contract_api::{runtime, storage},
@@ -11,8 +11,9 @@ use casper_contract::{
 use casper_types::{
     contracts::{ContractHash, ContractPackageHash},
     runtime_args, ApiError, CLTyped, EntryPoint, EntryPointAccess, EntryPointType, EntryPoints,
-    Group, Key, Parameter, RuntimeArgs, URef, U256,
+    Group, Key, Parameter, RuntimeArgs, URef, U128, U256,
 };
+use uniswap_types::PairState;
 
 pub mod mappings;
 
@@ -174,6 +175,106 @@ fn approve() {
     let _ret: () = runtime::call_contract(pair_address, "approve", args);
 }
 
+#[no_mangle]
+fn get_amount_out() {
+    let pair_address: ContractHash = mappings::get_key(&mappings::pair_key());
+    let amount_in: U256 = runtime::get_named_arg("amount_in");
+    let token_in: Key = runtime::get_named_arg("token_in");
+    let args: RuntimeArgs = runtime_args! {
+        "amount_in" => amount_in,
+        "token_in" => token_in,
+    };
+
+    let ret: U256 = runtime::call_contract(pair_address, "get_amount_out", args);
+    mappings::set_key(&mappings::amount_out_key(), ret);
+}
+
+#[no_mangle]
+fn get_amount_in() {
+    let pair_address: ContractHash = mappings::get_key(&mappings::pair_key());
+    let amount_out: U256 = runtime::get_named_arg("amount_out");
+    let token_out: Key = runtime::get_named_arg("token_out");
+    let args: RuntimeArgs = runtime_args! {
+        "amount_out" => amount_out,
+        "token_out" => token_out,
+    };
+
+    let ret: U256 = runtime::call_contract(pair_address, "get_amount_in", args);
+    mappings::set_key(&mappings::amount_in_key(), ret);
+}
+
+#[no_mangle]
+fn quote() {
+    let pair_address: ContractHash = mappings::get_key(&mappings::pair_key());
+    let amount: U256 = runtime::get_named_arg("amount");
+    let token: Key = runtime::get_named_arg("token");
+    let args: RuntimeArgs = runtime_args! {
+        "amount" => amount,
+        "token" => token,
+    };
+
+    let ret: U256 = runtime::call_contract(pair_address, "quote", args);
+    mappings::set_key(&mappings::quote_key(), ret);
+}
+
+#[no_mangle]
+fn get_state() {
+    let pair_address: ContractHash = mappings::get_key(&mappings::pair_key());
+
+    let ret: PairState = runtime::call_contract(pair_address, "get_state", runtime_args! {});
+    mappings::set_key(&mappings::state_key(), ret);
+}
+
+#[no_mangle]
+fn get_reserves() {
+    let pair_address: ContractHash = mappings::get_key(&mappings::pair_key());
+
+    let ret: (U128, U128, u64) =
+        runtime::call_contract(pair_address, "get_reserves", runtime_args! {});
+    mappings::set_key(&mappings::reserves_key(), ret);
+}
+
+#[no_mangle]
+fn get_votes() {
+    let pair_address: ContractHash = mappings::get_key(&mappings::pair_key());
+    let account: Key = runtime::get_named_arg("account");
+
+    let ret: U256 = runtime::call_contract(
+        pair_address,
+        "get_votes",
+        runtime_args! {"account" => account},
+    );
+    mappings::set_key(&mappings::get_votes_key(), ret);
+}
+
+#[no_mangle]
+fn get_past_votes() {
+    let pair_address: ContractHash = mappings::get_key(&mappings::pair_key());
+    let account: Key = runtime::get_named_arg("account");
+    let timestamp: u64 = runtime::get_named_arg("timestamp");
+    let args: RuntimeArgs = runtime_args! {
+        "account" => account,
+        "timestamp" => timestamp,
+    };
+
+    let ret: U256 = runtime::call_contract(pair_address, "get_past_votes", args);
+    mappings::set_key(&mappings::get_past_votes_key(), ret);
+}
+
+// records the LP tokens transfer_and_call sends, rejecting those sent with "reject"
+#[no_mangle]
+fn on_token_received() {
+    let from: Key = runtime::get_named_arg("from");
+    let amount: U256 = runtime::get_named_arg("amount");
+    let data: String = runtime::get_named_arg("data");
+    if data == "reject" {
+        runtime::revert(ApiError::User(0));
+    }
+    mappings::set_key(&mappings::received_from_key(), from);
+    mappings::set_key(&mappings::received_amount_key(), amount);
+    mappings::set_key(&mappings::received_data_key(), data);
+}
+
 fn get_entry_points() -> EntryPoints {
     let mut entry_points = EntryPoints::new();
     entry_points.add_entry_point(EntryPoint::new(
@@ -277,6 +378,79 @@ fn get_entry_points() -> EntryPoints {
         EntryPointAccess::Public,
         EntryPointType::Contract,
     ));
+    entry_points.add_entry_point(EntryPoint::new(
+        "get_amount_out",
+        vec![
+            Parameter::new("amount_in", U256::cl_type()),
+            Parameter::new("token_in", Key::cl_type()),
+        ],
+        <()>::cl_type(),
+        EntryPointAccess::Public,
+        EntryPointType::Contract,
+    ));
+    entry_points.add_entry_point(EntryPoint::new(
+        "get_amount_in",
+        vec![
+            Parameter::new("amount_out", U256::cl_type()),
+            Parameter::new("token_out", Key::cl_type()),
+        ],
+        <()>::cl_type(),
+        EntryPointAccess::Public,
+        EntryPointType::Contract,
+    ));
+    entry_points.add_entry_point(EntryPoint::new(
+        "quote",
+        vec![
+            Parameter::new("amount", U256::cl_type()),
+            Parameter::new("token", Key::cl_type()),
+        ],
+        <()>::cl_type(),
+        EntryPointAccess::Public,
+        EntryPointType::Contract,
+    ));
+    entry_points.add_entry_point(EntryPoint::new(
+        "get_state",
+        vec![],
+        <()>::cl_type(),
+        EntryPointAccess::Public,
+        EntryPointType::Contract,
+    ));
+    entry_points.add_entry_point(EntryPoint::new(
+        "get_reserves",
+        vec![],
+        <()>::cl_type(),
+        EntryPointAccess::Public,
+        EntryPointType::Contract,
+    ));
+    entry_points.add_entry_point(EntryPoint::new(
+        "get_votes",
+        vec![Parameter::new("account", Key::cl_type())],
+        <()>::cl_type(),
+        EntryPointAccess::Public,
+        EntryPointType::Contract,
+    ));
+    entry_points.add_entry_point(EntryPoint::new(
+        "get_past_votes",
+        vec![
+            Parameter::new("account", Key::cl_type()),
+            Parameter::new("timestamp", u64::cl_type()),
+        ],
+        <()>::cl_type(),
+        EntryPointAccess::Public,
+        EntryPointType::Contract,
+    ));
+    entry_points.add_entry_point(EntryPoint::new(
+        "on_token_received",
+        vec![
+            Parameter::new("operator", Key::cl_type()),
+            Parameter::new("from", Key::cl_type()),
+            Parameter::new("amount", U256::cl_type()),
+            Parameter::new("data", String::cl_type()),
+        ],
+        <()>::cl_type(),
+        EntryPointAccess::Public,
+        EntryPointType::Contract,
+    ));
     entry_points
 }
 
@@ -57,3 +57,33 @@ pub fn transfer_from_key() -> String {
 pub fn allowance() -> String {
     format!("allowance")
 }
+pub fn amount_out_key() -> String {
+    format!("amount_out")
+}
+pub fn amount_in_key() -> String {
+    format!("amount_in")
+}
+pub fn quote_key() -> String {
+    format!("quote")
+}
+pub fn state_key() -> String {
+    format!("state")
+}
+pub fn reserves_key() -> String {
+    format!("reserves")
+}
+pub fn received_from_key() -> String {
+    format!("received_from")
+}
+pub fn received_amount_key() -> String {
+    format!("received_amount")
+}
+pub fn received_data_key() -> String {
+    format!("received_data")
+}
+pub fn get_votes_key() -> String {
+    format!("get_votes")
+}
+pub fn get_past_votes_key() -> String {
+    format!("get_past_votes")
+}
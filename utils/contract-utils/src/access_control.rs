@@ -0,0 +1,257 @@
+use alloc::{
+    collections::BTreeMap,
+    string::{String, ToString},
+    vec::Vec,
+};
+use casper_contract::{
+    contract_api::{runtime, storage},
+    unwrap_or_revert::UnwrapOrRevert,
+};
+use casper_types::{ApiError, ContractPackageHash, Key, URef};
+
+use crate::{key_and_value_to_str, ContractContext, ContractStorage, Dict};
+
+pub const DEFAULT_ADMIN_ROLE: &str = "DEFAULT_ADMIN";
+pub const MINTER_ROLE: &str = "MINTER";
+pub const PAUSER_ROLE: &str = "PAUSER";
+pub const FEE_SETTER_ROLE: &str = "FEE_SETTER";
+pub const WHITELISTER_ROLE: &str = "WHITELISTER";
+pub const SNAPSHOT_ROLE: &str = "SNAPSHOT";
+
+const ROLES_DICT: &str = "roles";
+const ROLE_ADMINS_DICT: &str = "role_admins";
+
+pub enum AccessControlEvent {
+    RoleGranted {
+        role: String,
+        account: Key,
+        sender: Key,
+    },
+    RoleRevoked {
+        role: String,
+        account: Key,
+        sender: Key,
+    },
+    RoleAdminChanged {
+        role: String,
+        previous_admin_role: String,
+        new_admin_role: String,
+    },
+}
+
+impl AccessControlEvent {
+    pub fn type_name(&self) -> String {
+        match self {
+            AccessControlEvent::RoleGranted {
+                role: _,
+                account: _,
+                sender: _,
+            } => "role_granted",
+            AccessControlEvent::RoleRevoked {
+                role: _,
+                account: _,
+                sender: _,
+            } => "role_revoked",
+            AccessControlEvent::RoleAdminChanged {
+                role: _,
+                previous_admin_role: _,
+                new_admin_role: _,
+            } => "role_admin_changed",
+        }
+        .to_string()
+    }
+}
+
+#[repr(u16)]
+pub enum Error {
+    /// 65,663 for (UniswapV2 Core Access Control Missing Role)
+    UniswapV2CoreAccessControlMissingRole = 127,
+    /// 65,664 for (UniswapV2 Core Access Control Can Only Renounce For Self)
+    UniswapV2CoreAccessControlCanOnlyRenounceForSelf = 128,
+}
+
+impl From<Error> for ApiError {
+    fn from(error: Error) -> ApiError {
+        ApiError::User(error as u16)
+    }
+}
+
+/// Role based access control shared by the contracts in this workspace.
+///
+/// Every role is administered by another role, `DEFAULT_ADMIN` unless changed
+/// with `_set_role_admin`. Holders of a role's admin role may grant and revoke it.
+pub trait AccessControl<Storage: ContractStorage>: ContractContext<Storage> {
+    fn init(&mut self) {
+        Roles::init();
+        RoleAdmins::init();
+    }
+
+    /// Whether the role dictionaries exist. Contracts installed before access control
+    /// was added create them while migrating.
+    fn access_control_initialized(&self) -> bool {
+        runtime::has_key(ROLES_DICT) && runtime::has_key(ROLE_ADMINS_DICT)
+    }
+
+    fn has_role(&self, role: &str, account: Key) -> bool {
+        Roles::instance().has(role, &account)
+    }
+
+    fn get_role_admin(&self, role: &str) -> String {
+        RoleAdmins::instance().get(role)
+    }
+
+    fn assert_role(&self, role: &str) {
+        if !self.has_role(role, self.get_caller()) {
+            runtime::revert(Error::UniswapV2CoreAccessControlMissingRole);
+        }
+    }
+
+    fn grant_role(&mut self, role: String, account: Key) {
+        self.assert_role(&self.get_role_admin(&role));
+        self._grant_role(role, account);
+    }
+
+    fn revoke_role(&mut self, role: String, account: Key) {
+        self.assert_role(&self.get_role_admin(&role));
+        self._revoke_role(role, account);
+    }
+
+    fn renounce_role(&mut self, role: String, account: Key) {
+        if account != self.get_caller() {
+            runtime::revert(Error::UniswapV2CoreAccessControlCanOnlyRenounceForSelf);
+        }
+        self._revoke_role(role, account);
+    }
+
+    /// Grants `role` without checking the caller. Meant for constructors and migrations.
+    fn _grant_role(&mut self, role: String, account: Key) {
+        if !self.has_role(&role, account) {
+            Roles::instance().grant(&role, &account);
+            self.emit_access_control_event(&AccessControlEvent::RoleGranted {
+                role,
+                account,
+                sender: self.get_caller(),
+            });
+        }
+    }
+
+    fn _revoke_role(&mut self, role: String, account: Key) {
+        if self.has_role(&role, account) {
+            Roles::instance().revoke(&role, &account);
+            self.emit_access_control_event(&AccessControlEvent::RoleRevoked {
+                role,
+                account,
+                sender: self.get_caller(),
+            });
+        }
+    }
+
+    fn _set_role_admin(&mut self, role: String, admin_role: String) {
+        let previous_admin_role: String = self.get_role_admin(&role);
+        RoleAdmins::instance().set(&role, admin_role.clone());
+        self.emit_access_control_event(&AccessControlEvent::RoleAdminChanged {
+            role,
+            previous_admin_role,
+            new_admin_role: admin_role,
+        });
+    }
+
+    fn emit_access_control_event(&mut self, access_control_event: &AccessControlEvent) {
+        let mut events = Vec::new();
+        let package: ContractPackageHash = self.self_addr().into_hash().unwrap_or_revert().into();
+        match access_control_event {
+            AccessControlEvent::RoleGranted {
+                role,
+                account,
+                sender,
+            }
+            | AccessControlEvent::RoleRevoked {
+                role,
+                account,
+                sender,
+            } => {
+                let mut event = BTreeMap::new();
+                event.insert("contract_package_hash", package.to_string());
+                event.insert("event_type", access_control_event.type_name());
+                event.insert("role", role.clone());
+                event.insert("account", account.to_string());
+                event.insert("sender", sender.to_string());
+                events.push(event);
+            }
+            AccessControlEvent::RoleAdminChanged {
+                role,
+                previous_admin_role,
+                new_admin_role,
+            } => {
+                let mut event = BTreeMap::new();
+                event.insert("contract_package_hash", package.to_string());
+                event.insert("event_type", access_control_event.type_name());
+                event.insert("role", role.clone());
+                event.insert("previous_admin_role", previous_admin_role.clone());
+                event.insert("new_admin_role", new_admin_role.clone());
+                events.push(event);
+            }
+        };
+        for event in events {
+            let _: URef = storage::new_uref(event);
+        }
+    }
+}
+
+struct Roles {
+    dict: Dict,
+}
+
+impl Roles {
+    pub fn instance() -> Roles {
+        Roles {
+            dict: Dict::instance(ROLES_DICT),
+        }
+    }
+
+    pub fn init() {
+        Dict::init(ROLES_DICT)
+    }
+
+    pub fn has(&self, role: &str, account: &Key) -> bool {
+        self.dict
+            .get(&key_and_value_to_str(account, &role.to_string()))
+            .unwrap_or_default()
+    }
+
+    pub fn grant(&self, role: &str, account: &Key) {
+        self.dict
+            .set(&key_and_value_to_str(account, &role.to_string()), true);
+    }
+
+    pub fn revoke(&self, role: &str, account: &Key) {
+        self.dict
+            .remove::<bool>(&key_and_value_to_str(account, &role.to_string()));
+    }
+}
+
+struct RoleAdmins {
+    dict: Dict,
+}
+
+impl RoleAdmins {
+    pub fn instance() -> RoleAdmins {
+        RoleAdmins {
+            dict: Dict::instance(ROLE_ADMINS_DICT),
+        }
+    }
+
+    pub fn init() {
+        Dict::init(ROLE_ADMINS_DICT)
+    }
+
+    pub fn get(&self, role: &str) -> String {
+        self.dict
+            .get(role)
+            .unwrap_or_else(|| DEFAULT_ADMIN_ROLE.to_string())
+    }
+
+    pub fn set(&self, role: &str, admin_role: String) {
+        self.dict.set(role, admin_role);
+    }
+}
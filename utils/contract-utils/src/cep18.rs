@@ -0,0 +1,519 @@
+use alloc::{
+    collections::BTreeMap,
+    string::{String, ToString},
+    vec::Vec,
+};
+use casper_contract::{
+    contract_api::{runtime, storage},
+    unwrap_or_revert::UnwrapOrRevert,
+};
+use casper_types::{
+    bytesrepr::ToBytes, runtime_args, ApiError, ContractPackageHash, Key, RuntimeArgs, URef, U256,
+};
+
+use crate::{get_key, key_to_str, set_key, ContractContext, ContractStorage, Dict};
+
+pub const BALANCES_DICT: &str = "balances";
+pub const ALLOWANCES_DICT: &str = "allowances";
+
+pub const NAME: &str = "name";
+pub const SYMBOL: &str = "symbol";
+pub const DECIMALS: &str = "decimals";
+pub const TOTAL_SUPPLY: &str = "total_supply";
+
+/// Entry point `transfer_and_call` invokes on contract recipients.
+pub const ON_TOKEN_RECEIVED: &str = "on_token_received";
+
+pub enum CEP18Event {
+    Approval {
+        owner: Key,
+        spender: Key,
+        value: U256,
+    },
+    Transfer {
+        from: Key,
+        to: Key,
+        value: U256,
+    },
+    BatchTransfer {
+        from: Key,
+        recipients: Vec<Key>,
+        amounts: Vec<U256>,
+    },
+}
+
+impl CEP18Event {
+    pub fn type_name(&self) -> String {
+        match self {
+            CEP18Event::Approval {
+                owner: _,
+                spender: _,
+                value: _,
+            } => "approve",
+            CEP18Event::Transfer {
+                from: _,
+                to: _,
+                value: _,
+            } => "erc20_transfer",
+            CEP18Event::BatchTransfer {
+                from: _,
+                recipients: _,
+                amounts: _,
+            } => "erc20_batch_transfer",
+        }
+        .to_string()
+    }
+}
+
+#[repr(u16)]
+pub enum Error {
+    /// 65,656 for (UniswapV2 Core CEP18 Insufficient Balance)
+    UniswapV2CoreCEP18InsufficientBalance = 120,
+    /// 65,657 for (UniswapV2 Core CEP18 Insufficient Allowance)
+    UniswapV2CoreCEP18InsufficientAllowance = 121,
+    /// 65,658 for (UniswapV2 Core CEP18 Cannot Target Self)
+    UniswapV2CoreCEP18CannotTargetSelf = 122,
+    /// 65,659 for (UniswapV2 Core CEP18 Balance OverFlow)
+    UniswapV2CoreCEP18BalanceOverFlow = 123,
+    /// 65,660 for (UniswapV2 Core CEP18 Allowance OverFlow)
+    UniswapV2CoreCEP18AllowanceOverFlow = 124,
+    /// 65,661 for (UniswapV2 Core CEP18 Total Supply OverFlow)
+    UniswapV2CoreCEP18TotalSupplyOverFlow = 125,
+    /// 65,662 for (UniswapV2 Core CEP18 Total Supply UnderFlow)
+    UniswapV2CoreCEP18TotalSupplyUnderFlow = 126,
+    /// 65,689 for (UniswapV2 Core CEP18 Batch Length Mismatch)
+    UniswapV2CoreCEP18BatchLengthMismatch = 153,
+    /// 65,698 for (UniswapV2 Core CEP18 Batch Invalid Entry)
+    UniswapV2CoreCEP18BatchInvalidEntry = 162,
+}
+
+impl From<Error> for ApiError {
+    fn from(error: Error) -> ApiError {
+        ApiError::User(error as u16)
+    }
+}
+
+pub struct Balances {
+    dict: Dict,
+}
+
+impl Balances {
+    pub fn instance() -> Balances {
+        Balances {
+            dict: Dict::instance(BALANCES_DICT),
+        }
+    }
+
+    pub fn init() {
+        Dict::init(BALANCES_DICT)
+    }
+
+    /// Falls back to the `key_to_str` item tokens installed before the shared core wrote, until
+    /// the balance is written again or moved by `migrate`.
+    pub fn get(&self, owner: &Key) -> U256 {
+        self.dict
+            .get(&balance_key(owner))
+            .or_else(|| self.dict.get(&key_to_str(owner)))
+            .unwrap_or_default()
+    }
+
+    pub fn set(&self, owner: &Key, value: U256) {
+        self.dict.set(&balance_key(owner), value);
+    }
+
+    /// Moves a balance stored under the legacy `key_to_str` item to the CEP-18 item key.
+    pub fn migrate(&self, owner: &Key) {
+        let legacy_key: String = key_to_str(owner);
+        if let Some(value) = self.dict.get::<U256>(&legacy_key) {
+            if self.dict.get::<U256>(&balance_key(owner)).is_none() {
+                self.dict.set(&balance_key(owner), value);
+            }
+            self.dict.remove::<U256>(&legacy_key);
+        }
+    }
+}
+
+/// CEP-18 item key of `owner`'s balance: the base64 encoded bytes of the Key.
+pub fn balance_key(owner: &Key) -> String {
+    base64::encode(owner.to_bytes().unwrap_or_revert())
+}
+
+pub struct Allowances {
+    dict: Dict,
+}
+
+impl Allowances {
+    pub fn instance() -> Allowances {
+        Allowances {
+            dict: Dict::instance(ALLOWANCES_DICT),
+        }
+    }
+
+    pub fn init() {
+        Dict::init(ALLOWANCES_DICT)
+    }
+
+    pub fn get(&self, owner: &Key, spender: &Key) -> U256 {
+        self.dict.get_by_keys((owner, spender)).unwrap_or_default()
+    }
+
+    pub fn set(&self, owner: &Key, spender: &Key, value: U256) {
+        self.dict.set_by_keys((owner, spender), value);
+    }
+}
+
+/// Moves `amount` from `sender`'s balance to `recipient`'s, reverting if `sender` holds too little.
+fn move_balance(sender: Key, recipient: Key, amount: U256) {
+    let balances: Balances = Balances::instance();
+    balances.set(
+        &sender,
+        balances
+            .get(&sender)
+            .checked_sub(amount)
+            .ok_or(Error::UniswapV2CoreCEP18InsufficientBalance)
+            .unwrap_or_revert(),
+    );
+    balances.set(
+        &recipient,
+        balances
+            .get(&recipient)
+            .checked_add(amount)
+            .ok_or(Error::UniswapV2CoreCEP18BalanceOverFlow)
+            .unwrap_or_revert(),
+    );
+}
+
+/// Comma separated list of `values` for event fields.
+fn join<T: ToString>(values: &[T]) -> String {
+    values
+        .iter()
+        .map(ToString::to_string)
+        .collect::<Vec<String>>()
+        .join(",")
+}
+
+pub fn zero_address() -> Key {
+    Key::from_formatted_str(
+        "account-hash-0000000000000000000000000000000000000000000000000000000000000000",
+    )
+    .unwrap_or_revert()
+}
+
+/// Token core shared by every fungible token in the workspace.
+///
+/// Storage layout and entry point names follow CEP-18, so the named keys
+/// `name`, `symbol`, `decimals`, `total_supply` and the `balances` / `allowances`
+/// dictionaries are the ones wallets and explorers look for. Balances are keyed by the base64
+/// encoded bytes of the owner's Key and allowances by the hex encoded blake2b hash of the
+/// owner's and spender's bytes, the CEP-18 item keys.
+pub trait CEP18<Storage: ContractStorage>: ContractContext<Storage> {
+    fn init(&mut self, name: String, symbol: String, decimals: u8) {
+        set_key(NAME, name);
+        set_key(SYMBOL, symbol);
+        set_key(DECIMALS, decimals);
+        set_key(TOTAL_SUPPLY, U256::zero());
+        Balances::init();
+        Allowances::init();
+    }
+
+    fn name(&mut self) -> String {
+        get_key(NAME).unwrap_or_revert()
+    }
+
+    fn symbol(&mut self) -> String {
+        get_key(SYMBOL).unwrap_or_revert()
+    }
+
+    fn decimals(&mut self) -> u8 {
+        get_key(DECIMALS).unwrap_or_revert()
+    }
+
+    fn total_supply(&mut self) -> U256 {
+        get_key(TOTAL_SUPPLY).unwrap_or_default()
+    }
+
+    fn balance_of(&mut self, owner: Key) -> U256 {
+        Balances::instance().get(&owner)
+    }
+
+    fn allowance(&mut self, owner: Key, spender: Key) -> U256 {
+        Allowances::instance().get(&owner, &spender)
+    }
+
+    /// Moves the balances of `owners` written by tokens installed before the shared core to
+    /// the CEP-18 item key, so wallets can read them. Anyone may call it, a few owners per
+    /// deploy, as moving a balance between item keys leaves it unchanged.
+    fn migrate_balances(&mut self, owners: Vec<Key>) {
+        let balances: Balances = Balances::instance();
+        for owner in owners.iter() {
+            balances.migrate(owner);
+        }
+    }
+
+    fn transfer(&mut self, recipient: Key, amount: U256) -> Result<(), u32> {
+        self.make_transfer(self.get_caller(), recipient, amount)
+    }
+
+    /// Transfers `amount` to `recipient` like `transfer`, then calls `on_token_received` on
+    /// `recipient` if it is a contract package, passing the caller as `operator` and `from`
+    /// along with `amount` and `data`. The token is the caller of the hook. A recipient
+    /// without the entry point, or whose hook reverts, reverts the whole transfer.
+    fn transfer_and_call(&mut self, recipient: Key, amount: U256, data: String) -> Result<(), u32> {
+        let sender: Key = self.get_caller();
+        self.make_transfer(sender, recipient, amount)?;
+        if let Key::Hash(package_hash) = recipient {
+            let _: () = runtime::call_versioned_contract(
+                ContractPackageHash::new(package_hash),
+                None,
+                ON_TOKEN_RECEIVED,
+                runtime_args! {
+                    "operator" => sender,
+                    "from" => sender,
+                    "amount" => amount,
+                    "data" => data,
+                },
+            );
+        }
+        Ok(())
+    }
+
+    fn approve(&mut self, spender: Key, amount: U256) {
+        self._approve(self.get_caller(), spender, amount);
+    }
+
+    fn _approve(&mut self, owner: Key, spender: Key, amount: U256) {
+        Allowances::instance().set(&owner, &spender, amount);
+        self.emit_cep18_event(&CEP18Event::Approval {
+            owner,
+            spender,
+            value: amount,
+        });
+    }
+
+    fn increase_allowance(&mut self, spender: Key, amount: U256) -> Result<(), u32> {
+        let owner: Key = self.get_caller();
+        if owner == spender {
+            return Err(Error::UniswapV2CoreCEP18CannotTargetSelf as u32);
+        }
+        let new_allowance: U256 = self
+            .allowance(owner, spender)
+            .checked_add(amount)
+            .ok_or(Error::UniswapV2CoreCEP18AllowanceOverFlow)
+            .unwrap_or_revert();
+        self._approve(owner, spender, new_allowance);
+        Ok(())
+    }
+
+    fn decrease_allowance(&mut self, spender: Key, amount: U256) -> Result<(), u32> {
+        let owner: Key = self.get_caller();
+        if owner == spender {
+            return Err(Error::UniswapV2CoreCEP18CannotTargetSelf as u32);
+        }
+        let new_allowance: U256 = self
+            .allowance(owner, spender)
+            .checked_sub(amount)
+            .ok_or(Error::UniswapV2CoreCEP18InsufficientAllowance)
+            .unwrap_or_revert();
+        self._approve(owner, spender, new_allowance);
+        Ok(())
+    }
+
+    /// Moves `amount` from `owner` to `recipient` on behalf of the caller.
+    ///
+    /// The allowance is spent before any balance is touched so a failed
+    /// allowance check never leaves a half applied transfer behind.
+    fn transfer_from(&mut self, owner: Key, recipient: Key, amount: U256) -> Result<(), u32> {
+        if amount.is_zero() {
+            return Ok(());
+        }
+        let spender: Key = self.get_caller();
+        if owner != spender {
+            let new_allowance: U256 = self
+                .allowance(owner, spender)
+                .checked_sub(amount)
+                .ok_or(Error::UniswapV2CoreCEP18InsufficientAllowance)
+                .unwrap_or_revert();
+            self._approve(owner, spender, new_allowance);
+        }
+        self.make_transfer(owner, recipient, amount)
+    }
+
+    /// Sends `amounts[i]` to `recipients[i]` from the caller. The batch is all-or-nothing: it
+    /// reverts as a whole if the lengths differ or any transfer fails, and emits a single
+    /// `BatchTransfer` event instead of one `Transfer` per recipient. Entries sending to the
+    /// sender or sending nothing are rejected rather than skipped, so every entry the event
+    /// lists moved tokens.
+    fn batch_transfer(&mut self, recipients: Vec<Key>, amounts: Vec<U256>) -> Result<(), u32> {
+        self.make_batch_transfer(self.get_caller(), recipients, amounts)
+    }
+
+    /// Same as `batch_transfer` from `owner`, spending the caller's allowance for the sum of
+    /// `amounts` once.
+    fn batch_transfer_from(
+        &mut self,
+        owner: Key,
+        recipients: Vec<Key>,
+        amounts: Vec<U256>,
+    ) -> Result<(), u32> {
+        let spender: Key = self.get_caller();
+        if owner != spender {
+            let total: U256 = amounts
+                .iter()
+                .try_fold(U256::zero(), |total, amount| total.checked_add(*amount))
+                .ok_or(Error::UniswapV2CoreCEP18InsufficientAllowance)
+                .unwrap_or_revert();
+            let new_allowance: U256 = self
+                .allowance(owner, spender)
+                .checked_sub(total)
+                .ok_or(Error::UniswapV2CoreCEP18InsufficientAllowance)
+                .unwrap_or_revert();
+            self._approve(owner, spender, new_allowance);
+        }
+        self.make_batch_transfer(owner, recipients, amounts)
+    }
+
+    fn mint(&mut self, recipient: Key, amount: U256) {
+        let from: Key = zero_address();
+        self.before_transfer(from, recipient, amount);
+        let balances: Balances = Balances::instance();
+        balances.set(
+            &recipient,
+            balances
+                .get(&recipient)
+                .checked_add(amount)
+                .ok_or(Error::UniswapV2CoreCEP18BalanceOverFlow)
+                .unwrap_or_revert(),
+        );
+        set_key(
+            TOTAL_SUPPLY,
+            self.total_supply()
+                .checked_add(amount)
+                .ok_or(Error::UniswapV2CoreCEP18TotalSupplyOverFlow)
+                .unwrap_or_revert(),
+        );
+        self.emit_cep18_event(&CEP18Event::Transfer {
+            from,
+            to: recipient,
+            value: amount,
+        });
+        self.after_transfer(from, recipient, amount);
+    }
+
+    fn burn(&mut self, owner: Key, amount: U256) {
+        let to: Key = zero_address();
+        self.before_transfer(owner, to, amount);
+        let balances: Balances = Balances::instance();
+        balances.set(
+            &owner,
+            balances
+                .get(&owner)
+                .checked_sub(amount)
+                .ok_or(Error::UniswapV2CoreCEP18InsufficientBalance)
+                .unwrap_or_revert(),
+        );
+        set_key(
+            TOTAL_SUPPLY,
+            self.total_supply()
+                .checked_sub(amount)
+                .ok_or(Error::UniswapV2CoreCEP18TotalSupplyUnderFlow)
+                .unwrap_or_revert(),
+        );
+        self.emit_cep18_event(&CEP18Event::Transfer {
+            from: owner,
+            to,
+            value: amount,
+        });
+        self.after_transfer(owner, to, amount);
+    }
+
+    fn make_transfer(&mut self, sender: Key, recipient: Key, amount: U256) -> Result<(), u32> {
+        if sender == recipient || amount.is_zero() {
+            return Ok(());
+        }
+        self.before_transfer(sender, recipient, amount);
+        move_balance(sender, recipient, amount);
+        self.emit_cep18_event(&CEP18Event::Transfer {
+            from: sender,
+            to: recipient,
+            value: amount,
+        });
+        self.after_transfer(sender, recipient, amount);
+        Ok(())
+    }
+
+    fn make_batch_transfer(
+        &mut self,
+        sender: Key,
+        recipients: Vec<Key>,
+        amounts: Vec<U256>,
+    ) -> Result<(), u32> {
+        if recipients.len() != amounts.len() {
+            runtime::revert(Error::UniswapV2CoreCEP18BatchLengthMismatch);
+        }
+        for (recipient, amount) in recipients.iter().zip(amounts.iter()) {
+            if sender == *recipient || amount.is_zero() {
+                runtime::revert(Error::UniswapV2CoreCEP18BatchInvalidEntry);
+            }
+            self.before_transfer(sender, *recipient, *amount);
+            move_balance(sender, *recipient, *amount);
+            self.after_transfer(sender, *recipient, *amount);
+        }
+        self.emit_cep18_event(&CEP18Event::BatchTransfer {
+            from: sender,
+            recipients,
+            amounts,
+        });
+        Ok(())
+    }
+
+    /// Called before any balance changes. Mints come from and burns go to the zero address.
+    fn before_transfer(&mut self, _from: Key, _to: Key, _amount: U256) {}
+
+    /// Called after balances and total supply have been updated.
+    fn after_transfer(&mut self, _from: Key, _to: Key, _amount: U256) {}
+
+    fn emit_cep18_event(&mut self, cep18_event: &CEP18Event) {
+        let mut events = Vec::new();
+        let package: ContractPackageHash = self.self_addr().into_hash().unwrap_or_revert().into();
+        match cep18_event {
+            CEP18Event::Approval {
+                owner,
+                spender,
+                value,
+            } => {
+                let mut event = BTreeMap::new();
+                event.insert("contract_package_hash", package.to_string());
+                event.insert("event_type", cep18_event.type_name());
+                event.insert("owner", owner.to_string());
+                event.insert("spender", spender.to_string());
+                event.insert("value", value.to_string());
+                events.push(event);
+            }
+            CEP18Event::Transfer { from, to, value } => {
+                let mut event = BTreeMap::new();
+                event.insert("contract_package_hash", package.to_string());
+                event.insert("event_type", cep18_event.type_name());
+                event.insert("from", from.to_string());
+                event.insert("to", to.to_string());
+                event.insert("value", value.to_string());
+                events.push(event);
+            }
+            CEP18Event::BatchTransfer {
+                from,
+                recipients,
+                amounts,
+            } => {
+                let mut event = BTreeMap::new();
+                event.insert("contract_package_hash", package.to_string());
+                event.insert("event_type", cep18_event.type_name());
+                event.insert("from", from.to_string());
+                event.insert("recipients", join(recipients));
+                event.insert("amounts", join(amounts));
+                events.push(event);
+            }
+        };
+        for event in events {
+            let _: URef = storage::new_uref(event);
+        }
+    }
+}
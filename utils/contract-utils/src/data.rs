@@ -96,6 +96,12 @@ pub fn key_and_value_to_str<T: CLTyped + ToBytes>(key: &Key, value: &T) -> Strin
     hex::encode(bytes)
 }
 
+/// Contract package hash of all zeros, returned for contracts that do not exist, such as a
+/// pair the factory has not created.
+pub fn zero_hash() -> Key {
+    Key::Hash([0u8; 32])
+}
+
 pub fn get_key<T: FromBytes + CLTyped>(name: &str) -> Option<T> {
     match runtime::get_key(name) {
         None => None,
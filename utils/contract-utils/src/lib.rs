@@ -3,12 +3,27 @@
 
 extern crate alloc;
 
-mod admin_control;
+mod access_control;
+mod cep18;
 mod contract_context;
 mod contract_storage;
 mod data;
+mod signature;
+mod votes;
 
-pub use admin_control::AdminControl;
+pub use access_control::{
+    AccessControl, AccessControlEvent, Error as AccessControlError, DEFAULT_ADMIN_ROLE,
+    FEE_SETTER_ROLE, MINTER_ROLE, PAUSER_ROLE, SNAPSHOT_ROLE, WHITELISTER_ROLE,
+};
+pub use cep18::{
+    balance_key, zero_address, Allowances, Balances, CEP18Event, Error as CEP18Error,
+    ALLOWANCES_DICT, BALANCES_DICT, CEP18, DECIMALS, NAME, ON_TOKEN_RECEIVED, SYMBOL, TOTAL_SUPPLY,
+};
 pub use contract_context::ContractContext;
 pub use contract_storage::{ContractStorage, OnChainContractStorage};
-pub use data::{get_key, key_and_value_to_str, key_to_str, set_key, Dict};
+pub use data::{get_key, key_and_value_to_str, key_to_str, set_key, zero_hash, Dict};
+pub use signature::verify_signature;
+pub use votes::{
+    Checkpoint, Error as VotesError, Votes, VotesEvent, CHECKPOINTS_DICT, DELEGATES_DICT,
+    NUM_CHECKPOINTS_DICT,
+};
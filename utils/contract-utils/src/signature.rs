@@ -0,0 +1,7 @@
+use casper_types::{crypto, PublicKey, Signature};
+
+/// Returns whether `signature` is a valid ed25519 or secp256k1 signature of `message` by
+/// `public_key`. A signature of the other key type never verifies.
+pub fn verify_signature(public_key: &PublicKey, signature: &Signature, message: &[u8]) -> bool {
+    crypto::verify(message, signature, public_key).is_ok()
+}
@@ -0,0 +1,316 @@
+use alloc::{
+    collections::BTreeMap,
+    string::{String, ToString},
+    vec::Vec,
+};
+use casper_contract::{
+    contract_api::{runtime, storage},
+    unwrap_or_revert::UnwrapOrRevert,
+};
+use casper_types::{ApiError, ContractPackageHash, Key, URef, U256};
+
+use crate::{key_and_value_to_str, key_to_str, zero_address, ContractStorage, Dict, CEP18};
+
+pub const DELEGATES_DICT: &str = "delegates";
+pub const CHECKPOINTS_DICT: &str = "checkpoints";
+pub const NUM_CHECKPOINTS_DICT: &str = "num_checkpoints";
+
+/// Votes of a delegate from the block time, in milliseconds, at which they changed.
+pub type Checkpoint = (u64, U256);
+
+pub enum VotesEvent {
+    DelegateChanged {
+        delegator: Key,
+        from_delegate: Key,
+        to_delegate: Key,
+    },
+    DelegateVotesChanged {
+        delegate: Key,
+        previous_balance: U256,
+        new_balance: U256,
+    },
+}
+
+impl VotesEvent {
+    pub fn type_name(&self) -> String {
+        match self {
+            VotesEvent::DelegateChanged {
+                delegator: _,
+                from_delegate: _,
+                to_delegate: _,
+            } => "delegate_changed",
+            VotesEvent::DelegateVotesChanged {
+                delegate: _,
+                previous_balance: _,
+                new_balance: _,
+            } => "delegate_votes_changed",
+        }
+        .to_string()
+    }
+}
+
+#[repr(u16)]
+pub enum Error {
+    /// 65,693 for (UniswapV2 Core Votes Future Lookup)
+    UniswapV2CoreVotesFutureLookup = 157,
+    /// 65,694 for (UniswapV2 Core Votes OverFlow)
+    UniswapV2CoreVotesOverFlow = 158,
+    /// 65,695 for (UniswapV2 Core Votes UnderFlow)
+    UniswapV2CoreVotesUnderFlow = 159,
+}
+
+impl From<Error> for ApiError {
+    fn from(error: Error) -> ApiError {
+        ApiError::User(error as u16)
+    }
+}
+
+/// Compound style vote delegation shared by the governance and LP tokens.
+///
+/// Balances only count as votes once their holder delegates them, to themselves or to another
+/// account. Every change to a delegate's votes is checkpointed at the block time, so votes can
+/// be read at any past time. Each checkpoint is its own dictionary item, keyed by the delegate
+/// and its index, so a change writes one item however long the history grows. Tokens keep the
+/// checkpoints current by calling `move_voting_power` from `CEP18::after_transfer`.
+pub trait Votes<Storage: ContractStorage>: CEP18<Storage> {
+    /// Whether the delegation dictionaries exist. They are created by the first delegation, so
+    /// tokens installed before delegation was added need no migration.
+    fn votes_initialized(&self) -> bool {
+        runtime::has_key(DELEGATES_DICT)
+            && runtime::has_key(CHECKPOINTS_DICT)
+            && runtime::has_key(NUM_CHECKPOINTS_DICT)
+    }
+
+    fn delegates(&self, account: Key) -> Key {
+        if !self.votes_initialized() {
+            return zero_address();
+        }
+        Delegates::instance().get(&account)
+    }
+
+    fn get_votes(&self, account: Key) -> U256 {
+        if !self.votes_initialized() {
+            return U256::zero();
+        }
+        let checkpoints: Checkpoints = Checkpoints::instance();
+        match checkpoints.len(&account) {
+            0 => U256::zero(),
+            len => checkpoints.get(&account, len - 1).1,
+        }
+    }
+
+    /// Returns the votes `account` had at `timestamp`, a block time in milliseconds that must
+    /// have passed already.
+    fn get_past_votes(&self, account: Key, timestamp: u64) -> U256 {
+        let now: u64 = runtime::get_blocktime().into();
+        if timestamp >= now {
+            runtime::revert(Error::UniswapV2CoreVotesFutureLookup);
+        }
+        if !self.votes_initialized() {
+            return U256::zero();
+        }
+        let checkpoints: Checkpoints = Checkpoints::instance();
+        let len: u32 = checkpoints.len(&account);
+        if len == 0 {
+            return U256::zero();
+        }
+        // most lookups are of recent votes, so the latest checkpoint is tried first
+        let (time, votes): Checkpoint = checkpoints.get(&account, len - 1);
+        if time <= timestamp {
+            return votes;
+        }
+        if checkpoints.get(&account, 0).0 > timestamp {
+            return U256::zero();
+        }
+        // the last checkpoint at or before `timestamp` lies in lower..=upper
+        let mut lower: u32 = 0;
+        let mut upper: u32 = len - 1;
+        while upper > lower {
+            let center: u32 = upper - (upper - lower) / 2;
+            let (time, votes): Checkpoint = checkpoints.get(&account, center);
+            if time == timestamp {
+                return votes;
+            } else if time < timestamp {
+                lower = center;
+            } else {
+                upper = center - 1;
+            }
+        }
+        checkpoints.get(&account, lower).1
+    }
+
+    fn delegate(&mut self, delegatee: Key) {
+        self._delegate(self.get_caller(), delegatee);
+    }
+
+    fn _delegate(&mut self, delegator: Key, delegatee: Key) {
+        if !self.votes_initialized() {
+            Delegates::init();
+            Checkpoints::init();
+        }
+        let from_delegate: Key = self.delegates(delegator);
+        Delegates::instance().set(&delegator, delegatee);
+        self.emit_votes_event(&VotesEvent::DelegateChanged {
+            delegator,
+            from_delegate,
+            to_delegate: delegatee,
+        });
+        let balance: U256 = self.balance_of(delegator);
+        self.move_delegate_votes(from_delegate, delegatee, balance);
+    }
+
+    /// Moves the votes of `amount` transferred tokens between the delegates of `from` and
+    /// `to`. Mints come from and burns go to the zero address, which has no delegate.
+    fn move_voting_power(&mut self, from: Key, to: Key, amount: U256) {
+        if !self.votes_initialized() {
+            return;
+        }
+        let from_delegate: Key = self.delegates(from);
+        let to_delegate: Key = self.delegates(to);
+        self.move_delegate_votes(from_delegate, to_delegate, amount);
+    }
+
+    fn move_delegate_votes(&mut self, from_delegate: Key, to_delegate: Key, amount: U256) {
+        if from_delegate == to_delegate || amount.is_zero() {
+            return;
+        }
+        if from_delegate != zero_address() {
+            let previous_balance: U256 = self.get_votes(from_delegate);
+            let new_balance: U256 = previous_balance
+                .checked_sub(amount)
+                .ok_or(Error::UniswapV2CoreVotesUnderFlow)
+                .unwrap_or_revert();
+            self.write_checkpoint(from_delegate, previous_balance, new_balance);
+        }
+        if to_delegate != zero_address() {
+            let previous_balance: U256 = self.get_votes(to_delegate);
+            let new_balance: U256 = previous_balance
+                .checked_add(amount)
+                .ok_or(Error::UniswapV2CoreVotesOverFlow)
+                .unwrap_or_revert();
+            self.write_checkpoint(to_delegate, previous_balance, new_balance);
+        }
+    }
+
+    fn write_checkpoint(&mut self, delegate: Key, previous_balance: U256, new_balance: U256) {
+        let now: u64 = runtime::get_blocktime().into();
+        let checkpoints: Checkpoints = Checkpoints::instance();
+        let len: u32 = checkpoints.len(&delegate);
+        if len > 0 && checkpoints.get(&delegate, len - 1).0 == now {
+            checkpoints.set(&delegate, len - 1, (now, new_balance));
+        } else {
+            checkpoints.set(&delegate, len, (now, new_balance));
+            checkpoints.set_len(
+                &delegate,
+                len.checked_add(1)
+                    .ok_or(Error::UniswapV2CoreVotesOverFlow)
+                    .unwrap_or_revert(),
+            );
+        }
+        self.emit_votes_event(&VotesEvent::DelegateVotesChanged {
+            delegate,
+            previous_balance,
+            new_balance,
+        });
+    }
+
+    fn emit_votes_event(&mut self, votes_event: &VotesEvent) {
+        let mut events = Vec::new();
+        let package: ContractPackageHash = self.self_addr().into_hash().unwrap_or_revert().into();
+        match votes_event {
+            VotesEvent::DelegateChanged {
+                delegator,
+                from_delegate,
+                to_delegate,
+            } => {
+                let mut event = BTreeMap::new();
+                event.insert("contract_package_hash", package.to_string());
+                event.insert("event_type", votes_event.type_name());
+                event.insert("delegator", delegator.to_string());
+                event.insert("from_delegate", from_delegate.to_string());
+                event.insert("to_delegate", to_delegate.to_string());
+                events.push(event);
+            }
+            VotesEvent::DelegateVotesChanged {
+                delegate,
+                previous_balance,
+                new_balance,
+            } => {
+                let mut event = BTreeMap::new();
+                event.insert("contract_package_hash", package.to_string());
+                event.insert("event_type", votes_event.type_name());
+                event.insert("delegate", delegate.to_string());
+                event.insert("previous_balance", previous_balance.to_string());
+                event.insert("new_balance", new_balance.to_string());
+                events.push(event);
+            }
+        };
+        for event in events {
+            let _: URef = storage::new_uref(event);
+        }
+    }
+}
+
+struct Delegates {
+    dict: Dict,
+}
+
+impl Delegates {
+    pub fn instance() -> Delegates {
+        Delegates {
+            dict: Dict::instance(DELEGATES_DICT),
+        }
+    }
+
+    pub fn init() {
+        Dict::init(DELEGATES_DICT)
+    }
+
+    pub fn get(&self, account: &Key) -> Key {
+        self.dict
+            .get(&key_to_str(account))
+            .unwrap_or_else(zero_address)
+    }
+
+    pub fn set(&self, account: &Key, delegatee: Key) {
+        self.dict.set(&key_to_str(account), delegatee);
+    }
+}
+
+struct Checkpoints {
+    dict: Dict,
+    lengths: Dict,
+}
+
+impl Checkpoints {
+    pub fn instance() -> Checkpoints {
+        Checkpoints {
+            dict: Dict::instance(CHECKPOINTS_DICT),
+            lengths: Dict::instance(NUM_CHECKPOINTS_DICT),
+        }
+    }
+
+    pub fn init() {
+        Dict::init(CHECKPOINTS_DICT);
+        Dict::init(NUM_CHECKPOINTS_DICT);
+    }
+
+    pub fn len(&self, account: &Key) -> u32 {
+        self.lengths.get(&key_to_str(account)).unwrap_or_default()
+    }
+
+    pub fn set_len(&self, account: &Key, len: u32) {
+        self.lengths.set(&key_to_str(account), len);
+    }
+
+    pub fn get(&self, account: &Key, index: u32) -> Checkpoint {
+        self.dict
+            .get(&key_and_value_to_str(account, &index))
+            .unwrap_or_revert()
+    }
+
+    pub fn set(&self, account: &Key, index: u32, checkpoint: Checkpoint) {
+        self.dict
+            .set(&key_and_value_to_str(account, &index), checkpoint);
+    }
+}
@@ -5,3 +5,4 @@ use crate::test_env as other_test_env;
 
 pub use other_test_env::TestEnv;
 pub use test_contract::TestContract;
+pub use utils::balance_key;
@@ -46,6 +46,11 @@ impl TestContract {
             .query_account_named_key(self.contract_owner, &[contract_name, key])
     }
 
+    /// Names of the contract's named keys.
+    pub fn named_keys(&self) -> Vec<String> {
+        self.env.contract_named_keys(self.contract_hash())
+    }
+
     pub fn contract_hash(&self) -> [u8; 32] {
         let key = format!("{}_contract_hash_wrapped", self.name);
         self.env
@@ -64,4 +69,34 @@ impl TestContract {
         };
         self.env.run(sender, session_code, session_args);
     }
+
+    /// Calls `entry_point`, panicking unless the call reverts.
+    pub fn call_contract_expecting_failure(
+        &self,
+        sender: AccountHash,
+        entry_point: &str,
+        session_args: RuntimeArgs,
+    ) {
+        let session_code = DeploySource::ByContractHash {
+            hash: ContractHash::new(self.contract_hash()),
+            method: entry_point.to_string(),
+        };
+        self.env
+            .run_expecting_failure(sender, session_code, session_args);
+    }
+
+    pub fn call_contract_with_block_time(
+        &self,
+        sender: AccountHash,
+        entry_point: &str,
+        session_args: RuntimeArgs,
+        block_time: u64,
+    ) {
+        let session_code = DeploySource::ByContractHash {
+            hash: ContractHash::new(self.contract_hash()),
+            method: entry_point.to_string(),
+        };
+        self.env
+            .run_with_block_time(sender, session_code, session_args, block_time);
+    }
 }
@@ -1,8 +1,13 @@
-use std::sync::{Arc, Mutex};
+use std::{
+    collections::BTreeMap,
+    sync::{Arc, Mutex},
+};
 
 use casper_engine_test_support::{InMemoryWasmTestBuilder, DEFAULT_RUN_GENESIS_REQUEST};
+use casper_execution_engine::shared::transform::Transform;
 use casper_types::{
     account::AccountHash, bytesrepr::FromBytes, CLTyped, Key, PublicKey, RuntimeArgs, SecretKey,
+    StoredValue, U512,
 };
 
 use crate::utils::{deploy, fund_account, query, query_dictionary_item, DeploySource};
@@ -30,10 +35,69 @@ impl TestEnv {
         )
     }
 
+    /// Same as `run` for a deploy that has to fail, leaving the state it would have changed.
+    pub fn run_expecting_failure(
+        &self,
+        sender: AccountHash,
+        session_code: DeploySource,
+        session_args: RuntimeArgs,
+    ) {
+        deploy(
+            &mut self.state.lock().unwrap().builder,
+            &sender,
+            &session_code,
+            session_args,
+            false,
+            None,
+        )
+    }
+
+    /// Same as `run`, executed in a block with the given timestamp in milliseconds.
+    pub fn run_with_block_time(
+        &self,
+        sender: AccountHash,
+        session_code: DeploySource,
+        session_args: RuntimeArgs,
+        block_time: u64,
+    ) {
+        deploy(
+            &mut self.state.lock().unwrap().builder,
+            &sender,
+            &session_code,
+            session_args,
+            true,
+            Some(block_time),
+        )
+    }
+
     pub fn next_user(&self) -> AccountHash {
         self.state.lock().unwrap().next_user()
     }
 
+    /// Returns the gas spent by the last deploy run in this environment.
+    pub fn last_gas_cost(&self) -> U512 {
+        self.state
+            .lock()
+            .unwrap()
+            .builder
+            .last_exec_gas_cost()
+            .value()
+    }
+
+    /// Returns the events, maps holding an `event_type`, written by the last deploy run in
+    /// this environment.
+    pub fn last_events(&self) -> Vec<BTreeMap<String, String>> {
+        self.state.lock().unwrap().last_events()
+    }
+
+    /// Returns the names of the named keys of the contract `contract_hash`.
+    pub fn contract_named_keys(&self, contract_hash: [u8; 32]) -> Vec<String> {
+        self.state
+            .lock()
+            .unwrap()
+            .contract_named_keys(contract_hash)
+    }
+
     pub fn query_dictionary<T: CLTyped + FromBytes>(
         &self,
         contract_hash: [u8; 32],
@@ -159,4 +223,28 @@ impl TestEnvState {
     ) -> T {
         query(&self.builder, Key::Account(account), path)
     }
+
+    pub fn last_events(&self) -> Vec<BTreeMap<String, String>> {
+        let transforms = self.builder.get_transforms();
+        let last = match transforms.last() {
+            Some(last) => last,
+            None => return Vec::new(),
+        };
+        last.iter()
+            .filter_map(|(_, transform)| match transform {
+                Transform::Write(StoredValue::CLValue(value)) => {
+                    value.clone().into_t::<BTreeMap<String, String>>().ok()
+                }
+                _ => None,
+            })
+            .filter(|event| event.contains_key("event_type"))
+            .collect()
+    }
+
+    pub fn contract_named_keys(&self, contract_hash: [u8; 32]) -> Vec<String> {
+        match self.builder.query(None, Key::Hash(contract_hash), &[]) {
+            Ok(StoredValue::Contract(contract)) => contract.named_keys().keys().cloned().collect(),
+            _ => panic!("should be a contract."),
+        }
+    }
 }
@@ -6,8 +6,11 @@ use casper_engine_test_support::{
 };
 use casper_execution_engine::core::engine_state::ExecuteRequest;
 use casper_types::{
-    account::AccountHash, bytesrepr::FromBytes, runtime_args, system::mint, CLTyped, ContractHash,
-    ContractPackageHash, Key, RuntimeArgs, StoredValue, U512,
+    account::AccountHash,
+    bytesrepr::{FromBytes, ToBytes},
+    runtime_args,
+    system::mint,
+    CLTyped, ContractHash, ContractPackageHash, Key, RuntimeArgs, StoredValue, U512,
 };
 use rand::Rng;
 
@@ -26,6 +29,11 @@ pub fn query<T: FromBytes + CLTyped>(
         .expect("Wrong type in query result.")
 }
 
+/// CEP-18 item key of `owner` in a token's `balances` dictionary.
+pub fn balance_key(owner: &Key) -> String {
+    base64::encode(owner.to_bytes().unwrap())
+}
+
 pub fn fund_account(account: &AccountHash) -> ExecuteRequest {
     let deploy_item = DeployItemBuilder::new()
         .with_address(*DEFAULT_ACCOUNT_ADDR)
@@ -0,0 +1,200 @@
+//! Types shared by the contracts and their clients, encoded with `bytesrepr`.
+
+#![no_std]
+
+extern crate alloc;
+
+use alloc::vec::Vec;
+use casper_types::{
+    bytesrepr::{self, FromBytes, ToBytes},
+    CLType, CLTyped, Key, U128, U256,
+};
+
+/// A snapshot of a pair, as returned by its `get_state` entry point.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct PairState {
+    pub token0: Key,
+    pub token1: Key,
+    pub reserve0: U128,
+    pub reserve1: U128,
+    pub block_timestamp_last: u64,
+    pub total_supply: U256,
+    pub treasury_fee: U256,
+    pub k_last: U256,
+    pub price0_cumulative_last: U256,
+    pub price1_cumulative_last: U256,
+    pub factory_hash: Key,
+    pub swap_fee: u32,
+    pub curve_type: u8,
+    pub amplification: u64,
+}
+
+impl CLTyped for PairState {
+    fn cl_type() -> CLType {
+        CLType::Any
+    }
+}
+
+impl ToBytes for PairState {
+    fn to_bytes(&self) -> Result<Vec<u8>, bytesrepr::Error> {
+        let mut result = bytesrepr::allocate_buffer(self)?;
+        result.append(&mut self.token0.to_bytes()?);
+        result.append(&mut self.token1.to_bytes()?);
+        result.append(&mut self.reserve0.to_bytes()?);
+        result.append(&mut self.reserve1.to_bytes()?);
+        result.append(&mut self.block_timestamp_last.to_bytes()?);
+        result.append(&mut self.total_supply.to_bytes()?);
+        result.append(&mut self.treasury_fee.to_bytes()?);
+        result.append(&mut self.k_last.to_bytes()?);
+        result.append(&mut self.price0_cumulative_last.to_bytes()?);
+        result.append(&mut self.price1_cumulative_last.to_bytes()?);
+        result.append(&mut self.factory_hash.to_bytes()?);
+        result.append(&mut self.swap_fee.to_bytes()?);
+        result.append(&mut self.curve_type.to_bytes()?);
+        result.append(&mut self.amplification.to_bytes()?);
+        Ok(result)
+    }
+
+    fn serialized_length(&self) -> usize {
+        self.token0.serialized_length()
+            + self.token1.serialized_length()
+            + self.reserve0.serialized_length()
+            + self.reserve1.serialized_length()
+            + self.block_timestamp_last.serialized_length()
+            + self.total_supply.serialized_length()
+            + self.treasury_fee.serialized_length()
+            + self.k_last.serialized_length()
+            + self.price0_cumulative_last.serialized_length()
+            + self.price1_cumulative_last.serialized_length()
+            + self.factory_hash.serialized_length()
+            + self.swap_fee.serialized_length()
+            + self.curve_type.serialized_length()
+            + self.amplification.serialized_length()
+    }
+}
+
+impl FromBytes for PairState {
+    fn from_bytes(bytes: &[u8]) -> Result<(Self, &[u8]), bytesrepr::Error> {
+        let (token0, bytes) = Key::from_bytes(bytes)?;
+        let (token1, bytes) = Key::from_bytes(bytes)?;
+        let (reserve0, bytes) = U128::from_bytes(bytes)?;
+        let (reserve1, bytes) = U128::from_bytes(bytes)?;
+        let (block_timestamp_last, bytes) = u64::from_bytes(bytes)?;
+        let (total_supply, bytes) = U256::from_bytes(bytes)?;
+        let (treasury_fee, bytes) = U256::from_bytes(bytes)?;
+        let (k_last, bytes) = U256::from_bytes(bytes)?;
+        let (price0_cumulative_last, bytes) = U256::from_bytes(bytes)?;
+        let (price1_cumulative_last, bytes) = U256::from_bytes(bytes)?;
+        let (factory_hash, bytes) = Key::from_bytes(bytes)?;
+        let (swap_fee, bytes) = u32::from_bytes(bytes)?;
+        let (curve_type, bytes) = u8::from_bytes(bytes)?;
+        let (amplification, bytes) = u64::from_bytes(bytes)?;
+        Ok((
+            PairState {
+                token0,
+                token1,
+                reserve0,
+                reserve1,
+                block_timestamp_last,
+                total_supply,
+                treasury_fee,
+                k_last,
+                price0_cumulative_last,
+                price1_cumulative_last,
+                factory_hash,
+                swap_fee,
+                curve_type,
+                amplification,
+            },
+            bytes,
+        ))
+    }
+}
+
+/// Reserves of a pair and the block time they were last updated at, kept in the
+/// pair's `reserves` named key so `swap` reads and writes them at once.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct Reserves {
+    pub reserve0: U128,
+    pub reserve1: U128,
+    pub block_timestamp_last: u64,
+}
+
+impl CLTyped for Reserves {
+    fn cl_type() -> CLType {
+        CLType::Any
+    }
+}
+
+impl ToBytes for Reserves {
+    fn to_bytes(&self) -> Result<Vec<u8>, bytesrepr::Error> {
+        let mut result = bytesrepr::allocate_buffer(self)?;
+        result.append(&mut self.reserve0.to_bytes()?);
+        result.append(&mut self.reserve1.to_bytes()?);
+        result.append(&mut self.block_timestamp_last.to_bytes()?);
+        Ok(result)
+    }
+
+    fn serialized_length(&self) -> usize {
+        self.reserve0.serialized_length()
+            + self.reserve1.serialized_length()
+            + self.block_timestamp_last.serialized_length()
+    }
+}
+
+impl FromBytes for Reserves {
+    fn from_bytes(bytes: &[u8]) -> Result<(Self, &[u8]), bytesrepr::Error> {
+        let (reserve0, bytes) = U128::from_bytes(bytes)?;
+        let (reserve1, bytes) = U128::from_bytes(bytes)?;
+        let (block_timestamp_last, bytes) = u64::from_bytes(bytes)?;
+        Ok((
+            Reserves {
+                reserve0,
+                reserve1,
+                block_timestamp_last,
+            },
+            bytes,
+        ))
+    }
+}
+
+/// Cumulative prices of a pair, kept in the pair's `price_cumulatives` named key.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct PriceCumulatives {
+    pub price0_cumulative_last: U256,
+    pub price1_cumulative_last: U256,
+}
+
+impl CLTyped for PriceCumulatives {
+    fn cl_type() -> CLType {
+        CLType::Any
+    }
+}
+
+impl ToBytes for PriceCumulatives {
+    fn to_bytes(&self) -> Result<Vec<u8>, bytesrepr::Error> {
+        let mut result = bytesrepr::allocate_buffer(self)?;
+        result.append(&mut self.price0_cumulative_last.to_bytes()?);
+        result.append(&mut self.price1_cumulative_last.to_bytes()?);
+        Ok(result)
+    }
+
+    fn serialized_length(&self) -> usize {
+        self.price0_cumulative_last.serialized_length()
+            + self.price1_cumulative_last.serialized_length()
+    }
+}
+
+impl FromBytes for PriceCumulatives {
+    fn from_bytes(bytes: &[u8]) -> Result<(Self, &[u8]), bytesrepr::Error> {
+        let (price0_cumulative_last, bytes) = U256::from_bytes(bytes)?;
+        let (price1_cumulative_last, bytes) = U256::from_bytes(bytes)?;
+        Ok((
+            PriceCumulatives {
+                price0_cumulative_last,
+                price1_cumulative_last,
+            },
+            bytes,
+        ))
+    }
+}
@@ -4,6 +4,9 @@
 pub const PACKAGE_HASH_KEY_NAME: &str = "package_hash";
 pub const CONTRACT_HASH_KEY_NAME: &str = "contract_hash";
 pub const WCSPR_HASH_KEY_NAME: &str = "wcspr_hash";
+pub const RECEIVED_FROM_KEY_NAME: &str = "received_from";
+pub const RECEIVED_AMOUNT_KEY_NAME: &str = "received_amount";
+pub const RECEIVED_DATA_KEY_NAME: &str = "received_data";
 
 /// Name of named-key for `name`.
 pub const NAME_KEY_NAME: &str = "name";
@@ -7,7 +7,7 @@
 // We need to explicitly import the std alloc crate and `alloc::string::String` as we're in a
 // `no_std` environment.
 extern crate alloc;
-use alloc::{collections::BTreeSet, format, vec};
+use alloc::{collections::BTreeSet, format, string::String, vec};
 
 use casper_contract::{
     contract_api::{account, runtime, storage, system},
@@ -15,8 +15,8 @@ use casper_contract::{
 };
 use casper_types::{
     contracts::{ContractHash, ContractPackageHash},
-    runtime_args, CLTyped, EntryPoint, EntryPointAccess, EntryPointType, EntryPoints, Group, Key,
-    Parameter, RuntimeArgs, URef, U256, U512,
+    runtime_args, ApiError, CLTyped, EntryPoint, EntryPointAccess, EntryPointType, EntryPoints,
+    Group, Key, Parameter, RuntimeArgs, URef, U256, U512,
 };
 
 pub mod constants;
@@ -194,6 +194,20 @@ fn get_purse_balance() {
     set_key("result", ret);
 }
 
+// records the WCSPR transfer_and_call sends, rejecting that sent with "reject"
+#[no_mangle]
+fn on_token_received() {
+    let from: Key = runtime::get_named_arg("from");
+    let amount: U256 = runtime::get_named_arg("amount");
+    let data: String = runtime::get_named_arg("data");
+    if data == "reject" {
+        runtime::revert(ApiError::User(0));
+    }
+    set_key(RECEIVED_FROM_KEY_NAME, from);
+    set_key(RECEIVED_AMOUNT_KEY_NAME, amount);
+    set_key(RECEIVED_DATA_KEY_NAME, data);
+}
+
 // ================================== Helper functions ============================ //
 fn _create_hash_from_key(key: Key) -> ContractHash {
     ContractHash::from(key.into_hash().unwrap_or_default())
@@ -327,6 +341,18 @@ fn get_entry_points() -> EntryPoints {
         EntryPointAccess::Public,
         EntryPointType::Contract,
     ));
+    entry_points.add_entry_point(EntryPoint::new(
+        "on_token_received",
+        vec![
+            Parameter::new("operator", Key::cl_type()),
+            Parameter::new("from", Key::cl_type()),
+            Parameter::new("amount", U256::cl_type()),
+            Parameter::new("data", String::cl_type()),
+        ],
+        <()>::cl_type(),
+        EntryPointAccess::Public,
+        EntryPointType::Contract,
+    ));
     entry_points
 }
 
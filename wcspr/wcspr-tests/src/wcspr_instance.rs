@@ -6,7 +6,7 @@ use casper_types::{
     account::AccountHash, bytesrepr::ToBytes, runtime_args, ContractHash, ContractPackageHash, Key,
     RuntimeArgs, URef, U256, U512,
 };
-use test_env::{TestContract, TestEnv};
+use test_env::{balance_key, TestContract, TestEnv};
 
 // pub mod constants;
 use crate::constants::*;
@@ -36,6 +36,39 @@ impl WCSPRInstance {
         )
     }
 
+    /// Adds a new version to the package installed under `contract_name` by `sender`.
+    /// Installs the token as released before storage versions, to be upgraded in tests.
+    pub fn new_legacy(
+        env: &TestEnv,
+        contract_name: &str,
+        sender: AccountHash,
+        name: &str,
+        symbol: &str,
+        decimals: u8,
+    ) -> TestContract {
+        TestContract::new(
+            env,
+            "wcspr-token-legacy.wasm",
+            contract_name,
+            sender,
+            runtime_args! {
+                "name" => name,
+                "symbol" => symbol,
+                "decimals" => decimals
+            },
+        )
+    }
+
+    pub fn upgrade(env: &TestEnv, contract_name: &str, sender: AccountHash) -> TestContract {
+        TestContract::new(
+            env,
+            "wcspr-token.wasm",
+            contract_name,
+            sender,
+            runtime_args! {},
+        )
+    }
+
     pub fn instance(contract: TestContract) -> WCSPRInstance {
         WCSPRInstance(contract)
     }
@@ -85,6 +118,87 @@ impl WCSPRInstance {
         );
     }
 
+    pub fn transfer_and_call<T: Into<Key>>(
+        &self,
+        sender: AccountHash,
+        recipient: T,
+        amount: U256,
+        data: &str,
+    ) {
+        self.0.call_contract(
+            sender,
+            "transfer_and_call",
+            runtime_args! {
+                "recipient" => recipient.into(),
+                "amount" => amount,
+                "data" => data.to_string()
+            },
+        );
+    }
+
+    pub fn transfer_and_call_expecting_failure<T: Into<Key>>(
+        &self,
+        sender: AccountHash,
+        recipient: T,
+        amount: U256,
+        data: &str,
+    ) {
+        self.0.call_contract_expecting_failure(
+            sender,
+            "transfer_and_call",
+            runtime_args! {
+                "recipient" => recipient.into(),
+                "amount" => amount,
+                "data" => data.to_string()
+            },
+        );
+    }
+
+    pub fn batch_transfer(&self, sender: AccountHash, recipients: Vec<Key>, amounts: Vec<U256>) {
+        self.0.call_contract(
+            sender,
+            "batch_transfer",
+            runtime_args! {
+                "recipients" => recipients,
+                "amounts" => amounts
+            },
+        );
+    }
+
+    pub fn batch_transfer_expecting_failure(
+        &self,
+        sender: AccountHash,
+        recipients: Vec<Key>,
+        amounts: Vec<U256>,
+    ) {
+        self.0.call_contract_expecting_failure(
+            sender,
+            "batch_transfer",
+            runtime_args! {
+                "recipients" => recipients,
+                "amounts" => amounts
+            },
+        );
+    }
+
+    pub fn batch_transfer_from(
+        &self,
+        sender: AccountHash,
+        owner: Key,
+        recipients: Vec<Key>,
+        amounts: Vec<U256>,
+    ) {
+        self.0.call_contract(
+            sender,
+            "batch_transfer_from",
+            runtime_args! {
+                "owner" => owner,
+                "recipients" => recipients,
+                "amounts" => amounts
+            },
+        );
+    }
+
     pub fn transfer_from(&self, sender: AccountHash, owner: Key, recipient: Key, amount: U256) {
         self.0.call_contract(
             sender,
@@ -131,11 +245,28 @@ impl WCSPRInstance {
     }
 
     pub fn balance_of<T: Into<Key>>(&self, account: T) -> U256 {
+        self.0
+            .query_dictionary("balances", balance_key(&account.into()))
+            .unwrap_or_default()
+    }
+
+    /// Balance written under the `key_to_str` item by tokens installed before the shared core.
+    pub fn legacy_balance_of<T: Into<Key>>(&self, account: T) -> U256 {
         self.0
             .query_dictionary("balances", key_to_str(&account.into()))
             .unwrap_or_default()
     }
 
+    pub fn migrate_balances(&self, sender: AccountHash, owners: Vec<Key>) {
+        self.0.call_contract(
+            sender,
+            "migrate_balances",
+            runtime_args! {
+                "owners" => owners
+            },
+        );
+    }
+
     pub fn allowance<T: Into<Key>>(&self, owner: T, spender: T) -> U256 {
         let owner: Key = owner.into();
         let spender: Key = spender.into();
@@ -205,11 +336,27 @@ impl WCSPRInstance {
         self.0.query_named_key(String::from("symbol"))
     }
 
+    pub fn storage_version(&self) -> u32 {
+        self.0.query_named_key(String::from("storage_version"))
+    }
+
     // Result methods
     pub fn transfer_result(&self) -> Result<(), u32> {
         self.0
             .query_named_key(TRANSFER_TEST_RESULT_KEY_NAME.to_string())
     }
+    pub fn received_from(&self) -> Key {
+        self.0.query_named_key("received_from".to_string())
+    }
+
+    pub fn received_amount(&self) -> U256 {
+        self.0.query_named_key("received_amount".to_string())
+    }
+
+    pub fn received_data(&self) -> String {
+        self.0.query_named_key("received_data".to_string())
+    }
+
     // for test contract
     pub fn package_hash_result(&self) -> ContractPackageHash {
         self.0.query_named_key(PACKAGE_HASH_KEY_NAME.to_string())
@@ -75,6 +75,87 @@ fn test_wcspr_deposit() {
     assert_eq!(wcspr_purse_balance, amount);
 }
 
+#[test]
+fn test_wcspr_upgrade_keeps_deposits() {
+    let (env, token, _proxy, _, owner) = deploy();
+    let depositor = env.next_user();
+    let amount: U512 = 500.into();
+    let wcspr_package_hash: ContractPackageHash = token.self_package_hash_result();
+    deploy_purse_proxy(
+        &env,
+        depositor,
+        amount,
+        Key::from(wcspr_package_hash),
+        DESTINATION_DEPOSIT,
+    );
+    let contract_hash: Key = token.self_contract_hash_result();
+    assert_eq!(token.storage_version(), 1);
+
+    WCSPRInstance::upgrade(&env, NAME, owner);
+
+    assert_ne!(token.self_contract_hash_result(), contract_hash);
+    assert_eq!(token.self_package_hash_result(), wcspr_package_hash);
+    assert_eq!(token.storage_version(), 1);
+    assert_eq!(token.name(), NAME);
+    assert_eq!(token.balance_of(depositor), 500.into());
+
+    // the purse moved with the package, so deposits can still be withdrawn
+    deploy_purse_proxy(
+        &env,
+        depositor,
+        amount,
+        Key::from(wcspr_package_hash),
+        DESTINATION_WITHDRAW,
+    );
+    assert_eq!(token.balance_of(depositor), 0.into());
+}
+
+#[test]
+fn test_wcspr_upgrade_from_legacy_layout() {
+    let env = TestEnv::new();
+    let owner = env.next_user();
+    let depositor = env.next_user();
+    let user = env.next_user();
+    let token = WCSPRInstance::instance(WCSPRInstance::new_legacy(
+        &env,
+        "legacy_wcspr",
+        owner,
+        NAME,
+        SYMBOL,
+        DECIMALS,
+    ));
+    let wcspr_package_hash: ContractPackageHash = token.self_package_hash_result();
+    deploy_purse_proxy(
+        &env,
+        depositor,
+        500.into(),
+        Key::from(wcspr_package_hash),
+        DESTINATION_DEPOSIT,
+    );
+    token.transfer(depositor, user, 100.into());
+    assert_eq!(token.legacy_balance_of(depositor), 400.into());
+    assert_eq!(token.legacy_balance_of(user), 100.into());
+
+    WCSPRInstance::upgrade(&env, "legacy_wcspr", owner);
+
+    assert_eq!(token.storage_version(), 1);
+    assert_eq!(token.self_package_hash_result(), wcspr_package_hash);
+    assert_eq!(token.name(), NAME);
+
+    // balances written before the shared core are still read, and move when they change
+    deploy_purse_proxy(
+        &env,
+        depositor,
+        400.into(),
+        Key::from(wcspr_package_hash),
+        DESTINATION_WITHDRAW,
+    );
+    assert_eq!(token.balance_of(depositor), 0.into());
+    token.migrate_balances(owner, vec![user.into()]);
+    assert_eq!(token.balance_of(user), 100.into());
+    assert_eq!(token.legacy_balance_of(user), 0.into());
+}
+
 #[test]
 #[should_panic]
 fn test_wcspr_deposit_too_much() {
@@ -429,3 +510,125 @@ fn test_wcspr_decrease_allowance() {
     proxy.allowance_fn(owner, Key::from(package_hash), Key::from(package_hash2));
     assert_eq!(proxy.allowance_res(), 100.into());
 }
+
+/// Wraps `amount` motes of `depositor` into as many WCSPR.
+fn deposit_for(env: &TestEnv, token: &WCSPRInstance, depositor: AccountHash, amount: U512) {
+    let wcspr_package_hash: ContractPackageHash = token.self_package_hash_result();
+    deploy_purse_proxy(
+        env,
+        depositor,
+        amount,
+        Key::from(wcspr_package_hash),
+        DESTINATION_DEPOSIT,
+    );
+}
+
+#[test]
+fn test_wcspr_transfer_and_call() {
+    let (env, token, proxy, _, _owner) = deploy();
+    let package_hash = proxy.package_hash_result();
+    let depositor = env.next_user();
+    deposit_for(&env, &token, depositor, 500.into());
+    let amount: U256 = 200.into();
+
+    // the proxy's on_token_received records what it was called with
+    token.transfer_and_call(depositor, package_hash, amount, "deposit");
+
+    assert_eq!(token.balance_of(package_hash), amount);
+    assert_eq!(token.balance_of(depositor), 300.into());
+    assert_eq!(proxy.received_from(), Key::from(depositor));
+    assert_eq!(proxy.received_amount(), amount);
+    assert_eq!(proxy.received_data(), "deposit");
+}
+
+#[test]
+fn test_wcspr_transfer_and_call_rejected_by_hook() {
+    let (env, token, proxy, _, _owner) = deploy();
+    let package_hash = proxy.package_hash_result();
+    let depositor = env.next_user();
+    deposit_for(&env, &token, depositor, 500.into());
+
+    // the proxy's on_token_received reverts, taking the transfer with it
+    token.transfer_and_call_expecting_failure(depositor, package_hash, 200.into(), "reject");
+
+    assert_eq!(token.balance_of(package_hash), 0.into());
+    assert_eq!(token.balance_of(depositor), 500.into());
+}
+
+#[test]
+fn test_wcspr_batch_transfer() {
+    let (env, token, _, _, _owner) = deploy();
+    let depositor = env.next_user();
+    let user1 = env.next_user();
+    let user2 = env.next_user();
+    deposit_for(&env, &token, depositor, 500.into());
+
+    token.batch_transfer(
+        depositor,
+        vec![user1.into(), user2.into(), user1.into()],
+        vec![100.into(), 200.into(), 50.into()],
+    );
+
+    assert_eq!(token.balance_of(depositor), 150.into());
+    assert_eq!(token.balance_of(user1), 150.into());
+    assert_eq!(token.balance_of(user2), 200.into());
+}
+
+#[test]
+fn test_wcspr_batch_transfer_too_much() {
+    let (env, token, _, _, _owner) = deploy();
+    let depositor = env.next_user();
+    let user1 = env.next_user();
+    let user2 = env.next_user();
+    deposit_for(&env, &token, depositor, 500.into());
+
+    // the first transfer alone would succeed, the batch does not
+    token.batch_transfer_expecting_failure(
+        depositor,
+        vec![user1.into(), user2.into()],
+        vec![500.into(), 1.into()],
+    );
+
+    assert_eq!(token.balance_of(depositor), 500.into());
+    assert_eq!(token.balance_of(user1), 0.into());
+    assert_eq!(token.balance_of(user2), 0.into());
+}
+
+#[test]
+fn test_wcspr_batch_transfer_to_sender() {
+    let (env, token, _, _, _owner) = deploy();
+    let depositor = env.next_user();
+    let user = env.next_user();
+    deposit_for(&env, &token, depositor, 500.into());
+
+    token.batch_transfer_expecting_failure(
+        depositor,
+        vec![user.into(), depositor.into()],
+        vec![100.into(), 100.into()],
+    );
+
+    assert_eq!(token.balance_of(depositor), 500.into());
+    assert_eq!(token.balance_of(user), 0.into());
+}
+
+#[test]
+fn test_wcspr_batch_transfer_from() {
+    let (env, token, _, _, _owner) = deploy();
+    let depositor = env.next_user();
+    let spender = env.next_user();
+    let user1 = env.next_user();
+    let user2 = env.next_user();
+    deposit_for(&env, &token, depositor, 500.into());
+
+    token.approve(depositor, spender, 500.into());
+    token.batch_transfer_from(
+        spender,
+        depositor.into(),
+        vec![user1.into(), user2.into()],
+        vec![100.into(), 300.into()],
+    );
+
+    assert_eq!(token.balance_of(user1), 100.into());
+    assert_eq!(token.balance_of(user2), 300.into());
+    assert_eq!(token.allowance(depositor, spender), 100.into());
+}
@@ -2,7 +2,7 @@
 #![no_std]
 
 extern crate alloc;
-use alloc::{boxed::Box, collections::BTreeSet, format, string::String, vec};
+use alloc::{boxed::Box, collections::BTreeSet, format, string::String, vec, vec::Vec};
 use casper_contract::{
     contract_api::{runtime, storage, system},
     unwrap_or_revert::UnwrapOrRevert,
@@ -12,7 +12,7 @@ use casper_types::{
     EntryPointAccess, EntryPointType, EntryPoints, Group, Key, Parameter, RuntimeArgs, URef, U256,
     U512,
 };
-use contract_utils::{ContractContext, OnChainContractStorage};
+use contract_utils::{ContractContext, OnChainContractStorage, CEP18};
 use wcspr::{
     data, {self, WCSPR},
 };
@@ -26,6 +26,7 @@ impl ContractContext<OnChainContractStorage> for Token {
     }
 }
 
+impl CEP18<OnChainContractStorage> for Token {}
 impl WCSPR<OnChainContractStorage> for Token {}
 
 impl Token {
@@ -61,6 +62,19 @@ fn constructor() {
     Token::default().constructor(name, symbol, decimals, contract_hash, package_hash, purse);
 }
 
+/// This function is to bring the storage of an upgraded WCSPR up to date, only callable by the installer while upgrading
+///
+/// # Parameters
+///
+/// * `contract_hash` - A ContractHash that holds the hash of the new contract version
+///
+
+#[no_mangle]
+fn migrate() {
+    let contract_hash: ContractHash = runtime::get_named_arg("contract_hash");
+    WCSPR::migrate(&mut Token::default(), Key::from(contract_hash));
+}
+
 /// This function is to transfer tokens against the address that user provided
 ///
 /// # Parameters
@@ -78,6 +92,26 @@ fn transfer() {
     runtime::ret(CLValue::from_t(ret).unwrap_or_revert());
 }
 
+/// This function is to transfer tokens to a recipient and, if it is a contract, call its `on_token_received` entry point in the same deploy
+///
+/// # Parameters
+///
+/// * `recipient` - A Key that holds the account address or contract package hash of the recipient
+///
+/// * `amount` - A U256 that holds the amount for transfer
+///
+/// * `data` - A String that holds data passed on to `on_token_received`
+///
+
+#[no_mangle]
+fn transfer_and_call() {
+    let recipient: Key = runtime::get_named_arg("recipient");
+    let amount: U256 = runtime::get_named_arg("amount");
+    let data: String = runtime::get_named_arg("data");
+    let ret = Token::default().transfer_and_call(recipient, amount, data);
+    runtime::ret(CLValue::from_t(ret).unwrap_or_revert());
+}
+
 /// This function is to transfer tokens against the address that has been approved before by owner
 ///
 /// # Parameters
@@ -109,6 +143,43 @@ fn transfer_from() {
     runtime::ret(CLValue::from_t(ret).unwrap_or_revert());
 }
 
+/// This function is to transfer tokens to several recipients at once, reverting the whole batch if any transfer fails
+///
+/// # Parameters
+///
+/// * `recipients` - A Vec of Keys that holds the account addresses of the recipients
+///
+/// * `amounts` - A Vec of U256 that holds the amount for each recipient, in the same order
+///
+
+#[no_mangle]
+fn batch_transfer() {
+    let recipients: Vec<Key> = runtime::get_named_arg("recipients");
+    let amounts: Vec<U256> = runtime::get_named_arg("amounts");
+    let ret = Token::default().batch_transfer(recipients, amounts);
+    runtime::ret(CLValue::from_t(ret).unwrap_or_revert());
+}
+
+/// This function is to transfer tokens of an owner to several recipients at once against the allowance given by the owner
+///
+/// # Parameters
+///
+/// * `owner` - A Key that holds the account address of the owner
+///
+/// * `recipients` - A Vec of Keys that holds the account addresses of the recipients
+///
+/// * `amounts` - A Vec of U256 that holds the amount for each recipient, in the same order
+///
+
+#[no_mangle]
+fn batch_transfer_from() {
+    let owner: Key = runtime::get_named_arg("owner");
+    let recipients: Vec<Key> = runtime::get_named_arg("recipients");
+    let amounts: Vec<U256> = runtime::get_named_arg("amounts");
+    let ret = Token::default().batch_transfer_from(owner, recipients, amounts);
+    runtime::ret(CLValue::from_t(ret).unwrap_or_revert());
+}
+
 /// This function is to approve tokens against the address that user provided
 ///
 /// # Parameters
@@ -200,6 +271,19 @@ fn balance_of() {
     runtime::ret(CLValue::from_t(ret).unwrap_or_revert());
 }
 
+/// This function is to move balances written before the CEP-18 item keys to them
+///
+/// # Parameters
+///
+/// * `owners` - A Vec<Key> that holds the account addresses whose balances are moved
+///
+
+#[no_mangle]
+fn migrate_balances() {
+    let owners: Vec<Key> = runtime::get_named_arg("owners");
+    Token::default().migrate_balances(owners);
+}
+
 /// This function is to return the Name of contract
 ///
 
@@ -218,6 +302,24 @@ fn symbol() {
     runtime::ret(CLValue::from_t(ret).unwrap_or_revert());
 }
 
+/// This function is to return the Decimals of contract
+///
+
+#[no_mangle]
+fn decimals() {
+    let ret: u8 = Token::default().decimals();
+    runtime::ret(CLValue::from_t(ret).unwrap_or_revert());
+}
+
+/// This function is to return the Total Supply of contract
+///
+
+#[no_mangle]
+fn total_supply() {
+    let ret: U256 = Token::default().total_supply();
+    runtime::ret(CLValue::from_t(ret).unwrap_or_revert());
+}
+
 /// This function is to return the Package Hash of contract
 ///
 
@@ -346,6 +448,13 @@ fn get_entry_points() -> EntryPoints {
         EntryPointAccess::Groups(vec![Group::new("constructor")]),
         EntryPointType::Contract,
     ));
+    entry_points.add_entry_point(EntryPoint::new(
+        "migrate",
+        vec![Parameter::new("contract_hash", ContractHash::cl_type())],
+        <()>::cl_type(),
+        EntryPointAccess::Groups(vec![Group::new("migration")]),
+        EntryPointType::Contract,
+    ));
     entry_points.add_entry_point(EntryPoint::new(
         "transfer",
         vec![
@@ -359,6 +468,20 @@ fn get_entry_points() -> EntryPoints {
         EntryPointAccess::Public,
         EntryPointType::Contract,
     ));
+    entry_points.add_entry_point(EntryPoint::new(
+        "transfer_and_call",
+        vec![
+            Parameter::new("recipient", Key::cl_type()),
+            Parameter::new("amount", U256::cl_type()),
+            Parameter::new("data", String::cl_type()),
+        ],
+        CLType::Result {
+            ok: Box::new(CLType::Unit),
+            err: Box::new(CLType::U32),
+        },
+        EntryPointAccess::Public,
+        EntryPointType::Contract,
+    ));
     entry_points.add_entry_point(EntryPoint::new(
         "transfer_from",
         vec![
@@ -373,6 +496,33 @@ fn get_entry_points() -> EntryPoints {
         EntryPointAccess::Public,
         EntryPointType::Contract,
     ));
+    entry_points.add_entry_point(EntryPoint::new(
+        "batch_transfer",
+        vec![
+            Parameter::new("recipients", Vec::<Key>::cl_type()),
+            Parameter::new("amounts", Vec::<U256>::cl_type()),
+        ],
+        CLType::Result {
+            ok: Box::new(CLType::Unit),
+            err: Box::new(CLType::U32),
+        },
+        EntryPointAccess::Public,
+        EntryPointType::Contract,
+    ));
+    entry_points.add_entry_point(EntryPoint::new(
+        "batch_transfer_from",
+        vec![
+            Parameter::new("owner", Key::cl_type()),
+            Parameter::new("recipients", Vec::<Key>::cl_type()),
+            Parameter::new("amounts", Vec::<U256>::cl_type()),
+        ],
+        CLType::Result {
+            ok: Box::new(CLType::Unit),
+            err: Box::new(CLType::U32),
+        },
+        EntryPointAccess::Public,
+        EntryPointType::Contract,
+    ));
     entry_points.add_entry_point(EntryPoint::new(
         "approve",
         vec![
@@ -390,6 +540,13 @@ fn get_entry_points() -> EntryPoints {
         EntryPointAccess::Public,
         EntryPointType::Contract,
     ));
+    entry_points.add_entry_point(EntryPoint::new(
+        "migrate_balances",
+        vec![Parameter::new("owners", Vec::<Key>::cl_type())],
+        <()>::cl_type(),
+        EntryPointAccess::Public,
+        EntryPointType::Contract,
+    ));
     entry_points.add_entry_point(EntryPoint::new(
         "allowance",
         vec![
@@ -453,6 +610,20 @@ fn get_entry_points() -> EntryPoints {
         EntryPointAccess::Public,
         EntryPointType::Contract,
     ));
+    entry_points.add_entry_point(EntryPoint::new(
+        "decimals",
+        vec![],
+        u8::cl_type(),
+        EntryPointAccess::Public,
+        EntryPointType::Contract,
+    ));
+    entry_points.add_entry_point(EntryPoint::new(
+        "total_supply",
+        vec![],
+        U256::cl_type(),
+        EntryPointAccess::Public,
+        EntryPointType::Contract,
+    ));
     entry_points.add_entry_point(EntryPoint::new(
         "self_purse",
         vec![],
@@ -606,8 +777,49 @@ fn call() {
                 .unwrap()
                 .into();
 
+        let previous_contract_hash: ContractHash =
+            runtime::get_key(&format!("{}_contract_hash", contract_name))
+                .unwrap_or_revert()
+                .into_hash()
+                .unwrap_or_revert()
+                .into();
+
+        // The package access token stored at install time authorizes the new version.
+        // Named keys of the previous version, and with them all state, carry over.
         let (contract_hash, _): (ContractHash, _) =
             storage::add_contract_version(package_hash, get_entry_points(), Default::default());
+        storage::disable_contract_version(package_hash, previous_contract_hash).unwrap_or_revert();
+
+        // Provision a single URef of the migration group, creating the group on the first upgrade.
+        let migration_access: URef =
+            match storage::provision_contract_user_group_uref(package_hash, "migration") {
+                Ok(migration_access) => migration_access,
+                Err(_) => storage::create_contract_user_group(
+                    package_hash,
+                    "migration",
+                    1,
+                    Default::default(),
+                )
+                .unwrap_or_revert()
+                .pop()
+                .unwrap_or_revert(),
+            };
+
+        // Run the migration hooks of the new version
+        let _: () = runtime::call_versioned_contract(
+            package_hash,
+            None,
+            "migrate",
+            runtime_args! {
+                "contract_hash" => contract_hash
+            },
+        );
+
+        // Remove the URef again, so migrations only run as part of an upgrade.
+        let mut urefs = BTreeSet::new();
+        urefs.insert(migration_access);
+        storage::remove_contract_user_group_urefs(package_hash, "migration", urefs)
+            .unwrap_or_revert();
 
         // update contract hash
         runtime::put_key(
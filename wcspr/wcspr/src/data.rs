@@ -2,51 +2,21 @@ use crate::alloc::string::ToString;
 use alloc::string::String;
 use casper_contract::contract_api::runtime;
 use casper_contract::unwrap_or_revert::UnwrapOrRevert;
-use casper_types::{ApiError, ContractPackageHash, Key, URef, U256, U512};
-use contract_utils::{get_key, set_key, Dict};
+use casper_types::{ApiError, ContractPackageHash, Key, URef, U512};
+use contract_utils::{get_key, set_key};
 //use casper_contract::{value::account::PurseId ,contract_api::{runtime,system}, unwrap_or_revert::UnwrapOrRevert};
 
 // Events
 
 pub enum WcsprEvents {
-    Approval {
-        owner: Key,
-        spender: Key,
-        value: U256,
-    },
-
-    Transfer {
-        from: Key,
-        to: Key,
-        value: U256,
-    },
-
-    Deposit {
-        src_purse: URef,
-        amount: U512,
-    },
-
-    Withdraw {
-        recipient_purse: URef,
-        amount: U512,
-    },
+    Deposit { src_purse: URef, amount: U512 },
+
+    Withdraw { recipient_purse: URef, amount: U512 },
 }
 
 impl WcsprEvents {
     pub fn type_name(&self) -> String {
         match self {
-            WcsprEvents::Approval {
-                owner: _,
-                spender: _,
-                value: _,
-            } => "approve",
-
-            WcsprEvents::Transfer {
-                from: _,
-                to: _,
-                value: _,
-            } => "erc20_transfer",
-
             WcsprEvents::Deposit {
                 src_purse: _,
                 amount: _,
@@ -61,93 +31,19 @@ impl WcsprEvents {
     }
 }
 
-pub const BALANCES_DICT: &str = "balances";
-pub const ALLOWANCES_DICT: &str = "allowances";
-pub const NAME: &str = "name";
-pub const SYMBOL: &str = "symbol";
 pub const SELF_CONTRACT_HASH: &str = "self_contract_hash";
 pub const SELF_PURSE: &str = "self_purse";
-pub const DECIMALS: &str = "decimals";
 pub const CONTRACT_PACKAGE_HASH: &str = "self_package_hash";
-pub const TOTAL_SUPPLY: &str = "total_supply";
+pub const STORAGE_VERSION: &str = "storage_version";
+/// Storage layout written by this version of the contract. Bump it together with a
+/// migration step in `WCSPR::migrate` whenever named keys or dictionaries change.
+pub const CURRENT_STORAGE_VERSION: u32 = 1;
 
 #[repr(u16)]
 pub enum ErrorCodes {
     Abort = 35,
 }
 
-pub struct Balances {
-    dict: Dict,
-}
-
-impl Balances {
-    pub fn instance() -> Balances {
-        Balances {
-            dict: Dict::instance(BALANCES_DICT),
-        }
-    }
-
-    pub fn init() {
-        Dict::init(BALANCES_DICT)
-    }
-
-    pub fn get(&self, owner: &Key) -> U256 {
-        self.dict.get_by_key(owner).unwrap_or_default()
-    }
-
-    pub fn set(&self, owner: &Key, value: U256) {
-        self.dict.set_by_key(owner, value);
-    }
-}
-
-pub struct Allowances {
-    dict: Dict,
-}
-
-impl Allowances {
-    pub fn instance() -> Allowances {
-        Allowances {
-            dict: Dict::instance(ALLOWANCES_DICT),
-        }
-    }
-
-    pub fn init() {
-        Dict::init(ALLOWANCES_DICT)
-    }
-
-    pub fn get(&self, owner: &Key, spender: &Key) -> U256 {
-        self.dict.get_by_keys((owner, spender)).unwrap_or_default()
-    }
-
-    pub fn set(&self, owner: &Key, spender: &Key, value: U256) {
-        self.dict.set_by_keys((owner, spender), value);
-    }
-}
-
-pub fn name() -> String {
-    get_key(NAME).unwrap_or_revert()
-}
-
-pub fn set_name(name: String) {
-    set_key(NAME, name);
-}
-
-pub fn symbol() -> String {
-    get_key(SYMBOL).unwrap_or_revert()
-}
-
-pub fn set_symbol(symbol: String) {
-    set_key(SYMBOL, symbol);
-}
-
-pub fn decimals() -> u8 {
-    get_key(DECIMALS).unwrap_or_revert()
-}
-
-pub fn set_decimals(decimals: u8) {
-    set_key(DECIMALS, decimals);
-}
-
 pub fn set_hash(contract_hash: Key) {
     set_key(SELF_CONTRACT_HASH, contract_hash);
 }
@@ -177,10 +73,11 @@ pub fn get_package_hash() -> ContractPackageHash {
     get_key(CONTRACT_PACKAGE_HASH).unwrap_or_revert()
 }
 
-pub fn set_totalsupply(value: U256) {
-    set_key(TOTAL_SUPPLY, value);
+pub fn set_storage_version(storage_version: u32) {
+    set_key(STORAGE_VERSION, storage_version);
 }
 
-pub fn get_totalsupply() -> U256 {
-    get_key(TOTAL_SUPPLY).unwrap_or_revert()
+/// Tokens installed before storage versioning have no version key and read as 0.
+pub fn get_storage_version() -> u32 {
+    get_key(STORAGE_VERSION).unwrap_or_default()
 }
@@ -1,12 +1,12 @@
 use crate::alloc::string::ToString;
-use crate::data::{self, Allowances, Balances, WcsprEvents};
+use crate::data::{self, WcsprEvents};
 use alloc::{collections::BTreeMap, string::String, vec::Vec};
 use casper_contract::{
     contract_api::{runtime, storage, system},
     unwrap_or_revert::UnwrapOrRevert,
 };
 use casper_types::{ApiError, ContractPackageHash, Key, URef, U256, U512};
-use contract_utils::{ContractContext, ContractStorage};
+use contract_utils::{ContractStorage, CEP18};
 use num_traits::cast::AsPrimitive;
 #[repr(u16)]
 pub enum Error {
@@ -41,7 +41,7 @@ impl From<Error> for ApiError {
         ApiError::User(error as u16)
     }
 }
-pub trait WCSPR<Storage: ContractStorage>: ContractContext<Storage> {
+pub trait WCSPR<Storage: ContractStorage>: CEP18<Storage> {
     fn init(
         &mut self,
         name: String,
@@ -51,100 +51,19 @@ pub trait WCSPR<Storage: ContractStorage>: ContractContext<Storage> {
         package_hash: ContractPackageHash,
         purse: URef,
     ) {
-        data::set_name(name);
-        data::set_symbol(symbol);
+        CEP18::init(self, name, symbol, decimals);
         data::set_hash(contract_hash);
-        data::set_decimals(decimals);
         data::set_package_hash(package_hash);
         data::set_self_purse(purse);
-
-        Balances::init();
-        Allowances::init();
-        data::set_totalsupply(0.into());
-    }
-
-    fn balance_of(&mut self, owner: Key) -> U256 {
-        Balances::instance().get(&owner)
-    }
-
-    fn transfer(&mut self, recipient: Key, amount: U256) -> Result<(), u32> {
-        self.make_transfer(self.get_caller(), recipient, amount)
-    }
-
-    fn approve(&mut self, spender: Key, amount: U256) {
-        self._approve(self.get_caller(), spender, amount);
+        data::set_storage_version(data::CURRENT_STORAGE_VERSION);
     }
 
-    fn _approve(&mut self, owner: Key, spender: Key, amount: U256) {
-        Allowances::instance().set(&owner, &spender, amount);
-        self.emit(&WcsprEvents::Approval {
-            owner: owner,
-            spender: spender,
-            value: amount,
-        });
-    }
-
-    fn allowance(&mut self, owner: Key, spender: Key) -> U256 {
-        Allowances::instance().get(&owner, &spender)
-    }
-    fn increase_allowance(&mut self, spender: Key, amount: U256) -> Result<(), u32> {
-        let allowances = Allowances::instance();
-        let owner: Key = self.get_caller();
-        let spender_allowance: U256 = allowances.get(&owner, &spender);
-
-        let new_allowance: U256 = spender_allowance
-            .checked_add(amount)
-            .ok_or(Error::UniswapV2CoreWCSPROverFlow1)
-            .unwrap_or_revert();
-
-        if owner != spender {
-            self._approve(owner, spender, new_allowance);
-            return Ok(());
-        } else {
-            return Err(4);
-        }
-    }
-
-    fn decrease_allowance(&mut self, spender: Key, amount: U256) -> Result<(), u32> {
-        let allowances = Allowances::instance();
-        let owner: Key = self.get_caller();
-        let spender_allowance: U256 = allowances.get(&owner, &spender);
-
-        let new_allowance: U256 = spender_allowance
-            .checked_sub(amount)
-            .ok_or(Error::UniswapV2CoreWCSPRUnderFlow1)
-            .unwrap_or_revert();
-
-        if new_allowance >= 0.into() && new_allowance < spender_allowance && owner != spender {
-            self._approve(owner, spender, new_allowance);
-            return Ok(());
-        } else {
-            return Err(4);
-        }
-    }
-
-    fn transfer_from(&mut self, owner: Key, recipient: Key, amount: U256) -> Result<(), u32> {
-        if owner != recipient && amount != 0.into() {
-            let ret: Result<(), u32> = self.make_transfer(owner, recipient, amount);
-            if ret.is_ok() {
-                let allowances = Allowances::instance();
-                let spender_allowance: U256 = allowances.get(&owner, &self.get_caller());
-                let new_allowance: U256 = spender_allowance
-                    .checked_sub(amount)
-                    .ok_or(Error::UniswapV2CoreWCSPRUnderFlow2)
-                    .unwrap_or_revert();
-                if new_allowance >= 0.into()
-                    && new_allowance < spender_allowance
-                    && owner != self.get_caller()
-                {
-                    self._approve(owner, self.get_caller(), new_allowance);
-                    return Ok(());
-                } else {
-                    return Err(4);
-                }
-            }
-        }
-        Ok(())
+    /// Brings the storage of an upgraded token up to `CURRENT_STORAGE_VERSION`. Called by
+    /// the installer right after a new version is added, each step runs once per token.
+    fn migrate(&mut self, contract_hash: Key) {
+        data::set_hash(contract_hash);
+        // version 1 only starts tracking the storage version, the layout is unchanged
+        data::set_storage_version(data::CURRENT_STORAGE_VERSION);
     }
 
     fn deposit(&mut self, amount_to_transfer: U512, purse: URef) -> Result<(), u32> {
@@ -181,24 +100,7 @@ pub trait WCSPR<Storage: ContractStorage>: ContractContext<Storage> {
         .unwrap_or_revert(); // transfers native cspr from source purse to destination purse
 
         // mint wcspr for the caller
-        let caller = self.get_caller();
-        let balances = Balances::instance();
-        let balance = balances.get(&caller);
-        balances.set(
-            &caller,
-            balance
-                .checked_add(amount_to_transfer_u256)
-                .ok_or(Error::UniswapV2CoreWCSPROverFlow2)
-                .unwrap_or_revert(),
-        );
-
-        // update total supply
-        data::set_totalsupply(
-            data::get_totalsupply()
-                .checked_add(amount_to_transfer_u256)
-                .ok_or(Error::UniswapV2CoreWCSPROverFlow3)
-                .unwrap_or_revert(),
-        );
+        CEP18::mint(self, self.get_caller(), amount_to_transfer_u256);
 
         self.emit(&WcsprEvents::Deposit {
             src_purse: purse,
@@ -209,9 +111,6 @@ pub trait WCSPR<Storage: ContractStorage>: ContractContext<Storage> {
     }
 
     fn withdraw(&mut self, recipient_purse: URef, amount: U512) -> Result<(), u32> {
-        let caller = self.get_caller();
-        let balances = Balances::instance();
-        let balance = balances.get(&caller); // get balance of the caller
         if amount
             > U512::from(<casper_types::U256 as AsPrimitive<casper_types::U512>>::as_(U256::MAX))
         {
@@ -224,6 +123,9 @@ pub trait WCSPR<Storage: ContractStorage>: ContractContext<Storage> {
             return Err(5); // Amount to transfer is 0
         }
 
+        // burn wcspr of the caller
+        CEP18::burn(self, self.get_caller(), cspr_amount_u256);
+
         let contract_main_purse = data::get_self_purse();
 
         system::transfer_from_purse_to_purse(
@@ -235,22 +137,6 @@ pub trait WCSPR<Storage: ContractStorage>: ContractContext<Storage> {
         )
         .unwrap_or_revert();
 
-        balances.set(
-            &caller,
-            balance
-                .checked_sub(cspr_amount_u256)
-                .ok_or(Error::UniswapV2CoreWCSPRUnderFlow3)
-                .unwrap_or_revert(),
-        );
-
-        // update total supply
-        data::set_totalsupply(
-            data::get_totalsupply()
-                .checked_sub(cspr_amount_u256)
-                .ok_or(Error::UniswapV2CoreWCSPROverFlow4)
-                .unwrap_or_revert(),
-        );
-
         self.emit(&WcsprEvents::Withdraw {
             recipient_purse: recipient_purse,
             amount: amount,
@@ -259,42 +145,6 @@ pub trait WCSPR<Storage: ContractStorage>: ContractContext<Storage> {
         Ok(())
     }
 
-    fn make_transfer(&mut self, sender: Key, recipient: Key, amount: U256) -> Result<(), u32> {
-        if sender != recipient && amount != 0.into() {
-            let balances: Balances = Balances::instance();
-            let sender_balance: U256 = balances.get(&sender);
-            let recipient_balance: U256 = balances.get(&recipient);
-            balances.set(
-                &sender,
-                sender_balance
-                    .checked_sub(amount)
-                    .ok_or(Error::UniswapV2CoreWCSPRUnderFlow4)
-                    .unwrap_or_revert(),
-            );
-            balances.set(
-                &recipient,
-                recipient_balance
-                    .checked_add(amount)
-                    .ok_or(Error::UniswapV2CoreWCSPROverFlow5)
-                    .unwrap_or_revert(),
-            );
-            self.emit(&WcsprEvents::Transfer {
-                from: sender,
-                to: recipient,
-                value: amount,
-            });
-        }
-        Ok(())
-    }
-
-    fn name(&mut self) -> String {
-        data::name()
-    }
-
-    fn symbol(&mut self) -> String {
-        data::symbol()
-    }
-
     fn purse(&mut self) -> URef {
         data::get_self_purse()
     }
@@ -309,30 +159,6 @@ pub trait WCSPR<Storage: ContractStorage>: ContractContext<Storage> {
         let package = data::get_package_hash();
 
         match wcspr_event {
-            WcsprEvents::Approval {
-                owner,
-                spender,
-                value,
-            } => {
-                let mut event = BTreeMap::new();
-                event.insert("contract_package_hash", package.to_string());
-                event.insert("event_type", wcspr_event.type_name());
-                event.insert("owner", owner.to_string());
-                event.insert("spender", spender.to_string());
-                event.insert("value", value.to_string());
-                events.push(event);
-            }
-
-            WcsprEvents::Transfer { from, to, value } => {
-                let mut event = BTreeMap::new();
-                event.insert("contract_package_hash", package.to_string());
-                event.insert("event_type", wcspr_event.type_name());
-                event.insert("from", from.to_string());
-                event.insert("to", to.to_string());
-                event.insert("value", value.to_string());
-                events.push(event);
-            }
-
             WcsprEvents::Deposit { src_purse, amount } => {
                 let mut event = BTreeMap::new();
                 event.insert("contract_package_hash", package.to_string());